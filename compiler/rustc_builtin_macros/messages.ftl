@@ -59,6 +59,8 @@ builtin_macros_bad_derive_target = `derive` may only be applied to `struct`s, `e
     .label = not applicable here
     .label2 = not a `struct`, `enum` or `union`
 
+builtin_macros_bench_params = `#[test_params]` cannot be used on benchmarks
+
 builtin_macros_bench_sig = functions used as benches must have signature `fn(&mut Bencher) -> impl Termination`
 
 
@@ -187,6 +189,13 @@ builtin_macros_format_unused_args = multiple unused formatting arguments
 
 builtin_macros_format_use_positional = consider using a positional formatting argument instead
 
+builtin_macros_fuzz_params = `#[test_params]` cannot be used on fuzz targets
+
+builtin_macros_fuzz_should_panic = `#[should_panic]` cannot be used on fuzz targets
+    .note = every panic of a fuzz target is reported as a crash
+
+builtin_macros_fuzz_sig = functions used as fuzz targets must have signature `fn(&[u8]) -> impl Termination`
+
 builtin_macros_global_asm_clobber_abi = `clobber_abi` cannot be used with `global_asm!`
 
 builtin_macros_invalid_crate_attribute = invalid crate attribute
@@ -228,8 +237,23 @@ builtin_macros_requires_cfg_pattern =
 builtin_macros_test_bad_fn = {$kind} functions cannot be used for tests
     .label = `{$kind}` because of this
 
+builtin_macros_test_bad_timeout = invalid time limit `{$value}` in `#[test_timeout]`
+    .help = expected a time limit such as `500ms`, `10s` or `2m`
+
 builtin_macros_test_case_non_item = `#[test_case]` attribute is only allowed on items
 
+builtin_macros_test_params_count = this test case has {$found ->
+        [one] {$found} argument
+        *[other] {$found} arguments
+    } but the test function takes {$expected}
+    .label = test function defined here
+
+builtin_macros_test_params_ignore = expected `ignore` or `ignore = "reason"` after the arguments of a test case
+    .suggestion = ignore the test case
+
+builtin_macros_test_params_malformed = malformed `test_params` attribute input
+    .suggestion = must be of the form
+
 builtin_macros_test_runner_invalid = `test_runner` argument must be a path
 builtin_macros_test_runner_nargs = `#![test_runner(..)]` accepts exactly 1 argument
 
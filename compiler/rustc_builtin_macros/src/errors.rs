@@ -129,6 +129,13 @@ pub(crate) struct TraceMacros {
     pub(crate) span: Span,
 }
 
+#[derive(Diagnostic)]
+#[diag(builtin_macros_bench_params)]
+pub(crate) struct BenchParams {
+    #[primary_span]
+    pub(crate) span: Span,
+}
+
 #[derive(Diagnostic)]
 #[diag(builtin_macros_bench_sig)]
 pub(crate) struct BenchSig {
@@ -136,6 +143,28 @@ pub(crate) struct BenchSig {
     pub(crate) span: Span,
 }
 
+#[derive(Diagnostic)]
+#[diag(builtin_macros_fuzz_params)]
+pub(crate) struct FuzzParams {
+    #[primary_span]
+    pub(crate) span: Span,
+}
+
+#[derive(Diagnostic)]
+#[diag(builtin_macros_fuzz_should_panic)]
+#[note]
+pub(crate) struct FuzzShouldPanic {
+    #[primary_span]
+    pub(crate) span: Span,
+}
+
+#[derive(Diagnostic)]
+#[diag(builtin_macros_fuzz_sig)]
+pub(crate) struct FuzzSig {
+    #[primary_span]
+    pub(crate) span: Span,
+}
+
 #[derive(Diagnostic)]
 #[diag(builtin_macros_alloc_must_statics)]
 pub(crate) struct AllocMustStatics {
@@ -669,6 +698,42 @@ pub(crate) struct TestBadFn {
     pub(crate) kind: &'static str,
 }
 
+#[derive(Diagnostic)]
+#[diag(builtin_macros_test_bad_timeout)]
+#[help]
+pub(crate) struct TestBadTimeout {
+    #[primary_span]
+    pub(crate) span: Span,
+    pub(crate) value: Symbol,
+}
+
+#[derive(Diagnostic)]
+#[diag(builtin_macros_test_params_count)]
+pub(crate) struct TestParamsCount {
+    #[primary_span]
+    pub(crate) span: Span,
+    #[label]
+    pub(crate) fn_span: Span,
+    pub(crate) expected: usize,
+    pub(crate) found: usize,
+}
+
+#[derive(Diagnostic)]
+#[diag(builtin_macros_test_params_ignore)]
+pub(crate) struct TestParamsIgnore {
+    #[primary_span]
+    #[suggestion(code = "ignore", applicability = "maybe-incorrect")]
+    pub(crate) span: Span,
+}
+
+#[derive(Diagnostic)]
+#[diag(builtin_macros_test_params_malformed)]
+pub(crate) struct TestParamsMalformed {
+    #[primary_span]
+    #[suggestion(code = "#[test_params(...)]", applicability = "has-placeholders")]
+    pub(crate) span: Span,
+}
+
 #[derive(Diagnostic)]
 #[diag(builtin_macros_asm_explicit_register_name)]
 pub(crate) struct AsmExplicitRegisterName {
@@ -111,6 +111,7 @@ pub fn register_builtin_macros(resolver: &mut dyn ResolverExpand) {
         cfg_eval: cfg_eval::expand,
         derive: derive::Expander(false),
         derive_const: derive::Expander(true),
+        fuzz: test::expand_fuzz,
         global_allocator: global_allocator::expand,
         test: test::expand_test,
         test_case: test::expand_test_case,
@@ -3,10 +3,12 @@
 /// Ideally, this code would be in libtest but for efficiency and error messages it lives here.
 use crate::util::{check_builtin_macro_attribute, warn_on_duplicate_attribute};
 use rustc_ast::ptr::P;
+use rustc_ast::token::{self, Delimiter};
 use rustc_ast::{self as ast, attr, GenericParamKind};
 use rustc_ast_pretty::pprust;
 use rustc_errors::{Applicability, Diag, Level};
 use rustc_expand::base::*;
+use rustc_session::parse::feature_err;
 use rustc_span::symbol::{sym, Ident, Symbol};
 use rustc_span::{ErrorGuaranteed, FileNameDisplayPreference, Span};
 use std::assert_matches::assert_matches;
@@ -81,7 +83,7 @@ pub fn expand_test(
 ) -> Vec<Annotatable> {
     check_builtin_macro_attribute(cx, meta_item, sym::test);
     warn_on_duplicate_attribute(cx, &item, sym::test);
-    expand_test_or_bench(cx, attr_sp, item, false)
+    expand_test_or_bench(cx, attr_sp, item, TestKind::Test)
 }
 
 pub fn expand_bench(
@@ -92,14 +94,36 @@ pub fn expand_bench(
 ) -> Vec<Annotatable> {
     check_builtin_macro_attribute(cx, meta_item, sym::bench);
     warn_on_duplicate_attribute(cx, &item, sym::bench);
-    expand_test_or_bench(cx, attr_sp, item, true)
+    expand_test_or_bench(cx, attr_sp, item, TestKind::Bench)
+}
+
+pub fn expand_fuzz(
+    cx: &mut ExtCtxt<'_>,
+    attr_sp: Span,
+    meta_item: &ast::MetaItem,
+    item: Annotatable,
+) -> Vec<Annotatable> {
+    check_builtin_macro_attribute(cx, meta_item, sym::fuzz);
+    warn_on_duplicate_attribute(cx, &item, sym::fuzz);
+    expand_test_or_bench(cx, attr_sp, item, TestKind::Fuzz)
+}
+
+/// The kind of function a test attribute is applied to.
+#[derive(Clone, Copy, PartialEq)]
+pub enum TestKind {
+    /// `#[test]`
+    Test,
+    /// `#[bench]`
+    Bench,
+    /// `#[fuzz]`
+    Fuzz,
 }
 
 pub fn expand_test_or_bench(
     cx: &ExtCtxt<'_>,
     attr_sp: Span,
     item: Annotatable,
-    is_bench: bool,
+    kind: TestKind,
 ) -> Vec<Annotatable> {
     // If we're not in test configuration, remove the annotated item
     if !cx.ecfg.should_test {
@@ -134,18 +158,21 @@ pub fn expand_test_or_bench(
     // check_*_signature will report any errors in the type so compilation
     // will fail. We shouldn't try to expand in this case because the errors
     // would be spurious.
-    let check_result = if is_bench {
-        check_bench_signature(cx, &item, fn_)
-    } else {
-        check_test_signature(cx, &item, fn_)
-    };
-    if check_result.is_err() {
+    let check_result = test_cases(cx, &item).and_then(|cases| {
+        match kind {
+            TestKind::Test => check_test_signature(cx, &item, fn_, &cases)?,
+            TestKind::Bench => check_bench_signature(cx, &item, fn_, &cases)?,
+            TestKind::Fuzz => check_fuzz_signature(cx, &item, fn_, &cases)?,
+        }
+        Ok(cases)
+    });
+    let Ok(cases) = check_result else {
         return if is_stmt {
             vec![Annotatable::Stmt(P(cx.stmt_item(item.span, item)))]
         } else {
             vec![Annotatable::Item(item)]
         };
-    }
+    };
 
     let sp = cx.with_def_site_ctxt(item.span);
     let ret_ty_sp = cx.with_def_site_ctxt(fn_.sig.decl.output.span());
@@ -193,73 +220,167 @@ pub fn expand_test_or_bench(
         expr
     };
 
-    let test_fn = if is_bench {
+    // creates test::$constructor(#[coverage(off)] |$arg| test::assert_test_result($test_fn($arg)))
+    let lambda1_test_fn = |constructor, arg| {
         // A simple ident for a lambda
-        let b = Ident::from_str_and_span("b", attr_sp);
+        let arg = Ident::from_str_and_span(arg, attr_sp);
 
         cx.expr_call(
             sp,
-            cx.expr_path(test_path("StaticBenchFn")),
+            cx.expr_path(test_path(constructor)),
             thin_vec![
                 // #[coverage(off)]
-                // |b| self::test::assert_test_result(
+                // |arg| self::test::assert_test_result(
                 coverage_off(cx.lambda1(
                     sp,
                     cx.expr_call(
                         sp,
                         cx.expr_path(test_path("assert_test_result")),
                         thin_vec![
-                            // super::$test_fn(b)
+                            // super::$test_fn(arg)
                             cx.expr_call(
                                 ret_ty_sp,
                                 cx.expr_path(cx.path(sp, vec![item.ident])),
-                                thin_vec![cx.expr_ident(sp, b)],
+                                thin_vec![cx.expr_ident(sp, arg)],
                             ),
                         ],
                     ),
-                    b,
-                )), // )
-            ],
-        )
-    } else {
-        cx.expr_call(
-            sp,
-            cx.expr_path(test_path("StaticTestFn")),
-            thin_vec![
-                // #[coverage(off)]
-                // || {
-                coverage_off(cx.lambda0(
-                    sp,
-                    // test::assert_test_result(
-                    cx.expr_call(
-                        sp,
-                        cx.expr_path(test_path("assert_test_result")),
-                        thin_vec![
-                            // $test_fn()
-                            cx.expr_call(
-                                ret_ty_sp,
-                                cx.expr_path(cx.path(sp, vec![item.ident])),
-                                ThinVec::new(),
-                            ), // )
-                        ],
-                    ), // }
+                    arg,
                 )), // )
             ],
         )
     };
 
+    let test_fn = |mut stmts: ThinVec<ast::Stmt>, args: ThinVec<P<ast::Expr>>| match kind {
+        TestKind::Bench => lambda1_test_fn("StaticBenchFn", "b"),
+        TestKind::Fuzz => lambda1_test_fn("StaticFuzzFn", "data"),
+        TestKind::Test => {
+            // test::assert_test_result(
+            let mut body = cx.expr_call(
+                sp,
+                cx.expr_path(test_path("assert_test_result")),
+                thin_vec![
+                    // $test_fn($args)
+                    cx.expr_call(ret_ty_sp, cx.expr_path(cx.path(sp, vec![item.ident])), args),
+                ],
+            ); // )
+            if !stmts.is_empty() {
+                // { $stmts; test::assert_test_result(..) }
+                stmts.push(cx.stmt_expr(body));
+                body = cx.expr_block(cx.block(sp, stmts));
+            }
+            cx.expr_call(
+                sp,
+                cx.expr_path(test_path("StaticTestFn")),
+                thin_vec![
+                    // #[coverage(off)]
+                    // || {
+                    coverage_off(cx.lambda0(sp, body)), // })
+                ],
+            )
+        }
+    };
+
     let test_path_symbol = Symbol::intern(&item_path(
         // skip the name of the root module
         &cx.current_expansion.module.mod_path[1..],
         &item.ident,
     ));
 
-    let location_info = get_location_info(cx, &item);
+    // Checked once rather than per case, so that a bad `#[test_timeout]` is reported once.
+    let timeout = test_timeout(cx, &item);
+
+    // creates the `test::TestDesc` of the test, or of one case of a parameterized test
+    let test_desc = |test_path_symbol, span, (ignore, ignore_message): (bool, Option<Symbol>)| {
+        let location_info = get_location_info(cx, span);
+
+        cx.expr_struct(
+            sp,
+            test_path("TestDesc"),
+            thin_vec![
+                // name: "path::to::test"
+                field(
+                    "name",
+                    cx.expr_call(
+                        sp,
+                        cx.expr_path(test_path("StaticTestName")),
+                        thin_vec![cx.expr_str(sp, test_path_symbol)],
+                    ),
+                ),
+                // ignore: true | false
+                field("ignore", cx.expr_bool(sp, ignore)),
+                // ignore_message: Some("...") | None
+                field(
+                    "ignore_message",
+                    if let Some(msg) = ignore_message {
+                        cx.expr_some(sp, cx.expr_str(sp, msg))
+                    } else {
+                        cx.expr_none(sp)
+                    },
+                ),
+                // source_file: <relative_path_of_source_file>
+                field("source_file", cx.expr_str(sp, location_info.0)),
+                // start_line: start line of the test fn identifier.
+                field("start_line", cx.expr_usize(sp, location_info.1)),
+                // start_col: start column of the test fn identifier.
+                field("start_col", cx.expr_usize(sp, location_info.2)),
+                // end_line: end line of the test fn identifier.
+                field("end_line", cx.expr_usize(sp, location_info.3)),
+                // end_col: end column of the test fn identifier.
+                field("end_col", cx.expr_usize(sp, location_info.4)),
+                // compile_fail: true | false
+                field("compile_fail", cx.expr_bool(sp, false)),
+                // no_run: true | false
+                field("no_run", cx.expr_bool(sp, false)),
+                // should_panic: ...
+                field(
+                    "should_panic",
+                    match should_panic(cx, &item) {
+                        // test::ShouldPanic::No
+                        ShouldPanic::No => cx.expr_path(should_panic_path("No")),
+                        // test::ShouldPanic::Yes
+                        ShouldPanic::Yes(None) => cx.expr_path(should_panic_path("Yes")),
+                        // test::ShouldPanic::YesWithMessage("...")
+                        ShouldPanic::Yes(Some(sym)) => cx.expr_call(
+                            sp,
+                            cx.expr_path(should_panic_path("YesWithMessage")),
+                            thin_vec![cx.expr_str(sp, sym)],
+                        ),
+                    },
+                ),
+                // timeout_ms: Some(...) | None
+                field(
+                    "timeout_ms",
+                    if let Some(millis) = timeout {
+                        cx.expr_some(sp, cx.expr_u64(sp, millis))
+                    } else {
+                        cx.expr_none(sp)
+                    },
+                ),
+                // test_type: ...
+                field(
+                    "test_type",
+                    match test_type(cx) {
+                        // test::TestType::UnitTest
+                        TestType::UnitTest => cx.expr_path(test_type_path("UnitTest")),
+                        // test::TestType::IntegrationTest
+                        TestType::IntegrationTest => {
+                            cx.expr_path(test_type_path("IntegrationTest"))
+                        }
+                        // test::TestPath::Unknown
+                        TestType::Unknown => cx.expr_path(test_type_path("Unknown")),
+                    },
+                ),
+            ],
+        )
+    };
 
-    let mut test_const =
+    // creates the `test::TestDescAndFn` const of the test, or of one case of a
+    // parameterized test
+    let test_const = |ident, test_path_symbol, span, ignore, test_fn| {
         cx.item(
             sp,
-            Ident::new(item.ident.name, sp),
+            ident,
             thin_vec![
                 // #[cfg(test)]
                 cx.attr_nested_word(sym::cfg, sym::test, attr_sp),
@@ -273,131 +394,77 @@ pub fn expand_test_or_bench(
                     generics: ast::Generics::default(),
                     ty: cx.ty(sp, ast::TyKind::Path(None, test_path("TestDescAndFn"))),
                     // test::TestDescAndFn {
-                    expr: Some(
-                        cx.expr_struct(
-                            sp,
-                            test_path("TestDescAndFn"),
-                            thin_vec![
-                        // desc: test::TestDesc {
-                        field(
-                            "desc",
-                            cx.expr_struct(
-                                sp,
-                                test_path("TestDesc"),
-                                thin_vec![
-                                    // name: "path::to::test"
-                                    field(
-                                        "name",
-                                        cx.expr_call(
-                                            sp,
-                                            cx.expr_path(test_path("StaticTestName")),
-                                            thin_vec![cx.expr_str(sp, test_path_symbol)],
-                                        ),
-                                    ),
-                                    // ignore: true | false
-                                    field("ignore", cx.expr_bool(sp, should_ignore(&item)),),
-                                    // ignore_message: Some("...") | None
-                                    field(
-                                        "ignore_message",
-                                        if let Some(msg) = should_ignore_message(&item) {
-                                            cx.expr_some(sp, cx.expr_str(sp, msg))
-                                        } else {
-                                            cx.expr_none(sp)
-                                        },
-                                    ),
-                                    // source_file: <relative_path_of_source_file>
-                                    field("source_file", cx.expr_str(sp, location_info.0)),
-                                    // start_line: start line of the test fn identifier.
-                                    field("start_line", cx.expr_usize(sp, location_info.1)),
-                                    // start_col: start column of the test fn identifier.
-                                    field("start_col", cx.expr_usize(sp, location_info.2)),
-                                    // end_line: end line of the test fn identifier.
-                                    field("end_line", cx.expr_usize(sp, location_info.3)),
-                                    // end_col: end column of the test fn identifier.
-                                    field("end_col", cx.expr_usize(sp, location_info.4)),
-                                    // compile_fail: true | false
-                                    field("compile_fail", cx.expr_bool(sp, false)),
-                                    // no_run: true | false
-                                    field("no_run", cx.expr_bool(sp, false)),
-                                    // should_panic: ...
-                                    field(
-                                        "should_panic",
-                                        match should_panic(cx, &item) {
-                                            // test::ShouldPanic::No
-                                            ShouldPanic::No => {
-                                                cx.expr_path(should_panic_path("No"))
-                                            }
-                                            // test::ShouldPanic::Yes
-                                            ShouldPanic::Yes(None) => {
-                                                cx.expr_path(should_panic_path("Yes"))
-                                            }
-                                            // test::ShouldPanic::YesWithMessage("...")
-                                            ShouldPanic::Yes(Some(sym)) => cx.expr_call(
-                                                sp,
-                                                cx.expr_path(should_panic_path("YesWithMessage")),
-                                                thin_vec![cx.expr_str(sp, sym)],
-                                            ),
-                                        },
-                                    ),
-                                    // test_type: ...
-                                    field(
-                                        "test_type",
-                                        match test_type(cx) {
-                                            // test::TestType::UnitTest
-                                            TestType::UnitTest => {
-                                                cx.expr_path(test_type_path("UnitTest"))
-                                            }
-                                            // test::TestType::IntegrationTest
-                                            TestType::IntegrationTest => {
-                                                cx.expr_path(test_type_path("IntegrationTest"))
-                                            }
-                                            // test::TestPath::Unknown
-                                            TestType::Unknown => {
-                                                cx.expr_path(test_type_path("Unknown"))
-                                            }
-                                        },
-                                    ),
-                                    // },
-                                ],
-                            ),
-                        ),
-                        // testfn: test::StaticTestFn(...) | test::StaticBenchFn(...)
-                        field("testfn", test_fn), // }
-                    ],
-                        ), // }
-                    ),
+                    expr: Some(cx.expr_struct(
+                        sp,
+                        test_path("TestDescAndFn"),
+                        thin_vec![
+                            // desc: test::TestDesc { .. },
+                            field("desc", test_desc(test_path_symbol, span, ignore)),
+                            // testfn: test::StaticTestFn(...) | test::StaticBenchFn(...)
+                            //       | test::StaticFuzzFn(...)
+                            field("testfn", test_fn), // }
+                        ],
+                    )),
                 }
                 .into(),
             ),
-        );
-    test_const = test_const.map(|mut tc| {
-        tc.vis.kind = ast::VisibilityKind::Public;
-        tc
-    });
+        )
+    };
+
+    let ignore = (should_ignore(&item), should_ignore_message(&item));
+
+    // A test with `#[test_params]` is expanded to one test per case, which calls the
+    // test function with the arguments of that case.
+    let mut test_consts: Vec<_> = if cases.is_empty() {
+        let (stmts, args) = match kind {
+            TestKind::Test => fixture_args(cx, sp, test_id, fn_, test_path_symbol),
+            TestKind::Bench | TestKind::Fuzz => (ThinVec::new(), ThinVec::new()),
+        };
+        vec![test_const(
+            Ident::new(item.ident.name, sp),
+            test_path_symbol,
+            item.ident.span,
+            ignore,
+            test_fn(stmts, args),
+        )]
+    } else {
+        cases
+            .into_iter()
+            .enumerate()
+            .map(|(i, case)| {
+                // Cases are numbered from 1, in the order of their attributes.
+                let n = i + 1;
+                // A case is ignored if the whole test is, or if the case itself is.
+                let ignore = match case.ignore {
+                    Some(message) if !ignore.0 => (true, message),
+                    _ => ignore,
+                };
+                test_const(
+                    Ident::from_str_and_span(&format!("{}__case_{n}", item.ident.name), sp),
+                    Symbol::intern(&format!("{test_path_symbol}::case_{n}")),
+                    case.span,
+                    ignore,
+                    test_fn(ThinVec::new(), case.args),
+                )
+            })
+            .collect()
+    };
 
     // extern crate test
     let test_extern = cx.item(sp, test_id, ast::AttrVec::new(), ast::ItemKind::ExternCrate(None));
 
-    debug!("synthetic test item:\n{}\n", pprust::item_to_string(&test_const));
+    for test_const in &mut test_consts {
+        test_const.vis.kind = ast::VisibilityKind::Public;
+        debug!("synthetic test item:\n{}\n", pprust::item_to_string(test_const));
+    }
 
+    // Access to libtest under a hygienic name, the generated test cases and the
+    // original item
+    let items = iter::once(test_extern).chain(test_consts).chain(iter::once(item));
     if is_stmt {
-        vec![
-            // Access to libtest under a hygienic name
-            Annotatable::Stmt(P(cx.stmt_item(sp, test_extern))),
-            // The generated test case
-            Annotatable::Stmt(P(cx.stmt_item(sp, test_const))),
-            // The original item
-            Annotatable::Stmt(P(cx.stmt_item(sp, item))),
-        ]
+        items.map(|i| Annotatable::Stmt(P(cx.stmt_item(sp, i)))).collect()
     } else {
-        vec![
-            // Access to libtest under a hygienic name
-            Annotatable::Item(test_extern),
-            // The generated test case
-            Annotatable::Item(test_const),
-            // The original item
-            Annotatable::Item(item),
-        ]
+        items.map(Annotatable::Item).collect()
     }
 }
 
@@ -430,8 +497,7 @@ fn not_testable_error(cx: &ExtCtxt<'_>, attr_sp: Span, item: Option<&ast::Item>)
         .emit();
 }
 
-fn get_location_info(cx: &ExtCtxt<'_>, item: &ast::Item) -> (Symbol, usize, usize, usize, usize) {
-    let span = item.ident.span;
+fn get_location_info(cx: &ExtCtxt<'_>, span: Span) -> (Symbol, usize, usize, usize, usize) {
     let (source_file, lo_line, lo_col, hi_line, hi_col) =
         cx.sess.source_map().span_to_location_info(span);
 
@@ -443,6 +509,59 @@ fn get_location_info(cx: &ExtCtxt<'_>, item: &ast::Item) -> (Symbol, usize, usiz
     (Symbol::intern(&file_name), lo_line, lo_col, hi_line, hi_col)
 }
 
+/// Creates the statements setting up the fixtures taken by the test function, and
+/// the arguments passing them to it:
+///
+/// ```ignore (illustrative)
+/// let fixture_0 = test::fixture::acquire::<$ty>("path::to", "path::to::test");
+/// ```
+fn fixture_args(
+    cx: &ExtCtxt<'_>,
+    sp: Span,
+    test_id: Ident,
+    f: &ast::Fn,
+    test_path_symbol: Symbol,
+) -> (ThinVec<ast::Stmt>, ThinVec<P<ast::Expr>>) {
+    // The fixtures of a module are shared by the tests defined directly in it.
+    let module = cx.current_expansion.module.mod_path[1..]
+        .iter()
+        .map(|x| x.to_string())
+        .collect::<Vec<String>>()
+        .join("::");
+
+    f.sig
+        .decl
+        .inputs
+        .iter()
+        .enumerate()
+        .map(|(i, param)| {
+            let ty = fixture_ty(param).expect("test arguments are checked to be fixtures").clone();
+            let fixture = Ident::from_str_and_span(&format!("fixture_{i}"), sp);
+            let acquire = cx.path_all(
+                sp,
+                false,
+                vec![
+                    test_id,
+                    Ident::from_str_and_span("fixture", sp),
+                    Ident::from_str_and_span("acquire", sp),
+                ],
+                vec![ast::GenericArg::Type(ty)],
+            );
+            let setup = cx.expr_call(
+                sp,
+                cx.expr_path(acquire),
+                thin_vec![
+                    cx.expr_str(sp, Symbol::intern(&module)),
+                    cx.expr_str(sp, test_path_symbol)
+                ],
+            );
+            // &*fixture_0
+            let arg = cx.expr_addr_of(sp, cx.expr_deref(sp, cx.expr_ident(sp, fixture)));
+            (cx.stmt_let(sp, false, fixture, setup), arg)
+        })
+        .unzip()
+}
+
 fn item_path(mod_path: &[Ident], item_ident: &Ident) -> String {
     mod_path
         .iter()
@@ -512,6 +631,81 @@ fn should_panic(cx: &ExtCtxt<'_>, i: &ast::Item) -> ShouldPanic {
     }
 }
 
+/// Returns the hard time limit of the test in milliseconds, if it has one.
+fn test_timeout(cx: &ExtCtxt<'_>, i: &ast::Item) -> Option<u64> {
+    let attr = attr::find_by_name(&i.attrs, sym::test_timeout)?;
+    // Malformed attributes are reported by the attribute template check.
+    let value = attr.value_str()?;
+    let millis = parse_timeout_millis(value.as_str());
+    if millis.is_none() {
+        cx.dcx().emit_err(errors::TestBadTimeout { span: attr.span, value });
+    }
+    millis
+}
+
+/// Parses a time limit such as `500ms`, `10s` or `2m` into milliseconds, which is
+/// what libtest is given. A number without a unit is a number of seconds.
+fn parse_timeout_millis(timeout: &str) -> Option<u64> {
+    let (value, unit_millis) = if let Some(millis) = timeout.strip_suffix("ms") {
+        (millis, 1)
+    } else if let Some(secs) = timeout.strip_suffix('s') {
+        (secs, 1000)
+    } else if let Some(mins) = timeout.strip_suffix('m') {
+        (mins, 60 * 1000)
+    } else {
+        (timeout, 1000)
+    };
+    match value.parse::<u64>() {
+        Ok(0) | Err(_) => None,
+        Ok(value) => value.checked_mul(unit_millis),
+    }
+}
+
+/// A case of a parameterized test, declared with `#[test_params(...)]`.
+struct TestCase {
+    span: Span,
+    args: ThinVec<P<ast::Expr>>,
+    /// `Some` if the case ends with `; ignore` or `; ignore = "reason"`.
+    ignore: Option<Option<Symbol>>,
+}
+
+/// Returns the cases of the test, in the order of their `#[test_params]` attributes.
+fn test_cases(cx: &ExtCtxt<'_>, i: &ast::Item) -> Result<Vec<TestCase>, ErrorGuaranteed> {
+    let mut cases = Vec::new();
+    for attr in i.attrs.iter().filter(|attr| attr.has_name(sym::test_params)) {
+        // The arguments are expressions rather than meta items, so the attribute is
+        // exempt from the usual checks of builtin attributes and is validated here.
+        let ast::AttrArgs::Delimited(ast::DelimArgs {
+            delim: Delimiter::Parenthesis, tokens, ..
+        }) = &attr.get_normal_item().args
+        else {
+            return Err(cx.dcx().emit_err(errors::TestParamsMalformed { span: attr.span }));
+        };
+
+        let mut p = cx.new_parser_from_tts(tokens.clone());
+        let mut args = ThinVec::new();
+        while p.token != token::Eof && p.token != token::Semi {
+            args.push(p.parse_expr().map_err(|err| err.emit())?);
+            if p.token != token::Eof && p.token != token::Semi {
+                p.expect(&token::Comma).map_err(|err| err.emit())?;
+            }
+        }
+
+        // `#[test_params(1, 2; ignore = "reason")]` ignores this case only.
+        let mut ignore = None;
+        if p.eat(&token::Semi) {
+            let meta = p.parse_meta_item().map_err(|err| err.emit())?;
+            if !meta.has_name(sym::ignore) || !(meta.is_word() || meta.value_str().is_some()) {
+                return Err(cx.dcx().emit_err(errors::TestParamsIgnore { span: meta.span }));
+            }
+            p.expect(&token::Eof).map_err(|err| err.emit())?;
+            ignore = Some(meta.value_str());
+        }
+        cases.push(TestCase { span: attr.span, args, ignore });
+    }
+    Ok(cases)
+}
+
 enum TestType {
     UnitTest,
     IntegrationTest,
@@ -540,10 +734,19 @@ fn test_type(cx: &ExtCtxt<'_>) -> TestType {
     }
 }
 
+/// Returns the type of the fixture passed as `param`, if it is a shared reference.
+fn fixture_ty(param: &ast::Param) -> Option<&P<ast::Ty>> {
+    match &param.ty.kind {
+        ast::TyKind::Ref(_, ast::MutTy { ty, mutbl: ast::Mutability::Not }) => Some(ty),
+        _ => None,
+    }
+}
+
 fn check_test_signature(
     cx: &ExtCtxt<'_>,
     i: &ast::Item,
     f: &ast::Fn,
+    cases: &[TestCase],
 ) -> Result<(), ErrorGuaranteed> {
     let has_should_panic_attr = attr::contains_name(&i.attrs, sym::should_panic);
     let dcx = cx.dcx();
@@ -586,10 +789,36 @@ fn check_test_signature(
         _ => true,
     };
 
-    if !f.sig.decl.inputs.is_empty() {
+    // Without `#[test_params]`, the only arguments a test can take are shared
+    // references to fixtures, which are set up by the test harness.
+    if cases.is_empty() && f.sig.decl.inputs.iter().any(|param| fixture_ty(param).is_none()) {
         return Err(dcx.span_err(i.span, "functions used as tests can not have any arguments"));
     }
 
+    if cases.is_empty()
+        && let Some(param) = f.sig.decl.inputs.first()
+        && !cx.ecfg.features.test_fixtures
+    {
+        return Err(feature_err(
+            &cx.sess,
+            sym::test_fixtures,
+            param.span,
+            "fixtures passed to tests are experimental",
+        )
+        .emit());
+    }
+
+    for case in cases {
+        if case.args.len() != f.sig.decl.inputs.len() {
+            return Err(dcx.emit_err(errors::TestParamsCount {
+                span: case.span,
+                fn_span: f.sig.span,
+                expected: f.sig.decl.inputs.len(),
+                found: case.args.len(),
+            }));
+        }
+    }
+
     if has_should_panic_attr && has_output {
         return Err(dcx.span_err(i.span, "functions using `#[should_panic]` must return `()`"));
     }
@@ -608,7 +837,11 @@ fn check_bench_signature(
     cx: &ExtCtxt<'_>,
     i: &ast::Item,
     f: &ast::Fn,
+    cases: &[TestCase],
 ) -> Result<(), ErrorGuaranteed> {
+    if let Some(case) = cases.first() {
+        return Err(cx.dcx().emit_err(errors::BenchParams { span: case.span }));
+    }
     // N.B., inadequate check, but we're running
     // well before resolve, can't get too deep.
     if f.sig.decl.inputs.len() != 1 {
@@ -616,3 +849,25 @@ fn check_bench_signature(
     }
     Ok(())
 }
+
+fn check_fuzz_signature(
+    cx: &ExtCtxt<'_>,
+    i: &ast::Item,
+    f: &ast::Fn,
+    cases: &[TestCase],
+) -> Result<(), ErrorGuaranteed> {
+    if let Some(case) = cases.first() {
+        return Err(cx.dcx().emit_err(errors::FuzzParams { span: case.span }));
+    }
+    if let Some(attr) = attr::find_by_name(&i.attrs, sym::should_panic) {
+        return Err(cx.dcx().emit_err(errors::FuzzShouldPanic { span: attr.span }));
+    }
+    // Like for benches, the type of the input is checked by the type checker.
+    if f.sig.decl.inputs.len() != 1 {
+        return Err(cx.dcx().emit_err(errors::FuzzSig { span: i.span }));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,14 @@
+use super::parse_timeout_millis;
+
+#[test]
+fn test_timeout_values() {
+    assert_eq!(parse_timeout_millis("500ms"), Some(500));
+    assert_eq!(parse_timeout_millis("10s"), Some(10_000));
+    assert_eq!(parse_timeout_millis("2m"), Some(120_000));
+    assert_eq!(parse_timeout_millis("3"), Some(3000));
+    assert_eq!(parse_timeout_millis("0s"), None);
+    assert_eq!(parse_timeout_millis("1h"), None);
+    assert_eq!(parse_timeout_millis("ms"), None);
+    assert_eq!(parse_timeout_millis("-1s"), None);
+    assert_eq!(parse_timeout_millis(&format!("{}m", u64::MAX / 1000)), None);
+}
@@ -467,6 +467,14 @@ pub struct BuiltinAttribute {
         EncodeCrossCrate::Yes, custom_test_frameworks,
         "custom test frameworks are an unstable feature",
     ),
+    gated!(
+        test_params, Normal, template!(List: "args"), DuplicatesOk,
+        EncodeCrossCrate::No, experimental!(test_params)
+    ),
+    gated!(
+        test_timeout, Normal, template!(NameValueStr: "duration"), ErrorFollowing,
+        EncodeCrossCrate::No, experimental!(test_timeout)
+    ),
     // RFC #1268
     gated!(
         marker, Normal, template!(Word), WarnFollowing, EncodeCrossCrate::No,
@@ -595,6 +595,14 @@ pub fn internal(&self, feature: Symbol) -> bool {
     (unstable, string_deref_patterns, "1.67.0", Some(87121)),
     /// Allows the use of `#[target_feature]` on safe functions.
     (unstable, target_feature_11, "1.45.0", Some(69098)),
+    // no-tracking-issue-start
+    /// Allows tests to take shared references to fixtures set up by the test harness.
+    (unstable, test_fixtures, "CURRENT_RUSTC_VERSION", None),
+    /// Allows using `#[test_params]` to run a test with several sets of arguments.
+    (unstable, test_params, "CURRENT_RUSTC_VERSION", None),
+    /// Allows using `#[test_timeout]` to set a hard time limit on a test.
+    (unstable, test_timeout, "CURRENT_RUSTC_VERSION", None),
+    // no-tracking-issue-end
     /// Allows using `#[thread_local]` on `static` items.
     (unstable, thread_local, "1.0.0", Some(29594)),
     /// Allows defining `trait X = A + B;` alias items.
@@ -23,7 +23,12 @@ pub fn check_attr(psess: &ParseSess, attr: &Attribute) {
     // Check input tokens for built-in and key-value attributes.
     match attr_info {
         // `rustc_dummy` doesn't have any restrictions specific to built-in attributes.
-        Some(BuiltinAttribute { name, template, .. }) if *name != sym::rustc_dummy => {
+        // The arguments of `test_params` are arbitrary expressions such as `-1` or
+        // `vec![0; 3]`, which aren't meta items and would be rejected as malformed here;
+        // the `#[test]` macro parses and checks them instead.
+        Some(BuiltinAttribute { name, template, .. })
+            if *name != sym::rustc_dummy && *name != sym::test_params =>
+        {
             check_builtin_attribute(psess, attr, *name, *template)
         }
         _ if let AttrArgs::Eq(..) = attr.get_normal_item().args => {
@@ -896,6 +896,7 @@
         fused_iterator,
         future,
         future_trait,
+        fuzz,
         gdb_script_file,
         ge,
         gen_blocks,
@@ -1812,8 +1813,11 @@
         test_2018_feature,
         test_accepted_feature,
         test_case,
+        test_fixtures,
+        test_params,
         test_removed_feature,
         test_runner,
+        test_timeout,
         test_unstable_lint,
         thread,
         thread_local,
@@ -1,7 +1,7 @@
 use super::merge_iter::MergeIterInner;
-use super::node::{self, Root};
-use core::alloc::Allocator;
+use super::node::{self, marker, ForceResult::*, NodeAlloc, NodeRef, Root, SplitResult};
 use core::iter::FusedIterator;
+use core::mem;
 
 impl<K, V> Root<K, V> {
     /// Appends all key-value pairs from the union of two ascending iterators,
@@ -15,7 +15,7 @@ impl<K, V> Root<K, V> {
     /// a `BTreeMap`, both iterators should produce keys in strictly ascending
     /// order, each greater than all keys in the tree, including any keys
     /// already in the tree upon entry.
-    pub fn append_from_sorted_iters<I, A: Allocator + Clone>(
+    pub fn append_from_sorted_iters<I, A: NodeAlloc>(
         &mut self,
         left: I,
         right: I,
@@ -35,7 +35,7 @@ pub fn append_from_sorted_iters<I, A: Allocator + Clone>(
     /// Pushes all key-value pairs to the end of the tree, incrementing a
     /// `length` variable along the way. The latter makes it easier for the
     /// caller to avoid a leak when the iterator panicks.
-    pub fn bulk_push<I, A: Allocator + Clone>(&mut self, iter: I, length: &mut usize, alloc: A)
+    pub fn bulk_push<I, A: NodeAlloc>(&mut self, iter: I, length: &mut usize, alloc: A)
     where
         I: Iterator<Item = (K, V)>,
     {
@@ -76,7 +76,7 @@ pub fn bulk_push<I, A: Allocator + Clone>(&mut self, iter: I, length: &mut usize
                 for _ in 0..tree_height {
                     right_tree.push_internal_level(alloc.clone());
                 }
-                open_node.push(key, value, right_tree);
+                open_node.push(key, value, right_tree, &alloc);
 
                 // Go down to the right-most leaf again.
                 cur_node = open_node.forget_type().last_leaf_edge().into_node();
@@ -86,7 +86,84 @@ pub fn bulk_push<I, A: Allocator + Clone>(&mut self, iter: I, length: &mut usize
             // the appended elements even if advancing the iterator panicks.
             *length += 1;
         }
-        self.fix_right_border_of_plentiful();
+        self.borrow_mut().fix_all_subtree_lens(&alloc);
+        self.fix_right_border_of_plentiful(&alloc);
+    }
+}
+
+impl<K, V> Root<K, V> {
+    /// Appends a key-value pair and then all key-value pairs of another tree,
+    /// in time proportional to the height of the taller tree.
+    ///
+    /// If both trees respect all `BTreeMap` tree invariants, and the key is
+    /// greater than all keys in the tree and less than all keys in `right`,
+    /// then the joined tree respects those invariants too.
+    pub fn join<A: NodeAlloc>(&mut self, key: K, val: V, mut right: Self, alloc: A) {
+        // The shorter tree is adopted by a node on the border of the taller
+        // tree, or both are adopted by a new root if they're equally tall.
+        let adopted_on_right = self.height() >= right.height();
+        let both_adopted = self.height() == right.height();
+        let height;
+        let split = if adopted_on_right {
+            height = right.height();
+            if both_adopted {
+                self.push_internal_level(alloc.clone());
+            }
+            let parent = self.border_internal(height + 1, adopted_on_right);
+            parent.last_edge().insert_recursing(key, val, right, alloc.clone())
+        } else {
+            height = self.height();
+            mem::swap(self, &mut right);
+            let left = right;
+            let mut edge = self.border_internal(height + 1, adopted_on_right).first_edge();
+            // SAFETY: the handle is not used until the reborrow is gone.
+            let child = unsafe { edge.reborrow_mut() }.replace_child(left);
+            edge.insert_recursing(key, val, child, alloc.clone())
+        };
+        if let Some(SplitResult { kv: (key, val), right, .. }) = split {
+            self.push_internal_level(alloc.clone()).push(key, val, right.forget_type(), &alloc);
+        }
+
+        // Only the adopted root, and the ancestors it may drag along, can be
+        // underfull. So can the former root of the left tree, if it was
+        // adopted by a new root too.
+        let mut adopted = self.border_node(height, adopted_on_right);
+        adopted.fix_subtree_lens_above(&alloc);
+        adopted.fix_node_and_affected_ancestors(alloc.clone());
+        self.fix_top(alloc.clone());
+        if both_adopted && self.height() > height {
+            self.border_node(height, false).fix_node_and_affected_ancestors(alloc.clone());
+            self.fix_top(alloc);
+        }
+    }
+
+    /// Returns the node at the given height on the right or left border.
+    fn border_node(
+        &mut self,
+        height: usize,
+        on_right: bool,
+    ) -> NodeRef<marker::Mut<'_>, K, V, marker::LeafOrInternal> {
+        let mut node = self.borrow_mut();
+        while node.height() > height {
+            node = match node.force() {
+                Internal(internal) if on_right => internal.last_edge().descend(),
+                Internal(internal) => internal.first_edge().descend(),
+                Leaf(_) => unreachable!(),
+            };
+        }
+        node
+    }
+
+    /// Returns the internal node at the given height on the right or left border.
+    fn border_internal(
+        &mut self,
+        height: usize,
+        on_right: bool,
+    ) -> NodeRef<marker::Mut<'_>, K, V, marker::Internal> {
+        match self.border_node(height, on_right).force() {
+            Internal(internal) => internal,
+            Leaf(_) => unreachable!(),
+        }
     }
 }
 
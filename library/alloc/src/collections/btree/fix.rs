@@ -1,13 +1,14 @@
 use super::map::MIN_LEN;
-use super::node::{marker, ForceResult::*, Handle, LeftOrRight::*, NodeRef, Root};
-use core::alloc::Allocator;
+use super::node::{marker, LeftOrRight::*, NodeAlloc, NodeRef};
+#[cfg(not(no_global_oom_handling))]
+use super::node::{ForceResult::*, Handle, Root};
 
 impl<'a, K: 'a, V: 'a> NodeRef<marker::Mut<'a>, K, V, marker::LeafOrInternal> {
     /// Stocks up a possibly underfull node by merging with or stealing from a
     /// sibling. If successful but at the cost of shrinking the parent node,
     /// returns that shrunk parent node. Returns an `Err` if the node is
     /// an empty root.
-    fn fix_node_through_parent<A: Allocator + Clone>(
+    fn fix_node_through_parent<A: NodeAlloc>(
         self,
         alloc: A,
     ) -> Result<Option<NodeRef<marker::Mut<'a>, K, V, marker::Internal>>, Self> {
@@ -21,7 +22,7 @@ fn fix_node_through_parent<A: Allocator + Clone>(
                         let parent = left_parent_kv.merge_tracking_parent(alloc);
                         Ok(Some(parent))
                     } else {
-                        left_parent_kv.bulk_steal_left(MIN_LEN - len);
+                        left_parent_kv.bulk_steal_left(MIN_LEN - len, &alloc);
                         Ok(None)
                     }
                 }
@@ -30,7 +31,7 @@ fn fix_node_through_parent<A: Allocator + Clone>(
                         let parent = right_parent_kv.merge_tracking_parent(alloc);
                         Ok(Some(parent))
                     } else {
-                        right_parent_kv.bulk_steal_right(MIN_LEN - len);
+                        right_parent_kv.bulk_steal_right(MIN_LEN - len, &alloc);
                         Ok(None)
                     }
                 }
@@ -54,7 +55,7 @@ impl<'a, K: 'a, V: 'a> NodeRef<marker::Mut<'a>, K, V, marker::LeafOrInternal> {
     ///
     /// This method does not expect ancestors to already be underfull upon entry
     /// and panics if it encounters an empty ancestor.
-    pub fn fix_node_and_affected_ancestors<A: Allocator + Clone>(mut self, alloc: A) -> bool {
+    pub fn fix_node_and_affected_ancestors<A: NodeAlloc>(mut self, alloc: A) -> bool {
         loop {
             match self.fix_node_through_parent(alloc.clone()) {
                 Ok(Some(parent)) => self = parent.forget_type(),
@@ -65,9 +66,10 @@ pub fn fix_node_and_affected_ancestors<A: Allocator + Clone>(mut self, alloc: A)
     }
 }
 
+#[cfg(not(no_global_oom_handling))]
 impl<K, V> Root<K, V> {
     /// Removes empty levels on the top, but keeps an empty leaf if the entire tree is empty.
-    pub fn fix_top<A: Allocator + Clone>(&mut self, alloc: A) {
+    pub fn fix_top<A: NodeAlloc>(&mut self, alloc: A) {
         while self.height() > 0 && self.len() == 0 {
             self.pop_internal_level(alloc.clone());
         }
@@ -76,7 +78,7 @@ pub fn fix_top<A: Allocator + Clone>(&mut self, alloc: A) {
     /// Stocks up or merge away any underfull nodes on the right border of the
     /// tree. The other nodes, those that are not the root nor a rightmost edge,
     /// must already have at least MIN_LEN elements.
-    pub fn fix_right_border<A: Allocator + Clone>(&mut self, alloc: A) {
+    pub fn fix_right_border<A: NodeAlloc>(&mut self, alloc: A) {
         self.fix_top(alloc.clone());
         if self.len() > 0 {
             self.borrow_mut().last_kv().fix_right_border_of_right_edge(alloc.clone());
@@ -85,7 +87,7 @@ pub fn fix_right_border<A: Allocator + Clone>(&mut self, alloc: A) {
     }
 
     /// The symmetric clone of `fix_right_border`.
-    pub fn fix_left_border<A: Allocator + Clone>(&mut self, alloc: A) {
+    pub fn fix_left_border<A: NodeAlloc>(&mut self, alloc: A) {
         self.fix_top(alloc.clone());
         if self.len() > 0 {
             self.borrow_mut().first_kv().fix_left_border_of_left_edge(alloc.clone());
@@ -96,7 +98,7 @@ pub fn fix_left_border<A: Allocator + Clone>(&mut self, alloc: A) {
     /// Stocks up any underfull nodes on the right border of the tree.
     /// The other nodes, those that are neither the root nor a rightmost edge,
     /// must be prepared to have up to MIN_LEN elements stolen.
-    pub fn fix_right_border_of_plentiful(&mut self) {
+    pub fn fix_right_border_of_plentiful<A: NodeAlloc>(&mut self, alloc: &A) {
         let mut cur_node = self.borrow_mut();
         while let Internal(internal) = cur_node.force() {
             // Check if right-most child is underfull.
@@ -105,7 +107,7 @@ pub fn fix_right_border_of_plentiful(&mut self) {
             let right_child_len = last_kv.right_child_len();
             if right_child_len < MIN_LEN {
                 // We need to steal.
-                last_kv.bulk_steal_left(MIN_LEN - right_child_len);
+                last_kv.bulk_steal_left(MIN_LEN - right_child_len, alloc);
             }
 
             // Go further down.
@@ -114,15 +116,16 @@ pub fn fix_right_border_of_plentiful(&mut self) {
     }
 }
 
+#[cfg(not(no_global_oom_handling))]
 impl<'a, K: 'a, V: 'a> Handle<NodeRef<marker::Mut<'a>, K, V, marker::LeafOrInternal>, marker::KV> {
-    fn fix_left_border_of_left_edge<A: Allocator + Clone>(mut self, alloc: A) {
+    fn fix_left_border_of_left_edge<A: NodeAlloc>(mut self, alloc: A) {
         while let Internal(internal_kv) = self.force() {
             self = internal_kv.fix_left_child(alloc.clone()).first_kv();
             debug_assert!(self.reborrow().into_node().len() > MIN_LEN);
         }
     }
 
-    fn fix_right_border_of_right_edge<A: Allocator + Clone>(mut self, alloc: A) {
+    fn fix_right_border_of_right_edge<A: NodeAlloc>(mut self, alloc: A) {
         while let Internal(internal_kv) = self.force() {
             self = internal_kv.fix_right_child(alloc.clone()).last_kv();
             debug_assert!(self.reborrow().into_node().len() > MIN_LEN);
@@ -130,12 +133,13 @@ fn fix_right_border_of_right_edge<A: Allocator + Clone>(mut self, alloc: A) {
     }
 }
 
+#[cfg(not(no_global_oom_handling))]
 impl<'a, K: 'a, V: 'a> Handle<NodeRef<marker::Mut<'a>, K, V, marker::Internal>, marker::KV> {
     /// Stocks up the left child, assuming the right child isn't underfull, and
     /// provisions an extra element to allow merging its children in turn
     /// without becoming underfull.
     /// Returns the left child.
-    fn fix_left_child<A: Allocator + Clone>(
+    fn fix_left_child<A: NodeAlloc>(
         self,
         alloc: A,
     ) -> NodeRef<marker::Mut<'a>, K, V, marker::LeafOrInternal> {
@@ -148,7 +152,7 @@ fn fix_left_child<A: Allocator + Clone>(
             // `MIN_LEN + 1` to avoid readjust if merge happens on the next level.
             let count = (MIN_LEN + 1).saturating_sub(left_len);
             if count > 0 {
-                internal_kv.bulk_steal_right(count);
+                internal_kv.bulk_steal_right(count, &alloc);
             }
             internal_kv.into_left_child()
         }
@@ -158,7 +162,7 @@ fn fix_left_child<A: Allocator + Clone>(
     /// provisions an extra element to allow merging its children in turn
     /// without becoming underfull.
     /// Returns wherever the right child ended up.
-    fn fix_right_child<A: Allocator + Clone>(
+    fn fix_right_child<A: NodeAlloc>(
         self,
         alloc: A,
     ) -> NodeRef<marker::Mut<'a>, K, V, marker::LeafOrInternal> {
@@ -171,7 +175,7 @@ fn fix_right_child<A: Allocator + Clone>(
             // `MIN_LEN + 1` to avoid readjust if merge happens on the next level.
             let count = (MIN_LEN + 1).saturating_sub(right_len);
             if count > 0 {
-                internal_kv.bulk_steal_left(count);
+                internal_kv.bulk_steal_left(count, &alloc);
             }
             internal_kv.into_right_child()
         }
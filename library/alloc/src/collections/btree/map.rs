@@ -1,3 +1,4 @@
+#[cfg(not(no_global_oom_handling))]
 use crate::vec::Vec;
 use core::borrow::Borrow;
 use core::cmp::Ordering;
@@ -11,11 +12,16 @@
 use core::ptr;
 
 use crate::alloc::{Allocator, Global};
+use crate::collections::TryReserveError;
 
 use super::borrow::DormantMutRef;
+#[cfg(not(no_global_oom_handling))]
 use super::dedup_sorted_iter::DedupSortedIter;
 use super::navigate::{LazyLeafRange, LeafRange};
-use super::node::{self, marker, ForceResult::*, Handle, NodeRef, Root};
+#[cfg(not(no_global_oom_handling))]
+use super::node::ForceResult::*;
+use super::node::{self, marker, Handle, NodeAlloc, NodeRef, Root};
+use super::order_statistics::Nodes;
 use super::search::{SearchBound, SearchResult::*};
 use super::set_val::SetValZST;
 
@@ -24,6 +30,9 @@
 #[stable(feature = "rust1", since = "1.0.0")]
 pub use entry::{Entry, OccupiedEntry, OccupiedError, VacantEntry};
 
+#[unstable(feature = "btree_order_statistics", issue = "none")]
+pub use super::order_statistics::{NoStatistics, OrderStatistics, Statistics};
+
 use Entry::*;
 
 /// Minimum number of elements in a node that is not a root.
@@ -174,17 +183,20 @@ pub struct BTreeMap<
     K,
     V,
     #[unstable(feature = "allocator_api", issue = "32838")] A: Allocator + Clone = Global,
+    #[unstable(feature = "btree_order_statistics", issue = "none")] S: Statistics = NoStatistics,
 > {
     root: Option<Root<K, V>>,
     length: usize,
     /// `ManuallyDrop` to control drop order (needs to be dropped after all the nodes).
-    pub(super) alloc: ManuallyDrop<A>,
+    pub(super) alloc: ManuallyDrop<Nodes<A, S>>,
     // For dropck; the `Box` avoids making the `Unpin` impl more strict than before
     _marker: PhantomData<crate::boxed::Box<(K, V), A>>,
 }
 
 #[stable(feature = "btree_drop", since = "1.7.0")]
-unsafe impl<#[may_dangle] K, #[may_dangle] V, A: Allocator + Clone> Drop for BTreeMap<K, V, A> {
+unsafe impl<#[may_dangle] K, #[may_dangle] V, A: Allocator + Clone, S: Statistics> Drop
+    for BTreeMap<K, V, A, S>
+{
     fn drop(&mut self) {
         drop(unsafe { ptr::read(self) }.into_iter())
     }
@@ -195,7 +207,7 @@ fn drop(&mut self) {
 // Maybe we can fix it nonetheless with a crater run, or if the `UnwindSafe`
 // traits are deprecated, or disarmed (no longer causing hard errors) in the future.
 #[stable(feature = "btree_unwindsafe", since = "1.64.0")]
-impl<K, V, A: Allocator + Clone> core::panic::UnwindSafe for BTreeMap<K, V, A>
+impl<K, V, A: Allocator + Clone, S: Statistics> core::panic::UnwindSafe for BTreeMap<K, V, A, S>
 where
     A: core::panic::UnwindSafe,
     K: core::panic::RefUnwindSafe,
@@ -203,13 +215,14 @@ impl<K, V, A: Allocator + Clone> core::panic::UnwindSafe for BTreeMap<K, V, A>
 {
 }
 
+#[cfg(not(no_global_oom_handling))]
 #[stable(feature = "rust1", since = "1.0.0")]
-impl<K: Clone, V: Clone, A: Allocator + Clone> Clone for BTreeMap<K, V, A> {
-    fn clone(&self) -> BTreeMap<K, V, A> {
-        fn clone_subtree<'a, K: Clone, V: Clone, A: Allocator + Clone>(
+impl<K: Clone, V: Clone, A: Allocator + Clone, S: Statistics> Clone for BTreeMap<K, V, A, S> {
+    fn clone(&self) -> BTreeMap<K, V, A, S> {
+        fn clone_subtree<'a, K: Clone, V: Clone, A: Allocator + Clone, S: Statistics>(
             node: NodeRef<marker::Immut<'a>, K, V, marker::LeafOrInternal>,
-            alloc: A,
-        ) -> BTreeMap<K, V, A>
+            alloc: Nodes<A, S>,
+        ) -> BTreeMap<K, V, A, S>
         where
             K: 'a,
             V: 'a,
@@ -271,6 +284,7 @@ fn clone_subtree<'a, K: Clone, V: Clone, A: Allocator + Clone>(
                                 k,
                                 v,
                                 subroot.unwrap_or_else(|| Root::new(alloc.clone())),
+                                &alloc,
                             );
                             out_tree.length += 1 + sublength;
                         }
@@ -282,14 +296,15 @@ fn clone_subtree<'a, K: Clone, V: Clone, A: Allocator + Clone>(
         }
 
         if self.is_empty() {
-            BTreeMap::new_in((*self.alloc).clone())
+            BTreeMap::new_in_nodes((*self.alloc).clone())
         } else {
             clone_subtree(self.root.as_ref().unwrap().reborrow(), (*self.alloc).clone()) // unwrap succeeds because not empty
         }
     }
 }
 
-impl<K, Q: ?Sized, A: Allocator + Clone> super::Recover<Q> for BTreeMap<K, SetValZST, A>
+impl<K, Q: ?Sized, A: Allocator + Clone, S: Statistics> super::Recover<Q>
+    for BTreeMap<K, SetValZST, A, S>
 where
     K: Borrow<Q> + Ord,
     Q: Ord,
@@ -322,6 +337,7 @@ fn take(&mut self, key: &Q) -> Option<K> {
         }
     }
 
+    #[cfg(not(no_global_oom_handling))]
     fn replace(&mut self, key: K) -> Option<K> {
         let (map, dormant_map) = DormantMutRef::new(self);
         let root_node =
@@ -427,14 +443,15 @@ pub struct IntoIter<
     K,
     V,
     #[unstable(feature = "allocator_api", issue = "32838")] A: Allocator + Clone = Global,
+    #[unstable(feature = "btree_order_statistics", issue = "none")] S: Statistics = NoStatistics,
 > {
     range: LazyLeafRange<marker::Dying, K, V>,
     length: usize,
     /// The BTreeMap will outlive this IntoIter so we don't care about drop order for `alloc`.
-    alloc: A,
+    alloc: Nodes<A, S>,
 }
 
-impl<K, V, A: Allocator + Clone> IntoIter<K, V, A> {
+impl<K, V, A: Allocator + Clone, S: Statistics> IntoIter<K, V, A, S> {
     /// Returns an iterator of references over the remaining items.
     #[inline]
     pub(super) fn iter(&self) -> Iter<'_, K, V> {
@@ -443,14 +460,14 @@ pub(super) fn iter(&self) -> Iter<'_, K, V> {
 }
 
 #[stable(feature = "collection_debug", since = "1.17.0")]
-impl<K: Debug, V: Debug, A: Allocator + Clone> Debug for IntoIter<K, V, A> {
+impl<K: Debug, V: Debug, A: Allocator + Clone, S: Statistics> Debug for IntoIter<K, V, A, S> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_list().entries(self.iter()).finish()
     }
 }
 
 #[stable(feature = "default_iters", since = "1.70.0")]
-impl<K, V, A> Default for IntoIter<K, V, A>
+impl<K, V, A, S: Statistics> Default for IntoIter<K, V, A, S>
 where
     A: Allocator + Default + Clone,
 {
@@ -531,12 +548,17 @@ fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
 /// [`into_keys`]: BTreeMap::into_keys
 #[must_use = "iterators are lazy and do nothing unless consumed"]
 #[stable(feature = "map_into_keys_values", since = "1.54.0")]
-pub struct IntoKeys<K, V, A: Allocator + Clone = Global> {
-    inner: IntoIter<K, V, A>,
+pub struct IntoKeys<
+    K,
+    V,
+    A: Allocator + Clone = Global,
+    #[unstable(feature = "btree_order_statistics", issue = "none")] S: Statistics = NoStatistics,
+> {
+    inner: IntoIter<K, V, A, S>,
 }
 
 #[stable(feature = "map_into_keys_values", since = "1.54.0")]
-impl<K: fmt::Debug, V, A: Allocator + Clone> fmt::Debug for IntoKeys<K, V, A> {
+impl<K: fmt::Debug, V, A: Allocator + Clone, S: Statistics> fmt::Debug for IntoKeys<K, V, A, S> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_list().entries(self.inner.iter().map(|(key, _)| key)).finish()
     }
@@ -554,12 +576,13 @@ pub struct IntoValues<
     K,
     V,
     #[unstable(feature = "allocator_api", issue = "32838")] A: Allocator + Clone = Global,
+    #[unstable(feature = "btree_order_statistics", issue = "none")] S: Statistics = NoStatistics,
 > {
-    inner: IntoIter<K, V, A>,
+    inner: IntoIter<K, V, A, S>,
 }
 
 #[stable(feature = "map_into_keys_values", since = "1.54.0")]
-impl<K, V: fmt::Debug, A: Allocator + Clone> fmt::Debug for IntoValues<K, V, A> {
+impl<K, V: fmt::Debug, A: Allocator + Clone, S: Statistics> fmt::Debug for IntoValues<K, V, A, S> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_list().entries(self.inner.iter().map(|(_, val)| val)).finish()
     }
@@ -627,11 +650,107 @@ impl<K, V> BTreeMap<K, V> {
     #[inline]
     #[must_use]
     pub const fn new() -> BTreeMap<K, V> {
-        BTreeMap { root: None, length: 0, alloc: ManuallyDrop::new(Global), _marker: PhantomData }
+        BTreeMap::new_in(Global)
+    }
+
+    /// Creates a `BTreeMap` from an iterator, returning an error instead of
+    /// panicking or aborting if allocating a node fails.
+    ///
+    /// As with `collect`, later values overwrite earlier values with the same key.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(fallible_allocation)]
+    /// use std::collections::BTreeMap;
+    ///
+    /// let map = BTreeMap::try_from_iter([(2, "b"), (1, "a"), (2, "c")]).unwrap();
+    /// assert_eq!(map.into_iter().collect::<Vec<_>>(), [(1, "a"), (2, "c")]);
+    /// ```
+    #[unstable(feature = "fallible_allocation", issue = "none")]
+    pub fn try_from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Result<Self, TryReserveError>
+    where
+        K: Ord,
+    {
+        let mut map = BTreeMap::new();
+        map.try_extend(iter)?;
+        Ok(map)
+    }
+}
+
+impl<K, V> BTreeMap<K, V, Global, OrderStatistics> {
+    /// Makes a new, empty `BTreeMap` that keeps [`OrderStatistics`], so that
+    /// [`nth`], [`rank`] and [`range_count`] take logarithmic time.
+    ///
+    /// Does not allocate anything on its own.
+    ///
+    /// [`nth`]: BTreeMap::nth
+    /// [`rank`]: BTreeMap::rank
+    /// [`range_count`]: BTreeMap::range_count
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(btree_order_statistics)]
+    /// use std::collections::BTreeMap;
+    ///
+    /// let mut map = BTreeMap::with_order_statistics();
+    /// map.insert(3, "c");
+    /// map.insert(1, "a");
+    /// assert_eq!(map.nth(1), Some((&3, &"c")));
+    /// ```
+    #[unstable(feature = "btree_order_statistics", issue = "none")]
+    #[must_use]
+    pub const fn with_order_statistics() -> Self {
+        BTreeMap::with_order_statistics_in(Global)
     }
 }
 
 impl<K, V, A: Allocator + Clone> BTreeMap<K, V, A> {
+    /// Makes a new empty BTreeMap with a reasonable choice for B.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #![feature(allocator_api)]
+    /// # #![feature(btreemap_alloc)]
+    /// use std::collections::BTreeMap;
+    /// use std::alloc::Global;
+    ///
+    /// let mut map = BTreeMap::new_in(Global);
+    ///
+    /// // entries can now be inserted into the empty map
+    /// map.insert(1, "a");
+    /// ```
+    #[unstable(feature = "btreemap_alloc", issue = "32838")]
+    pub const fn new_in(alloc: A) -> BTreeMap<K, V, A> {
+        BTreeMap::new_in_nodes(Nodes::new(alloc))
+    }
+}
+
+impl<K, V, A: Allocator + Clone> BTreeMap<K, V, A, OrderStatistics> {
+    /// Makes a new, empty `BTreeMap` that keeps [`OrderStatistics`] and
+    /// allocates through the provided allocator.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #![feature(allocator_api)]
+    /// # #![feature(btree_order_statistics)]
+    /// use std::collections::BTreeMap;
+    /// use std::alloc::Global;
+    ///
+    /// let mut map = BTreeMap::with_order_statistics_in(Global);
+    /// map.insert(1, "a");
+    /// assert_eq!(map.rank(&1), 0);
+    /// ```
+    #[unstable(feature = "btree_order_statistics", issue = "none")]
+    pub const fn with_order_statistics_in(alloc: A) -> Self {
+        BTreeMap::new_in_nodes(Nodes::new(alloc))
+    }
+}
+
+impl<K, V, A: Allocator + Clone, S: Statistics> BTreeMap<K, V, A, S> {
     /// Clears the map, removing all elements.
     ///
     /// # Examples
@@ -655,7 +774,12 @@ pub fn clear(&mut self) {
         });
     }
 
-    /// Makes a new empty BTreeMap with a reasonable choice for B.
+    /// Makes a new empty map that allocates its nodes through `alloc`.
+    const fn new_in_nodes(alloc: Nodes<A, S>) -> Self {
+        BTreeMap { root: None, length: 0, alloc: ManuallyDrop::new(alloc), _marker: PhantomData }
+    }
+
+    /// Returns a reference to the underlying allocator.
     ///
     /// # Examples
     ///
@@ -665,18 +789,17 @@ pub fn clear(&mut self) {
     /// use std::collections::BTreeMap;
     /// use std::alloc::Global;
     ///
-    /// let mut map = BTreeMap::new_in(Global);
-    ///
-    /// // entries can now be inserted into the empty map
-    /// map.insert(1, "a");
+    /// let map: BTreeMap<i32, i32, _> = BTreeMap::new_in(Global);
+    /// let _: &Global = map.allocator();
     /// ```
-    #[unstable(feature = "btreemap_alloc", issue = "32838")]
-    pub const fn new_in(alloc: A) -> BTreeMap<K, V, A> {
-        BTreeMap { root: None, length: 0, alloc: ManuallyDrop::new(alloc), _marker: PhantomData }
+    #[unstable(feature = "allocator_api", issue = "32838")]
+    #[inline]
+    pub fn allocator(&self) -> &A {
+        self.alloc.allocator()
     }
 }
 
-impl<K, V, A: Allocator + Clone> BTreeMap<K, V, A> {
+impl<K, V, A: Allocator + Clone, S: Statistics> BTreeMap<K, V, A, S> {
     /// Returns a reference to the value corresponding to the key.
     ///
     /// The key may be any borrowed form of the map's key type, but the ordering
@@ -776,7 +899,7 @@ pub fn first_key_value(&self) -> Option<(&K, &V)>
     /// assert_eq!(*map.get(&2).unwrap(), "b");
     /// ```
     #[stable(feature = "map_first_last", since = "1.66.0")]
-    pub fn first_entry(&mut self) -> Option<OccupiedEntry<'_, K, V, A>>
+    pub fn first_entry(&mut self) -> Option<OccupiedEntry<'_, K, V, A, S>>
     where
         K: Ord,
     {
@@ -859,7 +982,7 @@ pub fn last_key_value(&self) -> Option<(&K, &V)>
     /// assert_eq!(*map.get(&2).unwrap(), "last");
     /// ```
     #[stable(feature = "map_first_last", since = "1.66.0")]
-    pub fn last_entry(&mut self) -> Option<OccupiedEntry<'_, K, V, A>>
+    pub fn last_entry(&mut self) -> Option<OccupiedEntry<'_, K, V, A, S>>
     where
         K: Ord,
     {
@@ -979,6 +1102,7 @@ pub fn get_mut<Q: ?Sized>(&mut self, key: &Q) -> Option<&mut V>
     /// assert_eq!(map.insert(37, "c"), Some("b"));
     /// assert_eq!(map[&37], "c");
     /// ```
+    #[cfg(not(no_global_oom_handling))]
     #[stable(feature = "rust1", since = "1.0.0")]
     #[rustc_confusables("push", "put", "set")]
     pub fn insert(&mut self, key: K, value: V) -> Option<V>
@@ -1015,8 +1139,9 @@ pub fn insert(&mut self, key: K, value: V) -> Option<V>
     /// assert_eq!(err.entry.get(), &"a");
     /// assert_eq!(err.value, "b");
     /// ```
+    #[cfg(not(no_global_oom_handling))]
     #[unstable(feature = "map_try_insert", issue = "82766")]
-    pub fn try_insert(&mut self, key: K, value: V) -> Result<&mut V, OccupiedError<'_, K, V, A>>
+    pub fn try_insert(&mut self, key: K, value: V) -> Result<&mut V, OccupiedError<'_, K, V, A, S>>
     where
         K: Ord,
     {
@@ -1146,6 +1271,7 @@ pub fn retain<F>(&mut self, mut f: F)
     /// assert_eq!(a[&4], "e");
     /// assert_eq!(a[&5], "f");
     /// ```
+    #[cfg(not(no_global_oom_handling))]
     #[stable(feature = "btree_append", since = "1.11.0")]
     pub fn append(&mut self, other: &mut Self)
     where
@@ -1163,8 +1289,8 @@ pub fn append(&mut self, other: &mut Self)
             return;
         }
 
-        let self_iter = mem::replace(self, Self::new_in((*self.alloc).clone())).into_iter();
-        let other_iter = mem::replace(other, Self::new_in((*self.alloc).clone())).into_iter();
+        let self_iter = mem::replace(self, Self::new_in_nodes((*self.alloc).clone())).into_iter();
+        let other_iter = mem::replace(other, Self::new_in_nodes((*self.alloc).clone())).into_iter();
         let root = self.root.get_or_insert_with(|| Root::new((*self.alloc).clone()));
         root.append_from_sorted_iters(
             self_iter,
@@ -1174,6 +1300,78 @@ pub fn append(&mut self, other: &mut Self)
         )
     }
 
+    /// Extends the map with the key-value pairs of an iterator, returning an
+    /// error instead of panicking or aborting if allocating a node fails.
+    ///
+    /// Values of keys already present are overwritten, as with `extend`.
+    /// If an error is returned, the pairs inserted before the failure remain
+    /// in the map, and the remainder of the iterator is dropped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(fallible_allocation)]
+    /// use std::collections::BTreeMap;
+    ///
+    /// let mut map = BTreeMap::from([(1, "a")]);
+    /// map.try_extend([(1, "b"), (2, "c")]).unwrap();
+    /// assert_eq!(map.into_iter().collect::<Vec<_>>(), [(1, "b"), (2, "c")]);
+    /// ```
+    #[unstable(feature = "fallible_allocation", issue = "none")]
+    pub fn try_extend<I: IntoIterator<Item = (K, V)>>(
+        &mut self,
+        iter: I,
+    ) -> Result<(), TryReserveError>
+    where
+        K: Ord,
+    {
+        for (key, value) in iter {
+            match self.entry(key) {
+                Occupied(mut entry) => {
+                    entry.insert(value);
+                }
+                Vacant(entry) => {
+                    entry.try_insert(value)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Clones the map, returning an error instead of panicking or aborting
+    /// if allocating a node fails.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(fallible_allocation)]
+    /// use std::collections::BTreeMap;
+    ///
+    /// let map = BTreeMap::from([(1, "a"), (2, "b")]);
+    /// assert_eq!(map.try_clone().unwrap(), map);
+    /// ```
+    #[unstable(feature = "fallible_allocation", issue = "none")]
+    pub fn try_clone(&self) -> Result<Self, TryReserveError>
+    where
+        K: Ord + Clone,
+        V: Clone,
+    {
+        let mut clone = BTreeMap::new_in_nodes((*self.alloc).clone());
+        for (key, value) in self {
+            // The keys arrive in order, so each one belongs right after the last.
+            let (map, dormant_map) = DormantMutRef::new(&mut clone);
+            let entry = VacantEntry {
+                key: key.clone(),
+                handle: map.root.as_mut().map(|root| root.borrow_mut().last_leaf_edge()),
+                dormant_map,
+                alloc: (*map.alloc).clone(),
+                _marker: PhantomData,
+            };
+            entry.try_insert(value.clone())?;
+        }
+        Ok(clone)
+    }
+
     /// Constructs a double-ended iterator over a sub-range of elements in the map.
     /// The simplest way is to use the range syntax `min..max`, thus `range(min..max)` will
     /// yield elements from min (inclusive) to max (exclusive).
@@ -1274,7 +1472,7 @@ pub fn range_mut<T: ?Sized, R>(&mut self, range: R) -> RangeMut<'_, K, V>
     /// assert_eq!(count["c"], 1);
     /// ```
     #[stable(feature = "rust1", since = "1.0.0")]
-    pub fn entry(&mut self, key: K) -> Entry<'_, K, V, A>
+    pub fn entry(&mut self, key: K) -> Entry<'_, K, V, A, S>
     where
         K: Ord,
     {
@@ -1332,6 +1530,7 @@ pub fn entry(&mut self, key: K) -> Entry<'_, K, V, A>
     /// assert_eq!(b[&17], "d");
     /// assert_eq!(b[&41], "e");
     /// ```
+    #[cfg(not(no_global_oom_handling))]
     #[stable(feature = "btree_split_off", since = "1.11.0")]
     pub fn split_off<Q: ?Sized + Ord>(&mut self, key: &Q) -> Self
     where
@@ -1339,7 +1538,7 @@ pub fn split_off<Q: ?Sized + Ord>(&mut self, key: &Q) -> Self
         A: Clone,
     {
         if self.is_empty() {
-            return Self::new_in((*self.alloc).clone());
+            return Self::new_in_nodes((*self.alloc).clone());
         }
 
         let total_num = self.len();
@@ -1388,7 +1587,7 @@ pub fn split_off<Q: ?Sized + Ord>(&mut self, key: &Q) -> Self
     /// assert_eq!(odds.keys().copied().collect::<Vec<_>>(), [1, 3, 5, 7]);
     /// ```
     #[unstable(feature = "btree_extract_if", issue = "70530")]
-    pub fn extract_if<F>(&mut self, pred: F) -> ExtractIf<'_, K, V, F, A>
+    pub fn extract_if<F>(&mut self, pred: F) -> ExtractIf<'_, K, V, F, A, S>
     where
         K: Ord,
         F: FnMut(&K, &mut V) -> bool,
@@ -1397,7 +1596,7 @@ pub fn extract_if<F>(&mut self, pred: F) -> ExtractIf<'_, K, V, F, A>
         ExtractIf { pred, inner, alloc }
     }
 
-    pub(super) fn extract_if_inner(&mut self) -> (ExtractIfInner<'_, K, V>, A)
+    pub(super) fn extract_if_inner(&mut self) -> (ExtractIfInner<'_, K, V>, Nodes<A, S>)
     where
         K: Ord,
     {
@@ -1442,7 +1641,7 @@ pub(super) fn extract_if_inner(&mut self) -> (ExtractIfInner<'_, K, V>, A)
     /// ```
     #[inline]
     #[stable(feature = "map_into_keys_values", since = "1.54.0")]
-    pub fn into_keys(self) -> IntoKeys<K, V, A> {
+    pub fn into_keys(self) -> IntoKeys<K, V, A, S> {
         IntoKeys { inner: self.into_iter() }
     }
 
@@ -1464,12 +1663,13 @@ pub fn into_keys(self) -> IntoKeys<K, V, A> {
     /// ```
     #[inline]
     #[stable(feature = "map_into_keys_values", since = "1.54.0")]
-    pub fn into_values(self) -> IntoValues<K, V, A> {
+    pub fn into_values(self) -> IntoValues<K, V, A, S> {
         IntoValues { inner: self.into_iter() }
     }
 
     /// Makes a `BTreeMap` from a sorted iterator.
-    pub(crate) fn bulk_build_from_sorted_iter<I>(iter: I, alloc: A) -> Self
+    #[cfg(not(no_global_oom_handling))]
+    pub(crate) fn bulk_build_from_sorted_iter<I>(iter: I, alloc: Nodes<A, S>) -> Self
     where
         K: Ord,
         I: IntoIterator<Item = (K, V)>,
@@ -1481,8 +1681,113 @@ pub(crate) fn bulk_build_from_sorted_iter<I>(iter: I, alloc: A) -> Self
     }
 }
 
+impl<K, V, A: Allocator + Clone> BTreeMap<K, V, A, OrderStatistics> {
+    /// Returns the key-value pair at position `index` in the map, counting from
+    /// zero in ascending key order, or `None` if the map has no more than `index`
+    /// elements.
+    ///
+    /// This takes logarithmic time.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(btree_order_statistics)]
+    /// use std::collections::BTreeMap;
+    ///
+    /// let mut map = BTreeMap::with_order_statistics();
+    /// map.extend([(3, "c"), (1, "a"), (2, "b")]);
+    /// assert_eq!(map.nth(0), Some((&1, &"a")));
+    /// assert_eq!(map.nth(2), Some((&3, &"c")));
+    /// assert_eq!(map.nth(3), None);
+    /// ```
+    #[unstable(feature = "btree_order_statistics", issue = "none")]
+    pub fn nth(&self, index: usize) -> Option<(&K, &V)>
+    where
+        K: Ord,
+    {
+        let root_node = self.root.as_ref()?.reborrow();
+        // SAFETY: maps with `OrderStatistics` keep subtree lengths.
+        unsafe { root_node.find_kv_at(index) }.map(Handle::into_kv)
+    }
+
+    /// Returns the number of keys in the map that are less than `key`, i.e. the
+    /// position at which `key` is or would be in the map, counting from zero.
+    ///
+    /// The supplied key may be any borrowed form of the map's key type, but the ordering
+    /// on the borrowed form *must* match the ordering on the key type.
+    ///
+    /// This takes logarithmic time.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(btree_order_statistics)]
+    /// use std::collections::BTreeMap;
+    ///
+    /// let mut map = BTreeMap::with_order_statistics();
+    /// map.extend([(10, "a"), (20, "b"), (30, "c")]);
+    /// assert_eq!(map.rank(&5), 0);
+    /// assert_eq!(map.rank(&20), 1);
+    /// assert_eq!(map.rank(&25), 2);
+    /// assert_eq!(map.nth(map.rank(&20)), Some((&20, &"b")));
+    /// ```
+    #[unstable(feature = "btree_order_statistics", issue = "none")]
+    pub fn rank<Q: ?Sized>(&self, key: &Q) -> usize
+    where
+        K: Borrow<Q> + Ord,
+        Q: Ord,
+    {
+        match &self.root {
+            // SAFETY: maps with `OrderStatistics` keep subtree lengths.
+            Some(root) => unsafe {
+                root.reborrow().count_below_lower_bound(SearchBound::Included(key))
+            },
+            None => 0,
+        }
+    }
+
+    /// Returns the number of elements in a sub-range of the map, which is the
+    /// number of elements that [`range`] would yield for the same range.
+    ///
+    /// This takes logarithmic time.
+    ///
+    /// [`range`]: BTreeMap::range
+    ///
+    /// # Panics
+    ///
+    /// Panics if range `start > end`.
+    /// Panics if range `start == end` and both bounds are `Excluded`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(btree_order_statistics)]
+    /// use std::collections::BTreeMap;
+    /// use std::ops::Bound::{Excluded, Included};
+    ///
+    /// let mut map = BTreeMap::with_order_statistics();
+    /// map.extend((0..100).map(|i| (i * 2, i)));
+    /// assert_eq!(map.range_count(10..20), 5);
+    /// assert_eq!(map.range_count((Excluded(10), Included(20))), 5);
+    /// assert_eq!(map.range_count(..), 100);
+    /// ```
+    #[unstable(feature = "btree_order_statistics", issue = "none")]
+    pub fn range_count<T: ?Sized, R>(&self, range: R) -> usize
+    where
+        T: Ord,
+        K: Borrow<T> + Ord,
+        R: RangeBounds<T>,
+    {
+        match &self.root {
+            // SAFETY: maps with `OrderStatistics` keep subtree lengths.
+            Some(root) => unsafe { root.reborrow().count_in_range(&range) },
+            None => 0,
+        }
+    }
+}
+
 #[stable(feature = "rust1", since = "1.0.0")]
-impl<'a, K, V, A: Allocator + Clone> IntoIterator for &'a BTreeMap<K, V, A> {
+impl<'a, K, V, A: Allocator + Clone, S: Statistics> IntoIterator for &'a BTreeMap<K, V, A, S> {
     type Item = (&'a K, &'a V);
     type IntoIter = Iter<'a, K, V>;
 
@@ -1557,7 +1862,7 @@ fn clone(&self) -> Self {
 }
 
 #[stable(feature = "rust1", since = "1.0.0")]
-impl<'a, K, V, A: Allocator + Clone> IntoIterator for &'a mut BTreeMap<K, V, A> {
+impl<'a, K, V, A: Allocator + Clone, S: Statistics> IntoIterator for &'a mut BTreeMap<K, V, A, S> {
     type Item = (&'a K, &'a mut V);
     type IntoIter = IterMut<'a, K, V>;
 
@@ -1633,12 +1938,12 @@ pub(super) fn iter(&self) -> Iter<'_, K, V> {
 }
 
 #[stable(feature = "rust1", since = "1.0.0")]
-impl<K, V, A: Allocator + Clone> IntoIterator for BTreeMap<K, V, A> {
+impl<K, V, A: Allocator + Clone, S: Statistics> IntoIterator for BTreeMap<K, V, A, S> {
     type Item = (K, V);
-    type IntoIter = IntoIter<K, V, A>;
+    type IntoIter = IntoIter<K, V, A, S>;
 
     /// Gets an owning iterator over the entries of the map, sorted by key.
-    fn into_iter(self) -> IntoIter<K, V, A> {
+    fn into_iter(self) -> IntoIter<K, V, A, S> {
         let mut me = ManuallyDrop::new(self);
         if let Some(root) = me.root.take() {
             let full_range = root.into_dying().full_range();
@@ -1659,11 +1964,13 @@ fn into_iter(self) -> IntoIter<K, V, A> {
 }
 
 #[stable(feature = "btree_drop", since = "1.7.0")]
-impl<K, V, A: Allocator + Clone> Drop for IntoIter<K, V, A> {
+impl<K, V, A: Allocator + Clone, S: Statistics> Drop for IntoIter<K, V, A, S> {
     fn drop(&mut self) {
-        struct DropGuard<'a, K, V, A: Allocator + Clone>(&'a mut IntoIter<K, V, A>);
+        struct DropGuard<'a, K, V, A: Allocator + Clone, S: Statistics>(
+            &'a mut IntoIter<K, V, A, S>,
+        );
 
-        impl<'a, K, V, A: Allocator + Clone> Drop for DropGuard<'a, K, V, A> {
+        impl<'a, K, V, A: Allocator + Clone, S: Statistics> Drop for DropGuard<'a, K, V, A, S> {
             fn drop(&mut self) {
                 // Continue the same loop we perform below. This only runs when unwinding, so we
                 // don't have to care about panics this time (they'll abort).
@@ -1683,7 +1990,7 @@ fn drop(&mut self) {
     }
 }
 
-impl<K, V, A: Allocator + Clone> IntoIter<K, V, A> {
+impl<K, V, A: Allocator + Clone, S: Statistics> IntoIter<K, V, A, S> {
     /// Core of a `next` method returning a dying KV handle,
     /// invalidated by further calls to this function and some others.
     fn dying_next(
@@ -1714,7 +2021,7 @@ fn dying_next_back(
 }
 
 #[stable(feature = "rust1", since = "1.0.0")]
-impl<K, V, A: Allocator + Clone> Iterator for IntoIter<K, V, A> {
+impl<K, V, A: Allocator + Clone, S: Statistics> Iterator for IntoIter<K, V, A, S> {
     type Item = (K, V);
 
     fn next(&mut self) -> Option<(K, V)> {
@@ -1728,7 +2035,7 @@ fn size_hint(&self) -> (usize, Option<usize>) {
 }
 
 #[stable(feature = "rust1", since = "1.0.0")]
-impl<K, V, A: Allocator + Clone> DoubleEndedIterator for IntoIter<K, V, A> {
+impl<K, V, A: Allocator + Clone, S: Statistics> DoubleEndedIterator for IntoIter<K, V, A, S> {
     fn next_back(&mut self) -> Option<(K, V)> {
         // SAFETY: we consume the dying handle immediately.
         self.dying_next_back().map(unsafe { |kv| kv.into_key_val() })
@@ -1736,14 +2043,14 @@ fn next_back(&mut self) -> Option<(K, V)> {
 }
 
 #[stable(feature = "rust1", since = "1.0.0")]
-impl<K, V, A: Allocator + Clone> ExactSizeIterator for IntoIter<K, V, A> {
+impl<K, V, A: Allocator + Clone, S: Statistics> ExactSizeIterator for IntoIter<K, V, A, S> {
     fn len(&self) -> usize {
         self.length
     }
 }
 
 #[stable(feature = "fused", since = "1.26.0")]
-impl<K, V, A: Allocator + Clone> FusedIterator for IntoIter<K, V, A> {}
+impl<K, V, A: Allocator + Clone, S: Statistics> FusedIterator for IntoIter<K, V, A, S> {}
 
 #[stable(feature = "rust1", since = "1.0.0")]
 impl<'a, K, V> Iterator for Keys<'a, K, V> {
@@ -1878,13 +2185,14 @@ pub struct ExtractIf<
     V,
     F,
     #[unstable(feature = "allocator_api", issue = "32838")] A: Allocator + Clone = Global,
+    #[unstable(feature = "btree_order_statistics", issue = "none")] S: Statistics = NoStatistics,
 > where
     F: 'a + FnMut(&K, &mut V) -> bool,
 {
     pred: F,
     inner: ExtractIfInner<'a, K, V>,
     /// The BTreeMap will outlive this IntoIter so we don't care about drop order for `alloc`.
-    alloc: A,
+    alloc: Nodes<A, S>,
 }
 /// Most of the implementation of ExtractIf are generic over the type
 /// of the predicate, thus also serving for BTreeSet::ExtractIf.
@@ -1913,7 +2221,7 @@ fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
 }
 
 #[unstable(feature = "btree_extract_if", issue = "70530")]
-impl<K, V, F, A: Allocator + Clone> Iterator for ExtractIf<'_, K, V, F, A>
+impl<K, V, F, A: Allocator + Clone, S: Statistics> Iterator for ExtractIf<'_, K, V, F, A, S>
 where
     F: FnMut(&K, &mut V) -> bool,
 {
@@ -1936,7 +2244,7 @@ pub(super) fn peek(&self) -> Option<(&K, &V)> {
     }
 
     /// Implementation of a typical `ExtractIf::next` method, given the predicate.
-    pub(super) fn next<F, A: Allocator + Clone>(&mut self, pred: &mut F, alloc: A) -> Option<(K, V)>
+    pub(super) fn next<F, A: NodeAlloc>(&mut self, pred: &mut F, alloc: A) -> Option<(K, V)>
     where
         F: FnMut(&K, &mut V) -> bool,
     {
@@ -2051,7 +2359,7 @@ fn len(&self) -> usize {
 impl<K, V> FusedIterator for ValuesMut<'_, K, V> {}
 
 #[stable(feature = "map_into_keys_values", since = "1.54.0")]
-impl<K, V, A: Allocator + Clone> Iterator for IntoKeys<K, V, A> {
+impl<K, V, A: Allocator + Clone, S: Statistics> Iterator for IntoKeys<K, V, A, S> {
     type Item = K;
 
     fn next(&mut self) -> Option<K> {
@@ -2082,24 +2390,24 @@ fn max(mut self) -> Option<K>
 }
 
 #[stable(feature = "map_into_keys_values", since = "1.54.0")]
-impl<K, V, A: Allocator + Clone> DoubleEndedIterator for IntoKeys<K, V, A> {
+impl<K, V, A: Allocator + Clone, S: Statistics> DoubleEndedIterator for IntoKeys<K, V, A, S> {
     fn next_back(&mut self) -> Option<K> {
         self.inner.next_back().map(|(k, _)| k)
     }
 }
 
 #[stable(feature = "map_into_keys_values", since = "1.54.0")]
-impl<K, V, A: Allocator + Clone> ExactSizeIterator for IntoKeys<K, V, A> {
+impl<K, V, A: Allocator + Clone, S: Statistics> ExactSizeIterator for IntoKeys<K, V, A, S> {
     fn len(&self) -> usize {
         self.inner.len()
     }
 }
 
 #[stable(feature = "map_into_keys_values", since = "1.54.0")]
-impl<K, V, A: Allocator + Clone> FusedIterator for IntoKeys<K, V, A> {}
+impl<K, V, A: Allocator + Clone, S: Statistics> FusedIterator for IntoKeys<K, V, A, S> {}
 
 #[stable(feature = "default_iters", since = "1.70.0")]
-impl<K, V, A> Default for IntoKeys<K, V, A>
+impl<K, V, A, S: Statistics> Default for IntoKeys<K, V, A, S>
 where
     A: Allocator + Default + Clone,
 {
@@ -2116,7 +2424,7 @@ fn default() -> Self {
 }
 
 #[stable(feature = "map_into_keys_values", since = "1.54.0")]
-impl<K, V, A: Allocator + Clone> Iterator for IntoValues<K, V, A> {
+impl<K, V, A: Allocator + Clone, S: Statistics> Iterator for IntoValues<K, V, A, S> {
     type Item = V;
 
     fn next(&mut self) -> Option<V> {
@@ -2133,24 +2441,24 @@ fn last(mut self) -> Option<V> {
 }
 
 #[stable(feature = "map_into_keys_values", since = "1.54.0")]
-impl<K, V, A: Allocator + Clone> DoubleEndedIterator for IntoValues<K, V, A> {
+impl<K, V, A: Allocator + Clone, S: Statistics> DoubleEndedIterator for IntoValues<K, V, A, S> {
     fn next_back(&mut self) -> Option<V> {
         self.inner.next_back().map(|(_, v)| v)
     }
 }
 
 #[stable(feature = "map_into_keys_values", since = "1.54.0")]
-impl<K, V, A: Allocator + Clone> ExactSizeIterator for IntoValues<K, V, A> {
+impl<K, V, A: Allocator + Clone, S: Statistics> ExactSizeIterator for IntoValues<K, V, A, S> {
     fn len(&self) -> usize {
         self.inner.len()
     }
 }
 
 #[stable(feature = "map_into_keys_values", since = "1.54.0")]
-impl<K, V, A: Allocator + Clone> FusedIterator for IntoValues<K, V, A> {}
+impl<K, V, A: Allocator + Clone, S: Statistics> FusedIterator for IntoValues<K, V, A, S> {}
 
 #[stable(feature = "default_iters", since = "1.70.0")]
-impl<K, V, A> Default for IntoValues<K, V, A>
+impl<K, V, A, S: Statistics> Default for IntoValues<K, V, A, S>
 where
     A: Allocator + Default + Clone,
 {
@@ -2220,6 +2528,7 @@ fn next_back(&mut self) -> Option<(&'a K, &'a mut V)> {
 #[stable(feature = "fused", since = "1.26.0")]
 impl<K, V> FusedIterator for RangeMut<'_, K, V> {}
 
+#[cfg(not(no_global_oom_handling))]
 #[stable(feature = "rust1", since = "1.0.0")]
 impl<K: Ord, V> FromIterator<(K, V)> for BTreeMap<K, V> {
     fn from_iter<T: IntoIterator<Item = (K, V)>>(iter: T) -> BTreeMap<K, V> {
@@ -2231,12 +2540,13 @@ fn from_iter<T: IntoIterator<Item = (K, V)>>(iter: T) -> BTreeMap<K, V> {
 
         // use stable sort to preserve the insertion order.
         inputs.sort_by(|a, b| a.0.cmp(&b.0));
-        BTreeMap::bulk_build_from_sorted_iter(inputs, Global)
+        BTreeMap::bulk_build_from_sorted_iter(inputs, Nodes::new(Global))
     }
 }
 
+#[cfg(not(no_global_oom_handling))]
 #[stable(feature = "rust1", since = "1.0.0")]
-impl<K: Ord, V, A: Allocator + Clone> Extend<(K, V)> for BTreeMap<K, V, A> {
+impl<K: Ord, V, A: Allocator + Clone, S: Statistics> Extend<(K, V)> for BTreeMap<K, V, A, S> {
     #[inline]
     fn extend<T: IntoIterator<Item = (K, V)>>(&mut self, iter: T) {
         iter.into_iter().for_each(move |(k, v)| {
@@ -2250,9 +2560,10 @@ fn extend_one(&mut self, (k, v): (K, V)) {
     }
 }
 
+#[cfg(not(no_global_oom_handling))]
 #[stable(feature = "extend_ref", since = "1.2.0")]
-impl<'a, K: Ord + Copy, V: Copy, A: Allocator + Clone> Extend<(&'a K, &'a V)>
-    for BTreeMap<K, V, A>
+impl<'a, K: Ord + Copy, V: Copy, A: Allocator + Clone, S: Statistics> Extend<(&'a K, &'a V)>
+    for BTreeMap<K, V, A, S>
 {
     fn extend<I: IntoIterator<Item = (&'a K, &'a V)>>(&mut self, iter: I) {
         self.extend(iter.into_iter().map(|(&key, &value)| (key, value)));
@@ -2265,7 +2576,7 @@ fn extend_one(&mut self, (&k, &v): (&'a K, &'a V)) {
 }
 
 #[stable(feature = "rust1", since = "1.0.0")]
-impl<K: Hash, V: Hash, A: Allocator + Clone> Hash for BTreeMap<K, V, A> {
+impl<K: Hash, V: Hash, A: Allocator + Clone, S: Statistics> Hash for BTreeMap<K, V, A, S> {
     fn hash<H: Hasher>(&self, state: &mut H) {
         state.write_length_prefix(self.len());
         for elt in self {
@@ -2283,40 +2594,44 @@ fn default() -> BTreeMap<K, V> {
 }
 
 #[stable(feature = "rust1", since = "1.0.0")]
-impl<K: PartialEq, V: PartialEq, A: Allocator + Clone> PartialEq for BTreeMap<K, V, A> {
-    fn eq(&self, other: &BTreeMap<K, V, A>) -> bool {
+impl<K: PartialEq, V: PartialEq, A: Allocator + Clone, S: Statistics> PartialEq
+    for BTreeMap<K, V, A, S>
+{
+    fn eq(&self, other: &BTreeMap<K, V, A, S>) -> bool {
         self.len() == other.len() && self.iter().zip(other).all(|(a, b)| a == b)
     }
 }
 
 #[stable(feature = "rust1", since = "1.0.0")]
-impl<K: Eq, V: Eq, A: Allocator + Clone> Eq for BTreeMap<K, V, A> {}
+impl<K: Eq, V: Eq, A: Allocator + Clone, S: Statistics> Eq for BTreeMap<K, V, A, S> {}
 
 #[stable(feature = "rust1", since = "1.0.0")]
-impl<K: PartialOrd, V: PartialOrd, A: Allocator + Clone> PartialOrd for BTreeMap<K, V, A> {
+impl<K: PartialOrd, V: PartialOrd, A: Allocator + Clone, S: Statistics> PartialOrd
+    for BTreeMap<K, V, A, S>
+{
     #[inline]
-    fn partial_cmp(&self, other: &BTreeMap<K, V, A>) -> Option<Ordering> {
+    fn partial_cmp(&self, other: &BTreeMap<K, V, A, S>) -> Option<Ordering> {
         self.iter().partial_cmp(other.iter())
     }
 }
 
 #[stable(feature = "rust1", since = "1.0.0")]
-impl<K: Ord, V: Ord, A: Allocator + Clone> Ord for BTreeMap<K, V, A> {
+impl<K: Ord, V: Ord, A: Allocator + Clone, S: Statistics> Ord for BTreeMap<K, V, A, S> {
     #[inline]
-    fn cmp(&self, other: &BTreeMap<K, V, A>) -> Ordering {
+    fn cmp(&self, other: &BTreeMap<K, V, A, S>) -> Ordering {
         self.iter().cmp(other.iter())
     }
 }
 
 #[stable(feature = "rust1", since = "1.0.0")]
-impl<K: Debug, V: Debug, A: Allocator + Clone> Debug for BTreeMap<K, V, A> {
+impl<K: Debug, V: Debug, A: Allocator + Clone, S: Statistics> Debug for BTreeMap<K, V, A, S> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_map().entries(self.iter()).finish()
     }
 }
 
 #[stable(feature = "rust1", since = "1.0.0")]
-impl<K, Q: ?Sized, V, A: Allocator + Clone> Index<&Q> for BTreeMap<K, V, A>
+impl<K, Q: ?Sized, V, A: Allocator + Clone, S: Statistics> Index<&Q> for BTreeMap<K, V, A, S>
 where
     K: Borrow<Q> + Ord,
     Q: Ord,
@@ -2334,6 +2649,7 @@ fn index(&self, key: &Q) -> &V {
     }
 }
 
+#[cfg(not(no_global_oom_handling))]
 #[stable(feature = "std_collections_from_array", since = "1.56.0")]
 impl<K: Ord, V, const N: usize> From<[(K, V); N]> for BTreeMap<K, V> {
     /// Converts a `[(K, V); N]` into a `BTreeMap<(K, V)>`.
@@ -2352,11 +2668,11 @@ fn index(&self, key: &Q) -> &V {
 
         // use stable sort to preserve the insertion order.
         arr.sort_by(|a, b| a.0.cmp(&b.0));
-        BTreeMap::bulk_build_from_sorted_iter(arr, Global)
+        BTreeMap::bulk_build_from_sorted_iter(arr, Nodes::new(Global))
     }
 }
 
-impl<K, V, A: Allocator + Clone> BTreeMap<K, V, A> {
+impl<K, V, A: Allocator + Clone, S: Statistics> BTreeMap<K, V, A, S> {
     /// Gets an iterator over the entries of the map, sorted by key.
     ///
     /// # Examples
@@ -2620,7 +2936,7 @@ pub fn lower_bound<Q: ?Sized>(&self, bound: Bound<&Q>) -> Cursor<'_, K, V>
     /// assert_eq!(cursor.peek_next(), Some((&1, &mut "a")));
     /// ```
     #[unstable(feature = "btree_cursors", issue = "107540")]
-    pub fn lower_bound_mut<Q: ?Sized>(&mut self, bound: Bound<&Q>) -> CursorMut<'_, K, V, A>
+    pub fn lower_bound_mut<Q: ?Sized>(&mut self, bound: Bound<&Q>) -> CursorMut<'_, K, V, A, S>
     where
         K: Borrow<Q> + Ord,
         Q: Ord,
@@ -2743,7 +3059,7 @@ pub fn upper_bound<Q: ?Sized>(&self, bound: Bound<&Q>) -> Cursor<'_, K, V>
     /// assert_eq!(cursor.peek_next(), None);
     /// ```
     #[unstable(feature = "btree_cursors", issue = "107540")]
-    pub fn upper_bound_mut<Q: ?Sized>(&mut self, bound: Bound<&Q>) -> CursorMut<'_, K, V, A>
+    pub fn upper_bound_mut<Q: ?Sized>(&mut self, bound: Bound<&Q>) -> CursorMut<'_, K, V, A, S>
     where
         K: Borrow<Q> + Ord,
         Q: Ord,
@@ -2822,12 +3138,13 @@ pub struct CursorMut<
     K: 'a,
     V: 'a,
     #[unstable(feature = "allocator_api", issue = "32838")] A = Global,
+    #[unstable(feature = "btree_order_statistics", issue = "none")] S = NoStatistics,
 > {
-    inner: CursorMutKey<'a, K, V, A>,
+    inner: CursorMutKey<'a, K, V, A, S>,
 }
 
 #[unstable(feature = "btree_cursors", issue = "107540")]
-impl<K: Debug, V: Debug, A> Debug for CursorMut<'_, K, V, A> {
+impl<K: Debug, V: Debug, A, S> Debug for CursorMut<'_, K, V, A, S> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.write_str("CursorMut")
     }
@@ -2860,16 +3177,17 @@ pub struct CursorMutKey<
     K: 'a,
     V: 'a,
     #[unstable(feature = "allocator_api", issue = "32838")] A = Global,
+    #[unstable(feature = "btree_order_statistics", issue = "none")] S = NoStatistics,
 > {
     // If current is None then it means the tree has not been allocated yet.
     current: Option<Handle<NodeRef<marker::Mut<'a>, K, V, marker::Leaf>, marker::Edge>>,
     root: DormantMutRef<'a, Option<node::Root<K, V>>>,
     length: &'a mut usize,
-    alloc: &'a mut A,
+    alloc: &'a mut Nodes<A, S>,
 }
 
 #[unstable(feature = "btree_cursors", issue = "107540")]
-impl<K: Debug, V: Debug, A> Debug for CursorMutKey<'_, K, V, A> {
+impl<K: Debug, V: Debug, A, S> Debug for CursorMutKey<'_, K, V, A, S> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.write_str("CursorMutKey")
     }
@@ -2937,7 +3255,7 @@ pub fn peek_prev(&self) -> Option<(&'a K, &'a V)> {
     }
 }
 
-impl<'a, K, V, A> CursorMut<'a, K, V, A> {
+impl<'a, K, V, A, S> CursorMut<'a, K, V, A, S> {
     /// Advances the cursor to the next gap, returning the key and value of the
     /// element that it moved over.
     ///
@@ -3002,12 +3320,12 @@ pub fn as_cursor(&self) -> Cursor<'_, K, V> {
     /// * The key of the newly inserted element must be unique in the tree.
     /// * All keys in the tree must remain in sorted order.
     #[unstable(feature = "btree_cursors", issue = "107540")]
-    pub unsafe fn with_mutable_key(self) -> CursorMutKey<'a, K, V, A> {
+    pub unsafe fn with_mutable_key(self) -> CursorMutKey<'a, K, V, A, S> {
         self.inner
     }
 }
 
-impl<'a, K, V, A> CursorMutKey<'a, K, V, A> {
+impl<'a, K, V, A, S> CursorMutKey<'a, K, V, A, S> {
     /// Advances the cursor to the next gap, returning the key and value of the
     /// element that it moved over.
     ///
@@ -3099,7 +3417,7 @@ pub fn as_cursor(&self) -> Cursor<'_, K, V> {
 }
 
 // Now the tree editing operations
-impl<'a, K: Ord, V, A: Allocator + Clone> CursorMutKey<'a, K, V, A> {
+impl<'a, K: Ord, V, A: Allocator + Clone, S: Statistics> CursorMutKey<'a, K, V, A, S> {
     /// Inserts a new key-value pair into the map in the gap that the
     /// cursor is currently pointing to.
     ///
@@ -3113,6 +3431,7 @@ impl<'a, K: Ord, V, A: Allocator + Clone> CursorMutKey<'a, K, V, A> {
     ///
     /// * The key of the newly inserted element must be unique in the tree.
     /// * All keys in the tree must remain in sorted order.
+    #[cfg(not(no_global_oom_handling))]
     #[unstable(feature = "btree_cursors", issue = "107540")]
     pub unsafe fn insert_after_unchecked(&mut self, key: K, value: V) {
         let edge = match self.current.take() {
@@ -3137,7 +3456,12 @@ pub unsafe fn insert_after_unchecked(&mut self, key: K, value: V) {
             // SAFETY: The handle to the newly inserted value is always on a
             // leaf node, so adding a new root node doesn't invalidate it.
             let root = unsafe { self.root.reborrow().as_mut().unwrap() };
-            root.push_internal_level(self.alloc.clone()).push(ins.kv.0, ins.kv.1, ins.right)
+            root.push_internal_level(self.alloc.clone()).push(
+                ins.kv.0,
+                ins.kv.1,
+                ins.right,
+                &*self.alloc,
+            )
         });
         self.current = Some(handle.left_edge());
         *self.length += 1;
@@ -3156,6 +3480,7 @@ pub unsafe fn insert_after_unchecked(&mut self, key: K, value: V) {
     ///
     /// * The key of the newly inserted element must be unique in the tree.
     /// * All keys in the tree must remain in sorted order.
+    #[cfg(not(no_global_oom_handling))]
     #[unstable(feature = "btree_cursors", issue = "107540")]
     pub unsafe fn insert_before_unchecked(&mut self, key: K, value: V) {
         let edge = match self.current.take() {
@@ -3183,7 +3508,12 @@ pub unsafe fn insert_before_unchecked(&mut self, key: K, value: V) {
             // SAFETY: The handle to the newly inserted value is always on a
             // leaf node, so adding a new root node doesn't invalidate it.
             let root = unsafe { self.root.reborrow().as_mut().unwrap() };
-            root.push_internal_level(self.alloc.clone()).push(ins.kv.0, ins.kv.1, ins.right)
+            root.push_internal_level(self.alloc.clone()).push(
+                ins.kv.0,
+                ins.kv.1,
+                ins.right,
+                &*self.alloc,
+            )
         });
         self.current = Some(handle.right_edge());
         *self.length += 1;
@@ -3199,6 +3529,7 @@ pub unsafe fn insert_before_unchecked(&mut self, key: K, value: V) {
     /// (if any), or if it not less than the key after the cursor (if any),
     /// then an [`UnorderedKeyError`] is returned since this would
     /// invalidate the [`Ord`] invariant between the keys of the map.
+    #[cfg(not(no_global_oom_handling))]
     #[unstable(feature = "btree_cursors", issue = "107540")]
     pub fn insert_after(&mut self, key: K, value: V) -> Result<(), UnorderedKeyError> {
         if let Some((prev, _)) = self.peek_prev() {
@@ -3227,6 +3558,7 @@ pub fn insert_after(&mut self, key: K, value: V) -> Result<(), UnorderedKeyError
     /// (if any), or if it not less than the key after the cursor (if any),
     /// then an [`UnorderedKeyError`] is returned since this would
     /// invalidate the [`Ord`] invariant between the keys of the map.
+    #[cfg(not(no_global_oom_handling))]
     #[unstable(feature = "btree_cursors", issue = "107540")]
     pub fn insert_before(&mut self, key: K, value: V) -> Result<(), UnorderedKeyError> {
         if let Some((prev, _)) = self.peek_prev() {
@@ -3302,9 +3634,199 @@ pub fn remove_prev(&mut self) -> Option<(K, V)> {
         }
         Some(kv)
     }
+
+    /// Splits the map into two at the gap that the cursor is currently
+    /// pointing to. This returns a new map consisting of everything after
+    /// the cursor, with the original map retaining everything before.
+    ///
+    /// After the split the cursor will be pointing at the end of the map.
+    #[cfg(not(no_global_oom_handling))]
+    #[unstable(feature = "btree_cursors", issue = "107540")]
+    pub fn split_after(&mut self) -> BTreeMap<K, V, A, S> {
+        let (right_root, right_len) = match self.split_off_at_cursor() {
+            None => return BTreeMap::new_in_nodes(self.alloc.clone()),
+            Some(split) => split,
+        };
+        // SAFETY: `split_off_at_cursor` consumed the only handle into the tree.
+        let left_root = unsafe { self.root.reborrow().as_mut().unwrap() };
+        self.current = Some(left_root.borrow_mut().last_leaf_edge());
+        BTreeMap {
+            root: Some(right_root),
+            length: right_len,
+            alloc: ManuallyDrop::new(self.alloc.clone()),
+            _marker: PhantomData,
+        }
+    }
+
+    /// Splits the map into two at the gap that the cursor is currently
+    /// pointing to. This returns a new map consisting of everything before
+    /// the cursor, with the original map retaining everything after.
+    ///
+    /// After the split the cursor will be pointing at the start of the map.
+    #[cfg(not(no_global_oom_handling))]
+    #[unstable(feature = "btree_cursors", issue = "107540")]
+    pub fn split_before(&mut self) -> BTreeMap<K, V, A, S> {
+        let (mut other_root, mut other_len) = match self.split_off_at_cursor() {
+            None => return BTreeMap::new_in_nodes(self.alloc.clone()),
+            Some(split) => split,
+        };
+        // SAFETY: `split_off_at_cursor` consumed the only handle into the tree.
+        let root = unsafe { self.root.reborrow().as_mut().unwrap() };
+        mem::swap(root, &mut other_root);
+        mem::swap(self.length, &mut other_len);
+        self.current = Some(root.borrow_mut().first_leaf_edge());
+        BTreeMap {
+            root: Some(other_root),
+            length: other_len,
+            alloc: ManuallyDrop::new(self.alloc.clone()),
+            _marker: PhantomData,
+        }
+    }
+
+    /// Splits off the tree right of the cursor, returning it with its length
+    /// unless the tree has not been allocated yet. This leaves the cursor
+    /// without a position, to be restored by the caller.
+    #[cfg(not(no_global_oom_handling))]
+    fn split_off_at_cursor(&mut self) -> Option<(Root<K, V>, usize)> {
+        let current = self.current.take()?;
+        // Record the edges leading down to the cursor, from the root down.
+        let mut path = Vec::new();
+        let mut edge = current.forget_node_type();
+        loop {
+            path.push(edge.idx());
+            match edge.into_node().ascend() {
+                Ok(parent) => edge = parent.forget_node_type(),
+                Err(_) => break,
+            }
+        }
+        // SAFETY: We have no other reference to the tree.
+        let left_root = unsafe { self.root.reborrow().as_mut().unwrap() };
+        // SAFETY: the path leads down to where the cursor was.
+        let right_root =
+            unsafe { left_root.split_off_at_path(path.into_iter().rev(), self.alloc.clone()) };
+        let (left_len, right_len) = Root::calc_split_length(*self.length, left_root, &right_root);
+        *self.length = left_len;
+        Some((right_root, right_len))
+    }
+
+    /// Moves all elements from `other` into the map, in the gap that the
+    /// cursor is currently pointing to.
+    ///
+    /// After the splice the cursor will be pointing at the gap before the
+    /// newly inserted elements.
+    ///
+    /// This takes logarithmic time, because the nodes of `other` are taken over
+    /// as they are. The allocator of the map must thus be able to deallocate
+    /// memory allocated by the allocator of `other`.
+    ///
+    /// If the first key of `other` is not greater than the key before the
+    /// cursor (if any), or if its last key is not less than the key after the
+    /// cursor (if any), then an [`UnorderedSpliceError`] holding `other` is
+    /// returned and the map is not modified.
+    #[cfg(not(no_global_oom_handling))]
+    #[unstable(feature = "btree_cursors", issue = "107540")]
+    pub fn splice_after(
+        &mut self,
+        mut other: BTreeMap<K, V, A, S>,
+    ) -> Result<(), UnorderedSpliceError<K, V, A, S>> {
+        if !self.fits_in_gap(&other) {
+            return Err(UnorderedSpliceError { map: other });
+        }
+        if let Some((key, value)) = other.pop_first() {
+            self.splice_around(other, &key);
+            // SAFETY: `fits_in_gap` confirmed that all of `other` fits in the
+            // gap, and the key goes right before the rest of `other`.
+            unsafe { self.insert_after_unchecked(key, value) };
+        }
+        Ok(())
+    }
+
+    /// Moves all elements from `other` into the map, in the gap that the
+    /// cursor is currently pointing to.
+    ///
+    /// After the splice the cursor will be pointing at the gap after the
+    /// newly inserted elements.
+    ///
+    /// This takes logarithmic time, because the nodes of `other` are taken over
+    /// as they are. The allocator of the map must thus be able to deallocate
+    /// memory allocated by the allocator of `other`.
+    ///
+    /// If the first key of `other` is not greater than the key before the
+    /// cursor (if any), or if its last key is not less than the key after the
+    /// cursor (if any), then an [`UnorderedSpliceError`] holding `other` is
+    /// returned and the map is not modified.
+    #[cfg(not(no_global_oom_handling))]
+    #[unstable(feature = "btree_cursors", issue = "107540")]
+    pub fn splice_before(
+        &mut self,
+        mut other: BTreeMap<K, V, A, S>,
+    ) -> Result<(), UnorderedSpliceError<K, V, A, S>> {
+        if !self.fits_in_gap(&other) {
+            return Err(UnorderedSpliceError { map: other });
+        }
+        if let Some((key, value)) = other.pop_last() {
+            self.splice_around(other, &key);
+            // SAFETY: `fits_in_gap` confirmed that all of `other` fits in the
+            // gap, and the key goes right after the rest of `other`.
+            unsafe { self.insert_before_unchecked(key, value) };
+        }
+        Ok(())
+    }
+
+    #[cfg(not(no_global_oom_handling))]
+    fn fits_in_gap(&mut self, other: &BTreeMap<K, V, A, S>) -> bool {
+        if let (Some((first, _)), Some((prev, _))) = (other.first_key_value(), self.peek_prev()) {
+            if *first <= *prev {
+                return false;
+            }
+        }
+        if let (Some((last, _)), Some((next, _))) = (other.last_key_value(), self.peek_next()) {
+            if *last >= *next {
+                return false;
+            }
+        }
+        true
+    }
+
+    /// Joins the tree of `other` into the tree in the gap that the cursor is
+    /// pointing to, unless `other` is empty, and then points the cursor to the
+    /// gap where `key` goes. The nodes of `other` must be fit to be deallocated
+    /// by our allocator.
+    #[cfg(not(no_global_oom_handling))]
+    fn splice_around(&mut self, mut other: BTreeMap<K, V, A, S>, key: &K) {
+        // Take out the key-value pairs that are to separate the joined trees.
+        let Some((first_key, first_val)) = other.pop_first() else { return };
+        let last = other.pop_last();
+
+        let alloc = self.alloc.clone();
+        let right_root = match self.split_off_at_cursor() {
+            Some((right_root, right_len)) => {
+                *self.length += right_len;
+                right_root
+            }
+            None => Root::new(alloc.clone()),
+        };
+        // SAFETY: We have no other reference to the tree.
+        let root = unsafe { self.root.reborrow() }.get_or_insert_with(|| Root::new(alloc.clone()));
+        *self.length += 1;
+        match last {
+            None => root.join(first_key, first_val, right_root, alloc),
+            Some((last_key, last_val)) => {
+                *self.length += 1 + mem::take(&mut other.length);
+                let middle_root = other.root.take().unwrap();
+                root.join(first_key, first_val, middle_root, alloc.clone());
+                root.join(last_key, last_val, right_root, alloc);
+            }
+        }
+        self.current = match root.borrow_mut().search_tree(key) {
+            GoDown(edge) => Some(edge),
+            // Only an inconsistent `Ord` implementation finds the key.
+            Found(kv) => Some(kv.next_leaf_edge()),
+        };
+    }
 }
 
-impl<'a, K: Ord, V, A: Allocator + Clone> CursorMut<'a, K, V, A> {
+impl<'a, K: Ord, V, A: Allocator + Clone, S: Statistics> CursorMut<'a, K, V, A, S> {
     /// Inserts a new key-value pair into the map in the gap that the
     /// cursor is currently pointing to.
     ///
@@ -3318,6 +3840,7 @@ impl<'a, K: Ord, V, A: Allocator + Clone> CursorMut<'a, K, V, A> {
     ///
     /// * The key of the newly inserted element must be unique in the tree.
     /// * All keys in the tree must remain in sorted order.
+    #[cfg(not(no_global_oom_handling))]
     #[unstable(feature = "btree_cursors", issue = "107540")]
     pub unsafe fn insert_after_unchecked(&mut self, key: K, value: V) {
         unsafe { self.inner.insert_after_unchecked(key, value) }
@@ -3336,6 +3859,7 @@ pub unsafe fn insert_after_unchecked(&mut self, key: K, value: V) {
     ///
     /// * The key of the newly inserted element must be unique in the tree.
     /// * All keys in the tree must remain in sorted order.
+    #[cfg(not(no_global_oom_handling))]
     #[unstable(feature = "btree_cursors", issue = "107540")]
     pub unsafe fn insert_before_unchecked(&mut self, key: K, value: V) {
         unsafe { self.inner.insert_before_unchecked(key, value) }
@@ -3351,6 +3875,7 @@ pub unsafe fn insert_before_unchecked(&mut self, key: K, value: V) {
     /// (if any), or if it not less than the key after the cursor (if any),
     /// then an [`UnorderedKeyError`] is returned since this would
     /// invalidate the [`Ord`] invariant between the keys of the map.
+    #[cfg(not(no_global_oom_handling))]
     #[unstable(feature = "btree_cursors", issue = "107540")]
     pub fn insert_after(&mut self, key: K, value: V) -> Result<(), UnorderedKeyError> {
         self.inner.insert_after(key, value)
@@ -3366,6 +3891,7 @@ pub fn insert_after(&mut self, key: K, value: V) -> Result<(), UnorderedKeyError
     /// (if any), or if it not less than the key after the cursor (if any),
     /// then an [`UnorderedKeyError`] is returned since this would
     /// invalidate the [`Ord`] invariant between the keys of the map.
+    #[cfg(not(no_global_oom_handling))]
     #[unstable(feature = "btree_cursors", issue = "107540")]
     pub fn insert_before(&mut self, key: K, value: V) -> Result<(), UnorderedKeyError> {
         self.inner.insert_before(key, value)
@@ -3388,6 +3914,109 @@ pub fn remove_next(&mut self) -> Option<(K, V)> {
     pub fn remove_prev(&mut self) -> Option<(K, V)> {
         self.inner.remove_prev()
     }
+
+    /// Splits the map into two at the gap that the cursor is currently
+    /// pointing to. This returns a new map consisting of everything after
+    /// the cursor, with the original map retaining everything before.
+    ///
+    /// After the split the cursor will be pointing at the end of the map.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(btree_cursors)]
+    ///
+    /// use std::collections::BTreeMap;
+    /// use std::ops::Bound;
+    ///
+    /// let mut map = BTreeMap::from([(1, "a"), (2, "b"), (3, "c"), (4, "d")]);
+    /// let mut cursor = map.lower_bound_mut(Bound::Included(&3));
+    /// let tail = cursor.split_after();
+    /// assert_eq!(cursor.peek_next(), None);
+    /// assert_eq!(map.into_keys().collect::<Vec<_>>(), [1, 2]);
+    /// assert_eq!(tail.into_keys().collect::<Vec<_>>(), [3, 4]);
+    /// ```
+    #[cfg(not(no_global_oom_handling))]
+    #[unstable(feature = "btree_cursors", issue = "107540")]
+    pub fn split_after(&mut self) -> BTreeMap<K, V, A, S> {
+        self.inner.split_after()
+    }
+
+    /// Splits the map into two at the gap that the cursor is currently
+    /// pointing to. This returns a new map consisting of everything before
+    /// the cursor, with the original map retaining everything after.
+    ///
+    /// After the split the cursor will be pointing at the start of the map.
+    #[cfg(not(no_global_oom_handling))]
+    #[unstable(feature = "btree_cursors", issue = "107540")]
+    pub fn split_before(&mut self) -> BTreeMap<K, V, A, S> {
+        self.inner.split_before()
+    }
+
+    /// Moves all elements from `other` into the map, in the gap that the
+    /// cursor is currently pointing to.
+    ///
+    /// After the splice the cursor will be pointing at the gap before the
+    /// newly inserted elements.
+    ///
+    /// This takes logarithmic time, because the nodes of `other` are taken over
+    /// as they are. The allocator of the map must thus be able to deallocate
+    /// memory allocated by the allocator of `other`.
+    ///
+    /// If the first key of `other` is not greater than the key before the
+    /// cursor (if any), or if its last key is not less than the key after the
+    /// cursor (if any), then an [`UnorderedSpliceError`] holding `other` is
+    /// returned and the map is not modified.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(btree_cursors)]
+    ///
+    /// use std::collections::BTreeMap;
+    /// use std::ops::Bound;
+    ///
+    /// let mut map = BTreeMap::from([(1, "a"), (5, "e")]);
+    /// let mut cursor = map.lower_bound_mut(Bound::Included(&5));
+    /// cursor.splice_after(BTreeMap::from([(2, "b"), (3, "c")])).unwrap();
+    /// assert_eq!(cursor.peek_next(), Some((&2, &mut "b")));
+    ///
+    /// let overlapping = BTreeMap::from([(4, "d"), (6, "f")]);
+    /// let err = cursor.splice_after(overlapping).unwrap_err();
+    /// assert_eq!(err.map.len(), 2);
+    /// assert_eq!(map.into_keys().collect::<Vec<_>>(), [1, 2, 3, 5]);
+    /// ```
+    #[cfg(not(no_global_oom_handling))]
+    #[unstable(feature = "btree_cursors", issue = "107540")]
+    pub fn splice_after(
+        &mut self,
+        other: BTreeMap<K, V, A, S>,
+    ) -> Result<(), UnorderedSpliceError<K, V, A, S>> {
+        self.inner.splice_after(other)
+    }
+
+    /// Moves all elements from `other` into the map, in the gap that the
+    /// cursor is currently pointing to.
+    ///
+    /// After the splice the cursor will be pointing at the gap after the
+    /// newly inserted elements.
+    ///
+    /// This takes logarithmic time, because the nodes of `other` are taken over
+    /// as they are. The allocator of the map must thus be able to deallocate
+    /// memory allocated by the allocator of `other`.
+    ///
+    /// If the first key of `other` is not greater than the key before the
+    /// cursor (if any), or if its last key is not less than the key after the
+    /// cursor (if any), then an [`UnorderedSpliceError`] holding `other` is
+    /// returned and the map is not modified.
+    #[cfg(not(no_global_oom_handling))]
+    #[unstable(feature = "btree_cursors", issue = "107540")]
+    pub fn splice_before(
+        &mut self,
+        other: BTreeMap<K, V, A, S>,
+    ) -> Result<(), UnorderedSpliceError<K, V, A, S>> {
+        self.inner.splice_before(other)
+    }
 }
 
 /// Error type returned by [`CursorMut::insert_before`] and
@@ -3407,5 +4036,43 @@ fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
 #[unstable(feature = "btree_cursors", issue = "107540")]
 impl Error for UnorderedKeyError {}
 
+/// Error type returned by [`CursorMut::splice_before`] and
+/// [`CursorMut::splice_after`] if the keys of the map being spliced in are not
+/// properly ordered with regards to adjacent keys.
+///
+/// Contains the map that was not spliced in.
+#[unstable(feature = "btree_cursors", issue = "107540")]
+pub struct UnorderedSpliceError<
+    K,
+    V,
+    A: Allocator + Clone = Global,
+    #[unstable(feature = "btree_order_statistics", issue = "none")] S: Statistics = NoStatistics,
+> {
+    /// The map that was not spliced in.
+    pub map: BTreeMap<K, V, A, S>,
+}
+
+#[unstable(feature = "btree_cursors", issue = "107540")]
+impl<K: Debug, V: Debug, A: Allocator + Clone, S: Statistics> Debug
+    for UnorderedSpliceError<K, V, A, S>
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("UnorderedSpliceError").field("map", &self.map).finish()
+    }
+}
+
+#[unstable(feature = "btree_cursors", issue = "107540")]
+impl<K, V, A: Allocator + Clone, S: Statistics> fmt::Display for UnorderedSpliceError<K, V, A, S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "keys are not properly ordered relative to neighbors")
+    }
+}
+
+#[unstable(feature = "btree_cursors", issue = "107540")]
+impl<K: Debug, V: Debug, A: Allocator + Clone, S: Statistics> Error
+    for UnorderedSpliceError<K, V, A, S>
+{
+}
+
 #[cfg(test)]
 mod tests;
@@ -3,9 +3,12 @@
 use core::mem;
 
 use crate::alloc::{Allocator, Global};
+use crate::collections::TryReserveError;
 
 use super::super::borrow::DormantMutRef;
-use super::super::node::{marker, Handle, NodeRef};
+use super::super::node::{marker, Handle, NodeAlloc, NodeRef};
+use super::super::order_statistics::{NoStatistics, Nodes, Statistics};
+use super::super::reserve::ReservedNodes;
 use super::BTreeMap;
 
 use Entry::*;
@@ -22,18 +25,21 @@ pub enum Entry<
     K: 'a,
     V: 'a,
     #[unstable(feature = "allocator_api", issue = "32838")] A: Allocator + Clone = Global,
+    #[unstable(feature = "btree_order_statistics", issue = "none")] S: Statistics = NoStatistics,
 > {
     /// A vacant entry.
     #[stable(feature = "rust1", since = "1.0.0")]
-    Vacant(#[stable(feature = "rust1", since = "1.0.0")] VacantEntry<'a, K, V, A>),
+    Vacant(#[stable(feature = "rust1", since = "1.0.0")] VacantEntry<'a, K, V, A, S>),
 
     /// An occupied entry.
     #[stable(feature = "rust1", since = "1.0.0")]
-    Occupied(#[stable(feature = "rust1", since = "1.0.0")] OccupiedEntry<'a, K, V, A>),
+    Occupied(#[stable(feature = "rust1", since = "1.0.0")] OccupiedEntry<'a, K, V, A, S>),
 }
 
 #[stable(feature = "debug_btree_map", since = "1.12.0")]
-impl<K: Debug + Ord, V: Debug, A: Allocator + Clone> Debug for Entry<'_, K, V, A> {
+impl<K: Debug + Ord, V: Debug, A: Allocator + Clone, S: Statistics> Debug
+    for Entry<'_, K, V, A, S>
+{
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match *self {
             Vacant(ref v) => f.debug_tuple("Entry").field(v).finish(),
@@ -50,21 +56,22 @@ pub struct VacantEntry<
     K,
     V,
     #[unstable(feature = "allocator_api", issue = "32838")] A: Allocator + Clone = Global,
+    #[unstable(feature = "btree_order_statistics", issue = "none")] S: Statistics = NoStatistics,
 > {
     pub(super) key: K,
     /// `None` for a (empty) map without root
     pub(super) handle: Option<Handle<NodeRef<marker::Mut<'a>, K, V, marker::Leaf>, marker::Edge>>,
-    pub(super) dormant_map: DormantMutRef<'a, BTreeMap<K, V, A>>,
+    pub(super) dormant_map: DormantMutRef<'a, BTreeMap<K, V, A, S>>,
 
     /// The BTreeMap will outlive this IntoIter so we don't care about drop order for `alloc`.
-    pub(super) alloc: A,
+    pub(super) alloc: Nodes<A, S>,
 
     // Be invariant in `K` and `V`
     pub(super) _marker: PhantomData<&'a mut (K, V)>,
 }
 
 #[stable(feature = "debug_btree_map", since = "1.12.0")]
-impl<K: Debug + Ord, V, A: Allocator + Clone> Debug for VacantEntry<'_, K, V, A> {
+impl<K: Debug + Ord, V, A: Allocator + Clone, S: Statistics> Debug for VacantEntry<'_, K, V, A, S> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_tuple("VacantEntry").field(self.key()).finish()
     }
@@ -78,19 +85,22 @@ pub struct OccupiedEntry<
     K,
     V,
     #[unstable(feature = "allocator_api", issue = "32838")] A: Allocator + Clone = Global,
+    #[unstable(feature = "btree_order_statistics", issue = "none")] S: Statistics = NoStatistics,
 > {
     pub(super) handle: Handle<NodeRef<marker::Mut<'a>, K, V, marker::LeafOrInternal>, marker::KV>,
-    pub(super) dormant_map: DormantMutRef<'a, BTreeMap<K, V, A>>,
+    pub(super) dormant_map: DormantMutRef<'a, BTreeMap<K, V, A, S>>,
 
     /// The BTreeMap will outlive this IntoIter so we don't care about drop order for `alloc`.
-    pub(super) alloc: A,
+    pub(super) alloc: Nodes<A, S>,
 
     // Be invariant in `K` and `V`
     pub(super) _marker: PhantomData<&'a mut (K, V)>,
 }
 
 #[stable(feature = "debug_btree_map", since = "1.12.0")]
-impl<K: Debug + Ord, V: Debug, A: Allocator + Clone> Debug for OccupiedEntry<'_, K, V, A> {
+impl<K: Debug + Ord, V: Debug, A: Allocator + Clone, S: Statistics> Debug
+    for OccupiedEntry<'_, K, V, A, S>
+{
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("OccupiedEntry").field("key", self.key()).field("value", self.get()).finish()
     }
@@ -100,15 +110,23 @@ fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
 ///
 /// Contains the occupied entry, and the value that was not inserted.
 #[unstable(feature = "map_try_insert", issue = "82766")]
-pub struct OccupiedError<'a, K: 'a, V: 'a, A: Allocator + Clone = Global> {
+pub struct OccupiedError<
+    'a,
+    K: 'a,
+    V: 'a,
+    A: Allocator + Clone = Global,
+    #[unstable(feature = "btree_order_statistics", issue = "none")] S: Statistics = NoStatistics,
+> {
     /// The entry in the map that was already occupied.
-    pub entry: OccupiedEntry<'a, K, V, A>,
+    pub entry: OccupiedEntry<'a, K, V, A, S>,
     /// The value which was not inserted, because the entry was already occupied.
     pub value: V,
 }
 
 #[unstable(feature = "map_try_insert", issue = "82766")]
-impl<K: Debug + Ord, V: Debug, A: Allocator + Clone> Debug for OccupiedError<'_, K, V, A> {
+impl<K: Debug + Ord, V: Debug, A: Allocator + Clone, S: Statistics> Debug
+    for OccupiedError<'_, K, V, A, S>
+{
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("OccupiedError")
             .field("key", self.entry.key())
@@ -119,8 +137,8 @@ fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
 }
 
 #[unstable(feature = "map_try_insert", issue = "82766")]
-impl<'a, K: Debug + Ord, V: Debug, A: Allocator + Clone> fmt::Display
-    for OccupiedError<'a, K, V, A>
+impl<'a, K: Debug + Ord, V: Debug, A: Allocator + Clone, S: Statistics> fmt::Display
+    for OccupiedError<'a, K, V, A, S>
 {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(
@@ -143,7 +161,7 @@ fn description(&self) -> &str {
     }
 }
 
-impl<'a, K: Ord, V, A: Allocator + Clone> Entry<'a, K, V, A> {
+impl<'a, K: Ord, V, A: Allocator + Clone, S: Statistics> Entry<'a, K, V, A, S> {
     /// Ensures a value is in the entry by inserting the default if empty, and returns
     /// a mutable reference to the value in the entry.
     ///
@@ -157,6 +175,7 @@ impl<'a, K: Ord, V, A: Allocator + Clone> Entry<'a, K, V, A> {
     ///
     /// assert_eq!(map["poneyland"], 12);
     /// ```
+    #[cfg(not(no_global_oom_handling))]
     #[stable(feature = "rust1", since = "1.0.0")]
     pub fn or_insert(self, default: V) -> &'a mut V {
         match self {
@@ -165,6 +184,34 @@ pub fn or_insert(self, default: V) -> &'a mut V {
         }
     }
 
+    /// Ensures a value is in the entry by inserting the default if empty, and returns
+    /// a mutable reference to the value in the entry, or returns an error if the
+    /// allocator cannot provide the memory to insert it.
+    ///
+    /// This is the fallible counterpart to [`or_insert`]. If an error is
+    /// returned the map is left unchanged.
+    ///
+    /// [`or_insert`]: Entry::or_insert
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(fallible_allocation)]
+    /// use std::collections::BTreeMap;
+    ///
+    /// let mut map: BTreeMap<&str, usize> = BTreeMap::new();
+    /// *map.entry("poneyland").try_or_insert(12).unwrap() += 1;
+    ///
+    /// assert_eq!(map["poneyland"], 13);
+    /// ```
+    #[unstable(feature = "fallible_allocation", issue = "none")]
+    pub fn try_or_insert(self, default: V) -> Result<&'a mut V, TryReserveError> {
+        match self {
+            Occupied(entry) => Ok(entry.into_mut()),
+            Vacant(entry) => entry.try_insert(default),
+        }
+    }
+
     /// Ensures a value is in the entry by inserting the result of the default function if empty,
     /// and returns a mutable reference to the value in the entry.
     ///
@@ -180,6 +227,7 @@ pub fn or_insert(self, default: V) -> &'a mut V {
     ///
     /// assert_eq!(map["poneyland"], "hoho".to_string());
     /// ```
+    #[cfg(not(no_global_oom_handling))]
     #[stable(feature = "rust1", since = "1.0.0")]
     pub fn or_insert_with<F: FnOnce() -> V>(self, default: F) -> &'a mut V {
         match self {
@@ -206,6 +254,7 @@ pub fn or_insert_with<F: FnOnce() -> V>(self, default: F) -> &'a mut V {
     ///
     /// assert_eq!(map["poneyland"], 9);
     /// ```
+    #[cfg(not(no_global_oom_handling))]
     #[inline]
     #[stable(feature = "or_insert_with_key", since = "1.50.0")]
     pub fn or_insert_with_key<F: FnOnce(&K) -> V>(self, default: F) -> &'a mut V {
@@ -271,7 +320,8 @@ pub fn and_modify<F>(self, f: F) -> Self
     }
 }
 
-impl<'a, K: Ord, V: Default, A: Allocator + Clone> Entry<'a, K, V, A> {
+#[cfg(not(no_global_oom_handling))]
+impl<'a, K: Ord, V: Default, A: Allocator + Clone, S: Statistics> Entry<'a, K, V, A, S> {
     #[stable(feature = "entry_or_default", since = "1.28.0")]
     /// Ensures a value is in the entry by inserting the default value if empty,
     /// and returns a mutable reference to the value in the entry.
@@ -294,7 +344,7 @@ pub fn or_default(self) -> &'a mut V {
     }
 }
 
-impl<'a, K: Ord, V, A: Allocator + Clone> VacantEntry<'a, K, V, A> {
+impl<'a, K: Ord, V, A: Allocator + Clone, S: Statistics> VacantEntry<'a, K, V, A, S> {
     /// Gets a reference to the key that would be used when inserting a value
     /// through the VacantEntry.
     ///
@@ -346,29 +396,66 @@ pub fn into_key(self) -> K {
     /// }
     /// assert_eq!(map["poneyland"], 37);
     /// ```
+    #[cfg(not(no_global_oom_handling))]
     #[stable(feature = "rust1", since = "1.0.0")]
     #[rustc_confusables("push", "put")]
-    pub fn insert(mut self, value: V) -> &'a mut V {
+    pub fn insert(self, value: V) -> &'a mut V {
+        let alloc = self.alloc.clone();
+        self.insert_in(value, alloc)
+    }
+
+    /// Sets the value of the entry with the `VacantEntry`'s key,
+    /// and returns a mutable reference to it, or returns an error if the
+    /// allocator cannot provide the memory to insert it.
+    ///
+    /// This is the fallible counterpart to [`insert`]. If an error is
+    /// returned the map is left unchanged.
+    ///
+    /// [`insert`]: VacantEntry::insert
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(fallible_allocation)]
+    /// use std::collections::BTreeMap;
+    /// use std::collections::btree_map::Entry;
+    ///
+    /// let mut map: BTreeMap<&str, u32> = BTreeMap::new();
+    ///
+    /// if let Entry::Vacant(o) = map.entry("poneyland") {
+    ///     o.try_insert(37).unwrap();
+    /// }
+    /// assert_eq!(map["poneyland"], 37);
+    /// ```
+    #[unstable(feature = "fallible_allocation", issue = "none")]
+    pub fn try_insert(self, value: V) -> Result<&'a mut V, TryReserveError> {
+        let edge = self.handle.as_ref().map(|handle| handle.reborrow());
+        let reserved = ReservedNodes::for_insert(edge, self.alloc.clone())?;
+        Ok(self.insert_in(value, &reserved))
+    }
+
+    /// Inserts the value, allocating any new nodes with `alloc`.
+    fn insert_in<N: NodeAlloc>(mut self, value: V, alloc: N) -> &'a mut V {
         let out_ptr = match self.handle {
             None => {
                 // SAFETY: There is no tree yet so no reference to it exists.
                 let map = unsafe { self.dormant_map.awaken() };
-                let mut root = NodeRef::new_leaf(self.alloc.clone());
+                let mut root = NodeRef::new_leaf(alloc);
                 let val_ptr = root.borrow_mut().push(self.key, value);
                 map.root = Some(root.forget_type());
                 map.length = 1;
                 val_ptr
             }
             Some(handle) => {
-                let new_handle =
-                    handle.insert_recursing(self.key, value, self.alloc.clone(), |ins| {
-                        drop(ins.left);
-                        // SAFETY: Pushing a new root node doesn't invalidate
-                        // handles to existing nodes.
-                        let map = unsafe { self.dormant_map.reborrow() };
-                        let root = map.root.as_mut().unwrap(); // same as ins.left
-                        root.push_internal_level(self.alloc).push(ins.kv.0, ins.kv.1, ins.right)
-                    });
+                let new_handle = handle.insert_recursing(self.key, value, alloc.clone(), |ins| {
+                    drop(ins.left);
+                    // SAFETY: Pushing a new root node doesn't invalidate
+                    // handles to existing nodes.
+                    let map = unsafe { self.dormant_map.reborrow() };
+                    let root = map.root.as_mut().unwrap(); // same as ins.left
+                    root.push_internal_level(alloc.clone())
+                        .push(ins.kv.0, ins.kv.1, ins.right, &alloc)
+                });
 
                 // Get the pointer to the value
                 let val_ptr = new_handle.into_val_mut();
@@ -386,7 +473,7 @@ pub fn insert(mut self, value: V) -> &'a mut V {
     }
 }
 
-impl<'a, K: Ord, V, A: Allocator + Clone> OccupiedEntry<'a, K, V, A> {
+impl<'a, K: Ord, V, A: Allocator + Clone, S: Statistics> OccupiedEntry<'a, K, V, A, S> {
     /// Gets a reference to the key in the entry.
     ///
     /// # Examples
@@ -6,7 +6,10 @@
 use crate::testing::crash_test::{CrashTestDummy, Panic};
 use crate::testing::ord_chaos::{Cyclic3, Governed, Governor};
 use crate::testing::rng::DeterministicRng;
+use core::alloc::{AllocError, Layout};
 use core::assert_matches::assert_matches;
+use core::cell::Cell;
+use core::ptr::NonNull;
 use std::iter;
 use std::ops::Bound::{Excluded, Included, Unbounded};
 use std::panic::{catch_unwind, AssertUnwindSafe};
@@ -36,7 +39,7 @@ fn test_all_refs<'a, T: 'a>(dummy: &mut T, iter: impl Iterator<Item = &'a mut T>
     }
 }
 
-impl<K, V> BTreeMap<K, V> {
+impl<K, V, A: Allocator + Clone, S: Statistics> BTreeMap<K, V, A, S> {
     // Panics if the map (or the code navigating it) is corrupted.
     fn check_invariants(&self) {
         if let Some(root) = &self.root {
@@ -50,6 +53,12 @@ fn check_invariants(&self) {
             // Check consistency of `length` with what navigation code encounters.
             assert_eq!(self.length, root_node.calc_length());
 
+            // Check the subtree lengths kept to support order statistics.
+            if self.alloc.keeps_subtree_lens() {
+                assert_eq!(self.length, unsafe { root_node.subtree_len() });
+                unsafe { root_node.assert_subtree_lens() };
+            }
+
             // Lastly, check the invariant causing the least harm.
             root_node.assert_min_len(if root_node.height() > 0 { 1 } else { 0 });
         } else {
@@ -101,7 +110,9 @@ fn assert_strictly_ascending(&self)
             }
         }
     }
+}
 
+impl<K, V> BTreeMap<K, V> {
     // Transform the tree to minimize wasted space, obtaining fewer nodes that
     // are mostly filled up to their capacity. The same compact tree could have
     // been obtained by inserting keys in a shrewd order.
@@ -111,7 +122,8 @@ fn compact(&mut self)
     {
         let iter = mem::take(self).into_iter();
         if !iter.is_empty() {
-            self.root.insert(Root::new(*self.alloc)).bulk_push(iter, &mut self.length, *self.alloc);
+            let alloc = (*self.alloc).clone();
+            self.root.insert(Root::new(alloc.clone())).bulk_push(iter, &mut self.length, alloc);
         }
     }
 }
@@ -925,6 +937,109 @@ fn test_range_panic_3() {
     let _invalid_range = map.range((Excluded(&5), Excluded(&5)));
 }
 
+fn test_nth_and_rank_in<A: Allocator + Clone>(alloc: A) {
+    fn test<A: Allocator + Clone>(map: &BTreeMap<usize, usize, A, OrderStatistics>) {
+        for (i, (k, v)) in map.iter().enumerate() {
+            assert_eq!(map.nth(i), Some((k, v)));
+            assert_eq!(map.rank(k), i);
+            assert_eq!(map.rank(&(k + 1)), i + 1);
+        }
+        assert_eq!(map.nth(map.len()), None);
+    }
+
+    // Miri is too slow
+    let size = if cfg!(miri) { MIN_INSERTS_HEIGHT_2 } else { 1000 };
+    let mut map = BTreeMap::with_order_statistics_in(alloc);
+    test(&map);
+    assert_eq!(map.rank(&0), 0);
+
+    for i in 0..size {
+        map.insert(i * 2, i);
+    }
+    map.check();
+    test(&map);
+
+    map.retain(|k, _| k % 3 != 0);
+    map.check();
+    test(&map);
+
+    for i in 0..size {
+        map.try_insert(i * 2 + 1, i).unwrap();
+    }
+    map.check();
+    test(&map);
+
+    let right = map.split_off(&size);
+    map.check();
+    right.check();
+    test(&map);
+    test(&right);
+
+    let mut right = right.clone();
+    right.check();
+    test(&right);
+    map.append(&mut right);
+    map.check();
+    test(&map);
+}
+
+#[test]
+fn test_nth_and_rank() {
+    test_nth_and_rank_in(Global);
+}
+
+#[test]
+fn test_nth_and_rank_borrowed_alloc() {
+    test_nth_and_rank_in(&Global);
+}
+
+fn test_range_count_in<A: Allocator + Clone>(alloc: A) {
+    let size = MIN_INSERTS_HEIGHT_2 as u32;
+    let mut map = BTreeMap::with_order_statistics_in(alloc);
+    for i in 0..size {
+        map.insert(i * 2, ());
+    }
+    assert_eq!(map.height(), Some(2));
+    for start in 0..=size * 2 {
+        for end in start..=size * 2 {
+            for bounds in [
+                (Included(&start), Included(&end)),
+                (Included(&start), Excluded(&end)),
+                (Excluded(&start), Included(&end)),
+                (Excluded(&start), Excluded(&end)),
+                (Unbounded, Included(&end)),
+                (Included(&start), Unbounded),
+            ] {
+                if matches!(bounds, (Excluded(s), Excluded(e)) if s == e) {
+                    continue;
+                }
+                assert_eq!(map.range_count(bounds), map.range(bounds).count());
+            }
+        }
+    }
+    assert_eq!(map.range_count(..), map.len());
+    map.clear();
+    assert_eq!(map.range_count(..), 0);
+}
+
+#[test]
+fn test_range_count() {
+    test_range_count_in(Global);
+}
+
+#[test]
+fn test_range_count_borrowed_alloc() {
+    test_range_count_in(&Global);
+}
+
+#[should_panic(expected = "range start is greater than range end in BTreeMap")]
+#[test]
+fn test_range_count_panic() {
+    let mut map = BTreeMap::with_order_statistics();
+    map.extend([(3, "a"), (5, "b"), (8, "c")]);
+    let _count = map.range_count((Included(&8), Included(&3)));
+}
+
 #[test]
 fn test_retain() {
     let mut map = BTreeMap::from_iter((0..100).map(|x| (x, x * 10)));
@@ -2046,6 +2161,123 @@ fn test_try_insert() {
     assert_eq!(err.value, 200);
 }
 
+#[test]
+fn test_fallible_insert_into_full_height_1() {
+    let size = node::CAPACITY + 1 + node::CAPACITY;
+    for pos in 0..=size {
+        let mut map = BTreeMap::from_iter((0..size).map(|i| (i * 2 + 1, ())));
+        map.compact();
+        assert_eq!(map.entry(pos * 2).try_or_insert(()), Ok(&mut ()));
+        map.check();
+    }
+}
+
+#[test]
+fn test_fallible_extend_clone_from_iter() {
+    let mut rng = DeterministicRng::new();
+    let pairs: Vec<_> = (0..MIN_INSERTS_HEIGHT_2 * 4).map(|_| (rng.next(), rng.next())).collect();
+
+    let mut map = BTreeMap::try_from_iter(pairs.iter().copied()).unwrap();
+    map.check();
+    assert_eq!(map, BTreeMap::from_iter(pairs.iter().copied()));
+
+    let clone = map.try_clone().unwrap();
+    clone.check();
+    assert_eq!(clone, map);
+
+    // Overwrites values like `extend` does.
+    map.try_extend(pairs.iter().map(|&(k, _)| (k, 0))).unwrap();
+    map.check();
+    assert_eq!(map.len(), clone.len());
+    assert!(map.values().all(|&v| v == 0));
+}
+
+#[test]
+fn test_fallible_order_statistics() {
+    // Insert in ascending order, so that insertions split the root repeatedly.
+    let mut map = BTreeMap::with_order_statistics();
+    for i in 0..MIN_INSERTS_HEIGHT_2 {
+        if let Entry::Vacant(entry) = map.entry(i * 2) {
+            entry.try_insert(i).unwrap();
+        }
+        map.check();
+    }
+    assert_eq!(map.height(), Some(2));
+    map.try_extend((0..MIN_INSERTS_HEIGHT_2).map(|i| (i * 2 + 1, i))).unwrap();
+    map.check();
+
+    let clone = map.try_clone().unwrap();
+    clone.check();
+    for i in 0..MIN_INSERTS_HEIGHT_2 * 2 {
+        assert_eq!(clone.nth(i), Some((&i, &(i / 2))));
+        assert_eq!(clone.rank(&i), i);
+    }
+    assert_eq!(clone.range_count(10..20), 10);
+}
+
+// An allocator that fails once it has made a given number of allocations,
+// and keeps count of the allocations it still has to deallocate.
+#[derive(Clone)]
+struct Budget {
+    left: Rc<Cell<usize>>,
+    live: Rc<Cell<usize>>,
+}
+
+unsafe impl Allocator for Budget {
+    fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        if self.left.get() == 0 {
+            return Err(AllocError);
+        }
+        self.left.set(self.left.get() - 1);
+        self.live.set(self.live.get() + 1);
+        Global.allocate(layout)
+    }
+
+    unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+        self.live.set(self.live.get() - 1);
+        unsafe { Global.deallocate(ptr, layout) }
+    }
+}
+
+#[test]
+fn test_fallible_insert_alloc_failure() {
+    let budget = Budget { left: Rc::new(Cell::new(0)), live: Rc::new(Cell::new(0)) };
+    let mut map = BTreeMap::new_in(budget.clone());
+
+    assert!(map.entry(0).try_or_insert(0).is_err());
+    assert!(map.is_empty());
+    assert_eq!(budget.live.get(), 0);
+
+    // Insert in ascending order, so that the rightmost nodes are full and
+    // some insertions need to split a leaf and several of its ancestors.
+    for i in 0..MIN_INSERTS_HEIGHT_2 as i32 {
+        let live = budget.live.get();
+        let mut allowed = 0;
+        loop {
+            budget.left.set(allowed);
+            match map.entry(i).try_or_insert(i) {
+                Ok(_) => break,
+                Err(_) => {
+                    // Nothing changed, and any partial reservation was freed.
+                    assert_eq!(map.len(), i as usize);
+                    assert_eq!(budget.live.get(), live);
+                    allowed += 1;
+                }
+            }
+        }
+        assert_eq!(budget.live.get(), live + allowed);
+        assert!(map.iter().map(|(&k, &v)| (k, v)).eq((0..=i).map(|k| (k, k))));
+    }
+
+    let live = budget.live.get();
+    budget.left.set(1);
+    assert!(map.try_clone().is_err());
+    assert_eq!(budget.live.get(), live);
+
+    drop(map);
+    assert_eq!(budget.live.get(), 0);
+}
+
 macro_rules! create_append_test {
     ($name:ident, $len:expr) => {
         #[test]
@@ -2513,3 +2745,128 @@ fn cursor_peek_prev_agrees_with_cursor_mut() {
     let prev = cursor.peek_prev();
     assert_matches!(prev, Some((&3, _)));
 }
+
+#[test]
+fn test_cursor_mut_split() {
+    for len in [0, 1, 10, 100, 1000] {
+        for at in (0..=len).step_by(len / 10 + 1).chain([len]) {
+            let mut left: BTreeMap<_, _> = (0..len).map(|i| (i, i)).collect();
+            let mut cur = left.lower_bound_mut(Bound::Included(&at));
+            let right = cur.split_after();
+            assert_eq!(cur.peek_next(), None);
+            left.check();
+            right.check();
+            assert!(left.keys().copied().eq(0..at));
+            assert!(right.keys().copied().eq(at..len));
+
+            let mut right: BTreeMap<_, _> = (0..len).map(|i| (i, i)).collect();
+            let mut cur = right.lower_bound_mut(Bound::Included(&at));
+            let left = cur.split_before();
+            assert_eq!(cur.peek_prev(), None);
+            left.check();
+            right.check();
+            assert!(left.keys().copied().eq(0..at));
+            assert!(right.keys().copied().eq(at..len));
+        }
+    }
+}
+
+#[test]
+fn test_cursor_mut_splice() {
+    let mut map: BTreeMap<_, _> = (0..100).map(|i| (i * 10, i)).collect();
+    let mut cur = map.lower_bound_mut(Bound::Included(&500));
+    cur.splice_after((495..500).map(|i| (i, i)).collect()).unwrap();
+    assert_eq!(cur.peek_next(), Some((&495, &mut 495)));
+    cur.splice_before((491..495).map(|i| (i, i)).collect()).unwrap();
+    assert_eq!(cur.peek_prev(), Some((&494, &mut 494)));
+    assert_eq!(cur.peek_next(), Some((&495, &mut 495)));
+    map.check();
+    assert_eq!(map.len(), 109);
+    assert!(map.range(490..=500).map(|(&k, _)| k).eq(490..=500));
+
+    let mut empty = BTreeMap::new();
+    let mut cur = empty.lower_bound_mut(Bound::Unbounded);
+    cur.splice_before((0..1000).map(|i| (i, i)).collect()).unwrap();
+    assert_eq!(cur.peek_next(), None);
+    empty.check();
+    assert!(empty.keys().copied().eq(0..1000));
+}
+
+// Splices maps of all sizes into all sizes of maps at their start, in their
+// middle and at their end, which joins trees of every combination of heights.
+fn test_cursor_mut_splice_shapes_in<S: Statistics>(empty: BTreeMap<usize, usize, Global, S>) {
+    for len in [0usize, 1, 10, 100, 1000] {
+        for other_len in [1, 2, 3, 10, 300] {
+            for at in [0, len / 3, len] {
+                let maps = || {
+                    let (mut map, mut other) = (empty.clone(), empty.clone());
+                    map.extend((0..at).chain(at + other_len..len + other_len).map(|i| (i, i)));
+                    other.extend((at..at + other_len).map(|i| (i, i)));
+                    (map, other)
+                };
+
+                let (mut map, other) = maps();
+                let mut cur = map.upper_bound_mut(Bound::Excluded(&at));
+                cur.splice_after(other).unwrap();
+                assert_eq!(cur.peek_prev().map(|(k, _)| *k), at.checked_sub(1));
+                assert_eq!(cur.peek_next().map(|(k, _)| *k), Some(at));
+                map.check();
+                assert!(map.keys().copied().eq(0..len + other_len));
+
+                let (mut map, other) = maps();
+                let mut cur = map.upper_bound_mut(Bound::Excluded(&at));
+                cur.splice_before(other).unwrap();
+                assert_eq!(cur.peek_prev().map(|(k, _)| *k), Some(at + other_len - 1));
+                assert_eq!(
+                    cur.peek_next().map(|(k, _)| *k),
+                    Some(at + other_len).filter(|&k| k < len + other_len)
+                );
+                map.check();
+                assert!(map.keys().copied().eq(0..len + other_len));
+            }
+        }
+    }
+}
+
+#[test]
+fn test_cursor_mut_splice_shapes() {
+    test_cursor_mut_splice_shapes_in(BTreeMap::new());
+}
+
+#[test]
+fn test_cursor_mut_splice_shapes_order_statistics() {
+    test_cursor_mut_splice_shapes_in(BTreeMap::with_order_statistics());
+}
+
+#[test]
+fn test_cursor_mut_splice_unordered() {
+    let mut map = BTreeMap::from([(1, 'a'), (2, 'b'), (3, 'c')]);
+    let mut cur = map.upper_bound_mut(Bound::Included(&2));
+    for keys in [[0, 1], [2, 2], [1, 3], [2, 4], [3, 3]] {
+        let other = BTreeMap::from(keys.map(|k| (k, 'x')));
+        let other = cur.splice_after(other).unwrap_err().map;
+        let other = cur.splice_before(other).unwrap_err().map;
+        assert_eq!(other.len(), if keys[0] == keys[1] { 1 } else { 2 });
+    }
+    cur.splice_after(BTreeMap::new()).unwrap();
+    assert_eq!(map, BTreeMap::from([(1, 'a'), (2, 'b'), (3, 'c')]));
+}
+
+#[test]
+fn test_cursor_mut_splice_takes_over_nodes() {
+    // The clones of a `Budget` are interchangeable, since they share their counts.
+    let budget = Budget { left: Rc::new(Cell::new(usize::MAX)), live: Rc::new(Cell::new(0)) };
+    let mut map = BTreeMap::new_in(budget.clone());
+    map.extend((0..100).chain(10_000..10_100).map(|i| (i, i)));
+    let mut other = BTreeMap::new_in(budget.clone());
+    other.extend((100..10_000).map(|i| (i, i)));
+    let live = budget.live.get();
+    let mut cur = map.lower_bound_mut(Bound::Included(&10_000));
+    cur.splice_before(other).unwrap();
+    map.check();
+    assert!(map.keys().copied().eq(0..10_100));
+    // Only the nodes along the seams are new.
+    assert!(budget.live.get() < live + 10, "{} nodes allocated", budget.live.get() - live);
+    drop(map);
+    assert_eq!(budget.live.get(), 0);
+}
@@ -1,5 +1,7 @@
+#[cfg(not(no_global_oom_handling))]
 mod append;
 mod borrow;
+#[cfg(not(no_global_oom_handling))]
 mod dedup_sorted_iter;
 mod fix;
 pub mod map;
@@ -7,10 +9,15 @@
 mod merge_iter;
 mod navigate;
 mod node;
+mod order_statistics;
+#[cfg(all(not(no_global_oom_handling), target_has_atomic = "ptr"))]
+pub mod persistent;
 mod remove;
+mod reserve;
 mod search;
 pub mod set;
 mod set_val;
+#[cfg(not(no_global_oom_handling))]
 mod split;
 
 trait Recover<Q: ?Sized> {
@@ -18,5 +25,6 @@ trait Recover<Q: ?Sized> {
 
     fn get(&self, key: &Q) -> Option<&Self::Key>;
     fn take(&mut self, key: &Q) -> Option<Self::Key>;
+    #[cfg(not(no_global_oom_handling))]
     fn replace(&mut self, key: Self::Key) -> Option<Self::Key>;
 }
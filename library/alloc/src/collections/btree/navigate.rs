@@ -418,6 +418,7 @@ pub fn next_back_kv(
     }
 }
 
+#[cfg(not(no_global_oom_handling))]
 impl<BorrowType: marker::BorrowType, K, V>
     Handle<NodeRef<BorrowType, K, V, marker::Internal>, marker::Edge>
 {
@@ -652,12 +653,14 @@ pub fn last_leaf_edge(self) -> Handle<NodeRef<BorrowType, K, V, marker::Leaf>, m
     }
 }
 
+#[cfg(not(no_global_oom_handling))]
 pub enum Position<BorrowType, K, V> {
     Leaf(NodeRef<BorrowType, K, V, marker::Leaf>),
     Internal(NodeRef<BorrowType, K, V, marker::Internal>),
     InternalKV,
 }
 
+#[cfg(not(no_global_oom_handling))]
 impl<'a, K: 'a, V: 'a> NodeRef<marker::Immut<'a>, K, V, marker::LeafOrInternal> {
     /// Visits leaf nodes and internal KVs in order of ascending keys, and also
     /// visits internal nodes as a whole in a depth first order, meaning that
@@ -780,3 +783,42 @@ pub fn upper_bound<Q: ?Sized>(
         }
     }
 }
+
+impl<BorrowType: marker::BorrowType, K, V> NodeRef<BorrowType, K, V, marker::LeafOrInternal> {
+    /// Returns the KV at position `index`, counting from zero in ascending key
+    /// order, in or underneath a node, or `None` if there are not that many.
+    ///
+    /// # Safety
+    /// The tree must be allocated by an allocator that keeps subtree lengths.
+    pub unsafe fn find_kv_at(
+        self,
+        mut index: usize,
+    ) -> Option<Handle<NodeRef<BorrowType, K, V, marker::LeafOrInternal>, marker::KV>> {
+        let mut node = self;
+        loop {
+            let mut edge = match node.force() {
+                Leaf(leaf) if index < leaf.len() => {
+                    return Some(unsafe { Handle::new_kv(leaf, index) }.forget_node_type());
+                }
+                Leaf(_) => return None,
+                Internal(internal) => internal.first_edge(),
+            };
+            loop {
+                let child_len = unsafe { edge.reborrow().descend().subtree_len() };
+                if index < child_len {
+                    break;
+                }
+                index -= child_len;
+                match edge.right_kv() {
+                    Ok(kv) if index == 0 => return Some(kv.forget_node_type()),
+                    Ok(kv) => {
+                        index -= 1;
+                        edge = kv.right_edge();
+                    }
+                    Err(_) => return None,
+                }
+            }
+            node = edge.descend();
+        }
+    }
+}
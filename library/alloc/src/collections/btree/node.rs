@@ -30,6 +30,16 @@
 //   For a leaf node, "having an edge" only means we can identify a position in the node,
 //   since leaf edges are empty and need no data representation. In an internal node,
 //   an edge both identifies a position and contains a pointer to a child node.
+//
+// If the nodes of a tree are allocated by a `NodeAlloc` that keeps subtree lengths, as
+// those of maps with `OrderStatistics` are, each internal node also records the number of
+// key-value pairs in the subtree it heads, in room the allocator made in front of the node,
+// so that order statistics take logarithmic time. Functions that maintain that number take
+// the allocator of the tree and do nothing for other allocators. Splitting, merging and
+// stealing keep that number correct for every node they touch, and don't change it for
+// their ancestors. Adding or removing a key-value pair changes it for all ancestors, which
+// is taken care of by `insert_recursing` but left to the caller when removing. `move_suffix`
+// and pushing into a leaf also leave fixing up the numbers to the caller.
 
 use core::marker::PhantomData;
 use core::mem::{self, MaybeUninit};
@@ -39,6 +49,8 @@
 use crate::alloc::{Allocator, Layout};
 use crate::boxed::Box;
 
+use super::order_statistics::subtree_len_ptr;
+
 const B: usize = 6;
 pub const CAPACITY: usize = 2 * B - 1;
 pub const MIN_LEN_AFTER_SPLIT: usize = B - 1;
@@ -80,7 +92,7 @@ unsafe fn init(this: *mut Self) {
     /// Creates a new boxed `LeafNode`.
     fn new<A: Allocator + Clone>(alloc: A) -> Box<Self, A> {
         unsafe {
-            let mut leaf = Box::new_uninit_in(alloc);
+            let mut leaf = new_uninit_node(alloc);
             LeafNode::init(leaf.as_mut_ptr());
             leaf.assume_init()
         }
@@ -112,7 +124,7 @@ impl<K, V> InternalNode<K, V> {
     /// such an edge.
     unsafe fn new<A: Allocator + Clone>(alloc: A) -> Box<Self, A> {
         unsafe {
-            let mut node = Box::<Self, _>::new_uninit_in(alloc);
+            let mut node = new_uninit_node::<Self, _>(alloc);
             // We only need to initialize the data; the edges are MaybeUninit.
             LeafNode::init(ptr::addr_of_mut!((*node.as_mut_ptr()).data));
             node.assume_init()
@@ -204,6 +216,51 @@ fn clone(&self) -> Self {
     }
 }
 
+/// Allocates room for a new node.
+#[cfg(not(no_global_oom_handling))]
+fn new_uninit_node<N, A: Allocator>(alloc: A) -> Box<MaybeUninit<N>, A> {
+    Box::new_uninit_in(alloc)
+}
+
+/// Allocates room for a new node.
+///
+/// Without a global OOM handler, the only operations that add nodes to a tree
+/// are those that first reserve them with `ReservedNodes`, so this never fails.
+#[cfg(no_global_oom_handling)]
+fn new_uninit_node<N, A: Allocator>(alloc: A) -> Box<MaybeUninit<N>, A> {
+    match Box::try_new_uninit_in(alloc) {
+        Ok(node) => node,
+        Err(_) => unreachable!("allocated a B-tree node that was not reserved"),
+    }
+}
+
+/// Turns a newly allocated node into the pointer that its parent or root keeps.
+///
+/// Unlike `Box::leak`, this doesn't go through a reference, so the pointer keeps
+/// all the provenance the allocator handed out, and an allocator may find its
+/// way back from the pointer to bookkeeping it placed around the node.
+fn into_node_ptr<N, A: Allocator>(node: Box<N, A>) -> (NonNull<N>, A) {
+    let (ptr, alloc) = Box::into_raw_with_allocator(node);
+    // SAFETY: a `Box` is never null.
+    (unsafe { NonNull::new_unchecked(ptr) }, alloc)
+}
+
+/// Returns the layout that nodes at the given height are allocated with.
+pub fn node_layout<K, V>(height: usize) -> Layout {
+    if height > 0 { Layout::new::<InternalNode<K, V>>() } else { Layout::new::<LeafNode<K, V>>() }
+}
+
+/// The allocator of the nodes of a tree, which decides whether the internal
+/// nodes record the number of key-value pairs in the subtree they head.
+///
+/// # Safety
+/// If `keeps_subtree_lens` returns true, the allocator must make room in front
+/// of every node it allocates, where `subtree_len_ptr` finds it, and return
+/// the same for every clone of it.
+pub unsafe trait NodeAlloc: Allocator + Clone {
+    fn keeps_subtree_lens(&self) -> bool;
+}
+
 unsafe impl<BorrowType, K: Sync, V: Sync, Type> Sync for NodeRef<BorrowType, K, V, Type> {}
 
 unsafe impl<K: Sync, V: Sync, Type> Send for NodeRef<marker::Immut<'_>, K, V, Type> {}
@@ -218,12 +275,12 @@ pub fn new_leaf<A: Allocator + Clone>(alloc: A) -> Self {
     }
 
     fn from_new_leaf<A: Allocator + Clone>(leaf: Box<LeafNode<K, V>, A>) -> Self {
-        NodeRef { height: 0, node: NonNull::from(Box::leak(leaf)), _marker: PhantomData }
+        NodeRef { height: 0, node: into_node_ptr(leaf).0, _marker: PhantomData }
     }
 }
 
 impl<K, V> NodeRef<marker::Owned, K, V, marker::Internal> {
-    fn new_internal<A: Allocator + Clone>(child: Root<K, V>, alloc: A) -> Self {
+    fn new_internal<A: NodeAlloc>(child: Root<K, V>, alloc: A) -> Self {
         let mut new_node = unsafe { InternalNode::new(alloc) };
         new_node.edges[0].write(child.node);
         unsafe { NodeRef::from_new_internal(new_node, child.height + 1) }
@@ -231,14 +288,15 @@ fn new_internal<A: Allocator + Clone>(child: Root<K, V>, alloc: A) -> Self {
 
     /// # Safety
     /// `height` must not be zero.
-    unsafe fn from_new_internal<A: Allocator + Clone>(
+    unsafe fn from_new_internal<A: NodeAlloc>(
         internal: Box<InternalNode<K, V>, A>,
         height: usize,
     ) -> Self {
         debug_assert!(height > 0);
-        let node = NonNull::from(Box::leak(internal)).cast();
-        let mut this = NodeRef { height, node, _marker: PhantomData };
+        let (node, alloc) = into_node_ptr(internal);
+        let mut this = NodeRef { height, node: node.cast(), _marker: PhantomData };
         this.borrow_mut().correct_all_childrens_parent_links();
+        this.borrow_mut().fix_subtree_len(&alloc);
         this
     }
 }
@@ -285,6 +343,7 @@ pub fn len(&self) -> usize {
     /// root on top, the number says at which elevation the node appears.
     /// If you picture trees with leaves on top, the number says how high
     /// the tree extends above the node.
+    #[cfg(not(no_global_oom_handling))]
     pub fn height(&self) -> usize {
         self.height
     }
@@ -345,6 +404,7 @@ pub fn last_edge(self) -> Handle<Self, marker::Edge> {
     }
 
     /// Note that `self` must be nonempty.
+    #[cfg(not(no_global_oom_handling))]
     pub fn first_kv(self) -> Handle<Self, marker::KV> {
         let len = self.len();
         assert!(len > 0);
@@ -352,6 +412,7 @@ pub fn first_kv(self) -> Handle<Self, marker::KV> {
     }
 
     /// Note that `self` must be nonempty.
+    #[cfg(not(no_global_oom_handling))]
     pub fn last_kv(self) -> Handle<Self, marker::KV> {
         let len = self.len();
         assert!(len > 0);
@@ -412,6 +473,89 @@ pub unsafe fn deallocate_and_ascend<A: Allocator + Clone>(
         }
         ret
     }
+
+    /// Deallocates the node without looking at its parent link, which, for a
+    /// node shared between trees, need not point anywhere sensible.
+    /// Like `deallocate_and_ascend`, this leaves the keys, values and children
+    /// of the node to the caller.
+    #[cfg(not(no_global_oom_handling))]
+    pub unsafe fn deallocate<A: Allocator + Clone>(self, alloc: A) {
+        let (node, layout) = self.allocation();
+        unsafe { alloc.deallocate(node, layout) }
+    }
+}
+
+impl<BorrowType, K, V> NodeRef<BorrowType, K, V, marker::LeafOrInternal> {
+    /// Returns the pointer to the node and the layout it was allocated with.
+    pub fn allocation(&self) -> (NonNull<u8>, Layout) {
+        (self.node.cast(), node_layout::<K, V>(self.height))
+    }
+}
+
+#[cfg(not(no_global_oom_handling))]
+impl<'a, K: Clone + 'a, V: Clone + 'a> NodeRef<marker::Immut<'a>, K, V, marker::LeafOrInternal> {
+    /// Allocates a copy of this node alone, holding clones of its keys and
+    /// values and, if it is internal, edges to the very same children.
+    ///
+    /// The children are not told about the copy: their parent links keep
+    /// pointing at this node, and the caller is responsible for the fact that
+    /// they are now owned twice. The copy itself has no parent.
+    ///
+    /// If cloning a key or value panics, the clones made so far are dropped
+    /// and the copy is deallocated, leaving the children alone.
+    pub fn clone_node<A: NodeAlloc>(self, alloc: A) -> Root<K, V> {
+        struct Guard<K, V, A: Allocator + Clone> {
+            copy: Root<K, V>,
+            alloc: A,
+        }
+        impl<K, V, A: Allocator + Clone> Drop for Guard<K, V, A> {
+            fn drop(&mut self) {
+                let copy = unsafe { ptr::read(&self.copy) }.into_dying();
+                for i in 0..copy.len() {
+                    unsafe { Handle::new_kv(ptr::read(&copy), i).drop_key_val() };
+                }
+                unsafe { copy.deallocate(self.alloc.clone()) };
+            }
+        }
+
+        // Copy all edges first, so the copy is a valid node
+        // with whatever number of key-value pairs it has so far.
+        let copy = match self.force() {
+            ForceResult::Leaf(_) => NodeRef::new_leaf(alloc.clone()).forget_type(),
+            ForceResult::Internal(internal) => {
+                // SAFETY: there can be no mutable references into this tree borrowed as `Immut`.
+                let node = unsafe { &*NodeRef::as_internal_ptr(&internal) };
+                let len = usize::from(node.data.len);
+                let mut copy = unsafe { InternalNode::new(alloc.clone()) };
+                copy.edges[..=len].copy_from_slice(&node.edges[..=len]);
+                let node = into_node_ptr(copy).0.cast();
+                let mut copy: NodeRef<marker::Owned, K, V, marker::Internal> =
+                    NodeRef { height: internal.height, node, _marker: PhantomData };
+                if alloc.keeps_subtree_lens() {
+                    let subtree_len = unsafe { internal.forget_type().subtree_len() };
+                    unsafe { copy.borrow_mut().set_subtree_len(subtree_len) };
+                }
+                copy.forget_type()
+            }
+        };
+        let mut guard = Guard { copy, alloc };
+        let node = self.into_leaf();
+        for i in 0..usize::from(node.len) {
+            let k = unsafe { node.keys[i].assume_init_ref() }.clone();
+            // If this panics, `k` is not in the copy yet and is dropped while unwinding.
+            let v = unsafe { node.vals[i].assume_init_ref() }.clone();
+            let mut copy = guard.copy.borrow_mut();
+            unsafe {
+                copy.key_area_mut(i).write(k);
+                copy.val_area_mut(i).write(v);
+            }
+            *copy.len_mut() += 1;
+        }
+        // Move both fields out of the guard, so that the allocator is dropped.
+        let guard = mem::ManuallyDrop::new(guard);
+        let (copy, _alloc) = unsafe { (ptr::read(&guard.copy), ptr::read(&guard.alloc)) };
+        copy
+    }
 }
 
 impl<'a, K, V, Type> NodeRef<marker::Mut<'a>, K, V, Type> {
@@ -557,6 +701,115 @@ fn correct_all_childrens_parent_links(&mut self) {
         let len = self.len();
         unsafe { self.correct_childrens_parent_links(0..=len) };
     }
+
+    /// Sets the number of key-value pairs in the subtree headed by this node.
+    ///
+    /// # Safety
+    /// The node must be allocated by an allocator that keeps subtree lengths.
+    unsafe fn set_subtree_len(&mut self, subtree_len: usize) {
+        let (node, layout) = self.reborrow().forget_type().allocation();
+        unsafe { subtree_len_ptr(node, layout).write(subtree_len) }
+    }
+
+    /// Recomputes the number of key-value pairs in the subtree headed by this
+    /// node, assuming that number is correct for each of its children, if the
+    /// tree is allocated by `alloc` and `alloc` keeps subtree lengths.
+    fn fix_subtree_len<A: NodeAlloc>(&mut self, alloc: &A) {
+        if alloc.keeps_subtree_lens() {
+            let len = self.len();
+            let mut subtree_len = len;
+            for i in 0..=len {
+                let child = unsafe { Handle::new_edge(self.reborrow(), i) }.descend();
+                subtree_len += unsafe { child.subtree_len() };
+            }
+            unsafe { self.set_subtree_len(subtree_len) };
+        }
+    }
+}
+
+impl<BorrowType, K, V> NodeRef<BorrowType, K, V, marker::LeafOrInternal> {
+    /// Returns the number of key-value pairs in the subtree headed by this node.
+    ///
+    /// # Safety
+    /// The node must be allocated by an allocator that keeps subtree lengths.
+    pub unsafe fn subtree_len(&self) -> usize {
+        if self.height == 0 {
+            self.len()
+        } else {
+            let (node, layout) = self.allocation();
+            unsafe { *subtree_len_ptr(node, layout) }
+        }
+    }
+}
+
+#[cfg(not(no_global_oom_handling))]
+impl<'a, K, V> NodeRef<marker::Mut<'a>, K, V, marker::LeafOrInternal> {
+    /// Recomputes the number of key-value pairs in every subtree within the
+    /// subtree headed by this node, from the bottom up, if the tree is
+    /// allocated by `alloc` and `alloc` keeps subtree lengths.
+    pub fn fix_all_subtree_lens<A: NodeAlloc>(self, alloc: &A) {
+        if !alloc.keeps_subtree_lens() {
+            return;
+        }
+        if let ForceResult::Internal(mut internal) = self.force() {
+            for i in 0..=internal.len() {
+                let child = unsafe { Handle::new_edge(internal.reborrow_mut(), i) };
+                child.descend().fix_all_subtree_lens(alloc);
+            }
+            internal.fix_subtree_len(alloc);
+        }
+    }
+}
+
+impl<'a, K: 'a, V: 'a, Type> NodeRef<marker::Mut<'a>, K, V, Type> {
+    /// Records that a key-value pair is about to be added somewhere in the
+    /// subtree headed by this node, for the benefit of all of its ancestors,
+    /// if the tree is allocated by `alloc` and `alloc` keeps subtree lengths.
+    pub fn grow_subtree_lens_above<A: NodeAlloc>(&mut self, alloc: &A) {
+        self.update_subtree_lens_above(alloc, |len| len + 1);
+    }
+
+    /// Records that a key-value pair was removed from the subtree headed by
+    /// this node, for the benefit of all of its ancestors, if the tree is
+    /// allocated by `alloc` and `alloc` keeps subtree lengths.
+    pub fn shrink_subtree_lens_above<A: NodeAlloc>(&mut self, alloc: &A) {
+        self.update_subtree_lens_above(alloc, |len| len - 1);
+    }
+
+    /// Recomputes the number of key-value pairs in the subtrees headed by all
+    /// ancestors of this node, assuming it is correct for all other nodes,
+    /// if the tree is allocated by `alloc` and `alloc` keeps subtree lengths.
+    #[cfg(not(no_global_oom_handling))]
+    pub fn fix_subtree_lens_above<A: NodeAlloc>(&mut self, alloc: &A) {
+        if alloc.keeps_subtree_lens() {
+            self.update_ancestors(|mut parent| parent.fix_subtree_len(alloc));
+        }
+    }
+
+    fn update_subtree_lens_above<A: NodeAlloc>(
+        &mut self,
+        alloc: &A,
+        update: impl Fn(usize) -> usize,
+    ) {
+        if alloc.keeps_subtree_lens() {
+            self.update_ancestors(|mut parent| unsafe {
+                let subtree_len = parent.reborrow().forget_type().subtree_len();
+                parent.set_subtree_len(update(subtree_len));
+            });
+        }
+    }
+
+    fn update_ancestors(
+        &mut self,
+        mut update: impl FnMut(NodeRef<marker::Mut<'_>, K, V, marker::Internal>),
+    ) {
+        let mut parent = unsafe { self.reborrow_mut() }.ascend().ok();
+        while let Some(edge) = parent {
+            let mut node = edge.into_node();
+            update(unsafe { node.reborrow_mut() });
+            parent = node.ascend().ok();
+        }
+    }
 }
 
 impl<'a, K: 'a, V: 'a> NodeRef<marker::Mut<'a>, K, V, marker::LeafOrInternal> {
@@ -580,6 +833,7 @@ fn clear_parent_link(&mut self) {
 
 impl<K, V> NodeRef<marker::Owned, K, V, marker::LeafOrInternal> {
     /// Returns a new owned tree, with its own root node that is initially empty.
+    #[cfg(not(no_global_oom_handling))]
     pub fn new<A: Allocator + Clone>(alloc: A) -> Self {
         NodeRef::new_leaf(alloc).forget_type()
     }
@@ -587,7 +841,7 @@ pub fn new<A: Allocator + Clone>(alloc: A) -> Self {
     /// Adds a new internal node with a single edge pointing to the previous root node,
     /// make that new node the root node, and return it. This increases the height by 1
     /// and is the opposite of `pop_internal_level`.
-    pub fn push_internal_level<A: Allocator + Clone>(
+    pub fn push_internal_level<A: NodeAlloc>(
         &mut self,
         alloc: A,
     ) -> NodeRef<marker::Mut<'_>, K, V, marker::Internal> {
@@ -682,8 +936,8 @@ pub fn push(&mut self, key: K, val: V) -> *mut V {
 
 impl<'a, K: 'a, V: 'a> NodeRef<marker::Mut<'a>, K, V, marker::Internal> {
     /// Adds a key-value pair, and an edge to go to the right of that pair,
-    /// to the end of the node.
-    pub fn push(&mut self, key: K, val: V, edge: Root<K, V>) {
+    /// to the end of the node, which belongs to a tree allocated by `alloc`.
+    pub fn push<A: NodeAlloc>(&mut self, key: K, val: V, edge: Root<K, V>, alloc: &A) {
         assert!(edge.height == self.height - 1);
 
         let len = self.len_mut();
@@ -696,6 +950,12 @@ pub fn push(&mut self, key: K, val: V, edge: Root<K, V>) {
             self.edge_area_mut(idx + 1).write(edge.node);
             Handle::new_edge(self.reborrow_mut(), idx + 1).correct_parent_link();
         }
+        if alloc.keeps_subtree_lens() {
+            unsafe {
+                let subtree_len = self.reborrow().forget_type().subtree_len();
+                self.set_subtree_len(subtree_len + 1 + edge.subtree_len());
+            }
+        }
     }
 }
 
@@ -947,7 +1207,7 @@ fn insert<A: Allocator + Clone>(
         } else {
             let (middle_kv_idx, insertion) = splitpoint(self.idx);
             let middle = unsafe { Handle::new_kv(self.node, middle_kv_idx) };
-            let mut result = middle.split(alloc);
+            let mut result = middle.split(alloc.clone());
             let insertion_edge = match insertion {
                 LeftOrRight::Left(insert_idx) => unsafe {
                     Handle::new_edge(result.left.reborrow_mut(), insert_idx)
@@ -967,7 +1227,7 @@ fn insert<A: Allocator + Clone>(
 impl<'a, K, V> Handle<NodeRef<marker::Mut<'a>, K, V, marker::Internal>, marker::Edge> {
     /// Fixes the parent pointer and index in the child node that this edge
     /// links to. This is useful when the ordering of edges has been changed,
-    fn correct_parent_link(self) {
+    pub fn correct_parent_link(self) {
         // Create backpointer without invalidating other references to the node.
         let ptr = unsafe { NonNull::new_unchecked(NodeRef::as_internal_ptr(&self.node)) };
         let idx = self.idx;
@@ -977,6 +1237,18 @@ fn correct_parent_link(self) {
 }
 
 impl<'a, K: 'a, V: 'a> Handle<NodeRef<marker::Mut<'a>, K, V, marker::Internal>, marker::Edge> {
+    /// Makes this edge link to `child` instead, and returns the previous child,
+    /// whose parent link is left untouched.
+    #[cfg(not(no_global_oom_handling))]
+    pub fn replace_child(mut self, child: Root<K, V>) -> Root<K, V> {
+        assert!(child.height == self.node.height - 1);
+        let old = self.reborrow().descend();
+        let old = NodeRef { height: old.height, node: old.node, _marker: PhantomData };
+        unsafe { self.node.edge_area_mut(self.idx).write(child.node) };
+        self.correct_parent_link();
+        old
+    }
+
     /// Inserts a new key-value pair and an edge that will go to the right of that new pair
     /// between this edge and the key-value pair to the right of this edge. This method assumes
     /// that there is enough space in the node for the new pair to fit.
@@ -998,7 +1270,7 @@ fn insert_fit(&mut self, key: K, val: V, edge: Root<K, V>) {
     /// Inserts a new key-value pair and an edge that will go to the right of that new pair
     /// between this edge and the key-value pair to the right of this edge. This method splits
     /// the node if there isn't enough room.
-    fn insert<A: Allocator + Clone>(
+    fn insert<A: NodeAlloc>(
         mut self,
         key: K,
         val: V,
@@ -1013,7 +1285,7 @@ fn insert<A: Allocator + Clone>(
         } else {
             let (middle_kv_idx, insertion) = splitpoint(self.idx);
             let middle = unsafe { Handle::new_kv(self.node, middle_kv_idx) };
-            let mut result = middle.split(alloc);
+            let mut result = middle.split(alloc.clone());
             let mut insertion_edge = match insertion {
                 LeftOrRight::Left(insert_idx) => unsafe {
                     Handle::new_edge(result.left.reborrow_mut(), insert_idx)
@@ -1023,9 +1295,39 @@ fn insert<A: Allocator + Clone>(
                 },
             };
             insertion_edge.insert_fit(key, val, edge);
+            insertion_edge.node.fix_subtree_len(&alloc);
             Some(result)
         }
     }
+
+    /// Inserts a new key-value pair and an edge that will go to the right of that new pair
+    /// between this edge and the key-value pair to the right of this edge. This method splits
+    /// the node if there isn't enough room, and tries to insert the split off portion into
+    /// the parent node recursively, until the root is reached.
+    ///
+    /// If the returned result is some `SplitResult`, the `left` field will be the root node.
+    /// The subtree lengths of the ancestors of the new edge are left for the caller to fix.
+    #[cfg(not(no_global_oom_handling))]
+    pub fn insert_recursing<A: NodeAlloc>(
+        self,
+        key: K,
+        val: V,
+        edge: Root<K, V>,
+        alloc: A,
+    ) -> Option<SplitResult<'a, K, V, marker::Internal>> {
+        let mut split = self.insert(key, val, edge, alloc.clone())?;
+        loop {
+            split = match split.left.ascend() {
+                Ok(parent) => parent.insert(
+                    split.kv.0,
+                    split.kv.1,
+                    split.right.forget_type(),
+                    alloc.clone(),
+                )?,
+                Err(root) => return Some(SplitResult { left: root, ..split }),
+            };
+        }
+    }
 }
 
 impl<'a, K: 'a, V: 'a> Handle<NodeRef<marker::Mut<'a>, K, V, marker::Leaf>, marker::Edge> {
@@ -1036,13 +1338,16 @@ impl<'a, K: 'a, V: 'a> Handle<NodeRef<marker::Mut<'a>, K, V, marker::Leaf>, mark
     /// If the returned result is some `SplitResult`, the `left` field will be the root node.
     /// The returned pointer points to the inserted value, which in the case of `SplitResult`
     /// is in the `left` or `right` tree.
-    pub fn insert_recursing<A: Allocator + Clone>(
-        self,
+    pub fn insert_recursing<A: NodeAlloc>(
+        mut self,
         key: K,
         value: V,
         alloc: A,
         split_root: impl FnOnce(SplitResult<'a, K, V, marker::LeafOrInternal>),
     ) -> Handle<NodeRef<marker::Mut<'a>, K, V, marker::Leaf>, marker::KV> {
+        // Any splits below an ancestor keep its subtree length as it is.
+        self.node.grow_subtree_lens_above(&alloc);
+
         let (mut split, handle) = match self.insert(key, value, alloc.clone()) {
             // SAFETY: we have finished splitting and can now re-awaken the
             // handle to the inserted element.
@@ -1109,6 +1414,7 @@ pub fn into_kv(self) -> (&'a K, &'a V) {
 }
 
 impl<'a, K: 'a, V: 'a, NodeType> Handle<NodeRef<marker::Mut<'a>, K, V, NodeType>, marker::KV> {
+    #[cfg(not(no_global_oom_handling))]
     pub fn key_mut(&mut self) -> &mut K {
         unsafe { self.node.key_area_mut(self.idx).assume_init_mut() }
     }
@@ -1249,13 +1555,10 @@ impl<'a, K: 'a, V: 'a> Handle<NodeRef<marker::Mut<'a>, K, V, marker::Internal>,
     /// - The key and value pointed to by this handle are extracted.
     /// - All the edges and key-value pairs to the right of this handle are put into
     ///   a newly allocated node.
-    pub fn split<A: Allocator + Clone>(
-        mut self,
-        alloc: A,
-    ) -> SplitResult<'a, K, V, marker::Internal> {
+    pub fn split<A: NodeAlloc>(mut self, alloc: A) -> SplitResult<'a, K, V, marker::Internal> {
         let old_len = self.node.len();
         unsafe {
-            let mut new_node = InternalNode::new(alloc);
+            let mut new_node = InternalNode::new(alloc.clone());
             let kv = self.split_leaf_data(&mut new_node.data);
             let new_len = usize::from(new_node.data.len);
             move_to_slice(
@@ -1265,6 +1568,7 @@ pub fn split<A: Allocator + Clone>(
 
             let height = self.node.height;
             let right = NodeRef::from_new_internal(new_node, height);
+            self.node.fix_subtree_len(&alloc);
 
             SplitResult { left: self.node, kv, right }
         }
@@ -1279,6 +1583,7 @@ pub struct BalancingContext<'a, K, V> {
     right_child: NodeRef<marker::Mut<'a>, K, V, marker::LeafOrInternal>,
 }
 
+#[cfg(not(no_global_oom_handling))]
 impl<'a, K, V> Handle<NodeRef<marker::Mut<'a>, K, V, marker::Internal>, marker::KV> {
     pub fn consider_for_balancing(self) -> BalancingContext<'a, K, V> {
         let self1 = unsafe { ptr::read(&self) };
@@ -1337,10 +1642,12 @@ pub fn right_child_len(&self) -> usize {
         self.right_child.len()
     }
 
+    #[cfg(not(no_global_oom_handling))]
     pub fn into_left_child(self) -> NodeRef<marker::Mut<'a>, K, V, marker::LeafOrInternal> {
         self.left_child
     }
 
+    #[cfg(not(no_global_oom_handling))]
     pub fn into_right_child(self) -> NodeRef<marker::Mut<'a>, K, V, marker::LeafOrInternal> {
         self.right_child
     }
@@ -1360,7 +1667,7 @@ fn do_merge<
             NodeRef<marker::Mut<'a>, K, V, marker::LeafOrInternal>,
         ) -> R,
         R,
-        A: Allocator,
+        A: NodeAlloc,
     >(
         self,
         result: F,
@@ -1408,6 +1715,7 @@ fn do_merge<
                 );
 
                 left_node.correct_childrens_parent_links(old_left_len + 1..new_left_len + 1);
+                left_node.fix_subtree_len(&alloc);
 
                 alloc.deallocate(right_node.node.cast(), Layout::new::<InternalNode<K, V>>());
             } else {
@@ -1421,7 +1729,7 @@ fn do_merge<
     /// the left child node and returns the shrunk parent node.
     ///
     /// Panics unless we `.can_merge()`.
-    pub fn merge_tracking_parent<A: Allocator + Clone>(
+    pub fn merge_tracking_parent<A: NodeAlloc>(
         self,
         alloc: A,
     ) -> NodeRef<marker::Mut<'a>, K, V, marker::Internal> {
@@ -1432,7 +1740,7 @@ pub fn merge_tracking_parent<A: Allocator + Clone>(
     /// the left child node and returns that child node.
     ///
     /// Panics unless we `.can_merge()`.
-    pub fn merge_tracking_child<A: Allocator + Clone>(
+    pub fn merge_tracking_child<A: NodeAlloc>(
         self,
         alloc: A,
     ) -> NodeRef<marker::Mut<'a>, K, V, marker::LeafOrInternal> {
@@ -1444,7 +1752,7 @@ pub fn merge_tracking_child<A: Allocator + Clone>(
     /// where the tracked child edge ended up,
     ///
     /// Panics unless we `.can_merge()`.
-    pub fn merge_tracking_child_edge<A: Allocator + Clone>(
+    pub fn merge_tracking_child_edge<A: NodeAlloc>(
         self,
         track_edge_idx: LeftOrRight<usize>,
         alloc: A,
@@ -1467,11 +1775,12 @@ pub fn merge_tracking_child_edge<A: Allocator + Clone>(
     /// of the parent, while pushing the old parent key-value pair into the right child.
     /// Returns a handle to the edge in the right child corresponding to where the original
     /// edge specified by `track_right_edge_idx` ended up.
-    pub fn steal_left(
+    pub fn steal_left<A: NodeAlloc>(
         mut self,
         track_right_edge_idx: usize,
+        alloc: &A,
     ) -> Handle<NodeRef<marker::Mut<'a>, K, V, marker::LeafOrInternal>, marker::Edge> {
-        self.bulk_steal_left(1);
+        self.bulk_steal_left(1, alloc);
         unsafe { Handle::new_edge(self.right_child, 1 + track_right_edge_idx) }
     }
 
@@ -1479,16 +1788,17 @@ pub fn steal_left(
     /// of the parent, while pushing the old parent key-value pair onto the left child.
     /// Returns a handle to the edge in the left child specified by `track_left_edge_idx`,
     /// which didn't move.
-    pub fn steal_right(
+    pub fn steal_right<A: NodeAlloc>(
         mut self,
         track_left_edge_idx: usize,
+        alloc: &A,
     ) -> Handle<NodeRef<marker::Mut<'a>, K, V, marker::LeafOrInternal>, marker::Edge> {
-        self.bulk_steal_right(1);
+        self.bulk_steal_right(1, alloc);
         unsafe { Handle::new_edge(self.left_child, track_left_edge_idx) }
     }
 
     /// This does stealing similar to `steal_left` but steals multiple elements at once.
-    pub fn bulk_steal_left(&mut self, count: usize) {
+    pub fn bulk_steal_left<A: NodeAlloc>(&mut self, count: usize, alloc: &A) {
         assert!(count > 0);
         unsafe {
             let left_node = &mut self.left_child;
@@ -1543,6 +1853,8 @@ pub fn bulk_steal_left(&mut self, count: usize) {
                     );
 
                     right.correct_childrens_parent_links(0..new_right_len + 1);
+                    left.fix_subtree_len(alloc);
+                    right.fix_subtree_len(alloc);
                 }
                 (ForceResult::Leaf(_), ForceResult::Leaf(_)) => {}
                 _ => unreachable!(),
@@ -1551,7 +1863,7 @@ pub fn bulk_steal_left(&mut self, count: usize) {
     }
 
     /// The symmetric clone of `bulk_steal_left`.
-    pub fn bulk_steal_right(&mut self, count: usize) {
+    pub fn bulk_steal_right<A: NodeAlloc>(&mut self, count: usize, alloc: &A) {
         assert!(count > 0);
         unsafe {
             let left_node = &mut self.left_child;
@@ -1607,6 +1919,8 @@ pub fn bulk_steal_right(&mut self, count: usize) {
 
                     left.correct_childrens_parent_links(old_left_len + 1..new_left_len + 1);
                     right.correct_childrens_parent_links(0..new_right_len + 1);
+                    left.fix_subtree_len(alloc);
+                    right.fix_subtree_len(alloc);
                 }
                 (ForceResult::Leaf(_), ForceResult::Leaf(_)) => {}
                 _ => unreachable!(),
@@ -1639,6 +1953,14 @@ pub fn forget_node_type(
     }
 }
 
+impl<BorrowType, K, V> Handle<NodeRef<BorrowType, K, V, marker::Internal>, marker::KV> {
+    pub fn forget_node_type(
+        self,
+    ) -> Handle<NodeRef<BorrowType, K, V, marker::LeafOrInternal>, marker::KV> {
+        unsafe { Handle::new_kv(self.node.forget_type(), self.idx) }
+    }
+}
+
 impl<BorrowType, K, V, Type> Handle<NodeRef<BorrowType, K, V, marker::LeafOrInternal>, Type> {
     /// Checks whether the underlying node is an `Internal` node or a `Leaf` node.
     pub fn force(
@@ -1668,6 +1990,7 @@ pub unsafe fn cast_to_leaf_unchecked(
     }
 }
 
+#[cfg(not(no_global_oom_handling))]
 impl<'a, K, V> Handle<NodeRef<marker::Mut<'a>, K, V, marker::LeafOrInternal>, marker::Edge> {
     /// Move the suffix after `self` from one node to another one. `right` must be empty.
     /// The first edge of `right` remains unchanged.
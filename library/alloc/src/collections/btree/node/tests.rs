@@ -1,4 +1,5 @@
 use super::super::navigate;
+use super::super::order_statistics::{NoStatistics, Nodes};
 use super::*;
 use crate::alloc::Global;
 use crate::fmt::Debug;
@@ -17,6 +18,21 @@ pub fn assert_back_pointers(self) {
         }
     }
 
+    // Asserts that each reachable internal node knows the length of its subtree,
+    // in a tree allocated by an allocator that keeps subtree lengths.
+    pub unsafe fn assert_subtree_lens(self) {
+        let subtree_len = unsafe { self.subtree_len() };
+        if let ForceResult::Internal(node) = self.force() {
+            let mut len = node.len();
+            for idx in 0..=node.len() {
+                let child = unsafe { Handle::new_edge(node, idx) }.descend();
+                unsafe { child.assert_subtree_lens() };
+                len += unsafe { child.subtree_len() };
+            }
+            assert_eq!(subtree_len, len);
+        }
+    }
+
     // Renders a multi-line display of the keys in order and in tree hierarchy,
     // picturing the tree growing sideways from its root on the left to its
     // leaves on the right.
@@ -64,10 +80,11 @@ fn test_splitpoint() {
 
 #[test]
 fn test_partial_eq() {
-    let mut root1 = NodeRef::new_leaf(Global);
+    let alloc: Nodes<Global, NoStatistics> = Nodes::new(Global);
+    let mut root1 = NodeRef::new_leaf(alloc.clone());
     root1.borrow_mut().push(1, ());
-    let mut root1 = NodeRef::new_internal(root1.forget_type(), Global).forget_type();
-    let root2 = Root::new(Global);
+    let mut root1 = NodeRef::new_internal(root1.forget_type(), alloc.clone()).forget_type();
+    let root2 = Root::new(alloc.clone());
     root1.reborrow().assert_back_pointers();
     root2.reborrow().assert_back_pointers();
 
@@ -83,9 +100,9 @@ fn test_partial_eq() {
     assert!(top_edge_1 == top_edge_1);
     assert!(top_edge_1 != top_edge_2);
 
-    root1.pop_internal_level(Global);
-    unsafe { root1.into_dying().deallocate_and_ascend(Global) };
-    unsafe { root2.into_dying().deallocate_and_ascend(Global) };
+    root1.pop_internal_level(alloc.clone());
+    unsafe { root1.into_dying().deallocate_and_ascend(alloc.clone()) };
+    unsafe { root2.into_dying().deallocate_and_ascend(alloc) };
 }
 
 #[test]
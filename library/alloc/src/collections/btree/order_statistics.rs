@@ -0,0 +1,162 @@
+use core::marker::PhantomData;
+use core::ptr::NonNull;
+
+use crate::alloc::{AllocError, Allocator, Layout};
+
+use super::node::NodeAlloc;
+
+/// The bookkeeping that a [`BTreeMap`] or [`BTreeSet`] does in its nodes
+/// besides storing its elements, chosen by its last type parameter.
+///
+/// This trait is sealed: it is implemented by [`NoStatistics`], the default,
+/// and by [`OrderStatistics`], and cannot be implemented outside of the
+/// standard library.
+///
+/// [`BTreeMap`]: super::map::BTreeMap
+/// [`BTreeSet`]: super::set::BTreeSet
+#[unstable(feature = "btree_order_statistics", issue = "none")]
+pub trait Statistics: private::Sealed {}
+
+mod private {
+    #[unstable(feature = "btree_order_statistics", issue = "none")]
+    pub trait Sealed {
+        /// Whether internal nodes record the number of elements in their subtree.
+        #[unstable(feature = "btree_order_statistics", issue = "none")]
+        const SUBTREE_LENS: bool;
+    }
+}
+
+/// The default bookkeeping of a [`BTreeMap`] or [`BTreeSet`], which is none.
+///
+/// [`BTreeMap`]: super::map::BTreeMap
+/// [`BTreeSet`]: super::set::BTreeSet
+#[unstable(feature = "btree_order_statistics", issue = "none")]
+#[derive(Debug)]
+pub enum NoStatistics {}
+
+/// Bookkeeping that makes a [`BTreeMap`] or [`BTreeSet`] record in every
+/// node the number of elements in the subtree it heads, so that order
+/// statistics, such as [`BTreeMap::nth`] and [`BTreeMap::rank`], take
+/// logarithmic instead of linear time.
+///
+/// Keeping these numbers costs a little memory for every node and, when
+/// inserting or removing an element, a little time for every level of the
+/// tree. Maps and sets with the default [`NoStatistics`] don't pay for it,
+/// and don't offer order statistics.
+///
+/// [`BTreeMap`]: super::map::BTreeMap
+/// [`BTreeSet`]: super::set::BTreeSet
+/// [`BTreeMap::nth`]: super::map::BTreeMap::nth
+/// [`BTreeMap::rank`]: super::map::BTreeMap::rank
+///
+/// # Examples
+///
+/// ```
+/// #![feature(btree_order_statistics)]
+/// use std::collections::BTreeMap;
+///
+/// let mut map = BTreeMap::with_order_statistics();
+/// map.extend((0..1000).map(|i| (i * 2, i)));
+/// assert_eq!(map.nth(500), Some((&1000, &500)));
+/// assert_eq!(map.rank(&1000), 500);
+/// assert_eq!(map.range_count(10..20), 5);
+/// ```
+#[unstable(feature = "btree_order_statistics", issue = "none")]
+#[derive(Debug)]
+pub enum OrderStatistics {}
+
+#[unstable(feature = "btree_order_statistics", issue = "none")]
+impl Statistics for NoStatistics {}
+
+#[unstable(feature = "btree_order_statistics", issue = "none")]
+impl Statistics for OrderStatistics {}
+
+#[unstable(feature = "btree_order_statistics", issue = "none")]
+impl private::Sealed for NoStatistics {
+    const SUBTREE_LENS: bool = false;
+}
+
+#[unstable(feature = "btree_order_statistics", issue = "none")]
+impl private::Sealed for OrderStatistics {
+    const SUBTREE_LENS: bool = true;
+}
+
+/// The allocator through which a map with statistics `S` allocates its nodes:
+/// the map's allocator, asked for room in front of every node for the number
+/// of elements in the subtree headed by the node, if `S` keeps that number.
+pub struct Nodes<A, S> {
+    alloc: A,
+    _statistics: PhantomData<S>,
+}
+
+impl<A, S> Nodes<A, S> {
+    pub const fn new(alloc: A) -> Self {
+        Nodes { alloc, _statistics: PhantomData }
+    }
+
+    /// Returns the map's allocator.
+    pub fn allocator(&self) -> &A {
+        &self.alloc
+    }
+}
+
+impl<A: Clone, S> Clone for Nodes<A, S> {
+    fn clone(&self) -> Self {
+        Nodes::new(self.alloc.clone())
+    }
+}
+
+impl<A: Default, S> Default for Nodes<A, S> {
+    fn default() -> Self {
+        Nodes::new(A::default())
+    }
+}
+
+/// Returns the layout of an allocation holding both the length and
+/// something of the given layout, and the offset of the latter.
+fn with_len(layout: Layout) -> Result<(Layout, usize), AllocError> {
+    Layout::new::<usize>().extend(layout).map_err(|_| AllocError)
+}
+
+unsafe impl<A: Allocator, S: Statistics> Allocator for Nodes<A, S> {
+    fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        if !S::SUBTREE_LENS {
+            return self.alloc.allocate(layout);
+        }
+        let (with_len, offset) = with_len(layout)?;
+        let ptr = self.alloc.allocate(with_len)?.as_mut_ptr();
+        unsafe {
+            // The node pointer is derived from the pointer to the whole
+            // allocation, and `node` keeps it that way, so that the length
+            // can be found again.
+            let node = NonNull::new_unchecked(ptr.add(offset));
+            Ok(NonNull::slice_from_raw_parts(node, layout.size()))
+        }
+    }
+
+    unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+        if !S::SUBTREE_LENS {
+            return unsafe { self.alloc.deallocate(ptr, layout) };
+        }
+        // SAFETY: `allocate` accepted the same layout.
+        let (with_len, offset) = unsafe { with_len(layout).unwrap_unchecked() };
+        unsafe { self.alloc.deallocate(NonNull::new_unchecked(ptr.as_ptr().sub(offset)), with_len) }
+    }
+}
+
+unsafe impl<A: Allocator + Clone, S: Statistics> NodeAlloc for Nodes<A, S> {
+    fn keeps_subtree_lens(&self) -> bool {
+        S::SUBTREE_LENS
+    }
+}
+
+/// Returns a pointer to the room for the subtree length in front of a node.
+///
+/// # Safety
+/// The node must be allocated by a `NodeAlloc` that keeps subtree lengths,
+/// with the given layout.
+pub unsafe fn subtree_len_ptr(node: NonNull<u8>, layout: Layout) -> *mut usize {
+    // SAFETY: the node was allocated with this layout, so it was accepted.
+    let offset = unsafe { with_len(layout).unwrap_unchecked() }.1;
+    unsafe { node.as_ptr().sub(offset).cast::<usize>() }
+}
@@ -0,0 +1,889 @@
+use crate::vec::Vec;
+use core::borrow::Borrow;
+use core::fmt::{self, Debug};
+use core::intrinsics::abort;
+use core::iter::FusedIterator;
+use core::mem;
+use core::ptr::{self, NonNull};
+use core::sync::atomic::Ordering::{Acquire, Relaxed, Release};
+use core::sync::atomic::{self, AtomicUsize};
+
+use crate::alloc::{AllocError, Allocator, Global, Layout};
+
+use super::borrow::DormantMutRef;
+use super::dedup_sorted_iter::DedupSortedIter;
+use super::map::MIN_LEN;
+use super::node::{marker, ForceResult::*, Handle, NodeAlloc, NodeRef, Root, CAPACITY};
+use super::search::SearchResult::*;
+
+#[cfg(test)]
+mod tests;
+
+// A tree in a `PersistentBTreeMap` is a tree like the one in a `BTreeMap`,
+// except that any node may be linked to by several trees: as the root of
+// some and as a child in others, perhaps even at different positions.
+// Every node is allocated by `SharedNodes` and carries the number of such
+// links, and its keys, values and children live as long as that number.
+// As with `Arc`, the trees sharing a node may live in different threads,
+// and whichever gives up the last link drops the node.
+//
+// A node linked to more than once must not change at all, not even its
+// parent link, which is meaningless anyway. Therefore, before modifying a
+// tree, we walk down the affected path, replacing each shared node by a copy
+// of its own and making the parent link of each node point to where we came
+// from. That way, the machinery in `node` works as usual, because it only
+// ever ascends from the nodes on that path. Splitting, merging and stealing
+// also write the parent links of the children they move around, so before
+// any of that can happen, we replace those children by copies as well.
+
+/// Allocates nodes with a reference count in front of them.
+#[derive(Clone, Copy)]
+struct SharedNodes;
+
+impl SharedNodes {
+    /// Returns the layout of an allocation holding both the count and
+    /// something of the given layout, and the offset of the latter.
+    fn counted(layout: Layout) -> Result<(Layout, usize), AllocError> {
+        Layout::new::<AtomicUsize>().extend(layout).map_err(|_| AllocError)
+    }
+}
+
+unsafe impl Allocator for SharedNodes {
+    fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        let (counted, offset) = Self::counted(layout)?;
+        let ptr = Global.allocate(counted)?.as_mut_ptr();
+        unsafe {
+            ptr.cast::<AtomicUsize>().write(AtomicUsize::new(1));
+            // The node pointer is derived from the pointer to the whole
+            // allocation, and `node` keeps it that way, so that the count
+            // can be found again.
+            let node = NonNull::new_unchecked(ptr.add(offset));
+            Ok(NonNull::slice_from_raw_parts(node, layout.size()))
+        }
+    }
+
+    unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+        // SAFETY: `allocate` accepted the same layout.
+        let (counted, offset) = unsafe { Self::counted(layout).unwrap_unchecked() };
+        unsafe { Global.deallocate(NonNull::new_unchecked(ptr.as_ptr().sub(offset)), counted) }
+    }
+}
+
+unsafe impl NodeAlloc for SharedNodes {
+    fn keeps_subtree_lens(&self) -> bool {
+        false
+    }
+}
+
+/// Returns the number of links to a node.
+///
+/// # Safety
+/// The node must be allocated by `SharedNodes`, and stay allocated
+/// for as long as the count is used.
+unsafe fn ref_count<'a, BorrowType, K, V>(
+    node: &NodeRef<BorrowType, K, V, marker::LeafOrInternal>,
+) -> &'a AtomicUsize {
+    let (ptr, layout) = node.allocation();
+    let offset = unsafe { SharedNodes::counted(layout).unwrap_unchecked() }.1;
+    unsafe { &*ptr.as_ptr().sub(offset).cast::<AtomicUsize>() }
+}
+
+/// A soft limit on the number of links to a node, as for `Arc`.
+const MAX_REFCOUNT: usize = isize::MAX as usize;
+
+/// Adds a link to a node that the caller already links to.
+fn link<BorrowType, K, V>(node: &NodeRef<BorrowType, K, V, marker::LeafOrInternal>) {
+    // As in `Arc::clone`, the existing link keeps the node alive, so there is
+    // nothing to synchronize with, but the count must not overflow.
+    if unsafe { ref_count(node) }.fetch_add(1, Relaxed) > MAX_REFCOUNT {
+        abort();
+    }
+}
+
+/// Returns `true` if the caller holds the only link to a node.
+fn is_unique<BorrowType, K, V>(node: &NodeRef<BorrowType, K, V, marker::LeafOrInternal>) -> bool {
+    // As in `Arc::get_mut`, this synchronizes with other trees giving up their
+    // links, so that everything they did with the node happens before we
+    // modify it.
+    unsafe { ref_count(node) }.load(Acquire) == 1
+}
+
+/// Gives up one link to the subtree headed by `node`, dropping and
+/// deallocating whatever is no longer linked to.
+///
+/// # Safety
+/// The link given up must be one that is no longer used.
+unsafe fn release<K, V>(node: NodeRef<marker::Dying, K, V, marker::LeafOrInternal>) {
+    // As in `Arc`'s `drop`, whoever gives up the last link waits until
+    // everything that others did with the node has happened.
+    if unsafe { ref_count(&node) }.fetch_sub(1, Release) != 1 {
+        return;
+    }
+    atomic::fence(Acquire);
+    for i in 0..node.len() {
+        unsafe { Handle::new_kv(ptr::read(&node), i).drop_key_val() };
+    }
+    if let Internal(internal) = unsafe { ptr::read(&node) }.force() {
+        for i in 0..=internal.len() {
+            unsafe { release(Handle::new_edge(ptr::read(&internal), i).descend()) };
+        }
+    }
+    unsafe { node.deallocate(SharedNodes) };
+}
+
+/// Replaces `root` by a copy of its own, if it is shared.
+fn make_root_unique<K: Clone, V: Clone>(root: &mut Root<K, V>) {
+    if !is_unique(root) {
+        let copy = copy_node(root.reborrow());
+        let old = mem::replace(root, copy);
+        unsafe { release(old.into_dying()) };
+    }
+}
+
+/// Makes sure the child behind `edge` is linked to by the parent only,
+/// and that the child's parent link points back, and returns the child.
+fn descend_unique<'a, K: Clone + 'a, V: Clone + 'a>(
+    mut edge: Handle<NodeRef<marker::Mut<'a>, K, V, marker::Internal>, marker::Edge>,
+) -> NodeRef<marker::Mut<'a>, K, V, marker::LeafOrInternal> {
+    if is_unique(&edge.reborrow().descend()) {
+        unsafe { edge.reborrow_mut() }.correct_parent_link();
+    } else {
+        let copy = copy_node(edge.reborrow().descend());
+        let old = unsafe { edge.reborrow_mut() }.replace_child(copy);
+        unsafe { release(old.into_dying()) };
+    }
+    edge.descend()
+}
+
+/// Makes sure all children of `node` are linked to by `node` only,
+/// and returns `node`.
+fn make_children_unique<'a, K: Clone + 'a, V: Clone + 'a>(
+    node: NodeRef<marker::Mut<'a>, K, V, marker::Internal>,
+) -> NodeRef<marker::Mut<'a>, K, V, marker::Internal> {
+    let mut edge = node.first_edge();
+    loop {
+        descend_unique(unsafe { edge.reborrow_mut() });
+        edge = match edge.right_kv() {
+            Ok(kv) => kv.right_edge(),
+            Err(last_edge) => return last_edge.into_node(),
+        }
+    }
+}
+
+/// Before inserting into the leaf `node`, whose ancestors are unique, makes
+/// sure that the children that splitting might move around are unique too.
+fn prepare_splits<'a, K: Clone + 'a, V: Clone + 'a>(
+    mut node: NodeRef<marker::Mut<'a>, K, V, marker::LeafOrInternal>,
+) {
+    // A node splits if it is full and, unless it is the leaf, gains a child
+    // because a child split. In turn, its parent gains a child.
+    while node.len() == CAPACITY {
+        let Ok(parent) = node.ascend() else { return };
+        node = make_children_unique(parent.into_node()).forget_type();
+    }
+}
+
+/// Before removing from the leaf `node`, whose ancestors are unique, makes
+/// sure that the children that merging or stealing might move around are
+/// unique too.
+fn prepare_merges<'a, K: Clone + 'a, V: Clone + 'a>(
+    mut node: NodeRef<marker::Mut<'a>, K, V, marker::LeafOrInternal>,
+) {
+    // A node becomes underfull if it is as short as allowed and, unless it is
+    // the leaf, loses a child because two children merged. It then steals
+    // from or merges with a sibling, moving around the children of either,
+    // and if they merge, their parent loses a child.
+    while node.len() <= MIN_LEN {
+        let Ok(parent) = node.ascend() else { return };
+        let idx = parent.idx();
+        let parent = make_children_unique(parent.into_node());
+        let mut edge = unsafe { Handle::new_edge(parent, idx) };
+        // Same choice as `choose_parent_kv`.
+        let sibling_idx = if idx > 0 { idx - 1 } else { idx + 1 };
+        for i in [idx, sibling_idx] {
+            let parent = unsafe { edge.reborrow_mut() }.into_node();
+            if let Internal(child) = unsafe { Handle::new_edge(parent, i) }.descend().force() {
+                make_children_unique(child);
+            }
+        }
+        node = edge.into_node().forget_type();
+    }
+}
+
+/// Copies a node linked to from elsewhere, sharing its children with the original.
+fn copy_node<'a, K: Clone + 'a, V: Clone + 'a>(
+    node: NodeRef<marker::Immut<'a>, K, V, marker::LeafOrInternal>,
+) -> Root<K, V> {
+    let copy = node.clone_node(SharedNodes);
+    if let Internal(internal) = node.force() {
+        for i in 0..=internal.len() {
+            link(&unsafe { Handle::new_edge(internal, i) }.descend());
+        }
+    }
+    copy
+}
+
+/// An ordered map based on a B-Tree, whose clones share their nodes.
+///
+/// A `PersistentBTreeMap` offers a subset of the operations of a [`BTreeMap`],
+/// with the same time complexity, except that:
+/// - Cloning the map takes constant time and memory, because the clone
+///   shares all of its nodes with the original.
+/// - Modifying the map first copies the nodes it is about to touch that are
+///   still shared, i.e. the path from the root to the node affected, and,
+///   when nodes on that path split or merge, the nodes those move around.
+///   This requires `K: Clone` and `V: Clone`. The nodes not touched remain
+///   shared.
+///
+/// This makes it cheap to take a snapshot of a large map that is modified
+/// as it is being read, by cloning it, and to keep many versions of a map
+/// around at the same time.
+///
+/// Nodes are reference counted atomically, as by [`Arc`], so a snapshot can
+/// be sent to, and read by, another thread while the map keeps changing.
+///
+/// [`BTreeMap`]: crate::collections::BTreeMap
+/// [`Arc`]: crate::sync::Arc
+///
+/// # Examples
+///
+/// ```
+/// #![feature(persistent_btree_map)]
+/// use std::collections::persistent_btree_map::PersistentBTreeMap;
+///
+/// let mut config = PersistentBTreeMap::new();
+/// config.insert("threads", 4);
+/// config.insert("verbose", 0);
+///
+/// let snapshot = config.clone();
+/// config.insert("threads", 8);
+/// config.remove("verbose");
+///
+/// assert_eq!(snapshot.get("threads"), Some(&4));
+/// assert_eq!(snapshot.get("verbose"), Some(&0));
+/// assert_eq!(config.get("threads"), Some(&8));
+/// assert_eq!(config.get("verbose"), None);
+/// ```
+#[unstable(feature = "persistent_btree_map", issue = "none")]
+pub struct PersistentBTreeMap<K, V> {
+    root: Option<Root<K, V>>,
+    length: usize,
+}
+
+// As with `Arc`, sending a map to another thread shares its keys and values
+// with the maps left behind, and any of them may end up dropping those.
+#[unstable(feature = "persistent_btree_map", issue = "none")]
+unsafe impl<K: Send + Sync, V: Send + Sync> Send for PersistentBTreeMap<K, V> {}
+
+#[unstable(feature = "persistent_btree_map", issue = "none")]
+unsafe impl<K: Send + Sync, V: Send + Sync> Sync for PersistentBTreeMap<K, V> {}
+
+#[unstable(feature = "persistent_btree_map", issue = "none")]
+impl<K, V> Drop for PersistentBTreeMap<K, V> {
+    fn drop(&mut self) {
+        if let Some(root) = self.root.take() {
+            unsafe { release(root.into_dying()) };
+        }
+    }
+}
+
+#[unstable(feature = "persistent_btree_map", issue = "none")]
+impl<K, V> Clone for PersistentBTreeMap<K, V> {
+    /// Makes a clone of the map that shares all nodes with the original,
+    /// in constant time.
+    fn clone(&self) -> Self {
+        let root = self.root.as_ref().map(|root| {
+            link(root);
+            // SAFETY: we just accounted for the additional link.
+            unsafe { ptr::read(root) }
+        });
+        PersistentBTreeMap { root, length: self.length }
+    }
+}
+
+impl<K, V> PersistentBTreeMap<K, V> {
+    /// Makes a new, empty `PersistentBTreeMap`.
+    ///
+    /// Does not allocate anything on its own.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(persistent_btree_map)]
+    /// use std::collections::persistent_btree_map::PersistentBTreeMap;
+    ///
+    /// let mut map = PersistentBTreeMap::new();
+    /// map.insert(1, "a");
+    /// ```
+    #[unstable(feature = "persistent_btree_map", issue = "none")]
+    #[must_use]
+    pub const fn new() -> PersistentBTreeMap<K, V> {
+        PersistentBTreeMap { root: None, length: 0 }
+    }
+
+    /// Returns the number of elements in the map.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(persistent_btree_map)]
+    /// use std::collections::persistent_btree_map::PersistentBTreeMap;
+    ///
+    /// let mut a = PersistentBTreeMap::new();
+    /// assert_eq!(a.len(), 0);
+    /// a.insert(1, "a");
+    /// assert_eq!(a.len(), 1);
+    /// ```
+    #[unstable(feature = "persistent_btree_map", issue = "none")]
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.length
+    }
+
+    /// Returns `true` if the map contains no elements.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(persistent_btree_map)]
+    /// use std::collections::persistent_btree_map::PersistentBTreeMap;
+    ///
+    /// let mut a = PersistentBTreeMap::new();
+    /// assert!(a.is_empty());
+    /// a.insert(1, "a");
+    /// assert!(!a.is_empty());
+    /// ```
+    #[unstable(feature = "persistent_btree_map", issue = "none")]
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns `true` if both maps share their root node, which means that
+    /// neither was modified since one was cloned from the other, and that
+    /// they hold the very same keys and values.
+    ///
+    /// Maps holding equal elements need not share their root node. Empty
+    /// maps may or may not share their root node.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(persistent_btree_map)]
+    /// use std::collections::persistent_btree_map::PersistentBTreeMap;
+    ///
+    /// let mut map = PersistentBTreeMap::new();
+    /// map.insert(1, "a");
+    /// let snapshot = map.clone();
+    /// assert!(PersistentBTreeMap::ptr_eq(&map, &snapshot));
+    ///
+    /// map.insert(1, "a");
+    /// assert!(!PersistentBTreeMap::ptr_eq(&map, &snapshot));
+    /// assert!(map == snapshot);
+    /// ```
+    #[unstable(feature = "persistent_btree_map", issue = "none")]
+    #[must_use]
+    pub fn ptr_eq(this: &Self, other: &Self) -> bool {
+        match (&this.root, &other.root) {
+            (Some(a), Some(b)) => a.allocation().0 == b.allocation().0,
+            (None, None) => true,
+            _ => false,
+        }
+    }
+
+    /// Clears the map, removing all elements.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(persistent_btree_map)]
+    /// use std::collections::persistent_btree_map::PersistentBTreeMap;
+    ///
+    /// let mut a = PersistentBTreeMap::new();
+    /// a.insert(1, "a");
+    /// a.clear();
+    /// assert!(a.is_empty());
+    /// ```
+    #[unstable(feature = "persistent_btree_map", issue = "none")]
+    pub fn clear(&mut self) {
+        drop(mem::replace(self, PersistentBTreeMap::new()));
+    }
+
+    /// Returns a reference to the value corresponding to the key.
+    ///
+    /// The key may be any borrowed form of the map's key type, but the ordering
+    /// on the borrowed form *must* match the ordering on the key type.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(persistent_btree_map)]
+    /// use std::collections::persistent_btree_map::PersistentBTreeMap;
+    ///
+    /// let mut map = PersistentBTreeMap::new();
+    /// map.insert(1, "a");
+    /// assert_eq!(map.get(&1), Some(&"a"));
+    /// assert_eq!(map.get(&2), None);
+    /// ```
+    #[unstable(feature = "persistent_btree_map", issue = "none")]
+    pub fn get<Q: ?Sized>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q> + Ord,
+        Q: Ord,
+    {
+        let root_node = self.root.as_ref()?.reborrow();
+        match root_node.search_tree(key) {
+            Found(handle) => Some(handle.into_kv().1),
+            GoDown(_) => None,
+        }
+    }
+
+    /// Returns `true` if the map contains a value for the specified key.
+    ///
+    /// The key may be any borrowed form of the map's key type, but the ordering
+    /// on the borrowed form *must* match the ordering on the key type.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(persistent_btree_map)]
+    /// use std::collections::persistent_btree_map::PersistentBTreeMap;
+    ///
+    /// let mut map = PersistentBTreeMap::new();
+    /// map.insert(1, "a");
+    /// assert_eq!(map.contains_key(&1), true);
+    /// assert_eq!(map.contains_key(&2), false);
+    /// ```
+    #[unstable(feature = "persistent_btree_map", issue = "none")]
+    pub fn contains_key<Q: ?Sized>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q> + Ord,
+        Q: Ord,
+    {
+        self.get(key).is_some()
+    }
+
+    /// Returns the first key-value pair in the map.
+    /// The key in this pair is the minimum key in the map.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(persistent_btree_map)]
+    /// use std::collections::persistent_btree_map::PersistentBTreeMap;
+    ///
+    /// let mut map = PersistentBTreeMap::new();
+    /// assert_eq!(map.first_key_value(), None);
+    /// map.insert(1, "b");
+    /// map.insert(2, "a");
+    /// assert_eq!(map.first_key_value(), Some((&1, &"b")));
+    /// ```
+    #[unstable(feature = "persistent_btree_map", issue = "none")]
+    pub fn first_key_value(&self) -> Option<(&K, &V)> {
+        let root_node = self.root.as_ref()?.reborrow();
+        root_node.first_leaf_edge().right_kv().ok().map(Handle::into_kv)
+    }
+
+    /// Returns the last key-value pair in the map.
+    /// The key in this pair is the maximum key in the map.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(persistent_btree_map)]
+    /// use std::collections::persistent_btree_map::PersistentBTreeMap;
+    ///
+    /// let mut map = PersistentBTreeMap::new();
+    /// map.insert(1, "b");
+    /// map.insert(2, "a");
+    /// assert_eq!(map.last_key_value(), Some((&2, &"a")));
+    /// ```
+    #[unstable(feature = "persistent_btree_map", issue = "none")]
+    pub fn last_key_value(&self) -> Option<(&K, &V)> {
+        let root_node = self.root.as_ref()?.reborrow();
+        root_node.last_leaf_edge().left_kv().ok().map(Handle::into_kv)
+    }
+
+    /// Gets an iterator over the entries of the map, sorted by key.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(persistent_btree_map)]
+    /// use std::collections::persistent_btree_map::PersistentBTreeMap;
+    ///
+    /// let mut map = PersistentBTreeMap::new();
+    /// map.insert(3, "c");
+    /// map.insert(2, "b");
+    /// map.insert(1, "a");
+    ///
+    /// for (key, value) in map.iter() {
+    ///     println!("{key}: {value}");
+    /// }
+    ///
+    /// let (first_key, first_value) = map.iter().next().unwrap();
+    /// assert_eq!((*first_key, *first_value), (1, "a"));
+    /// ```
+    #[unstable(feature = "persistent_btree_map", issue = "none")]
+    pub fn iter(&self) -> Iter<'_, K, V> {
+        let mut iter = Iter { stack: Vec::new(), front: None, length: self.length };
+        if let Some(root) = &self.root {
+            iter.descend_from(root.reborrow().first_edge());
+        }
+        iter
+    }
+}
+
+impl<K: Clone, V: Clone> PersistentBTreeMap<K, V> {
+    /// Returns a mutable reference to the value corresponding to the key,
+    /// after copying the nodes on the way to it that are shared.
+    ///
+    /// The key may be any borrowed form of the map's key type, but the ordering
+    /// on the borrowed form *must* match the ordering on the key type.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(persistent_btree_map)]
+    /// use std::collections::persistent_btree_map::PersistentBTreeMap;
+    ///
+    /// let mut map = PersistentBTreeMap::new();
+    /// map.insert(1, "a");
+    /// let snapshot = map.clone();
+    /// if let Some(x) = map.get_mut(&1) {
+    ///     *x = "b";
+    /// }
+    /// assert_eq!(map.get(&1), Some(&"b"));
+    /// assert_eq!(snapshot.get(&1), Some(&"a"));
+    /// ```
+    #[unstable(feature = "persistent_btree_map", issue = "none")]
+    pub fn get_mut<Q: ?Sized>(&mut self, key: &Q) -> Option<&mut V>
+    where
+        K: Borrow<Q> + Ord,
+        Q: Ord,
+    {
+        // Don't copy anything unless the key is present.
+        if let GoDown(_) = self.root.as_ref()?.reborrow().search_tree(key) {
+            return None;
+        }
+        let root = self.root.as_mut()?;
+        make_root_unique(root);
+        let mut node = root.borrow_mut();
+        loop {
+            node = match node.search_node(key) {
+                Found(handle) => return Some(handle.into_val_mut()),
+                GoDown(handle) => match handle.force() {
+                    Leaf(_) => unreachable!("key disappeared"),
+                    Internal(internal) => descend_unique(internal),
+                },
+            }
+        }
+    }
+
+    /// Inserts a key-value pair into the map, after copying the nodes on the
+    /// way to where it belongs that are shared.
+    ///
+    /// If the map did not have this key present, `None` is returned.
+    ///
+    /// If the map did have this key present, the value is updated, and the old
+    /// value is returned. The key is not updated, though.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(persistent_btree_map)]
+    /// use std::collections::persistent_btree_map::PersistentBTreeMap;
+    ///
+    /// let mut map = PersistentBTreeMap::new();
+    /// assert_eq!(map.insert(37, "a"), None);
+    /// assert_eq!(map.is_empty(), false);
+    ///
+    /// map.insert(37, "b");
+    /// assert_eq!(map.insert(37, "c"), Some("b"));
+    /// assert_eq!(map.get(&37), Some(&"c"));
+    /// ```
+    #[unstable(feature = "persistent_btree_map", issue = "none")]
+    pub fn insert(&mut self, key: K, value: V) -> Option<V>
+    where
+        K: Ord,
+    {
+        let Some(root) = self.root.as_mut() else {
+            let mut root = NodeRef::new_leaf(SharedNodes);
+            root.borrow_mut().push(key, value);
+            self.root = Some(root.forget_type());
+            self.length = 1;
+            return None;
+        };
+        make_root_unique(root);
+        let (root, mut dormant_root) = DormantMutRef::new(root);
+        let mut node = root.borrow_mut();
+        loop {
+            node = match node.search_node(&key) {
+                Found(handle) => return Some(mem::replace(handle.into_val_mut(), value)),
+                GoDown(handle) => match handle.force() {
+                    Leaf(mut leaf) => {
+                        prepare_splits(unsafe { leaf.reborrow_mut() }.into_node().forget_type());
+                        leaf.insert_recursing(key, value, SharedNodes, |ins| {
+                            drop(ins.left);
+                            // SAFETY: Pushing a new root node doesn't invalidate
+                            // handles to existing nodes.
+                            let root = unsafe { dormant_root.reborrow() };
+                            root.push_internal_level(SharedNodes).push(
+                                ins.kv.0,
+                                ins.kv.1,
+                                ins.right,
+                                &SharedNodes,
+                            )
+                        });
+                        self.length += 1;
+                        return None;
+                    }
+                    Internal(internal) => descend_unique(internal),
+                },
+            }
+        }
+    }
+
+    /// Removes a key from the map, returning the value at the key if the key
+    /// was previously in the map, after copying the nodes on the way to it
+    /// that are shared.
+    ///
+    /// The key may be any borrowed form of the map's key type, but the ordering
+    /// on the borrowed form *must* match the ordering on the key type.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(persistent_btree_map)]
+    /// use std::collections::persistent_btree_map::PersistentBTreeMap;
+    ///
+    /// let mut map = PersistentBTreeMap::new();
+    /// map.insert(1, "a");
+    /// assert_eq!(map.remove(&1), Some("a"));
+    /// assert_eq!(map.remove(&1), None);
+    /// ```
+    #[unstable(feature = "persistent_btree_map", issue = "none")]
+    pub fn remove<Q: ?Sized>(&mut self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q> + Ord,
+        Q: Ord,
+    {
+        self.remove_entry(key).map(|(_, v)| v)
+    }
+
+    /// Removes a key from the map, returning the stored key and value if the
+    /// key was previously in the map, after copying the nodes on the way to
+    /// it that are shared.
+    ///
+    /// The key may be any borrowed form of the map's key type, but the ordering
+    /// on the borrowed form *must* match the ordering on the key type.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(persistent_btree_map)]
+    /// use std::collections::persistent_btree_map::PersistentBTreeMap;
+    ///
+    /// let mut map = PersistentBTreeMap::new();
+    /// map.insert(1, "a");
+    /// assert_eq!(map.remove_entry(&1), Some((1, "a")));
+    /// assert_eq!(map.remove_entry(&1), None);
+    /// ```
+    #[unstable(feature = "persistent_btree_map", issue = "none")]
+    pub fn remove_entry<Q: ?Sized>(&mut self, key: &Q) -> Option<(K, V)>
+    where
+        K: Borrow<Q> + Ord,
+        Q: Ord,
+    {
+        // Don't copy anything unless the key is present.
+        if let GoDown(_) = self.root.as_ref()?.reborrow().search_tree(key) {
+            return None;
+        }
+        let root = self.root.as_mut()?;
+        make_root_unique(root);
+        let mut node = root.borrow_mut();
+        let mut handle = loop {
+            node = match node.search_node(key) {
+                Found(handle) => break handle,
+                GoDown(handle) => match handle.force() {
+                    Leaf(_) => unreachable!("key disappeared"),
+                    Internal(internal) => descend_unique(internal),
+                },
+            }
+        };
+        // Removing from an internal node takes the key-value pair
+        // preceding it out of a leaf, so we get that path ready too.
+        let leaf = match unsafe { handle.reborrow_mut() }.force() {
+            Leaf(leaf_kv) => leaf_kv.into_node(),
+            Internal(internal_kv) => {
+                let mut edge = internal_kv.left_edge();
+                loop {
+                    edge = match descend_unique(edge).force() {
+                        Leaf(leaf) => break leaf,
+                        Internal(node) => node.last_edge(),
+                    }
+                }
+            }
+        };
+        prepare_merges(leaf.forget_type());
+        let mut emptied_internal_root = false;
+        let (old_kv, _) = handle.remove_kv_tracking(|| emptied_internal_root = true, SharedNodes);
+        self.length -= 1;
+        if emptied_internal_root {
+            root.pop_internal_level(SharedNodes);
+        }
+        Some(old_kv)
+    }
+}
+
+#[unstable(feature = "persistent_btree_map", issue = "none")]
+impl<K, V> Default for PersistentBTreeMap<K, V> {
+    /// Creates an empty `PersistentBTreeMap`.
+    fn default() -> PersistentBTreeMap<K, V> {
+        PersistentBTreeMap::new()
+    }
+}
+
+#[unstable(feature = "persistent_btree_map", issue = "none")]
+impl<K: Debug, V: Debug> Debug for PersistentBTreeMap<K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+#[unstable(feature = "persistent_btree_map", issue = "none")]
+impl<K: PartialEq, V: PartialEq> PartialEq for PersistentBTreeMap<K, V> {
+    fn eq(&self, other: &PersistentBTreeMap<K, V>) -> bool {
+        self.len() == other.len()
+            && (PersistentBTreeMap::ptr_eq(self, other)
+                || self.iter().zip(other).all(|(a, b)| a == b))
+    }
+}
+
+#[unstable(feature = "persistent_btree_map", issue = "none")]
+impl<K: Eq, V: Eq> Eq for PersistentBTreeMap<K, V> {}
+
+#[unstable(feature = "persistent_btree_map", issue = "none")]
+impl<K: Ord, V> FromIterator<(K, V)> for PersistentBTreeMap<K, V> {
+    fn from_iter<T: IntoIterator<Item = (K, V)>>(iter: T) -> PersistentBTreeMap<K, V> {
+        let mut inputs: Vec<_> = iter.into_iter().collect();
+
+        if inputs.is_empty() {
+            return PersistentBTreeMap::new();
+        }
+
+        // use stable sort to preserve the insertion order.
+        inputs.sort_by(|a, b| a.0.cmp(&b.0));
+        let mut root = Root::new(SharedNodes);
+        let mut length = 0;
+        root.bulk_push(DedupSortedIter::new(inputs.into_iter()), &mut length, SharedNodes);
+        PersistentBTreeMap { root: Some(root), length }
+    }
+}
+
+#[unstable(feature = "persistent_btree_map", issue = "none")]
+impl<K: Ord + Clone, V: Clone> Extend<(K, V)> for PersistentBTreeMap<K, V> {
+    #[inline]
+    fn extend<T: IntoIterator<Item = (K, V)>>(&mut self, iter: T) {
+        iter.into_iter().for_each(move |(k, v)| {
+            self.insert(k, v);
+        });
+    }
+}
+
+#[unstable(feature = "persistent_btree_map", issue = "none")]
+impl<'a, K, V> IntoIterator for &'a PersistentBTreeMap<K, V> {
+    type Item = (&'a K, &'a V);
+    type IntoIter = Iter<'a, K, V>;
+
+    fn into_iter(self) -> Iter<'a, K, V> {
+        self.iter()
+    }
+}
+
+/// An iterator over the entries of a `PersistentBTreeMap`.
+///
+/// This `struct` is created by the [`iter`] method on [`PersistentBTreeMap`].
+/// See its documentation for more.
+///
+/// [`iter`]: PersistentBTreeMap::iter
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+#[unstable(feature = "persistent_btree_map", issue = "none")]
+pub struct Iter<'a, K: 'a, V: 'a> {
+    // Parent links can't be trusted, so we remember the way down instead:
+    // the edges of internal nodes leading to the leaf that `front` is in.
+    stack: Vec<Handle<NodeRef<marker::Immut<'a>, K, V, marker::Internal>, marker::Edge>>,
+    front: Option<Handle<NodeRef<marker::Immut<'a>, K, V, marker::Leaf>, marker::Edge>>,
+    length: usize,
+}
+
+impl<'a, K, V> Iter<'a, K, V> {
+    /// Moves `front` to the first leaf edge in the subtree below `edge`.
+    fn descend_from(
+        &mut self,
+        mut edge: Handle<NodeRef<marker::Immut<'a>, K, V, marker::LeafOrInternal>, marker::Edge>,
+    ) {
+        loop {
+            edge = match edge.force() {
+                Leaf(leaf_edge) => {
+                    self.front = Some(leaf_edge);
+                    return;
+                }
+                Internal(internal_edge) => {
+                    self.stack.push(internal_edge);
+                    internal_edge.descend().first_edge()
+                }
+            }
+        }
+    }
+}
+
+#[unstable(feature = "persistent_btree_map", issue = "none")]
+impl<K: Debug, V: Debug> Debug for Iter<'_, K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.clone()).finish()
+    }
+}
+
+#[unstable(feature = "persistent_btree_map", issue = "none")]
+impl<K, V> Clone for Iter<'_, K, V> {
+    fn clone(&self) -> Self {
+        Iter { stack: self.stack.clone(), front: self.front, length: self.length }
+    }
+}
+
+#[unstable(feature = "persistent_btree_map", issue = "none")]
+impl<'a, K: 'a, V: 'a> Iterator for Iter<'a, K, V> {
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<(&'a K, &'a V)> {
+        if self.length == 0 {
+            return None;
+        }
+        self.length -= 1;
+        let mut edge = self.front.take()?.forget_node_type();
+        loop {
+            edge = match edge.right_kv() {
+                Ok(kv) => {
+                    self.descend_from(kv.right_edge());
+                    return Some(kv.into_kv());
+                }
+                Err(_) => self.stack.pop()?.forget_node_type(),
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.length, Some(self.length))
+    }
+}
+
+#[unstable(feature = "persistent_btree_map", issue = "none")]
+impl<K, V> ExactSizeIterator for Iter<'_, K, V> {
+    fn len(&self) -> usize {
+        self.length
+    }
+}
+
+#[unstable(feature = "persistent_btree_map", issue = "none")]
+impl<K, V> FusedIterator for Iter<'_, K, V> {}
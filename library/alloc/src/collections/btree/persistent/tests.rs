@@ -0,0 +1,342 @@
+use super::super::map::MIN_LEN;
+use super::*;
+use crate::collections::BTreeMap;
+use crate::fmt::Debug;
+use crate::testing::crash_test::{CrashTestDummy, Panic};
+use crate::testing::rng::DeterministicRng;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::thread;
+
+impl<K, V> PersistentBTreeMap<K, V> {
+    // Panics if the map (or the code navigating it) is corrupted, or if the
+    // keys are not in strictly ascending order.
+    fn check(&self)
+    where
+        K: Debug + Ord,
+    {
+        if let Some(root) = &self.root {
+            let root_node = root.reborrow();
+            assert!(unsafe { ref_count(&root_node) }.load(Relaxed) >= 1);
+            if let Internal(node) = root_node.force() {
+                assert!(node.len() >= 1);
+                for idx in 0..=node.len() {
+                    unsafe { Handle::new_edge(node, idx) }.descend().assert_shared_min_len();
+                }
+            }
+        } else {
+            assert_eq!(self.length, 0);
+        }
+
+        assert_eq!(self.length, self.iter().count());
+        let mut keys = self.iter().map(|(k, _)| k);
+        if let Some(mut previous) = keys.next() {
+            for next in keys {
+                assert!(previous < next, "{:?} >= {:?}", previous, next);
+                previous = next;
+            }
+        }
+    }
+
+    // Returns the number of nodes that other maps link to as well.
+    fn shared_nodes(&self) -> usize {
+        fn count<'a, K: 'a, V: 'a>(
+            node: NodeRef<marker::Immut<'a>, K, V, marker::LeafOrInternal>,
+        ) -> usize {
+            if unsafe { ref_count(&node) }.load(Relaxed) > 1 {
+                return 1;
+            }
+            match node.force() {
+                Leaf(_) => 0,
+                Internal(node) => (0..=node.len())
+                    .map(|idx| count(unsafe { Handle::new_edge(node, idx) }.descend()))
+                    .sum(),
+            }
+        }
+        self.root.as_ref().map_or(0, |root| count(root.reborrow()))
+    }
+}
+
+impl<'a, K: 'a, V: 'a> NodeRef<marker::Immut<'a>, K, V, marker::LeafOrInternal> {
+    fn assert_shared_min_len(self) {
+        assert!(self.len() >= MIN_LEN, "node len {} < {}", self.len(), MIN_LEN);
+        assert!(unsafe { ref_count(&self) }.load(Relaxed) >= 1);
+        if let Internal(node) = self.force() {
+            for idx in 0..=node.len() {
+                unsafe { Handle::new_edge(node, idx) }.descend().assert_shared_min_len();
+            }
+        }
+    }
+}
+
+#[test]
+fn test_basic() {
+    let mut map = PersistentBTreeMap::new();
+    map.check();
+    assert_eq!(map.get(&1), None);
+    assert_eq!(map.first_key_value(), None);
+    assert_eq!(map.remove(&1), None);
+    for i in 0..100 {
+        assert_eq!(map.insert(i, i * 10), None);
+    }
+    map.check();
+    assert_eq!(map.len(), 100);
+    assert_eq!(map.insert(5, 0), Some(50));
+    assert_eq!(map.get(&5), Some(&0));
+    assert_eq!(map.first_key_value(), Some((&0, &0)));
+    assert_eq!(map.last_key_value(), Some((&99, &990)));
+    assert_eq!(map.remove_entry(&7), Some((7, 70)));
+    assert_eq!(map.remove(&7), None);
+    *map.get_mut(&8).unwrap() += 1;
+    assert_eq!(map.get(&8), Some(&81));
+    assert_eq!(map.get_mut(&7), None);
+    map.check();
+    assert_eq!(
+        format!("{:?}", map.iter().take(3).collect::<Vec<_>>()),
+        "[(0, 0), (1, 10), (2, 20)]"
+    );
+    map.clear();
+    map.check();
+    assert!(map.is_empty());
+}
+
+#[test]
+fn test_clone_shares_everything() {
+    let map: PersistentBTreeMap<_, _> = (0..1000).map(|i| (i, i)).collect();
+    map.check();
+    assert_eq!(map.shared_nodes(), 0);
+    let snapshot = map.clone();
+    assert!(PersistentBTreeMap::ptr_eq(&map, &snapshot));
+    assert_eq!(map.shared_nodes(), 1);
+    assert_eq!(map, snapshot);
+}
+
+#[test]
+fn test_insert_copies_path() {
+    let mut map = PersistentBTreeMap::new();
+    for i in 0..1000 {
+        map.insert(i, i);
+    }
+    let height = map.root.as_ref().unwrap().height();
+    assert!(height >= 2);
+    let snapshot = map.clone();
+
+    map.insert(500, 0);
+    map.check();
+    snapshot.check();
+    assert_eq!(map.get(&500), Some(&0));
+    assert_eq!(snapshot.get(&500), Some(&500));
+    assert!(!PersistentBTreeMap::ptr_eq(&map, &snapshot));
+    // Every node off the path is still shared: at each level of the path,
+    // the children but one.
+    let mut expected = 0;
+    let mut node = map.root.as_ref().unwrap().reborrow();
+    while let Internal(internal) = node.force() {
+        node = match node.search_node(&500) {
+            Found(_) => {
+                expected += internal.len() + 1;
+                break;
+            }
+            GoDown(edge) => {
+                expected += internal.len();
+                unsafe { Handle::new_edge(internal, edge.idx()) }.descend()
+            }
+        }
+    }
+    assert_eq!(map.shared_nodes(), expected);
+    assert_eq!(snapshot.shared_nodes(), expected);
+
+    // The path is ours now, so changing it again copies nothing.
+    map.insert(500, 1);
+    assert_eq!(map.shared_nodes(), expected);
+}
+
+#[test]
+fn test_snapshots_against_btreemap() {
+    let mut rng = DeterministicRng::new();
+    let mut map = PersistentBTreeMap::new();
+    let mut model = BTreeMap::new();
+    let mut snapshots = Vec::new();
+    for round in 0..2000 {
+        let key = rng.next() % 500;
+        if rng.next() % 3 == 0 {
+            assert_eq!(map.remove(&key), model.remove(&key));
+        } else {
+            assert_eq!(map.insert(key, round), model.insert(key, round));
+        }
+        if round % 100 == 0 {
+            map.check();
+            snapshots.push((map.clone(), model.clone()));
+        }
+        if round % 300 == 0 {
+            // Keep modifying an old snapshot, that now shares nodes both
+            // with the map and with the snapshots taken in between.
+            let (old, old_model) = &mut snapshots[round / 300];
+            old.remove(&key);
+            old_model.remove(&key);
+            old.insert(key + 1, round);
+            old_model.insert(key + 1, round);
+        }
+    }
+    map.check();
+    assert!(map.iter().eq(model.iter()));
+    for (snapshot, model) in &snapshots {
+        snapshot.check();
+        assert!(snapshot.iter().eq(model.iter()));
+    }
+
+    // Empty out all of them in turn, which exercises merging and stealing
+    // among nodes shared in every possible way.
+    snapshots.push((map, model));
+    while let Some((mut snapshot, model)) = snapshots.pop() {
+        for (k, v) in &model {
+            assert_eq!(snapshot.remove(k), Some(*v));
+        }
+        snapshot.check();
+        assert!(snapshot.is_empty());
+        for (other, other_model) in &snapshots {
+            assert!(other.iter().eq(other_model.iter()));
+        }
+    }
+}
+
+#[test]
+fn test_drop_once() {
+    let dummies: Vec<_> = (0..200).map(CrashTestDummy::new).collect();
+    let mut map = PersistentBTreeMap::new();
+    for d in &dummies {
+        map.insert(d.id, d.spawn(Panic::Never));
+    }
+    let snapshot = map.clone();
+    for id in [0, 100, 199] {
+        map.remove(&id);
+    }
+    map.check();
+    snapshot.check();
+    drop(map);
+    // Only values in copied nodes were cloned, and each copy is gone.
+    assert!(dummies.iter().any(|d| d.cloned() == 0));
+    for d in &dummies {
+        assert_eq!(d.dropped(), d.cloned());
+    }
+    drop(snapshot);
+    for d in &dummies {
+        assert_eq!(d.dropped(), d.cloned() + 1);
+    }
+}
+
+#[test]
+fn test_iter() {
+    let map: PersistentBTreeMap<_, _> = (0..300).rev().map(|i| (i, i * 2)).collect();
+    let mut iter = map.iter();
+    assert_eq!(iter.len(), 300);
+    for i in 0..300 {
+        assert_eq!(iter.next(), Some((&i, &(i * 2))));
+        assert_eq!(iter.len(), 299 - i);
+    }
+    assert_eq!(iter.next(), None);
+    assert_eq!(iter.next(), None);
+    assert_eq!((&map).into_iter().map(|(k, _)| k).sum::<usize>(), 299 * 300 / 2);
+}
+
+#[test]
+#[cfg_attr(not(panic = "unwind"), ignore = "test requires unwinding support")]
+fn test_copy_panic_leak() {
+    // A map with a leaf for root, and one with an internal root.
+    for size in [5, 100] {
+        // Find the second key in the root, whose clone fails partway through
+        // copying the root.
+        let shape: PersistentBTreeMap<_, _> = (0..size).map(|id| (id, ())).collect();
+        let root = shape.root.as_ref().unwrap().reborrow();
+        let (first, second) = (root.keys()[0], root.keys()[1]);
+
+        let dummies: Vec<_> = (0..size).map(CrashTestDummy::new).collect();
+        let mut map: PersistentBTreeMap<_, _> = dummies
+            .iter()
+            .map(|d| (d.spawn(if d.id == second { Panic::InClone } else { Panic::Never }), ()))
+            .collect();
+        let snapshot = map.clone();
+        let extra = CrashTestDummy::new(size);
+        catch_unwind(AssertUnwindSafe(|| map.insert(extra.spawn(Panic::Never), ()))).unwrap_err();
+        assert_eq!(extra.dropped(), 1);
+        for d in &dummies {
+            let copied = d.id == first || d.id == second;
+            assert_eq!(d.cloned(), if copied { 1 } else { 0 }, "id={}/{}", d.id, size);
+            assert_eq!(d.dropped(), if d.id == first { 1 } else { 0 }, "id={}/{}", d.id, size);
+        }
+        map.check();
+        assert_eq!(map.len(), size);
+        assert!(PersistentBTreeMap::ptr_eq(&map, &snapshot));
+
+        drop(map);
+        drop(snapshot);
+        for d in &dummies {
+            assert_eq!(d.dropped(), if d.id == first { 2 } else { 1 }, "id={}/{}", d.id, size);
+        }
+    }
+}
+
+#[test]
+#[cfg_attr(not(panic = "unwind"), ignore = "test requires unwinding support")]
+fn test_copy_panic_leak_in_value() {
+    for size in [5, 100] {
+        // Find the second key in the root, whose value's clone fails partway
+        // through copying the root, after the key itself has been cloned.
+        let shape: PersistentBTreeMap<_, _> = (0..size).map(|id| (id, ())).collect();
+        let root = shape.root.as_ref().unwrap().reborrow();
+        let (first, second) = (root.keys()[0], root.keys()[1]);
+
+        let keys: Vec<_> = (0..size).map(CrashTestDummy::new).collect();
+        let vals: Vec<_> = (0..size).map(CrashTestDummy::new).collect();
+        let (extra_key, extra_val) = (CrashTestDummy::new(size), CrashTestDummy::new(size));
+        let mut map: PersistentBTreeMap<_, _> = keys
+            .iter()
+            .zip(&vals)
+            .map(|(k, v)| {
+                let panic = if v.id == second { Panic::InClone } else { Panic::Never };
+                (k.spawn(Panic::Never), v.spawn(panic))
+            })
+            .collect();
+        let snapshot = map.clone();
+        catch_unwind(AssertUnwindSafe(|| {
+            map.insert(extra_key.spawn(Panic::Never), extra_val.spawn(Panic::Never))
+        }))
+        .unwrap_err();
+        assert_eq!(extra_key.dropped(), 1);
+        assert_eq!(extra_val.dropped(), 1);
+        for (k, v) in keys.iter().zip(&vals) {
+            let copied = k.id == first || k.id == second;
+            assert_eq!(k.cloned(), if copied { 1 } else { 0 }, "id={}/{}", k.id, size);
+            assert_eq!(k.dropped(), if copied { 1 } else { 0 }, "id={}/{}", k.id, size);
+            assert_eq!(v.cloned(), if copied { 1 } else { 0 }, "id={}/{}", v.id, size);
+            assert_eq!(v.dropped(), if v.id == first { 1 } else { 0 }, "id={}/{}", v.id, size);
+        }
+        map.check();
+        assert!(PersistentBTreeMap::ptr_eq(&map, &snapshot));
+    }
+}
+
+#[test]
+fn test_snapshots_across_threads() {
+    let map: PersistentBTreeMap<_, _> = (0..1000).map(|i| (i, i)).collect();
+    thread::scope(|s| {
+        for t in 0..4 {
+            let mut map = map.clone();
+            s.spawn(move || {
+                for i in (t..1000).step_by(4) {
+                    assert_eq!(map.remove(&i), Some(i));
+                }
+                for i in 0..100 {
+                    map.insert(1000 + t * 100 + i, t);
+                }
+                map.check();
+                assert_eq!(map.len(), 850);
+            });
+        }
+        let snapshot = map.clone();
+        s.spawn(move || {
+            assert!(snapshot.iter().map(|(&k, &v)| (k, v)).eq((0..1000).map(|i| (i, i))))
+        });
+    });
+    map.check();
+    assert!(map.iter().map(|(&k, &v)| (k, v)).eq((0..1000).map(|i| (i, i))));
+}
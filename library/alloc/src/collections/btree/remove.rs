@@ -1,13 +1,12 @@
 use super::map::MIN_LEN;
-use super::node::{marker, ForceResult::*, Handle, LeftOrRight::*, NodeRef};
-use core::alloc::Allocator;
+use super::node::{marker, ForceResult::*, Handle, LeftOrRight::*, NodeAlloc, NodeRef};
 
 impl<'a, K: 'a, V: 'a> Handle<NodeRef<marker::Mut<'a>, K, V, marker::LeafOrInternal>, marker::KV> {
     /// Removes a key-value pair from the tree, and returns that pair, as well as
     /// the leaf edge corresponding to that former pair. It's possible this empties
     /// a root node that is internal, which the caller should pop from the map
     /// holding the tree. The caller should also decrement the map's length.
-    pub fn remove_kv_tracking<F: FnOnce(), A: Allocator + Clone>(
+    pub fn remove_kv_tracking<F: FnOnce(), A: NodeAlloc>(
         self,
         handle_emptied_internal_root: F,
         alloc: A,
@@ -20,12 +19,14 @@ pub fn remove_kv_tracking<F: FnOnce(), A: Allocator + Clone>(
 }
 
 impl<'a, K: 'a, V: 'a> Handle<NodeRef<marker::Mut<'a>, K, V, marker::Leaf>, marker::KV> {
-    fn remove_leaf_kv<F: FnOnce(), A: Allocator + Clone>(
+    fn remove_leaf_kv<F: FnOnce(), A: NodeAlloc>(
         self,
         handle_emptied_internal_root: F,
         alloc: A,
     ) -> ((K, V), Handle<NodeRef<marker::Mut<'a>, K, V, marker::Leaf>, marker::Edge>) {
         let (old_kv, mut pos) = self.remove();
+        // Stealing and merging below an ancestor keep its subtree length as it is.
+        unsafe { pos.reborrow_mut() }.into_node().shrink_subtree_lens_above(&alloc);
         let len = pos.reborrow().into_node().len();
         if len < MIN_LEN {
             let idx = pos.idx();
@@ -38,7 +39,7 @@ fn remove_leaf_kv<F: FnOnce(), A: Allocator + Clone>(
                         left_parent_kv.merge_tracking_child_edge(Right(idx), alloc.clone())
                     } else {
                         debug_assert!(left_parent_kv.left_child_len() > MIN_LEN);
-                        left_parent_kv.steal_left(idx)
+                        left_parent_kv.steal_left(idx, &alloc)
                     }
                 }
                 Ok(Right(right_parent_kv)) => {
@@ -47,7 +48,7 @@ fn remove_leaf_kv<F: FnOnce(), A: Allocator + Clone>(
                         right_parent_kv.merge_tracking_child_edge(Left(idx), alloc.clone())
                     } else {
                         debug_assert!(right_parent_kv.right_child_len() > MIN_LEN);
-                        right_parent_kv.steal_right(idx)
+                        right_parent_kv.steal_right(idx, &alloc)
                     }
                 }
                 Err(pos) => unsafe { Handle::new_edge(pos, idx) },
@@ -73,7 +74,7 @@ fn remove_leaf_kv<F: FnOnce(), A: Allocator + Clone>(
 }
 
 impl<'a, K: 'a, V: 'a> Handle<NodeRef<marker::Mut<'a>, K, V, marker::Internal>, marker::KV> {
-    fn remove_internal_kv<F: FnOnce(), A: Allocator + Clone>(
+    fn remove_internal_kv<F: FnOnce(), A: NodeAlloc>(
         self,
         handle_emptied_internal_root: F,
         alloc: A,
@@ -0,0 +1,135 @@
+use core::cell::Cell;
+use core::ptr::NonNull;
+
+use crate::alloc::{AllocError, Allocator, Layout};
+use crate::collections::{TryReserveError, TryReserveErrorKind};
+
+use super::node::{marker, node_layout, Handle, NodeAlloc, NodeRef, CAPACITY};
+
+// Inserting a key-value pair splits every full node on the way from the leaf
+// to the first node with room to spare, or to the root, which then gets a new
+// parent. Once the first split happens, there's no turning back, so to report
+// allocation failure instead of aborting, we allocate all the new nodes before
+// touching the tree and let the insertion take them from `ReservedNodes`.
+
+/// Node allocations made ahead of an insertion, handed out in place of
+/// allocating anew. Whatever the insertion doesn't take is given back to
+/// the underlying allocator on drop.
+pub struct ReservedNodes<A: NodeAlloc> {
+    alloc: A,
+    leaf_layout: Layout,
+    internal_layout: Layout,
+    /// An insertion creates at most one leaf.
+    leaf: Cell<Option<NonNull<u8>>>,
+    /// The head of a list of internal nodes, each storing the next one in its first word.
+    internals: Cell<Option<NonNull<u8>>>,
+}
+
+impl<A: NodeAlloc> ReservedNodes<A> {
+    /// Reserves the nodes needed to insert a key-value pair at `edge`, or,
+    /// if there is no edge because the tree has no root yet, to insert
+    /// a key-value pair into a new root.
+    pub fn for_insert<K, V>(
+        edge: Option<Handle<NodeRef<marker::Immut<'_>, K, V, marker::Leaf>, marker::Edge>>,
+        alloc: A,
+    ) -> Result<Self, TryReserveError> {
+        let reserved = ReservedNodes {
+            alloc,
+            leaf_layout: node_layout::<K, V>(0),
+            internal_layout: node_layout::<K, V>(1),
+            leaf: Cell::new(None),
+            internals: Cell::new(None),
+        };
+        let Some(edge) = edge else {
+            reserved.reserve_leaf()?;
+            return Ok(reserved);
+        };
+        let mut node = edge.into_node().forget_type();
+        if node.len() < CAPACITY {
+            return Ok(reserved);
+        }
+        reserved.reserve_leaf()?;
+        loop {
+            match node.ascend() {
+                Ok(parent) => {
+                    let parent = parent.into_node();
+                    if parent.len() < CAPACITY {
+                        return Ok(reserved);
+                    }
+                    // The parent splits as well.
+                    reserved.reserve_internal()?;
+                    node = parent.forget_type();
+                }
+                Err(_) => {
+                    // The root splits, and gets a new parent.
+                    reserved.reserve_internal()?;
+                    return Ok(reserved);
+                }
+            }
+        }
+    }
+
+    fn reserve_leaf(&self) -> Result<(), TryReserveError> {
+        let node = self.allocate_anew(self.leaf_layout)?;
+        self.leaf.set(Some(node));
+        Ok(())
+    }
+
+    fn reserve_internal(&self) -> Result<(), TryReserveError> {
+        let node = self.allocate_anew(self.internal_layout)?;
+        unsafe { node.cast::<Option<NonNull<u8>>>().write(self.internals.get()) };
+        self.internals.set(Some(node));
+        Ok(())
+    }
+
+    fn allocate_anew(&self, layout: Layout) -> Result<NonNull<u8>, TryReserveError> {
+        match self.alloc.allocate(layout) {
+            Ok(ptr) => Ok(ptr.cast()),
+            Err(_) => Err(TryReserveErrorKind::AllocError { layout, non_exhaustive: () }.into()),
+        }
+    }
+
+    fn take_internal(&self) -> Option<NonNull<u8>> {
+        let node = self.internals.get()?;
+        self.internals.set(unsafe { node.cast::<Option<NonNull<u8>>>().read() });
+        Some(node)
+    }
+}
+
+unsafe impl<A: NodeAlloc> Allocator for ReservedNodes<A> {
+    fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        let node = if layout == self.leaf_layout {
+            self.leaf.take()
+        } else if layout == self.internal_layout {
+            self.take_internal()
+        } else {
+            None
+        };
+        match node {
+            Some(node) => Ok(NonNull::slice_from_raw_parts(node, layout.size())),
+            None => Err(AllocError),
+        }
+    }
+
+    unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+        unsafe { self.alloc.deallocate(ptr, layout) }
+    }
+}
+
+// Reserved nodes come from the underlying allocator, room for subtree lengths and all.
+unsafe impl<A: NodeAlloc> NodeAlloc for &ReservedNodes<A> {
+    fn keeps_subtree_lens(&self) -> bool {
+        self.alloc.keeps_subtree_lens()
+    }
+}
+
+impl<A: NodeAlloc> Drop for ReservedNodes<A> {
+    fn drop(&mut self) {
+        if let Some(leaf) = self.leaf.take() {
+            unsafe { self.alloc.deallocate(leaf, self.leaf_layout) };
+        }
+        while let Some(internal) = self.take_internal() {
+            unsafe { self.alloc.deallocate(internal, self.internal_layout) };
+        }
+    }
+}
@@ -181,6 +181,100 @@ pub fn find_upper_bound_edge<'r, Q>(
         let edge = unsafe { Handle::new_edge(self, edge_idx) };
         (edge, bound)
     }
+
+    /// Counts the key-value pairs in a (sub)tree headed by the node with a key
+    /// contained in the range.
+    ///
+    /// As a diagnostic service, panics if the range specifies impossible bounds.
+    ///
+    /// The result is meaningful only if the tree is ordered by key.
+    ///
+    /// # Safety
+    /// The tree must be allocated by an allocator that keeps subtree lengths.
+    pub unsafe fn count_in_range<Q: ?Sized, R>(self, range: &R) -> usize
+    where
+        Q: Ord,
+        K: Borrow<Q>,
+        R: RangeBounds<Q>,
+    {
+        let (node, lower_edge_idx, upper_edge_idx, lower_child_bound, upper_child_bound) =
+            match self.search_tree_for_bifurcation(range) {
+                Ok(bifurcation) => bifurcation,
+                Err(_) => return 0,
+            };
+        // All key-value pairs between the two edges are in the range.
+        let mut count = upper_edge_idx - lower_edge_idx;
+        if let Internal(internal) = node.force() {
+            let internal = internal.reborrow();
+            let child = |idx| unsafe { Handle::new_edge(internal, idx) }.descend();
+            unsafe {
+                for idx in lower_edge_idx + 1..upper_edge_idx {
+                    count += child(idx).subtree_len();
+                }
+                let lower_child = child(lower_edge_idx);
+                count += lower_child.subtree_len()
+                    - lower_child.count_below_lower_bound(lower_child_bound);
+                count += child(upper_edge_idx).count_below_upper_bound(upper_child_bound);
+            }
+        }
+        count
+    }
+
+    /// Counts the key-value pairs in a (sub)tree headed by the node that are
+    /// to the left of the leaf edge matching the lower bound of a range.
+    ///
+    /// The result is meaningful only if the tree is ordered by key.
+    ///
+    /// # Safety
+    /// The tree must be allocated by an allocator that keeps subtree lengths.
+    pub unsafe fn count_below_lower_bound<Q>(self, bound: SearchBound<&Q>) -> usize
+    where
+        Q: ?Sized + Ord,
+        K: Borrow<Q>,
+    {
+        unsafe { self.count_below_bound(bound, |node, bound| node.find_lower_bound_index(bound)) }
+    }
+
+    /// Clone of `count_below_lower_bound` for the upper bound.
+    pub unsafe fn count_below_upper_bound<Q>(self, bound: SearchBound<&Q>) -> usize
+    where
+        Q: ?Sized + Ord,
+        K: Borrow<Q>,
+    {
+        unsafe {
+            self.count_below_bound(bound, |node, bound| node.find_upper_bound_index(bound, 0))
+        }
+    }
+
+    unsafe fn count_below_bound<'r, Q, F>(
+        self,
+        mut bound: SearchBound<&'r Q>,
+        find_index: F,
+    ) -> usize
+    where
+        Q: ?Sized + Ord,
+        K: Borrow<Q>,
+        F: Fn(&Self, SearchBound<&'r Q>) -> (usize, SearchBound<&'r Q>),
+    {
+        let mut node = self;
+        let mut count = 0;
+        loop {
+            let (edge_idx, child_bound) = find_index(&node, bound);
+            // Each edge to the left is followed by a key-value pair to the left.
+            count += edge_idx;
+            match node.force() {
+                Leaf(_) => return count,
+                Internal(internal) => {
+                    for idx in 0..edge_idx {
+                        let child = unsafe { Handle::new_edge(internal.reborrow(), idx) }.descend();
+                        count += unsafe { child.subtree_len() };
+                    }
+                    node = unsafe { Handle::new_edge(internal, edge_idx) }.descend();
+                    bound = child_bound;
+                }
+            }
+        }
+    }
 }
 
 impl<BorrowType, K, V, Type> NodeRef<BorrowType, K, V, Type> {
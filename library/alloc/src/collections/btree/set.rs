@@ -1,3 +1,4 @@
+#[cfg(not(no_global_oom_handling))]
 use crate::vec::Vec;
 use core::borrow::Borrow;
 use core::cmp::Ordering::{self, Equal, Greater, Less};
@@ -5,15 +6,20 @@
 use core::fmt::{self, Debug};
 use core::hash::{Hash, Hasher};
 use core::iter::{FusedIterator, Peekable};
+#[cfg(not(no_global_oom_handling))]
 use core::mem::ManuallyDrop;
-use core::ops::{BitAnd, BitOr, BitXor, RangeBounds, Sub};
+use core::ops::RangeBounds;
+#[cfg(not(no_global_oom_handling))]
+use core::ops::{BitAnd, BitOr, BitXor, Sub};
 
-use super::map::{BTreeMap, Keys};
+use super::map::{BTreeMap, Entry, Keys, NoStatistics, OrderStatistics, Statistics};
+use super::order_statistics::Nodes;
 use super::merge_iter::MergeIterInner;
 use super::set_val::SetValZST;
 use super::Recover;
 
 use crate::alloc::{Allocator, Global};
+use crate::collections::TryReserveError;
 
 /// An ordered set based on a B-Tree.
 ///
@@ -75,43 +81,45 @@
 pub struct BTreeSet<
     T,
     #[unstable(feature = "allocator_api", issue = "32838")] A: Allocator + Clone = Global,
+    #[unstable(feature = "btree_order_statistics", issue = "none")] S: Statistics = NoStatistics,
 > {
-    map: BTreeMap<T, SetValZST, A>,
+    map: BTreeMap<T, SetValZST, A, S>,
 }
 
 #[stable(feature = "rust1", since = "1.0.0")]
-impl<T: Hash, A: Allocator + Clone> Hash for BTreeSet<T, A> {
+impl<T: Hash, A: Allocator + Clone, S: Statistics> Hash for BTreeSet<T, A, S> {
     fn hash<H: Hasher>(&self, state: &mut H) {
         self.map.hash(state)
     }
 }
 
 #[stable(feature = "rust1", since = "1.0.0")]
-impl<T: PartialEq, A: Allocator + Clone> PartialEq for BTreeSet<T, A> {
-    fn eq(&self, other: &BTreeSet<T, A>) -> bool {
+impl<T: PartialEq, A: Allocator + Clone, S: Statistics> PartialEq for BTreeSet<T, A, S> {
+    fn eq(&self, other: &BTreeSet<T, A, S>) -> bool {
         self.map.eq(&other.map)
     }
 }
 
 #[stable(feature = "rust1", since = "1.0.0")]
-impl<T: Eq, A: Allocator + Clone> Eq for BTreeSet<T, A> {}
+impl<T: Eq, A: Allocator + Clone, S: Statistics> Eq for BTreeSet<T, A, S> {}
 
 #[stable(feature = "rust1", since = "1.0.0")]
-impl<T: PartialOrd, A: Allocator + Clone> PartialOrd for BTreeSet<T, A> {
-    fn partial_cmp(&self, other: &BTreeSet<T, A>) -> Option<Ordering> {
+impl<T: PartialOrd, A: Allocator + Clone, S: Statistics> PartialOrd for BTreeSet<T, A, S> {
+    fn partial_cmp(&self, other: &BTreeSet<T, A, S>) -> Option<Ordering> {
         self.map.partial_cmp(&other.map)
     }
 }
 
 #[stable(feature = "rust1", since = "1.0.0")]
-impl<T: Ord, A: Allocator + Clone> Ord for BTreeSet<T, A> {
-    fn cmp(&self, other: &BTreeSet<T, A>) -> Ordering {
+impl<T: Ord, A: Allocator + Clone, S: Statistics> Ord for BTreeSet<T, A, S> {
+    fn cmp(&self, other: &BTreeSet<T, A, S>) -> Ordering {
         self.map.cmp(&other.map)
     }
 }
 
+#[cfg(not(no_global_oom_handling))]
 #[stable(feature = "rust1", since = "1.0.0")]
-impl<T: Clone, A: Allocator + Clone> Clone for BTreeSet<T, A> {
+impl<T: Clone, A: Allocator + Clone, S: Statistics> Clone for BTreeSet<T, A, S> {
     fn clone(&self) -> Self {
         BTreeSet { map: self.map.clone() }
     }
@@ -151,8 +159,9 @@ fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
 pub struct IntoIter<
     T,
     #[unstable(feature = "allocator_api", issue = "32838")] A: Allocator + Clone = Global,
+    #[unstable(feature = "btree_order_statistics", issue = "none")] S: Statistics = NoStatistics,
 > {
-    iter: super::map::IntoIter<T, SetValZST, A>,
+    iter: super::map::IntoIter<T, SetValZST, A, S>,
 }
 
 /// An iterator over a sub-range of items in a `BTreeSet`.
@@ -181,10 +190,11 @@ pub struct Difference<
     'a,
     T: 'a,
     #[unstable(feature = "allocator_api", issue = "32838")] A: Allocator + Clone = Global,
+    #[unstable(feature = "btree_order_statistics", issue = "none")] S: Statistics = NoStatistics,
 > {
-    inner: DifferenceInner<'a, T, A>,
+    inner: DifferenceInner<'a, T, A, S>,
 }
-enum DifferenceInner<'a, T: 'a, A: Allocator + Clone> {
+enum DifferenceInner<'a, T: 'a, A: Allocator + Clone, S: Statistics> {
     Stitch {
         // iterate all of `self` and some of `other`, spotting matches along the way
         self_iter: Iter<'a, T>,
@@ -193,13 +203,13 @@ enum DifferenceInner<'a, T: 'a, A: Allocator + Clone> {
     Search {
         // iterate `self`, look up in `other`
         self_iter: Iter<'a, T>,
-        other_set: &'a BTreeSet<T, A>,
+        other_set: &'a BTreeSet<T, A, S>,
     },
     Iterate(Iter<'a, T>), // simply produce all elements in `self`
 }
 
 // Explicit Debug impl necessary because of issue #26925
-impl<T: Debug, A: Allocator + Clone> Debug for DifferenceInner<'_, T, A> {
+impl<T: Debug, A: Allocator + Clone, S: Statistics> Debug for DifferenceInner<'_, T, A, S> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             DifferenceInner::Stitch { self_iter, other_iter } => f
@@ -218,7 +228,7 @@ fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
 }
 
 #[stable(feature = "collection_debug", since = "1.17.0")]
-impl<T: fmt::Debug, A: Allocator + Clone> fmt::Debug for Difference<'_, T, A> {
+impl<T: fmt::Debug, A: Allocator + Clone, S: Statistics> fmt::Debug for Difference<'_, T, A, S> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_tuple("Difference").field(&self.inner).finish()
     }
@@ -255,10 +265,11 @@ pub struct Intersection<
     'a,
     T: 'a,
     #[unstable(feature = "allocator_api", issue = "32838")] A: Allocator + Clone = Global,
+    #[unstable(feature = "btree_order_statistics", issue = "none")] S: Statistics = NoStatistics,
 > {
-    inner: IntersectionInner<'a, T, A>,
+    inner: IntersectionInner<'a, T, A, S>,
 }
-enum IntersectionInner<'a, T: 'a, A: Allocator + Clone> {
+enum IntersectionInner<'a, T: 'a, A: Allocator + Clone, S: Statistics> {
     Stitch {
         // iterate similarly sized sets jointly, spotting matches along the way
         a: Iter<'a, T>,
@@ -267,13 +278,13 @@ enum IntersectionInner<'a, T: 'a, A: Allocator + Clone> {
     Search {
         // iterate a small set, look up in the large set
         small_iter: Iter<'a, T>,
-        large_set: &'a BTreeSet<T, A>,
+        large_set: &'a BTreeSet<T, A, S>,
     },
     Answer(Option<&'a T>), // return a specific element or emptiness
 }
 
 // Explicit Debug impl necessary because of issue #26925
-impl<T: Debug, A: Allocator + Clone> Debug for IntersectionInner<'_, T, A> {
+impl<T: Debug, A: Allocator + Clone, S: Statistics> Debug for IntersectionInner<'_, T, A, S> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             IntersectionInner::Stitch { a, b } => {
@@ -290,7 +301,7 @@ fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
 }
 
 #[stable(feature = "collection_debug", since = "1.17.0")]
-impl<T: Debug, A: Allocator + Clone> Debug for Intersection<'_, T, A> {
+impl<T: Debug, A: Allocator + Clone, S: Statistics> Debug for Intersection<'_, T, A, S> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_tuple("Intersection").field(&self.inner).finish()
     }
@@ -341,6 +352,57 @@ impl<T> BTreeSet<T> {
     pub const fn new() -> BTreeSet<T> {
         BTreeSet { map: BTreeMap::new() }
     }
+
+    /// Creates a `BTreeSet` from an iterator, returning an error instead of
+    /// panicking or aborting if allocating a node fails.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(fallible_allocation)]
+    /// use std::collections::BTreeSet;
+    ///
+    /// let set = BTreeSet::try_from_iter([3, 1, 2, 1]).unwrap();
+    /// assert_eq!(set.into_iter().collect::<Vec<_>>(), [1, 2, 3]);
+    /// ```
+    #[unstable(feature = "fallible_allocation", issue = "none")]
+    pub fn try_from_iter<I: IntoIterator<Item = T>>(iter: I) -> Result<Self, TryReserveError>
+    where
+        T: Ord,
+    {
+        let mut set = BTreeSet::new();
+        set.try_extend(iter)?;
+        Ok(set)
+    }
+}
+
+impl<T> BTreeSet<T, Global, OrderStatistics> {
+    /// Makes a new, empty `BTreeSet` that keeps [`OrderStatistics`], so that
+    /// [`nth`], [`rank`] and [`range_count`] take logarithmic time.
+    ///
+    /// Does not allocate anything on its own.
+    ///
+    /// [`OrderStatistics`]: crate::collections::btree_map::OrderStatistics
+    /// [`nth`]: BTreeSet::nth
+    /// [`rank`]: BTreeSet::rank
+    /// [`range_count`]: BTreeSet::range_count
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(btree_order_statistics)]
+    /// use std::collections::BTreeSet;
+    ///
+    /// let mut set = BTreeSet::with_order_statistics();
+    /// set.insert(3);
+    /// set.insert(1);
+    /// assert_eq!(set.nth(1), Some(&3));
+    /// ```
+    #[unstable(feature = "btree_order_statistics", issue = "none")]
+    #[must_use]
+    pub const fn with_order_statistics() -> Self {
+        BTreeSet { map: BTreeMap::with_order_statistics() }
+    }
 }
 
 impl<T, A: Allocator + Clone> BTreeSet<T, A> {
@@ -361,6 +423,39 @@ impl<T, A: Allocator + Clone> BTreeSet<T, A> {
     pub const fn new_in(alloc: A) -> BTreeSet<T, A> {
         BTreeSet { map: BTreeMap::new_in(alloc) }
     }
+}
+
+impl<T, A: Allocator + Clone> BTreeSet<T, A, OrderStatistics> {
+    /// Makes a new, empty `BTreeSet` that keeps [`OrderStatistics`] and
+    /// allocates through the provided allocator.
+    ///
+    /// [`OrderStatistics`]: crate::collections::btree_map::OrderStatistics
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #![feature(allocator_api)]
+    /// # #![feature(btree_order_statistics)]
+    /// use std::collections::BTreeSet;
+    /// use std::alloc::Global;
+    ///
+    /// let mut set = BTreeSet::with_order_statistics_in(Global);
+    /// set.insert(1);
+    /// assert_eq!(set.rank(&1), 0);
+    /// ```
+    #[unstable(feature = "btree_order_statistics", issue = "none")]
+    pub const fn with_order_statistics_in(alloc: A) -> Self {
+        BTreeSet { map: BTreeMap::with_order_statistics_in(alloc) }
+    }
+}
+
+impl<T, A: Allocator + Clone, S: Statistics> BTreeSet<T, A, S> {
+    /// Returns a reference to the underlying allocator.
+    #[unstable(feature = "allocator_api", issue = "32838")]
+    #[inline]
+    pub fn allocator(&self) -> &A {
+        self.map.allocator()
+    }
 
     /// Constructs a double-ended iterator over a sub-range of elements in the set.
     /// The simplest way is to use the range syntax `min..max`, thus `range(min..max)` will
@@ -420,7 +515,7 @@ pub fn range<K: ?Sized, R>(&self, range: R) -> Range<'_, T>
     /// assert_eq!(diff, [1]);
     /// ```
     #[stable(feature = "rust1", since = "1.0.0")]
-    pub fn difference<'a>(&'a self, other: &'a BTreeSet<T, A>) -> Difference<'a, T, A>
+    pub fn difference<'a>(&'a self, other: &'a BTreeSet<T, A, S>) -> Difference<'a, T, A, S>
     where
         T: Ord,
     {
@@ -483,7 +578,7 @@ pub fn difference<'a>(&'a self, other: &'a BTreeSet<T, A>) -> Difference<'a, T,
     #[stable(feature = "rust1", since = "1.0.0")]
     pub fn symmetric_difference<'a>(
         &'a self,
-        other: &'a BTreeSet<T, A>,
+        other: &'a BTreeSet<T, A, S>,
     ) -> SymmetricDifference<'a, T>
     where
         T: Ord,
@@ -512,7 +607,7 @@ pub fn symmetric_difference<'a>(
     /// assert_eq!(intersection, [2]);
     /// ```
     #[stable(feature = "rust1", since = "1.0.0")]
-    pub fn intersection<'a>(&'a self, other: &'a BTreeSet<T, A>) -> Intersection<'a, T, A>
+    pub fn intersection<'a>(&'a self, other: &'a BTreeSet<T, A, S>) -> Intersection<'a, T, A, S>
     where
         T: Ord,
     {
@@ -563,7 +658,7 @@ pub fn intersection<'a>(&'a self, other: &'a BTreeSet<T, A>) -> Intersection<'a,
     /// assert_eq!(union, [1, 2]);
     /// ```
     #[stable(feature = "rust1", since = "1.0.0")]
-    pub fn union<'a>(&'a self, other: &'a BTreeSet<T, A>) -> Union<'a, T>
+    pub fn union<'a>(&'a self, other: &'a BTreeSet<T, A, S>) -> Union<'a, T>
     where
         T: Ord,
     {
@@ -658,7 +753,7 @@ pub fn get<Q: ?Sized>(&self, value: &Q) -> Option<&T>
     /// ```
     #[must_use]
     #[stable(feature = "rust1", since = "1.0.0")]
-    pub fn is_disjoint(&self, other: &BTreeSet<T, A>) -> bool
+    pub fn is_disjoint(&self, other: &BTreeSet<T, A, S>) -> bool
     where
         T: Ord,
     {
@@ -684,7 +779,7 @@ pub fn is_disjoint(&self, other: &BTreeSet<T, A>) -> bool
     /// ```
     #[must_use]
     #[stable(feature = "rust1", since = "1.0.0")]
-    pub fn is_subset(&self, other: &BTreeSet<T, A>) -> bool
+    pub fn is_subset(&self, other: &BTreeSet<T, A, S>) -> bool
     where
         T: Ord,
     {
@@ -764,7 +859,7 @@ pub fn is_subset(&self, other: &BTreeSet<T, A>) -> bool
     /// ```
     #[must_use]
     #[stable(feature = "rust1", since = "1.0.0")]
-    pub fn is_superset(&self, other: &BTreeSet<T, A>) -> bool
+    pub fn is_superset(&self, other: &BTreeSet<T, A, S>) -> bool
     where
         T: Ord,
     {
@@ -897,6 +992,7 @@ pub fn pop_last(&mut self) -> Option<T>
     /// assert_eq!(set.insert(2), false);
     /// assert_eq!(set.len(), 1);
     /// ```
+    #[cfg(not(no_global_oom_handling))]
     #[stable(feature = "rust1", since = "1.0.0")]
     #[rustc_confusables("push", "put")]
     pub fn insert(&mut self, value: T) -> bool
@@ -906,6 +1002,39 @@ pub fn insert(&mut self, value: T) -> bool
         self.map.insert(value, SetValZST::default()).is_none()
     }
 
+    /// Adds a value to the set, returning an error instead of panicking or
+    /// aborting if allocating a node fails.
+    ///
+    /// Returns whether the value was newly inserted, as [`insert`] does.
+    ///
+    /// [`insert`]: BTreeSet::insert
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(fallible_allocation)]
+    /// use std::collections::BTreeSet;
+    ///
+    /// let mut set = BTreeSet::new();
+    ///
+    /// assert_eq!(set.try_insert(2), Ok(true));
+    /// assert_eq!(set.try_insert(2), Ok(false));
+    /// assert_eq!(set.len(), 1);
+    /// ```
+    #[unstable(feature = "fallible_allocation", issue = "none")]
+    pub fn try_insert(&mut self, value: T) -> Result<bool, TryReserveError>
+    where
+        T: Ord,
+    {
+        match self.map.entry(value) {
+            Entry::Occupied(_) => Ok(false),
+            Entry::Vacant(entry) => {
+                entry.try_insert(SetValZST::default())?;
+                Ok(true)
+            }
+        }
+    }
+
     /// Adds a value to the set, replacing the existing element, if any, that is
     /// equal to the value. Returns the replaced element.
     ///
@@ -921,6 +1050,7 @@ pub fn insert(&mut self, value: T) -> bool
     /// set.replace(Vec::with_capacity(10));
     /// assert_eq!(set.get(&[][..]).unwrap().capacity(), 10);
     /// ```
+    #[cfg(not(no_global_oom_handling))]
     #[stable(feature = "set_recovery", since = "1.9.0")]
     #[rustc_confusables("swap")]
     pub fn replace(&mut self, value: T) -> Option<T>
@@ -1034,6 +1164,7 @@ pub fn retain<F>(&mut self, mut f: F)
     /// assert!(a.contains(&4));
     /// assert!(a.contains(&5));
     /// ```
+    #[cfg(not(no_global_oom_handling))]
     #[stable(feature = "btree_append", since = "1.11.0")]
     pub fn append(&mut self, other: &mut Self)
     where
@@ -1043,6 +1174,53 @@ pub fn append(&mut self, other: &mut Self)
         self.map.append(&mut other.map);
     }
 
+    /// Extends the set with the values of an iterator, returning an error
+    /// instead of panicking or aborting if allocating a node fails.
+    ///
+    /// If an error is returned, the values inserted before the failure remain
+    /// in the set, and the remainder of the iterator is dropped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(fallible_allocation)]
+    /// use std::collections::BTreeSet;
+    ///
+    /// let mut set = BTreeSet::from([1]);
+    /// set.try_extend([1, 2, 3]).unwrap();
+    /// assert_eq!(set.len(), 3);
+    /// ```
+    #[unstable(feature = "fallible_allocation", issue = "none")]
+    pub fn try_extend<I: IntoIterator<Item = T>>(&mut self, iter: I) -> Result<(), TryReserveError>
+    where
+        T: Ord,
+    {
+        for value in iter {
+            self.try_insert(value)?;
+        }
+        Ok(())
+    }
+
+    /// Clones the set, returning an error instead of panicking or aborting
+    /// if allocating a node fails.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(fallible_allocation)]
+    /// use std::collections::BTreeSet;
+    ///
+    /// let set = BTreeSet::from([1, 2, 3]);
+    /// assert_eq!(set.try_clone().unwrap(), set);
+    /// ```
+    #[unstable(feature = "fallible_allocation", issue = "none")]
+    pub fn try_clone(&self) -> Result<Self, TryReserveError>
+    where
+        T: Ord + Clone,
+    {
+        Ok(BTreeSet { map: self.map.try_clone()? })
+    }
+
     /// Splits the collection into two at the value. Returns a new collection
     /// with all elements greater than or equal to the value.
     ///
@@ -1072,6 +1250,7 @@ pub fn append(&mut self, other: &mut Self)
     /// assert!(b.contains(&17));
     /// assert!(b.contains(&41));
     /// ```
+    #[cfg(not(no_global_oom_handling))]
     #[stable(feature = "btree_split_off", since = "1.11.0")]
     pub fn split_off<Q: ?Sized + Ord>(&mut self, value: &Q) -> Self
     where
@@ -1108,7 +1287,7 @@ pub fn split_off<Q: ?Sized + Ord>(&mut self, value: &Q) -> Self
     /// assert_eq!(odds.into_iter().collect::<Vec<_>>(), vec![1, 3, 5, 7]);
     /// ```
     #[unstable(feature = "btree_extract_if", issue = "70530")]
-    pub fn extract_if<'a, F>(&'a mut self, pred: F) -> ExtractIf<'a, T, F, A>
+    pub fn extract_if<'a, F>(&'a mut self, pred: F) -> ExtractIf<'a, T, F, A, S>
     where
         T: Ord,
         F: 'a + FnMut(&T) -> bool,
@@ -1185,6 +1364,98 @@ pub const fn is_empty(&self) -> bool {
     }
 }
 
+impl<T, A: Allocator + Clone> BTreeSet<T, A, OrderStatistics> {
+    /// Returns the element at position `index` in the set, counting from zero
+    /// in ascending order, or `None` if the set has no more than `index` elements.
+    ///
+    /// This takes logarithmic time.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(btree_order_statistics)]
+    /// use std::collections::BTreeSet;
+    ///
+    /// let mut set = BTreeSet::with_order_statistics();
+    /// set.extend([30, 10, 20]);
+    /// assert_eq!(set.nth(0), Some(&10));
+    /// assert_eq!(set.nth(2), Some(&30));
+    /// assert_eq!(set.nth(3), None);
+    /// ```
+    #[unstable(feature = "btree_order_statistics", issue = "none")]
+    pub fn nth(&self, index: usize) -> Option<&T>
+    where
+        T: Ord,
+    {
+        self.map.nth(index).map(|(k, _)| k)
+    }
+
+    /// Returns the number of elements in the set that are less than `value`,
+    /// i.e. the position at which `value` is or would be in the set, counting
+    /// from zero.
+    ///
+    /// The value may be any borrowed form of the set's element type,
+    /// but the ordering on the borrowed form *must* match the
+    /// ordering on the element type.
+    ///
+    /// This takes logarithmic time.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(btree_order_statistics)]
+    /// use std::collections::BTreeSet;
+    ///
+    /// let mut set = BTreeSet::with_order_statistics();
+    /// set.extend([10, 20, 30]);
+    /// assert_eq!(set.rank(&5), 0);
+    /// assert_eq!(set.rank(&20), 1);
+    /// assert_eq!(set.rank(&25), 2);
+    /// ```
+    #[unstable(feature = "btree_order_statistics", issue = "none")]
+    pub fn rank<Q: ?Sized>(&self, value: &Q) -> usize
+    where
+        T: Borrow<Q> + Ord,
+        Q: Ord,
+    {
+        self.map.rank(value)
+    }
+
+    /// Returns the number of elements in a sub-range of the set, which is the
+    /// number of elements that [`range`] would yield for the same range.
+    ///
+    /// This takes logarithmic time.
+    ///
+    /// [`range`]: BTreeSet::range
+    ///
+    /// # Panics
+    ///
+    /// Panics if range `start > end`.
+    /// Panics if range `start == end` and both bounds are `Excluded`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(btree_order_statistics)]
+    /// use std::collections::BTreeSet;
+    ///
+    /// let mut set = BTreeSet::with_order_statistics();
+    /// set.extend((0..100).map(|i| i * 2));
+    /// assert_eq!(set.range_count(10..20), 5);
+    /// assert_eq!(set.range_count(..=10), 6);
+    /// ```
+    #[unstable(feature = "btree_order_statistics", issue = "none")]
+    pub fn range_count<K: ?Sized, R>(&self, range: R) -> usize
+    where
+        K: Ord,
+        T: Borrow<K> + Ord,
+        R: RangeBounds<K>,
+    {
+        self.map.range_count(range)
+    }
+}
+
+#[cfg(not(no_global_oom_handling))]
 #[stable(feature = "rust1", since = "1.0.0")]
 impl<T: Ord> FromIterator<T> for BTreeSet<T> {
     fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> BTreeSet<T> {
@@ -1196,18 +1467,20 @@ fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> BTreeSet<T> {
 
         // use stable sort to preserve the insertion order.
         inputs.sort();
-        BTreeSet::from_sorted_iter(inputs.into_iter(), Global)
+        BTreeSet::from_sorted_iter(inputs.into_iter(), Nodes::new(Global))
     }
 }
 
-impl<T: Ord, A: Allocator + Clone> BTreeSet<T, A> {
-    fn from_sorted_iter<I: Iterator<Item = T>>(iter: I, alloc: A) -> BTreeSet<T, A> {
+#[cfg(not(no_global_oom_handling))]
+impl<T: Ord, A: Allocator + Clone, S: Statistics> BTreeSet<T, A, S> {
+    fn from_sorted_iter<I: Iterator<Item = T>>(iter: I, alloc: Nodes<A, S>) -> BTreeSet<T, A, S> {
         let iter = iter.map(|k| (k, SetValZST::default()));
         let map = BTreeMap::bulk_build_from_sorted_iter(iter, alloc);
         BTreeSet { map }
     }
 }
 
+#[cfg(not(no_global_oom_handling))]
 #[stable(feature = "std_collections_from_array", since = "1.56.0")]
 impl<T: Ord, const N: usize> From<[T; N]> for BTreeSet<T> {
     /// Converts a `[T; N]` into a `BTreeSet<T>`.
@@ -1227,15 +1500,15 @@ fn from_sorted_iter<I: Iterator<Item = T>>(iter: I, alloc: A) -> BTreeSet<T, A>
         // use stable sort to preserve the insertion order.
         arr.sort();
         let iter = IntoIterator::into_iter(arr).map(|k| (k, SetValZST::default()));
-        let map = BTreeMap::bulk_build_from_sorted_iter(iter, Global);
+        let map = BTreeMap::bulk_build_from_sorted_iter(iter, Nodes::new(Global));
         BTreeSet { map }
     }
 }
 
 #[stable(feature = "rust1", since = "1.0.0")]
-impl<T, A: Allocator + Clone> IntoIterator for BTreeSet<T, A> {
+impl<T, A: Allocator + Clone, S: Statistics> IntoIterator for BTreeSet<T, A, S> {
     type Item = T;
-    type IntoIter = IntoIter<T, A>;
+    type IntoIter = IntoIter<T, A, S>;
 
     /// Gets an iterator for moving out the `BTreeSet`'s contents in ascending order.
     ///
@@ -1249,13 +1522,13 @@ impl<T, A: Allocator + Clone> IntoIterator for BTreeSet<T, A> {
     /// let v: Vec<_> = set.into_iter().collect();
     /// assert_eq!(v, [1, 2, 3, 4]);
     /// ```
-    fn into_iter(self) -> IntoIter<T, A> {
+    fn into_iter(self) -> IntoIter<T, A, S> {
         IntoIter { iter: self.map.into_iter() }
     }
 }
 
 #[stable(feature = "rust1", since = "1.0.0")]
-impl<'a, T, A: Allocator + Clone> IntoIterator for &'a BTreeSet<T, A> {
+impl<'a, T, A: Allocator + Clone, S: Statistics> IntoIterator for &'a BTreeSet<T, A, S> {
     type Item = &'a T;
     type IntoIter = Iter<'a, T>;
 
@@ -1272,6 +1545,7 @@ pub struct ExtractIf<
     T,
     F,
     #[unstable(feature = "allocator_api", issue = "32838")] A: Allocator + Clone = Global,
+    #[unstable(feature = "btree_order_statistics", issue = "none")] S: Statistics = NoStatistics,
 > where
     T: 'a,
     F: 'a + FnMut(&T) -> bool,
@@ -1279,11 +1553,11 @@ pub struct ExtractIf<
     pred: F,
     inner: super::map::ExtractIfInner<'a, T, SetValZST>,
     /// The BTreeMap will outlive this IntoIter so we don't care about drop order for `alloc`.
-    alloc: A,
+    alloc: Nodes<A, S>,
 }
 
 #[unstable(feature = "btree_extract_if", issue = "70530")]
-impl<T, F, A: Allocator + Clone> fmt::Debug for ExtractIf<'_, T, F, A>
+impl<T, F, A: Allocator + Clone, S: Statistics> fmt::Debug for ExtractIf<'_, T, F, A, S>
 where
     T: fmt::Debug,
     F: FnMut(&T) -> bool,
@@ -1294,7 +1568,7 @@ fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
 }
 
 #[unstable(feature = "btree_extract_if", issue = "70530")]
-impl<'a, T, F, A: Allocator + Clone> Iterator for ExtractIf<'_, T, F, A>
+impl<'a, T, F, A: Allocator + Clone, S: Statistics> Iterator for ExtractIf<'_, T, F, A, S>
 where
     F: 'a + FnMut(&T) -> bool,
 {
@@ -1312,10 +1586,14 @@ fn size_hint(&self) -> (usize, Option<usize>) {
 }
 
 #[unstable(feature = "btree_extract_if", issue = "70530")]
-impl<T, F, A: Allocator + Clone> FusedIterator for ExtractIf<'_, T, F, A> where F: FnMut(&T) -> bool {}
+impl<T, F, A: Allocator + Clone, S: Statistics> FusedIterator for ExtractIf<'_, T, F, A, S> where
+    F: FnMut(&T) -> bool
+{
+}
 
+#[cfg(not(no_global_oom_handling))]
 #[stable(feature = "rust1", since = "1.0.0")]
-impl<T: Ord, A: Allocator + Clone> Extend<T> for BTreeSet<T, A> {
+impl<T: Ord, A: Allocator + Clone, S: Statistics> Extend<T> for BTreeSet<T, A, S> {
     #[inline]
     fn extend<Iter: IntoIterator<Item = T>>(&mut self, iter: Iter) {
         iter.into_iter().for_each(move |elem| {
@@ -1329,8 +1607,11 @@ fn extend_one(&mut self, elem: T) {
     }
 }
 
+#[cfg(not(no_global_oom_handling))]
 #[stable(feature = "extend_ref", since = "1.2.0")]
-impl<'a, T: 'a + Ord + Copy, A: Allocator + Clone> Extend<&'a T> for BTreeSet<T, A> {
+impl<'a, T: 'a + Ord + Copy, A: Allocator + Clone, S: Statistics> Extend<&'a T>
+    for BTreeSet<T, A, S>
+{
     fn extend<I: IntoIterator<Item = &'a T>>(&mut self, iter: I) {
         self.extend(iter.into_iter().cloned());
     }
@@ -1349,9 +1630,12 @@ fn default() -> BTreeSet<T> {
     }
 }
 
+#[cfg(not(no_global_oom_handling))]
 #[stable(feature = "rust1", since = "1.0.0")]
-impl<T: Ord + Clone, A: Allocator + Clone> Sub<&BTreeSet<T, A>> for &BTreeSet<T, A> {
-    type Output = BTreeSet<T, A>;
+impl<T: Ord + Clone, A: Allocator + Clone, S: Statistics> Sub<&BTreeSet<T, A, S>>
+    for &BTreeSet<T, A, S>
+{
+    type Output = BTreeSet<T, A, S>;
 
     /// Returns the difference of `self` and `rhs` as a new `BTreeSet<T>`.
     ///
@@ -1366,7 +1650,7 @@ impl<T: Ord + Clone, A: Allocator + Clone> Sub<&BTreeSet<T, A>> for &BTreeSet<T,
     /// let result = &a - &b;
     /// assert_eq!(result, BTreeSet::from([1, 2]));
     /// ```
-    fn sub(self, rhs: &BTreeSet<T, A>) -> BTreeSet<T, A> {
+    fn sub(self, rhs: &BTreeSet<T, A, S>) -> BTreeSet<T, A, S> {
         BTreeSet::from_sorted_iter(
             self.difference(rhs).cloned(),
             ManuallyDrop::into_inner(self.map.alloc.clone()),
@@ -1374,9 +1658,12 @@ fn sub(self, rhs: &BTreeSet<T, A>) -> BTreeSet<T, A> {
     }
 }
 
+#[cfg(not(no_global_oom_handling))]
 #[stable(feature = "rust1", since = "1.0.0")]
-impl<T: Ord + Clone, A: Allocator + Clone> BitXor<&BTreeSet<T, A>> for &BTreeSet<T, A> {
-    type Output = BTreeSet<T, A>;
+impl<T: Ord + Clone, A: Allocator + Clone, S: Statistics> BitXor<&BTreeSet<T, A, S>>
+    for &BTreeSet<T, A, S>
+{
+    type Output = BTreeSet<T, A, S>;
 
     /// Returns the symmetric difference of `self` and `rhs` as a new `BTreeSet<T>`.
     ///
@@ -1391,7 +1678,7 @@ impl<T: Ord + Clone, A: Allocator + Clone> BitXor<&BTreeSet<T, A>> for &BTreeSet
     /// let result = &a ^ &b;
     /// assert_eq!(result, BTreeSet::from([1, 4]));
     /// ```
-    fn bitxor(self, rhs: &BTreeSet<T, A>) -> BTreeSet<T, A> {
+    fn bitxor(self, rhs: &BTreeSet<T, A, S>) -> BTreeSet<T, A, S> {
         BTreeSet::from_sorted_iter(
             self.symmetric_difference(rhs).cloned(),
             ManuallyDrop::into_inner(self.map.alloc.clone()),
@@ -1399,9 +1686,12 @@ fn bitxor(self, rhs: &BTreeSet<T, A>) -> BTreeSet<T, A> {
     }
 }
 
+#[cfg(not(no_global_oom_handling))]
 #[stable(feature = "rust1", since = "1.0.0")]
-impl<T: Ord + Clone, A: Allocator + Clone> BitAnd<&BTreeSet<T, A>> for &BTreeSet<T, A> {
-    type Output = BTreeSet<T, A>;
+impl<T: Ord + Clone, A: Allocator + Clone, S: Statistics> BitAnd<&BTreeSet<T, A, S>>
+    for &BTreeSet<T, A, S>
+{
+    type Output = BTreeSet<T, A, S>;
 
     /// Returns the intersection of `self` and `rhs` as a new `BTreeSet<T>`.
     ///
@@ -1416,7 +1706,7 @@ impl<T: Ord + Clone, A: Allocator + Clone> BitAnd<&BTreeSet<T, A>> for &BTreeSet
     /// let result = &a & &b;
     /// assert_eq!(result, BTreeSet::from([2, 3]));
     /// ```
-    fn bitand(self, rhs: &BTreeSet<T, A>) -> BTreeSet<T, A> {
+    fn bitand(self, rhs: &BTreeSet<T, A, S>) -> BTreeSet<T, A, S> {
         BTreeSet::from_sorted_iter(
             self.intersection(rhs).cloned(),
             ManuallyDrop::into_inner(self.map.alloc.clone()),
@@ -1424,9 +1714,12 @@ fn bitand(self, rhs: &BTreeSet<T, A>) -> BTreeSet<T, A> {
     }
 }
 
+#[cfg(not(no_global_oom_handling))]
 #[stable(feature = "rust1", since = "1.0.0")]
-impl<T: Ord + Clone, A: Allocator + Clone> BitOr<&BTreeSet<T, A>> for &BTreeSet<T, A> {
-    type Output = BTreeSet<T, A>;
+impl<T: Ord + Clone, A: Allocator + Clone, S: Statistics> BitOr<&BTreeSet<T, A, S>>
+    for &BTreeSet<T, A, S>
+{
+    type Output = BTreeSet<T, A, S>;
 
     /// Returns the union of `self` and `rhs` as a new `BTreeSet<T>`.
     ///
@@ -1441,7 +1734,7 @@ impl<T: Ord + Clone, A: Allocator + Clone> BitOr<&BTreeSet<T, A>> for &BTreeSet<
     /// let result = &a | &b;
     /// assert_eq!(result, BTreeSet::from([1, 2, 3, 4, 5]));
     /// ```
-    fn bitor(self, rhs: &BTreeSet<T, A>) -> BTreeSet<T, A> {
+    fn bitor(self, rhs: &BTreeSet<T, A, S>) -> BTreeSet<T, A, S> {
         BTreeSet::from_sorted_iter(
             self.union(rhs).cloned(),
             ManuallyDrop::into_inner(self.map.alloc.clone()),
@@ -1450,7 +1743,7 @@ fn bitor(self, rhs: &BTreeSet<T, A>) -> BTreeSet<T, A> {
 }
 
 #[stable(feature = "rust1", since = "1.0.0")]
-impl<T: Debug, A: Allocator + Clone> Debug for BTreeSet<T, A> {
+impl<T: Debug, A: Allocator + Clone, S: Statistics> Debug for BTreeSet<T, A, S> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_set().entries(self.iter()).finish()
     }
@@ -1509,7 +1802,7 @@ fn len(&self) -> usize {
 impl<T> FusedIterator for Iter<'_, T> {}
 
 #[stable(feature = "rust1", since = "1.0.0")]
-impl<T, A: Allocator + Clone> Iterator for IntoIter<T, A> {
+impl<T, A: Allocator + Clone, S: Statistics> Iterator for IntoIter<T, A, S> {
     type Item = T;
 
     fn next(&mut self) -> Option<T> {
@@ -1536,23 +1829,23 @@ fn default() -> Self {
 }
 
 #[stable(feature = "rust1", since = "1.0.0")]
-impl<T, A: Allocator + Clone> DoubleEndedIterator for IntoIter<T, A> {
+impl<T, A: Allocator + Clone, S: Statistics> DoubleEndedIterator for IntoIter<T, A, S> {
     fn next_back(&mut self) -> Option<T> {
         self.iter.next_back().map(|(k, _)| k)
     }
 }
 #[stable(feature = "rust1", since = "1.0.0")]
-impl<T, A: Allocator + Clone> ExactSizeIterator for IntoIter<T, A> {
+impl<T, A: Allocator + Clone, S: Statistics> ExactSizeIterator for IntoIter<T, A, S> {
     fn len(&self) -> usize {
         self.iter.len()
     }
 }
 
 #[stable(feature = "fused", since = "1.26.0")]
-impl<T, A: Allocator + Clone> FusedIterator for IntoIter<T, A> {}
+impl<T, A: Allocator + Clone, S: Statistics> FusedIterator for IntoIter<T, A, S> {}
 
 #[stable(feature = "default_iters", since = "1.70.0")]
-impl<T, A> Default for IntoIter<T, A>
+impl<T, A, S: Statistics> Default for IntoIter<T, A, S>
 where
     A: Allocator + Default + Clone,
 {
@@ -1627,7 +1920,7 @@ fn default() -> Self {
 }
 
 #[stable(feature = "rust1", since = "1.0.0")]
-impl<T, A: Allocator + Clone> Clone for Difference<'_, T, A> {
+impl<T, A: Allocator + Clone, S: Statistics> Clone for Difference<'_, T, A, S> {
     fn clone(&self) -> Self {
         Difference {
             inner: match &self.inner {
@@ -1644,7 +1937,7 @@ fn clone(&self) -> Self {
     }
 }
 #[stable(feature = "rust1", since = "1.0.0")]
-impl<'a, T: Ord, A: Allocator + Clone> Iterator for Difference<'a, T, A> {
+impl<'a, T: Ord, A: Allocator + Clone, S: Statistics> Iterator for Difference<'a, T, A, S> {
     type Item = &'a T;
 
     fn next(&mut self) -> Option<&'a T> {
@@ -1691,7 +1984,7 @@ fn min(mut self) -> Option<&'a T> {
 }
 
 #[stable(feature = "fused", since = "1.26.0")]
-impl<T: Ord, A: Allocator + Clone> FusedIterator for Difference<'_, T, A> {}
+impl<T: Ord, A: Allocator + Clone, S: Statistics> FusedIterator for Difference<'_, T, A, S> {}
 
 #[stable(feature = "rust1", since = "1.0.0")]
 impl<T> Clone for SymmetricDifference<'_, T> {
@@ -1729,7 +2022,7 @@ fn min(mut self) -> Option<&'a T> {
 impl<T: Ord> FusedIterator for SymmetricDifference<'_, T> {}
 
 #[stable(feature = "rust1", since = "1.0.0")]
-impl<T, A: Allocator + Clone> Clone for Intersection<'_, T, A> {
+impl<T, A: Allocator + Clone, S: Statistics> Clone for Intersection<'_, T, A, S> {
     fn clone(&self) -> Self {
         Intersection {
             inner: match &self.inner {
@@ -1745,7 +2038,7 @@ fn clone(&self) -> Self {
     }
 }
 #[stable(feature = "rust1", since = "1.0.0")]
-impl<'a, T: Ord, A: Allocator + Clone> Iterator for Intersection<'a, T, A> {
+impl<'a, T: Ord, A: Allocator + Clone, S: Statistics> Iterator for Intersection<'a, T, A, S> {
     type Item = &'a T;
 
     fn next(&mut self) -> Option<&'a T> {
@@ -1786,7 +2079,7 @@ fn min(mut self) -> Option<&'a T> {
 }
 
 #[stable(feature = "fused", since = "1.26.0")]
-impl<T: Ord, A: Allocator + Clone> FusedIterator for Intersection<'_, T, A> {}
+impl<T: Ord, A: Allocator + Clone, S: Statistics> FusedIterator for Intersection<'_, T, A, S> {}
 
 #[stable(feature = "rust1", since = "1.0.0")]
 impl<T> Clone for Union<'_, T> {
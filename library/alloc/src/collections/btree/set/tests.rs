@@ -521,6 +521,36 @@ fn test_extend_ref() {
     assert!(a.contains(&6));
 }
 
+#[test]
+fn test_fallible_insert_extend_clone() {
+    let mut a = BTreeSet::try_from_iter([3, 1, 2, 1]).unwrap();
+    assert_eq!(a.iter().copied().collect::<Vec<_>>(), [1, 2, 3]);
+
+    assert_eq!(a.try_insert(4), Ok(true));
+    assert_eq!(a.try_insert(4), Ok(false));
+    a.try_extend(0..100).unwrap();
+    assert!(a.iter().copied().eq(0..100));
+
+    let b = a.try_clone().unwrap();
+    assert_eq!(b, a);
+}
+
+#[test]
+fn test_fallible_insert_extend_clone_order_statistics() {
+    let mut a = BTreeSet::with_order_statistics();
+    for i in (0..100).rev() {
+        assert_eq!(a.try_insert(i * 2), Ok(true));
+    }
+    a.try_extend((0..100).map(|i| i * 2 + 1)).unwrap();
+
+    let b = a.try_clone().unwrap();
+    for i in 0..200 {
+        assert_eq!(b.nth(i), Some(&i));
+        assert_eq!(b.rank(&i), i);
+    }
+    assert_eq!(b.range_count(50..150), 100);
+}
+
 #[test]
 fn test_recovery() {
     #[derive(Debug)]
@@ -778,6 +808,32 @@ fn test_first_last() {
     assert_eq!(a.pop_last(), None);
 }
 
+fn test_order_statistics_in<A: Allocator + Clone>(alloc: A) {
+    let mut a = BTreeSet::with_order_statistics_in(alloc);
+    assert_eq!(a.nth(0), None);
+    assert_eq!(a.rank(&1), 0);
+    assert_eq!(a.range_count(..), 0);
+    for i in 0..100 {
+        a.insert(i * 3);
+    }
+    for i in 0..100 {
+        assert_eq!(a.nth(i), Some(&(i * 3)));
+        assert_eq!(a.rank(&(i * 3)), i);
+        assert_eq!(a.rank(&(i * 3 + 1)), i + 1);
+    }
+    assert_eq!(a.nth(100), None);
+    assert_eq!(a.range_count(..), 100);
+    assert_eq!(a.range_count(30..60), 10);
+    assert_eq!(a.range_count((Excluded(&30), Included(&60))), 10);
+    assert_eq!(a.range_count(1000..), 0);
+}
+
+#[test]
+fn test_order_statistics() {
+    test_order_statistics_in(Global);
+    test_order_statistics_in(&Global);
+}
+
 // Unlike the function with the same name in map/tests, returns no values.
 // Which also means it returns different predetermined pseudo-random keys,
 // and the test cases using this function explore slightly different trees.
@@ -853,3 +909,11 @@ fn test_range_panic_2() {
 
     let _invalid_range = set.range((Excluded(&5), Excluded(&5)));
 }
+
+#[should_panic(expected = "range start and end are equal and excluded in BTreeSet")]
+#[test]
+fn test_range_count_panic() {
+    let mut set = BTreeSet::with_order_statistics();
+    set.extend([3, 5, 8]);
+    let _count = set.range_count((Excluded(&5), Excluded(&5)));
+}
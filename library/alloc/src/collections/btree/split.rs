@@ -1,6 +1,5 @@
-use super::node::{ForceResult::*, Root};
+use super::node::{marker, ForceResult::*, Handle, NodeAlloc, NodeRef, Root};
 use super::search::SearchResult::*;
-use core::alloc::Allocator;
 use core::borrow::Borrow;
 
 impl<K, V> Root<K, V> {
@@ -29,21 +28,58 @@ pub fn calc_split_length(
     /// and if the ordering of `Q` corresponds to that of `K`.
     /// If `self` respects all `BTreeMap` tree invariants, then both
     /// `self` and the returned tree will respect those invariants.
-    pub fn split_off<Q: ?Sized + Ord, A: Allocator + Clone>(&mut self, key: &Q, alloc: A) -> Self
+    pub fn split_off<Q: ?Sized + Ord, A: NodeAlloc>(&mut self, key: &Q, alloc: A) -> Self
     where
         K: Borrow<Q>,
     {
+        self.split_off_by(alloc, |node| match node.search_node(key) {
+            // key is going to the right tree
+            Found(kv) => kv.left_edge(),
+            GoDown(edge) => edge,
+        })
+    }
+
+    /// Split off a tree with the key-value pairs right of a path of edges,
+    /// given as the index of the edge taken in each node from the root down
+    /// to a leaf. If `self` respects all `BTreeMap` tree invariants, then
+    /// both `self` and the returned tree will respect those invariants.
+    ///
+    /// # Safety
+    /// Each index in `path` must be that of an edge of the node it is taken
+    /// in, and `path` must reach down to a leaf.
+    pub unsafe fn split_off_at_path<A: NodeAlloc>(
+        &mut self,
+        path: impl IntoIterator<Item = usize>,
+        alloc: A,
+    ) -> Self {
+        let mut path = path.into_iter();
+        self.split_off_by(alloc, |node| {
+            let idx = path.next();
+            debug_assert!(idx.is_some_and(|idx| idx <= node.len()));
+            // SAFETY: the caller passes a valid path down to a leaf.
+            unsafe { Handle::new_edge(node, idx.unwrap_unchecked()) }
+        })
+    }
+
+    /// Split off a tree with the key-value pairs right of the edge that
+    /// `choose_edge` returns on each level, descending from the root.
+    fn split_off_by<A: NodeAlloc>(
+        &mut self,
+        alloc: A,
+        mut choose_edge: impl for<'b> FnMut(
+            NodeRef<marker::Mut<'b>, K, V, marker::LeafOrInternal>,
+        ) -> Handle<
+            NodeRef<marker::Mut<'b>, K, V, marker::LeafOrInternal>,
+            marker::Edge,
+        >,
+    ) -> Self {
         let left_root = self;
         let mut right_root = Root::new_pillar(left_root.height(), alloc.clone());
         let mut left_node = left_root.borrow_mut();
         let mut right_node = right_root.borrow_mut();
 
         loop {
-            let mut split_edge = match left_node.search_node(key) {
-                // key is going to the right tree
-                Found(kv) => kv.left_edge(),
-                GoDown(edge) => edge,
-            };
+            let mut split_edge = choose_edge(left_node);
 
             split_edge.move_suffix(&mut right_node);
 
@@ -52,7 +88,12 @@ pub fn split_off<Q: ?Sized + Ord, A: Allocator + Clone>(&mut self, key: &Q, allo
                     left_node = edge.descend();
                     right_node = node.first_edge().descend();
                 }
-                (Leaf(_), Leaf(_)) => break,
+                (Leaf(edge), Leaf(mut node)) => {
+                    // Only the nodes on the path we took down have changed.
+                    edge.into_node().fix_subtree_lens_above(&alloc);
+                    node.fix_subtree_lens_above(&alloc);
+                    break;
+                }
                 _ => unreachable!(),
             }
         }
@@ -63,7 +104,7 @@ pub fn split_off<Q: ?Sized + Ord, A: Allocator + Clone>(&mut self, key: &Q, allo
     }
 
     /// Creates a tree consisting of empty nodes.
-    fn new_pillar<A: Allocator + Clone>(height: usize, alloc: A) -> Self {
+    fn new_pillar<A: NodeAlloc>(height: usize, alloc: A) -> Self {
         let mut root = Root::new(alloc.clone());
         for _ in 0..height {
             root.push_internal_level(alloc.clone());
@@ -1609,13 +1609,17 @@ pub fn as_cursor(&self) -> Cursor<'_, T, A> {
 
 // Now the list editing operations
 
-impl<'a, T> CursorMut<'a, T> {
+impl<'a, T, A: Allocator> CursorMut<'a, T, A> {
     /// Inserts the elements from the given `LinkedList` after the current one.
     ///
     /// If the cursor is pointing at the "ghost" non-element then the new elements are
     /// inserted at the start of the `LinkedList`.
+    ///
+    /// This operation should compute in *O*(1) time, because the nodes of `list`
+    /// are taken over as they are. The allocator of the list must thus be able
+    /// to deallocate memory allocated by the allocator of `list`.
     #[unstable(feature = "linked_list_cursors", issue = "58533")]
-    pub fn splice_after(&mut self, list: LinkedList<T>) {
+    pub fn splice_after(&mut self, list: LinkedList<T, A>) {
         unsafe {
             let (splice_head, splice_tail, splice_len) = match list.detach_all_nodes() {
                 Some(parts) => parts,
@@ -1637,8 +1641,12 @@ pub fn splice_after(&mut self, list: LinkedList<T>) {
     ///
     /// If the cursor is pointing at the "ghost" non-element then the new elements are
     /// inserted at the end of the `LinkedList`.
+    ///
+    /// This operation should compute in *O*(1) time, because the nodes of `list`
+    /// are taken over as they are. The allocator of the list must thus be able
+    /// to deallocate memory allocated by the allocator of `list`.
     #[unstable(feature = "linked_list_cursors", issue = "58533")]
-    pub fn splice_before(&mut self, list: LinkedList<T>) {
+    pub fn splice_before(&mut self, list: LinkedList<T, A>) {
         unsafe {
             let (splice_head, splice_tail, splice_len) = match list.detach_all_nodes() {
                 Some(parts) => parts,
@@ -1705,7 +1713,7 @@ pub fn remove_current(&mut self) -> Option<T> {
         unsafe {
             self.current = unlinked_node.as_ref().next;
             self.list.unlink_node(unlinked_node);
-            let unlinked_node = Box::from_raw(unlinked_node.as_ptr());
+            let unlinked_node = Box::from_raw_in(unlinked_node.as_ptr(), &self.list.alloc);
             Some(unlinked_node.element)
         }
     }
@@ -1,7 +1,9 @@
 use super::*;
+use crate::alloc::{AllocError, Layout};
 use crate::testing::crash_test::{CrashTestDummy, Panic};
 use crate::vec::Vec;
 
+use std::cell::Cell;
 use std::panic::{catch_unwind, AssertUnwindSafe};
 use std::thread;
 
@@ -55,7 +57,7 @@ fn list_from<T: Clone>(v: &[T]) -> LinkedList<T> {
     v.iter().cloned().collect()
 }
 
-pub fn check_links<T>(list: &LinkedList<T>) {
+pub fn check_links<T, A: Allocator>(list: &LinkedList<T, A>) {
     unsafe {
         let mut len = 0;
         let mut last_ptr: Option<&Node<T>> = None;
@@ -715,6 +717,45 @@ fn test_cursor_mut_insert() {
     assert_eq!(m.iter().cloned().collect::<Vec<_>>(), &[200, 201, 202, 203, 1, 100, 101]);
 }
 
+// Counts the allocations it has not deallocated yet.
+struct Counting(Cell<usize>);
+
+unsafe impl Allocator for &Counting {
+    fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        self.0.set(self.0.get() + 1);
+        Global.allocate(layout)
+    }
+
+    unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+        self.0.set(self.0.get() - 1);
+        unsafe { Global.deallocate(ptr, layout) }
+    }
+}
+
+#[test]
+fn test_cursor_mut_splice_in() {
+    let a = Counting(Cell::new(0));
+    let mut m = LinkedList::new_in(&a);
+    m.extend([1, 2, 3]);
+    let mut p = LinkedList::new_in(&a);
+    p.extend([10, 11]);
+    let mut q = LinkedList::new_in(&a);
+    q.extend([20, 21]);
+    let mut cursor = m.cursor_front_mut();
+    cursor.move_next();
+    cursor.splice_after(p);
+    assert_eq!(cursor.current(), Some(&mut 2));
+    cursor.splice_before(q);
+    assert_eq!(cursor.index(), Some(3));
+    assert_eq!(cursor.remove_current(), Some(2));
+    check_links(&m);
+    assert!(m.iter().eq(&[1, 20, 21, 10, 11, 3]));
+    // The nodes of the spliced lists are taken over, not reallocated.
+    assert_eq!(a.0.get(), 6);
+    drop(m);
+    assert_eq!(a.0.get(), 0);
+}
+
 #[test]
 fn test_cursor_push_front_back() {
     let mut ll: LinkedList<u32> = LinkedList::new();
@@ -4,14 +4,11 @@
 
 #[cfg(not(no_global_oom_handling))]
 pub mod binary_heap;
-#[cfg(not(no_global_oom_handling))]
 mod btree;
 #[cfg(not(no_global_oom_handling))]
 pub mod linked_list;
-#[cfg(not(no_global_oom_handling))]
 pub mod vec_deque;
 
-#[cfg(not(no_global_oom_handling))]
 #[stable(feature = "rust1", since = "1.0.0")]
 pub mod btree_map {
     //! An ordered map based on a B-Tree.
@@ -19,7 +16,6 @@ pub mod btree_map {
     pub use super::btree::map::*;
 }
 
-#[cfg(not(no_global_oom_handling))]
 #[stable(feature = "rust1", since = "1.0.0")]
 pub mod btree_set {
     //! An ordered set based on a B-Tree.
@@ -27,17 +23,23 @@ pub mod btree_set {
     pub use super::btree::set::*;
 }
 
+#[cfg(all(not(no_global_oom_handling), target_has_atomic = "ptr"))]
+#[unstable(feature = "persistent_btree_map", issue = "none")]
+pub mod persistent_btree_map {
+    //! An ordered map based on a B-Tree, whose clones share their nodes.
+    #[unstable(feature = "persistent_btree_map", issue = "none")]
+    pub use super::btree::persistent::*;
+}
+
 #[cfg(not(no_global_oom_handling))]
 #[stable(feature = "rust1", since = "1.0.0")]
 #[doc(no_inline)]
 pub use binary_heap::BinaryHeap;
 
-#[cfg(not(no_global_oom_handling))]
 #[stable(feature = "rust1", since = "1.0.0")]
 #[doc(no_inline)]
 pub use btree_map::BTreeMap;
 
-#[cfg(not(no_global_oom_handling))]
 #[stable(feature = "rust1", since = "1.0.0")]
 #[doc(no_inline)]
 pub use btree_set::BTreeSet;
@@ -47,7 +49,6 @@ pub mod btree_set {
 #[doc(no_inline)]
 pub use linked_list::LinkedList;
 
-#[cfg(not(no_global_oom_handling))]
 #[stable(feature = "rust1", since = "1.0.0")]
 #[doc(no_inline)]
 pub use vec_deque::VecDeque;
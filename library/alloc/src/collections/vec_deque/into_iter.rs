@@ -12,7 +12,7 @@
 /// (provided by the [`IntoIterator`] trait). See its documentation for more.
 ///
 /// [`into_iter`]: VecDeque::into_iter
-#[derive(Clone)]
+#[cfg_attr(not(no_global_oom_handling), derive(Clone))]
 #[stable(feature = "rust1", since = "1.0.0")]
 pub struct IntoIter<
     T,
@@ -10,6 +10,7 @@
 use core::cmp::{self, Ordering};
 use core::fmt;
 use core::hash::{Hash, Hasher};
+#[cfg(not(no_global_oom_handling))]
 use core::iter::{repeat_n, repeat_with, ByRefSized};
 use core::mem::{ManuallyDrop, SizedTypeProperties};
 use core::ops::{Index, IndexMut, Range, RangeBounds};
@@ -31,6 +32,11 @@
 #[macro_use]
 mod macros;
 
+#[unstable(feature = "vec_deque_cursors", issue = "none")]
+pub use self::cursor::{Cursor, CursorMut};
+
+mod cursor;
+
 #[stable(feature = "drain", since = "1.6.0")]
 pub use self::drain::Drain;
 
@@ -51,12 +57,16 @@
 
 mod iter;
 
+#[cfg(not(no_global_oom_handling))]
 use self::spec_extend::SpecExtend;
 
+#[cfg(not(no_global_oom_handling))]
 mod spec_extend;
 
+#[cfg(not(no_global_oom_handling))]
 use self::spec_from_iter::SpecFromIter;
 
+#[cfg(not(no_global_oom_handling))]
 mod spec_from_iter;
 
 #[cfg(test)]
@@ -105,6 +115,7 @@ pub struct VecDeque<
     buf: RawVec<T, A>,
 }
 
+#[cfg(not(no_global_oom_handling))]
 #[stable(feature = "rust1", since = "1.0.0")]
 impl<T: Clone, A: Allocator + Clone> Clone for VecDeque<T, A> {
     fn clone(&self) -> Self {
@@ -392,6 +403,7 @@ unsafe fn wrap_copy(&mut self, src: usize, dst: usize, len: usize) {
 
     /// Copies all values from `src` to `dst`, wrapping around if needed.
     /// Assumes capacity is sufficient.
+    #[cfg(not(no_global_oom_handling))]
     #[inline]
     unsafe fn copy_slice(&mut self, dst: usize, src: &[T]) {
         debug_assert!(src.len() <= self.capacity());
@@ -415,6 +427,7 @@ unsafe fn copy_slice(&mut self, dst: usize, src: &[T]) {
     ///
     /// Assumes no wrapping around happens.
     /// Assumes capacity is sufficient.
+    #[cfg(not(no_global_oom_handling))]
     #[inline]
     unsafe fn write_iter(
         &mut self,
@@ -436,6 +449,7 @@ unsafe fn write_iter(
     ///
     /// Assumes that `iter` yields at most `len` items.
     /// Assumes capacity is sufficient.
+    #[cfg(not(no_global_oom_handling))]
     unsafe fn write_iter_wrapping(
         &mut self,
         dst: usize,
@@ -553,6 +567,7 @@ pub const fn new() -> VecDeque<T> {
     ///
     /// let deque: VecDeque<u32> = VecDeque::with_capacity(10);
     /// ```
+    #[cfg(not(no_global_oom_handling))]
     #[inline]
     #[stable(feature = "rust1", since = "1.0.0")]
     #[must_use]
@@ -583,6 +598,36 @@ pub fn with_capacity(capacity: usize) -> VecDeque<T> {
     pub fn try_with_capacity(capacity: usize) -> Result<VecDeque<T>, TryReserveError> {
         Ok(VecDeque { head: 0, len: 0, buf: RawVec::try_with_capacity_in(capacity, Global)? })
     }
+
+    /// Creates a deque from the elements of an iterator, returning an error
+    /// instead of panicking or aborting if the allocator reports a failure.
+    ///
+    /// This is the fallible counterpart to [`collect`]ing into a `VecDeque`.
+    ///
+    /// [`collect`]: Iterator::collect
+    ///
+    /// # Errors
+    ///
+    /// If the capacity overflows, or the allocator reports a failure, then an error
+    /// is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(fallible_allocation)]
+    /// use std::collections::VecDeque;
+    ///
+    /// let deque = VecDeque::try_from_iter(1..=3).unwrap();
+    /// assert_eq!(deque, [1, 2, 3]);
+    /// ```
+    #[unstable(feature = "fallible_allocation", issue = "none")]
+    pub fn try_from_iter<I: IntoIterator<Item = T>>(
+        iter: I,
+    ) -> Result<VecDeque<T>, TryReserveError> {
+        let mut deque = VecDeque::new();
+        deque.try_extend(iter)?;
+        Ok(deque)
+    }
 }
 
 impl<T, A: Allocator> VecDeque<T, A> {
@@ -610,6 +655,7 @@ pub const fn new_in(alloc: A) -> VecDeque<T, A> {
     ///
     /// let deque: VecDeque<u32> = VecDeque::with_capacity(10);
     /// ```
+    #[cfg(not(no_global_oom_handling))]
     #[unstable(feature = "allocator_api", issue = "32838")]
     pub fn with_capacity_in(capacity: usize, alloc: A) -> VecDeque<T, A> {
         VecDeque { head: 0, len: 0, buf: RawVec::with_capacity_in(capacity, alloc) }
@@ -774,6 +820,7 @@ pub fn capacity(&self) -> usize {
     /// ```
     ///
     /// [`reserve`]: VecDeque::reserve
+    #[cfg(not(no_global_oom_handling))]
     #[stable(feature = "rust1", since = "1.0.0")]
     pub fn reserve_exact(&mut self, additional: usize) {
         let new_cap = self.len.checked_add(additional).expect("capacity overflow");
@@ -803,6 +850,7 @@ pub fn reserve_exact(&mut self, additional: usize) {
     /// buf.reserve(10);
     /// assert!(buf.capacity() >= 11);
     /// ```
+    #[cfg(not(no_global_oom_handling))]
     #[stable(feature = "rust1", since = "1.0.0")]
     pub fn reserve(&mut self, additional: usize) {
         let new_cap = self.len.checked_add(additional).expect("capacity overflow");
@@ -934,6 +982,7 @@ pub fn try_reserve(&mut self, additional: usize) -> Result<(), TryReserveError>
     /// buf.shrink_to_fit();
     /// assert!(buf.capacity() >= 4);
     /// ```
+    #[cfg(not(no_global_oom_handling))]
     #[stable(feature = "deque_extras_15", since = "1.5.0")]
     pub fn shrink_to_fit(&mut self) {
         self.shrink_to(0);
@@ -959,6 +1008,7 @@ pub fn shrink_to_fit(&mut self) {
     /// buf.shrink_to(0);
     /// assert!(buf.capacity() >= 4);
     /// ```
+    #[cfg(not(no_global_oom_handling))]
     #[stable(feature = "shrink_to", since = "1.56.0")]
     pub fn shrink_to(&mut self, min_capacity: usize) {
         let target_cap = min_capacity.max(self.len);
@@ -1374,6 +1424,65 @@ pub fn range_mut<R>(&mut self, range: R) -> IterMut<'_, T>
         IterMut::new(a.iter_mut(), b.iter_mut())
     }
 
+    /// Provides a cursor at the front element.
+    ///
+    /// The cursor is pointing to the "ghost" non-element if the deque is empty.
+    #[inline]
+    #[must_use]
+    #[unstable(feature = "vec_deque_cursors", issue = "none")]
+    pub fn cursor_front(&self) -> Cursor<'_, T, A> {
+        Cursor::new(0, self)
+    }
+
+    /// Provides a cursor with editing operations at the front element.
+    ///
+    /// The cursor is pointing to the "ghost" non-element if the deque is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(vec_deque_cursors)]
+    ///
+    /// use std::collections::VecDeque;
+    ///
+    /// let mut buf: VecDeque<_> = "hello world".chars().collect();
+    /// let mut cursor = buf.cursor_front_mut();
+    /// while cursor.current() != Some(&mut ' ') {
+    ///     cursor.move_next();
+    /// }
+    /// cursor.remove_current();
+    /// cursor.splice_before(", ".chars().collect());
+    /// let tail = cursor.split_after();
+    /// assert_eq!(buf.iter().collect::<String>(), "hello, w");
+    /// assert_eq!(tail.iter().collect::<String>(), "orld");
+    /// ```
+    #[inline]
+    #[must_use]
+    #[unstable(feature = "vec_deque_cursors", issue = "none")]
+    pub fn cursor_front_mut(&mut self) -> CursorMut<'_, T, A> {
+        CursorMut::new(0, self)
+    }
+
+    /// Provides a cursor at the back element.
+    ///
+    /// The cursor is pointing to the "ghost" non-element if the deque is empty.
+    #[inline]
+    #[must_use]
+    #[unstable(feature = "vec_deque_cursors", issue = "none")]
+    pub fn cursor_back(&self) -> Cursor<'_, T, A> {
+        Cursor::new(self.len.saturating_sub(1), self)
+    }
+
+    /// Provides a cursor with editing operations at the back element.
+    ///
+    /// The cursor is pointing to the "ghost" non-element if the deque is empty.
+    #[inline]
+    #[must_use]
+    #[unstable(feature = "vec_deque_cursors", issue = "none")]
+    pub fn cursor_back_mut(&mut self) -> CursorMut<'_, T, A> {
+        CursorMut::new(self.len.saturating_sub(1), self)
+    }
+
     /// Removes the specified range from the deque in bulk, returning all
     /// removed elements as an iterator. If the iterator is dropped before
     /// being fully consumed, it drops the remaining removed elements.
@@ -1660,6 +1769,7 @@ pub fn pop_back(&mut self) -> Option<T> {
     /// d.push_front(2);
     /// assert_eq!(d.front(), Some(&2));
     /// ```
+    #[cfg(not(no_global_oom_handling))]
     #[stable(feature = "rust1", since = "1.0.0")]
     pub fn push_front(&mut self, value: T) {
         if self.is_full() {
@@ -1674,6 +1784,45 @@ pub fn push_front(&mut self, value: T) {
         }
     }
 
+    /// Prepends an element to the deque, or returns an error if the capacity
+    /// cannot be increased to make room for it.
+    ///
+    /// This is the fallible counterpart to [`push_front`]. If an error is
+    /// returned the deque is left unchanged and `value` is dropped.
+    ///
+    /// [`push_front`]: VecDeque::push_front
+    ///
+    /// # Errors
+    ///
+    /// If the capacity overflows, or the allocator reports a failure, then an error
+    /// is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(fallible_allocation)]
+    /// use std::collections::VecDeque;
+    ///
+    /// let mut d = VecDeque::new();
+    /// d.try_push_front(1).unwrap();
+    /// d.try_push_front(2).unwrap();
+    /// assert_eq!(d.front(), Some(&2));
+    /// ```
+    #[unstable(feature = "fallible_allocation", issue = "none")]
+    pub fn try_push_front(&mut self, value: T) -> Result<(), TryReserveError> {
+        if self.is_full() {
+            self.try_reserve(1)?;
+        }
+
+        self.head = self.wrap_sub(self.head, 1);
+        self.len += 1;
+
+        unsafe {
+            self.buffer_write(self.head, value);
+        }
+        Ok(())
+    }
+
     /// Appends an element to the back of the deque.
     ///
     /// # Examples
@@ -1686,6 +1835,7 @@ pub fn push_front(&mut self, value: T) {
     /// buf.push_back(3);
     /// assert_eq!(3, *buf.back().unwrap());
     /// ```
+    #[cfg(not(no_global_oom_handling))]
     #[stable(feature = "rust1", since = "1.0.0")]
     #[rustc_confusables("push", "put", "append")]
     pub fn push_back(&mut self, value: T) {
@@ -1697,6 +1847,79 @@ pub fn push_back(&mut self, value: T) {
         self.len += 1;
     }
 
+    /// Appends an element to the back of the deque, or returns an error if the
+    /// capacity cannot be increased to make room for it.
+    ///
+    /// This is the fallible counterpart to [`push_back`]. If an error is
+    /// returned the deque is left unchanged and `value` is dropped.
+    ///
+    /// [`push_back`]: VecDeque::push_back
+    ///
+    /// # Errors
+    ///
+    /// If the capacity overflows, or the allocator reports a failure, then an error
+    /// is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(fallible_allocation)]
+    /// use std::collections::VecDeque;
+    ///
+    /// let mut buf = VecDeque::new();
+    /// buf.try_push_back(1).unwrap();
+    /// buf.try_push_back(3).unwrap();
+    /// assert_eq!(3, *buf.back().unwrap());
+    /// ```
+    #[unstable(feature = "fallible_allocation", issue = "none")]
+    pub fn try_push_back(&mut self, value: T) -> Result<(), TryReserveError> {
+        if self.is_full() {
+            self.try_reserve(1)?;
+        }
+
+        unsafe { self.buffer_write(self.to_physical_idx(self.len), value) }
+        self.len += 1;
+        Ok(())
+    }
+
+    /// Appends the elements of an iterator to the back of the deque, returning
+    /// an error instead of panicking or aborting if the capacity cannot be
+    /// increased to make room for them.
+    ///
+    /// This is the fallible counterpart to [`extend`]. Capacity for the iterator's
+    /// lower size bound is reserved up front.
+    ///
+    /// [`extend`]: Extend::extend
+    ///
+    /// # Errors
+    ///
+    /// If the capacity overflows, or the allocator reports a failure, then an error
+    /// is returned. The elements appended before the failure remain in the deque.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(fallible_allocation)]
+    /// use std::collections::VecDeque;
+    ///
+    /// let mut buf = VecDeque::from([1]);
+    /// buf.try_extend([2, 3, 4]).unwrap();
+    /// assert_eq!(buf, [1, 2, 3, 4]);
+    /// ```
+    #[unstable(feature = "fallible_allocation", issue = "none")]
+    pub fn try_extend<I: IntoIterator<Item = T>>(
+        &mut self,
+        iter: I,
+    ) -> Result<(), TryReserveError> {
+        let iter = iter.into_iter();
+        let (lower, _) = iter.size_hint();
+        self.try_reserve(lower)?;
+        for element in iter {
+            self.try_push_back(element)?;
+        }
+        Ok(())
+    }
+
     #[inline]
     fn is_contiguous(&self) -> bool {
         // Do the calculation like this to avoid overflowing if len + head > usize::MAX
@@ -1796,6 +2019,7 @@ pub fn swap_remove_back(&mut self, index: usize) -> Option<T> {
     /// vec_deque.insert(1, 'd');
     /// assert_eq!(vec_deque, &['a', 'd', 'b', 'c']);
     /// ```
+    #[cfg(not(no_global_oom_handling))]
     #[stable(feature = "deque_extras_15", since = "1.5.0")]
     pub fn insert(&mut self, index: usize, value: T) {
         assert!(index <= self.len(), "index out of bounds");
@@ -1803,6 +2027,54 @@ pub fn insert(&mut self, index: usize, value: T) {
             self.grow();
         }
 
+        unsafe { self.insert_within_capacity(index, value) }
+    }
+
+    /// Inserts an element at `index` within the deque, shifting all elements
+    /// with indices greater than or equal to `index` towards the back, or
+    /// returns an error if the capacity cannot be increased to make room for it.
+    ///
+    /// This is the fallible counterpart to [`insert`]. If an error is returned
+    /// the deque is left unchanged and `value` is dropped.
+    ///
+    /// [`insert`]: VecDeque::insert
+    ///
+    /// # Errors
+    ///
+    /// If the capacity overflows, or the allocator reports a failure, then an error
+    /// is returned.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is greater than deque's length
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(fallible_allocation)]
+    /// use std::collections::VecDeque;
+    ///
+    /// let mut vec_deque = VecDeque::from(['a', 'b', 'c']);
+    /// vec_deque.try_insert(1, 'd').unwrap();
+    /// assert_eq!(vec_deque, &['a', 'd', 'b', 'c']);
+    /// ```
+    #[unstable(feature = "fallible_allocation", issue = "none")]
+    pub fn try_insert(&mut self, index: usize, value: T) -> Result<(), TryReserveError> {
+        assert!(index <= self.len(), "index out of bounds");
+        if self.is_full() {
+            self.try_reserve(1)?;
+        }
+
+        unsafe { self.insert_within_capacity(index, value) };
+        Ok(())
+    }
+
+    /// Inserts `value` at `index`, shifting whichever side of the deque is shorter.
+    ///
+    /// # Safety
+    ///
+    /// `index` must be at most `self.len()`, and the deque must not be full.
+    unsafe fn insert_within_capacity(&mut self, index: usize, value: T) {
         let k = self.len - index;
         if k < index {
             // `index + 1` can't overflow, because if index was usize::MAX, then either the
@@ -1897,6 +2169,7 @@ pub fn remove(&mut self, index: usize) -> Option<T> {
     /// assert_eq!(buf, [1]);
     /// assert_eq!(buf2, [2, 3]);
     /// ```
+    #[cfg(not(no_global_oom_handling))]
     #[inline]
     #[must_use = "use `.truncate()` if you don't need the other half"]
     #[stable(feature = "split_off", since = "1.4.0")]
@@ -1964,6 +2237,7 @@ pub fn split_off(&mut self, at: usize) -> Self
     /// assert_eq!(buf, [1, 2, 3, 4]);
     /// assert_eq!(buf2, []);
     /// ```
+    #[cfg(not(no_global_oom_handling))]
     #[inline]
     #[stable(feature = "append", since = "1.4.0")]
     pub fn append(&mut self, other: &mut Self) {
@@ -1989,6 +2263,41 @@ pub fn append(&mut self, other: &mut Self) {
         other.head = 0;
     }
 
+    /// Moves all the elements of `other` into `self` at `index`, leaving `other`
+    /// empty. Whichever end is closer to `index` will be moved to make room.
+    #[cfg(not(no_global_oom_handling))]
+    fn insert_deque(&mut self, index: usize, other: &mut Self) {
+        debug_assert!(index <= self.len);
+        if T::IS_ZST {
+            self.len = self.len.checked_add(other.len).expect("capacity overflow");
+            other.len = 0;
+            other.head = 0;
+            return;
+        }
+
+        let count = other.len;
+        self.reserve(count);
+        let k = self.len - index;
+        unsafe {
+            // The wrap_copy() calls are safe for the same reason as the one in
+            // `insert()`: the capacity fits both the moved elements and the gap.
+            if k < index {
+                self.wrap_copy(self.to_physical_idx(index), self.to_physical_idx(index + count), k);
+            } else {
+                let old_head = self.head;
+                self.head = self.wrap_sub(self.head, count);
+                self.wrap_copy(old_head, self.head, index);
+            }
+            let (left, right) = other.as_slices();
+            self.copy_slice(self.to_physical_idx(index), left);
+            self.copy_slice(self.to_physical_idx(index + left.len()), right);
+        }
+        self.len += count;
+        // Now that we own its values, forget everything in `other`.
+        other.len = 0;
+        other.head = 0;
+    }
+
     /// Retains only the elements specified by the predicate.
     ///
     /// In other words, remove all elements `e` for which `f(&e)` returns false.
@@ -2087,6 +2396,7 @@ pub fn retain_mut<F>(&mut self, mut f: F)
     // Double the buffer size. This method is inline(never), so we expect it to only
     // be called in cold paths.
     // This may panic or abort
+    #[cfg(not(no_global_oom_handling))]
     #[inline(never)]
     fn grow(&mut self) {
         // Extend or possibly remove this assertion when valid use-cases for growing the
@@ -2125,6 +2435,7 @@ fn grow(&mut self) {
     /// buf.resize_with(5, || { state += 1; state });
     /// assert_eq!(buf, [5, 10, 101, 102, 103]);
     /// ```
+    #[cfg(not(no_global_oom_handling))]
     #[stable(feature = "vec_resize_with", since = "1.33.0")]
     pub fn resize_with(&mut self, new_len: usize, generator: impl FnMut() -> T) {
         let len = self.len;
@@ -2671,6 +2982,7 @@ impl<T: Clone, A: Allocator> VecDeque<T, A> {
     /// buf.resize(5, 20);
     /// assert_eq!(buf, [5, 10, 20, 20, 20]);
     /// ```
+    #[cfg(not(no_global_oom_handling))]
     #[stable(feature = "deque_extras", since = "1.16.0")]
     pub fn resize(&mut self, new_len: usize, value: T) {
         if new_len > self.len() {
@@ -2680,6 +2992,38 @@ pub fn resize(&mut self, new_len: usize, value: T) {
             self.truncate(new_len);
         }
     }
+
+    /// Returns a copy of the deque, or an error if the allocator cannot provide
+    /// the memory for it.
+    ///
+    /// This is the fallible counterpart to [`clone`].
+    ///
+    /// [`clone`]: Clone::clone
+    ///
+    /// # Errors
+    ///
+    /// If the allocator reports a failure, then an error is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(fallible_allocation)]
+    /// use std::collections::VecDeque;
+    ///
+    /// let buf = VecDeque::from([5, 10, 15]);
+    /// let copy = buf.try_clone().unwrap();
+    /// assert_eq!(buf, copy);
+    /// ```
+    #[unstable(feature = "fallible_allocation", issue = "none")]
+    pub fn try_clone(&self) -> Result<Self, TryReserveError>
+    where
+        A: Clone,
+    {
+        let mut deq = Self::new_in(self.allocator().clone());
+        deq.try_reserve_exact(self.len())?;
+        deq.try_extend(self.iter().cloned())?;
+        Ok(deq)
+    }
 }
 
 /// Returns the index in the underlying buffer for a given logical element index.
@@ -2789,6 +3133,7 @@ fn index_mut(&mut self, index: usize) -> &mut T {
     }
 }
 
+#[cfg(not(no_global_oom_handling))]
 #[stable(feature = "rust1", since = "1.0.0")]
 impl<T> FromIterator<T> for VecDeque<T> {
     fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> VecDeque<T> {
@@ -2828,6 +3173,7 @@ fn into_iter(self) -> IterMut<'a, T> {
     }
 }
 
+#[cfg(not(no_global_oom_handling))]
 #[stable(feature = "rust1", since = "1.0.0")]
 impl<T, A: Allocator> Extend<T> for VecDeque<T, A> {
     fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
@@ -2845,6 +3191,7 @@ fn extend_reserve(&mut self, additional: usize) {
     }
 }
 
+#[cfg(not(no_global_oom_handling))]
 #[stable(feature = "extend_ref", since = "1.2.0")]
 impl<'a, T: 'a + Copy, A: Allocator> Extend<&'a T> for VecDeque<T, A> {
     fn extend<I: IntoIterator<Item = &'a T>>(&mut self, iter: I) {
@@ -2935,6 +3282,7 @@ fn from(mut other: VecDeque<T, A>) -> Self {
     }
 }
 
+#[cfg(not(no_global_oom_handling))]
 #[stable(feature = "std_collections_from_array", since = "1.56.0")]
 impl<T, const N: usize> From<[T; N]> for VecDeque<T> {
     /// Converts a `[T; N]` into a `VecDeque<T>`.
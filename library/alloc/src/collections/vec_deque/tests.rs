@@ -1172,3 +1172,185 @@ fn hash_code(value: impl Hash) -> u64 {
     assert_eq!(vda, vdb);
     assert_eq!(hash_code(vda), hash_code(vdb));
 }
+
+#[test]
+fn test_cursor_move_peek() {
+    let mut m: VecDeque<u32> = VecDeque::new();
+    m.extend(&[1, 2, 3, 4, 5, 6]);
+    let mut cursor = m.cursor_front();
+    assert_eq!(cursor.current(), Some(&1));
+    assert_eq!(cursor.peek_next(), Some(&2));
+    assert_eq!(cursor.peek_prev(), None);
+    assert_eq!(cursor.index(), Some(0));
+    cursor.move_prev();
+    assert_eq!(cursor.current(), None);
+    assert_eq!(cursor.peek_next(), Some(&1));
+    assert_eq!(cursor.peek_prev(), Some(&6));
+    assert_eq!(cursor.index(), None);
+    cursor.move_next();
+    cursor.move_next();
+    assert_eq!(cursor.current(), Some(&2));
+    assert_eq!(cursor.peek_next(), Some(&3));
+    assert_eq!(cursor.peek_prev(), Some(&1));
+    assert_eq!(cursor.index(), Some(1));
+
+    let mut cursor = m.cursor_back();
+    assert_eq!(cursor.current(), Some(&6));
+    assert_eq!(cursor.peek_next(), None);
+    assert_eq!(cursor.peek_prev(), Some(&5));
+    assert_eq!(cursor.index(), Some(5));
+    cursor.move_next();
+    assert_eq!(cursor.current(), None);
+    assert_eq!(cursor.peek_next(), Some(&1));
+    assert_eq!(cursor.peek_prev(), Some(&6));
+    assert_eq!(cursor.index(), None);
+    cursor.move_prev();
+    cursor.move_prev();
+    assert_eq!(cursor.current(), Some(&5));
+    assert_eq!(cursor.peek_next(), Some(&6));
+    assert_eq!(cursor.peek_prev(), Some(&4));
+    assert_eq!(cursor.index(), Some(4));
+
+    let mut cursor = m.cursor_front_mut();
+    cursor.move_prev();
+    assert_eq!(cursor.current(), None);
+    assert_eq!(cursor.peek_next(), Some(&mut 1));
+    assert_eq!(cursor.peek_prev(), Some(&mut 6));
+    cursor.move_next();
+    cursor.move_next();
+    *cursor.current().unwrap() = 20;
+    let mut cursor2 = cursor.as_cursor();
+    assert_eq!(cursor2.current(), Some(&20));
+    assert_eq!(cursor2.index(), Some(1));
+    cursor2.move_next();
+    assert_eq!(cursor2.current(), Some(&3));
+    assert_eq!(cursor2.index(), Some(2));
+
+    let mut empty: VecDeque<u32> = VecDeque::new();
+    let mut cursor = empty.cursor_back_mut();
+    assert_eq!(cursor.index(), None);
+    assert_eq!(cursor.peek_next(), None);
+    assert_eq!(cursor.peek_prev(), None);
+    cursor.move_next();
+    cursor.move_prev();
+    assert_eq!(cursor.current(), None);
+}
+
+#[test]
+fn test_cursor_mut_insert() {
+    let mut m: VecDeque<u32> = VecDeque::new();
+    m.extend(&[1, 2, 3, 4, 5, 6]);
+    let mut cursor = m.cursor_front_mut();
+    cursor.insert_before(7);
+    cursor.insert_after(8);
+    assert_eq!(m, [7, 1, 8, 2, 3, 4, 5, 6]);
+    let mut cursor = m.cursor_front_mut();
+    cursor.move_prev();
+    cursor.insert_before(9);
+    cursor.insert_after(10);
+    assert_eq!(cursor.index(), None);
+    assert_eq!(m, [10, 7, 1, 8, 2, 3, 4, 5, 6, 9]);
+    let mut cursor = m.cursor_front_mut();
+    cursor.move_prev();
+    assert_eq!(cursor.remove_current(), None);
+    cursor.move_next();
+    cursor.move_next();
+    assert_eq!(cursor.remove_current(), Some(7));
+    cursor.move_prev();
+    cursor.move_prev();
+    cursor.move_prev();
+    assert_eq!(cursor.remove_current(), Some(9));
+    cursor.move_next();
+    assert_eq!(cursor.remove_current(), Some(10));
+    assert_eq!(m, [1, 8, 2, 3, 4, 5, 6]);
+    let mut cursor = m.cursor_front_mut();
+    cursor.splice_after(VecDeque::from([100, 101, 102, 103]));
+    cursor.splice_before(VecDeque::from([200, 201, 202, 203]));
+    assert_eq!(cursor.current(), Some(&mut 1));
+    assert_eq!(m, [200, 201, 202, 203, 1, 100, 101, 102, 103, 8, 2, 3, 4, 5, 6]);
+    let mut cursor = m.cursor_front_mut();
+    cursor.move_prev();
+    let tmp = cursor.split_before();
+    assert_eq!(cursor.index(), None);
+    assert_eq!(m, []);
+    m = tmp;
+    let mut cursor = m.cursor_front_mut();
+    for _ in 0..6 {
+        cursor.move_next();
+    }
+    let tmp = cursor.split_after();
+    assert_eq!(cursor.current(), Some(&mut 101));
+    assert_eq!(tmp, [102, 103, 8, 2, 3, 4, 5, 6]);
+    assert_eq!(m, [200, 201, 202, 203, 1, 100, 101]);
+    let mut cursor = m.cursor_back_mut();
+    cursor.move_next();
+    cursor.splice_after(VecDeque::from([0]));
+    cursor.splice_before(VecDeque::from([7]));
+    assert_eq!(cursor.index(), None);
+    assert_eq!(m, [0, 200, 201, 202, 203, 1, 100, 101, 7]);
+}
+
+#[test]
+fn test_cursor_push_pop() {
+    let mut m: VecDeque<u32> = (1..=10).collect();
+    let mut c = m.cursor_front_mut();
+    c.push_front(0);
+    assert_eq!(c.current(), Some(&mut 1));
+    assert_eq!(c.index(), Some(1));
+    c.push_back(11);
+    assert_eq!(c.index(), Some(1));
+    assert_eq!(c.pop_front(), Some(0));
+    assert_eq!(c.current(), Some(&mut 1));
+    assert_eq!(c.pop_front(), Some(1));
+    assert_eq!(c.current(), Some(&mut 2));
+    assert_eq!(c.index(), Some(0));
+    c.move_prev();
+    c.push_back(12);
+    assert_eq!(c.index(), None);
+    assert_eq!(c.pop_back(), Some(12));
+    assert_eq!(c.index(), None);
+    assert_eq!(c.peek_prev(), Some(&mut 11));
+    c.move_prev();
+    assert_eq!(c.pop_back(), Some(11));
+    assert_eq!(c.index(), None);
+    assert_eq!(c.front(), Some(&2));
+    assert_eq!(c.back(), Some(&10));
+    *c.front_mut().unwrap() = 0;
+    *c.back_mut().unwrap() = 0;
+    assert_eq!(m, [0, 3, 4, 5, 6, 7, 8, 9, 0]);
+
+    let mut m = VecDeque::from([1]);
+    let mut c = m.cursor_front_mut();
+    assert_eq!(c.pop_front(), Some(1));
+    assert_eq!(c.index(), None);
+    assert_eq!(c.pop_back(), None);
+    assert_eq!(c.pop_front(), None);
+}
+
+#[test]
+fn test_cursor_splice_wrapping() {
+    // Splice at every position of deques wrapped at every offset, so that
+    // both ends get moved around the end of the buffer.
+    for cap in [8, 11] {
+        for len in 0..cap {
+            for offset in 0..cap {
+                for at in 0..=len {
+                    for splice_len in [0, 1, 3] {
+                        let mut m = VecDeque::with_capacity(cap);
+                        m.head = offset;
+                        m.extend(0..len);
+                        let mut cursor = m.cursor_front_mut();
+                        for _ in 0..at {
+                            cursor.move_next();
+                        }
+                        cursor.splice_before((100..100 + splice_len).collect());
+                        assert_eq!(cursor.index(), (at < len).then_some(at + splice_len));
+                        let expected: Vec<_> =
+                            (0..at).chain(100..100 + splice_len).chain(at..len).collect();
+                        assert_eq!(m, expected);
+                    }
+                }
+            }
+        }
+    }
+}
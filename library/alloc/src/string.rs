@@ -42,6 +42,7 @@
 
 #![stable(feature = "rust1", since = "1.0.0")]
 
+use core::cmp::Ordering;
 use core::error::Error;
 use core::fmt;
 use core::hash;
@@ -61,13 +62,14 @@
 #[cfg(not(no_global_oom_handling))]
 use core::str::Utf8Chunks;
 
+use crate::alloc::{Allocator, Global};
 #[cfg(not(no_global_oom_handling))]
 use crate::borrow::{Cow, ToOwned};
 use crate::boxed::Box;
 use crate::collections::TryReserveError;
-use crate::str::{self, from_utf8_unchecked_mut, Chars, Utf8Error};
 #[cfg(not(no_global_oom_handling))]
-use crate::str::{from_boxed_utf8_unchecked, FromStr};
+use crate::str::FromStr;
+use crate::str::{self, from_utf8_unchecked_mut, Chars, Utf8Error};
 use crate::vec::Vec;
 
 /// A UTF-8–encoded, growable string.
@@ -359,11 +361,10 @@
 /// [Deref]: core::ops::Deref "ops::Deref"
 /// [`Deref`]: core::ops::Deref "ops::Deref"
 /// [`as_str()`]: String::as_str
-#[derive(PartialEq, PartialOrd, Eq, Ord)]
 #[stable(feature = "rust1", since = "1.0.0")]
 #[cfg_attr(not(test), lang = "String")]
-pub struct String {
-    vec: Vec<u8>,
+pub struct String<#[unstable(feature = "allocator_api", issue = "32838")] A: Allocator = Global> {
+    vec: Vec<u8, A>,
 }
 
 /// A possible error value when converting a `String` from a UTF-8 byte vector.
@@ -505,6 +506,33 @@ pub fn try_with_capacity(capacity: usize) -> Result<String, TryReserveError> {
         Ok(String { vec: Vec::try_with_capacity(capacity)? })
     }
 
+    /// Creates a `String` from an iterator of [`char`]s, returning an error
+    /// instead of panicking or aborting if the allocator reports a failure.
+    ///
+    /// This is the fallible counterpart to [`collect`]ing into a `String`.
+    ///
+    /// [`collect`]: Iterator::collect
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Err`] if the capacity exceeds `isize::MAX` bytes,
+    /// or if the memory allocator reports failure.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(fallible_allocation)]
+    ///
+    /// let s = String::try_from_iter("hello".chars().rev()).unwrap();
+    /// assert_eq!("olleh", s);
+    /// ```
+    #[unstable(feature = "fallible_allocation", issue = "none")]
+    pub fn try_from_iter<I: IntoIterator<Item = char>>(iter: I) -> Result<String, TryReserveError> {
+        let mut string = String::new();
+        string.try_extend(iter)?;
+        Ok(string)
+    }
+
     // HACK(japaric): with cfg(test) the inherent `[T]::to_vec` method, which is
     // required for this method definition, is not available. Since we don't
     // require this method for testing purposes, I'll just stub it
@@ -993,6 +1021,129 @@ pub unsafe fn from_raw_parts(buf: *mut u8, length: usize, capacity: usize) -> St
     pub unsafe fn from_utf8_unchecked(bytes: Vec<u8>) -> String {
         String { vec: bytes }
     }
+}
+
+impl<A: Allocator> String<A> {
+    /// Creates a new empty `String` in the provided allocator.
+    ///
+    /// The `String` will not allocate until bytes are pushed onto it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(allocator_api)]
+    ///
+    /// use std::alloc::System;
+    ///
+    /// let mut s = String::new_in(System);
+    /// s.push_str("hello");
+    /// assert_eq!(s.as_str(), "hello");
+    /// ```
+    #[inline]
+    #[unstable(feature = "allocator_api", issue = "32838")]
+    #[must_use]
+    pub const fn new_in(alloc: A) -> Self {
+        String { vec: Vec::new_in(alloc) }
+    }
+
+    /// Creates a new empty `String` with at least the specified capacity in the
+    /// provided allocator.
+    ///
+    /// See [`with_capacity`] for the details of how the capacity is chosen.
+    ///
+    /// [`with_capacity`]: String::with_capacity
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(allocator_api)]
+    ///
+    /// use std::alloc::System;
+    ///
+    /// let mut s = String::with_capacity_in(10, System);
+    /// let cap = s.capacity();
+    /// s.push_str("0123456789");
+    /// assert_eq!(s.capacity(), cap);
+    /// ```
+    #[cfg(not(no_global_oom_handling))]
+    #[inline]
+    #[unstable(feature = "allocator_api", issue = "32838")]
+    #[must_use]
+    pub fn with_capacity_in(capacity: usize, alloc: A) -> Self {
+        String { vec: Vec::with_capacity_in(capacity, alloc) }
+    }
+
+    /// Creates a new empty `String` with at least the specified capacity in the
+    /// provided allocator.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Err`] if the capacity exceeds `isize::MAX` bytes,
+    /// or if the allocator reports failure.
+    #[inline]
+    #[unstable(feature = "allocator_api", issue = "32838")]
+    // #[unstable(feature = "try_with_capacity", issue = "91913")]
+    pub fn try_with_capacity_in(capacity: usize, alloc: A) -> Result<Self, TryReserveError> {
+        Ok(String { vec: Vec::try_with_capacity_in(capacity, alloc)? })
+    }
+
+    /// Creates a new `String` from a pointer, a length, a capacity and an
+    /// allocator.
+    ///
+    /// # Safety
+    ///
+    /// The same requirements as for [`from_raw_parts`] apply, except that
+    /// the memory at `buf` must have been allocated by `alloc` rather than
+    /// by the global allocator.
+    ///
+    /// [`from_raw_parts`]: String::from_raw_parts
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(allocator_api)]
+    ///
+    /// use std::alloc::System;
+    ///
+    /// let mut s = String::new_in(System);
+    /// s.push_str("hello");
+    ///
+    /// let (ptr, len, cap, alloc) = s.into_raw_parts_with_alloc();
+    ///
+    /// let rebuilt = unsafe { String::from_raw_parts_in(ptr, len, cap, alloc) };
+    /// assert_eq!(rebuilt.as_str(), "hello");
+    /// ```
+    #[inline]
+    #[unstable(feature = "allocator_api", issue = "32838")]
+    pub unsafe fn from_raw_parts_in(
+        buf: *mut u8,
+        length: usize,
+        capacity: usize,
+        alloc: A,
+    ) -> Self {
+        unsafe { String { vec: Vec::from_raw_parts_in(buf, length, capacity, alloc) } }
+    }
+
+    /// Decomposes a `String` into its raw components: `(pointer, length, capacity, allocator)`.
+    ///
+    /// This is the allocator-aware counterpart of [`into_raw_parts`]; the
+    /// components can be turned back into a `String` with [`from_raw_parts_in`].
+    ///
+    /// [`into_raw_parts`]: String::into_raw_parts
+    /// [`from_raw_parts_in`]: String::from_raw_parts_in
+    #[must_use = "`self` will be dropped if the result is not used"]
+    #[unstable(feature = "allocator_api", issue = "32838")]
+    // #[unstable(feature = "vec_into_raw_parts", reason = "new API", issue = "65816")]
+    pub fn into_raw_parts_with_alloc(self) -> (*mut u8, usize, usize, A) {
+        self.vec.into_raw_parts_with_alloc()
+    }
+
+    /// Returns a reference to the underlying allocator.
+    #[inline]
+    #[unstable(feature = "allocator_api", issue = "32838")]
+    pub fn allocator(&self) -> &A {
+        self.vec.allocator()
+    }
 
     /// Converts a `String` into a byte vector.
     ///
@@ -1009,7 +1160,7 @@ pub unsafe fn from_utf8_unchecked(bytes: Vec<u8>) -> String {
     #[inline]
     #[must_use = "`self` will be dropped if the result is not used"]
     #[stable(feature = "rust1", since = "1.0.0")]
-    pub fn into_bytes(self) -> Vec<u8> {
+    pub fn into_bytes(self) -> Vec<u8, A> {
         self.vec
     }
 
@@ -1067,6 +1218,36 @@ pub fn push_str(&mut self, string: &str) {
         self.vec.extend_from_slice(string.as_bytes())
     }
 
+    /// Appends a given string slice onto the end of this `String`, or returns an
+    /// error if the capacity cannot be increased to make room for it.
+    ///
+    /// This is the fallible counterpart to [`push_str`]. If an error is returned
+    /// the `String` is left unchanged.
+    ///
+    /// [`push_str`]: String::push_str
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Err`] if the capacity exceeds `isize::MAX` bytes,
+    /// or if the memory allocator reports failure.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(fallible_allocation)]
+    ///
+    /// let mut s = String::from("foo");
+    ///
+    /// s.try_push_str("bar").unwrap();
+    ///
+    /// assert_eq!("foobar", s);
+    /// ```
+    #[inline]
+    #[unstable(feature = "fallible_allocation", issue = "none")]
+    pub fn try_push_str(&mut self, string: &str) -> Result<(), TryReserveError> {
+        self.vec.try_extend_from_slice(string.as_bytes())
+    }
+
     /// Copies elements from `src` range to the end of the string.
     ///
     /// # Panics
@@ -1292,6 +1473,35 @@ pub fn try_reserve_exact(&mut self, additional: usize) -> Result<(), TryReserveE
         self.vec.try_reserve_exact(additional)
     }
 
+    /// Returns a copy of this `String`, or an error if the allocator cannot
+    /// provide the memory for it.
+    ///
+    /// This is the fallible counterpart to [`clone`].
+    ///
+    /// [`clone`]: Clone::clone
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Err`] if the memory allocator reports failure.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(fallible_allocation)]
+    ///
+    /// let s = String::from("hello");
+    /// let copy = s.try_clone().unwrap();
+    ///
+    /// assert_eq!(s, copy);
+    /// ```
+    #[unstable(feature = "fallible_allocation", issue = "none")]
+    pub fn try_clone(&self) -> Result<Self, TryReserveError>
+    where
+        A: Clone,
+    {
+        Ok(String { vec: self.vec.try_clone()? })
+    }
+
     /// Shrinks the capacity of this `String` to match its length.
     ///
     /// # Examples
@@ -1362,6 +1572,79 @@ pub fn push(&mut self, ch: char) {
         }
     }
 
+    /// Appends the given [`char`] to the end of this `String`, or returns an
+    /// error if the capacity cannot be increased to make room for it.
+    ///
+    /// This is the fallible counterpart to [`push`]. If an error is returned
+    /// the `String` is left unchanged.
+    ///
+    /// [`push`]: String::push
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Err`] if the capacity exceeds `isize::MAX` bytes,
+    /// or if the memory allocator reports failure.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(fallible_allocation)]
+    ///
+    /// let mut s = String::from("abc");
+    ///
+    /// s.try_push('1').unwrap();
+    /// s.try_push('\u{e9}').unwrap();
+    ///
+    /// assert_eq!("abc1\u{e9}", s);
+    /// ```
+    #[inline]
+    #[unstable(feature = "fallible_allocation", issue = "none")]
+    pub fn try_push(&mut self, ch: char) -> Result<(), TryReserveError> {
+        match ch.len_utf8() {
+            1 => self.vec.try_push(ch as u8),
+            _ => self.vec.try_extend_from_slice(ch.encode_utf8(&mut [0; 4]).as_bytes()),
+        }
+    }
+
+    /// Appends the [`char`]s of an iterator to this `String`, returning an error
+    /// instead of panicking or aborting if the capacity cannot be increased to
+    /// make room for them.
+    ///
+    /// This is the fallible counterpart to [`extend`].
+    ///
+    /// [`extend`]: Extend::extend
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Err`] if the capacity exceeds `isize::MAX` bytes,
+    /// or if the memory allocator reports failure. The characters appended
+    /// before the failure remain in the `String`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(fallible_allocation)]
+    ///
+    /// let mut s = String::from("abc");
+    ///
+    /// s.try_extend(['d', 'e', 'f']).unwrap();
+    ///
+    /// assert_eq!("abcdef", s);
+    /// ```
+    #[unstable(feature = "fallible_allocation", issue = "none")]
+    pub fn try_extend<I: IntoIterator<Item = char>>(
+        &mut self,
+        iter: I,
+    ) -> Result<(), TryReserveError> {
+        let iterator = iter.into_iter();
+        let (lower_bound, _) = iterator.size_hint();
+        self.try_reserve(lower_bound)?;
+        for ch in iterator {
+            self.try_push(ch)?;
+        }
+        Ok(())
+    }
+
     /// Returns a byte slice of this `String`'s contents.
     ///
     /// The inverse of this method is [`from_utf8`].
@@ -1581,13 +1864,13 @@ pub fn retain<F>(&mut self, mut f: F)
     where
         F: FnMut(char) -> bool,
     {
-        struct SetLenOnDrop<'a> {
-            s: &'a mut String,
+        struct SetLenOnDrop<'a, A: Allocator> {
+            s: &'a mut String<A>,
             idx: usize,
             del_bytes: usize,
         }
 
-        impl<'a> Drop for SetLenOnDrop<'a> {
+        impl<'a, A: Allocator> Drop for SetLenOnDrop<'a, A> {
             fn drop(&mut self) {
                 let new_len = self.idx - self.del_bytes;
                 debug_assert!(new_len <= self.s.len());
@@ -1665,11 +1948,67 @@ pub fn insert(&mut self, idx: usize, ch: char) {
         }
     }
 
+    /// Inserts a character into this `String` at a byte position, or returns an
+    /// error if the capacity cannot be increased to make room for it.
+    ///
+    /// This is the fallible counterpart to [`insert`]. If an error is returned
+    /// the `String` is left unchanged.
+    ///
+    /// [`insert`]: String::insert
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Err`] if the capacity exceeds `isize::MAX` bytes,
+    /// or if the memory allocator reports failure.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `idx` is larger than the `String`'s length, or if it does not
+    /// lie on a [`char`] boundary.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(fallible_allocation)]
+    ///
+    /// let mut s = String::from("fo");
+    ///
+    /// s.try_insert(2, 'o').unwrap();
+    ///
+    /// assert_eq!("foo", s);
+    /// ```
+    #[inline]
+    #[unstable(feature = "fallible_allocation", issue = "none")]
+    pub fn try_insert(&mut self, idx: usize, ch: char) -> Result<(), TryReserveError> {
+        assert!(self.is_char_boundary(idx));
+        let mut bits = [0; 4];
+        let bits = ch.encode_utf8(&mut bits).as_bytes();
+
+        unsafe { self.try_insert_bytes(idx, bits) }
+    }
+
     #[cfg(not(no_global_oom_handling))]
     unsafe fn insert_bytes(&mut self, idx: usize, bytes: &[u8]) {
+        self.vec.reserve(bytes.len());
+
+        unsafe {
+            self.insert_bytes_within_capacity(idx, bytes);
+        }
+    }
+
+    unsafe fn try_insert_bytes(&mut self, idx: usize, bytes: &[u8]) -> Result<(), TryReserveError> {
+        self.vec.try_reserve(bytes.len())?;
+
+        unsafe {
+            self.insert_bytes_within_capacity(idx, bytes);
+        }
+        Ok(())
+    }
+
+    /// Inserts `bytes` at `idx`; the caller has already reserved room for them.
+    unsafe fn insert_bytes_within_capacity(&mut self, idx: usize, bytes: &[u8]) {
         let len = self.len();
         let amt = bytes.len();
-        self.vec.reserve(amt);
 
         unsafe {
             ptr::copy(self.vec.as_ptr().add(idx), self.vec.as_mut_ptr().add(idx + amt), len - idx);
@@ -1708,6 +2047,43 @@ pub fn insert_str(&mut self, idx: usize, string: &str) {
         }
     }
 
+    /// Inserts a string slice into this `String` at a byte position, or returns
+    /// an error if the capacity cannot be increased to make room for it.
+    ///
+    /// This is the fallible counterpart to [`insert_str`]. If an error is
+    /// returned the `String` is left unchanged.
+    ///
+    /// [`insert_str`]: String::insert_str
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Err`] if the capacity exceeds `isize::MAX` bytes,
+    /// or if the memory allocator reports failure.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `idx` is larger than the `String`'s length, or if it does not
+    /// lie on a [`char`] boundary.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(fallible_allocation)]
+    ///
+    /// let mut s = String::from("bar");
+    ///
+    /// s.try_insert_str(0, "foo").unwrap();
+    ///
+    /// assert_eq!("foobar", s);
+    /// ```
+    #[inline]
+    #[unstable(feature = "fallible_allocation", issue = "none")]
+    pub fn try_insert_str(&mut self, idx: usize, string: &str) -> Result<(), TryReserveError> {
+        assert!(self.is_char_boundary(idx));
+
+        unsafe { self.try_insert_bytes(idx, string.as_bytes()) }
+    }
+
     /// Returns a mutable reference to the contents of this `String`.
     ///
     /// # Safety
@@ -1733,7 +2109,7 @@ pub fn insert_str(&mut self, idx: usize, string: &str) {
     /// ```
     #[inline]
     #[stable(feature = "rust1", since = "1.0.0")]
-    pub unsafe fn as_mut_vec(&mut self) -> &mut Vec<u8> {
+    pub unsafe fn as_mut_vec(&mut self) -> &mut Vec<u8, A> {
         &mut self.vec
     }
 
@@ -1804,10 +2180,13 @@ pub fn is_empty(&self) -> bool {
     #[inline]
     #[stable(feature = "string_split_off", since = "1.16.0")]
     #[must_use = "use `.truncate()` if you don't need the other half"]
-    pub fn split_off(&mut self, at: usize) -> String {
+    pub fn split_off(&mut self, at: usize) -> String<A>
+    where
+        A: Clone,
+    {
         assert!(self.is_char_boundary(at));
         let other = self.vec.split_off(at);
-        unsafe { String::from_utf8_unchecked(other) }
+        String { vec: other }
     }
 
     /// Truncates this `String`, removing all contents.
@@ -1866,7 +2245,7 @@ pub fn clear(&mut self) {
     /// assert_eq!(s, "");
     /// ```
     #[stable(feature = "drain", since = "1.6.0")]
-    pub fn drain<R>(&mut self, range: R) -> Drain<'_>
+    pub fn drain<R>(&mut self, range: R) -> Drain<'_, A>
     where
         R: RangeBounds<usize>,
     {
@@ -1957,9 +2336,10 @@ pub fn replace_range<R>(&mut self, range: R, replace_with: &str)
     #[stable(feature = "box_str", since = "1.4.0")]
     #[must_use = "`self` will be dropped if the result is not used"]
     #[inline]
-    pub fn into_boxed_str(self) -> Box<str> {
+    pub fn into_boxed_str(self) -> Box<str, A> {
         let slice = self.vec.into_boxed_slice();
-        unsafe { from_boxed_utf8_unchecked(slice) }
+        let (raw, alloc) = Box::into_raw_with_allocator(slice);
+        unsafe { Box::from_raw_in(raw as *mut str, alloc) }
     }
 
     /// Consumes and leaks the `String`, returning a mutable reference to the contents,
@@ -1985,7 +2365,10 @@ pub fn into_boxed_str(self) -> Box<str> {
     /// ```
     #[stable(feature = "string_leak", since = "1.72.0")]
     #[inline]
-    pub fn leak<'a>(self) -> &'a mut str {
+    pub fn leak<'a>(self) -> &'a mut str
+    where
+        A: 'a,
+    {
         let slice = self.vec.leak();
         unsafe { from_utf8_unchecked_mut(slice) }
     }
@@ -2092,7 +2475,7 @@ fn description(&self) -> &str {
 
 #[cfg(not(no_global_oom_handling))]
 #[stable(feature = "rust1", since = "1.0.0")]
-impl Clone for String {
+impl<A: Allocator + Clone> Clone for String<A> {
     fn clone(&self) -> Self {
         String { vec: self.vec.clone() }
     }
@@ -2183,7 +2566,7 @@ fn from_iter<I: IntoIterator<Item = Cow<'a, str>>>(iter: I) -> String {
 
 #[cfg(not(no_global_oom_handling))]
 #[stable(feature = "rust1", since = "1.0.0")]
-impl Extend<char> for String {
+impl<A: Allocator> Extend<char> for String<A> {
     fn extend<I: IntoIterator<Item = char>>(&mut self, iter: I) {
         let iterator = iter.into_iter();
         let (lower_bound, _) = iterator.size_hint();
@@ -2204,7 +2587,7 @@ fn extend_reserve(&mut self, additional: usize) {
 
 #[cfg(not(no_global_oom_handling))]
 #[stable(feature = "extend_ref", since = "1.2.0")]
-impl<'a> Extend<&'a char> for String {
+impl<'a, A: Allocator> Extend<&'a char> for String<A> {
     fn extend<I: IntoIterator<Item = &'a char>>(&mut self, iter: I) {
         self.extend(iter.into_iter().cloned());
     }
@@ -2222,7 +2605,7 @@ fn extend_reserve(&mut self, additional: usize) {
 
 #[cfg(not(no_global_oom_handling))]
 #[stable(feature = "rust1", since = "1.0.0")]
-impl<'a> Extend<&'a str> for String {
+impl<'a, A: Allocator> Extend<&'a str> for String<A> {
     fn extend<I: IntoIterator<Item = &'a str>>(&mut self, iter: I) {
         iter.into_iter().for_each(move |s| self.push_str(s));
     }
@@ -2235,7 +2618,7 @@ fn extend_one(&mut self, s: &'a str) {
 
 #[cfg(not(no_global_oom_handling))]
 #[stable(feature = "box_str2", since = "1.45.0")]
-impl Extend<Box<str>> for String {
+impl<A: Allocator> Extend<Box<str>> for String<A> {
     fn extend<I: IntoIterator<Item = Box<str>>>(&mut self, iter: I) {
         iter.into_iter().for_each(move |s| self.push_str(&s));
     }
@@ -2243,7 +2626,7 @@ fn extend<I: IntoIterator<Item = Box<str>>>(&mut self, iter: I) {
 
 #[cfg(not(no_global_oom_handling))]
 #[stable(feature = "extend_string", since = "1.4.0")]
-impl Extend<String> for String {
+impl<A: Allocator> Extend<String> for String<A> {
     fn extend<I: IntoIterator<Item = String>>(&mut self, iter: I) {
         iter.into_iter().for_each(move |s| self.push_str(&s));
     }
@@ -2256,7 +2639,7 @@ fn extend_one(&mut self, s: String) {
 
 #[cfg(not(no_global_oom_handling))]
 #[stable(feature = "herd_cows", since = "1.19.0")]
-impl<'a> Extend<Cow<'a, str>> for String {
+impl<'a, A: Allocator> Extend<Cow<'a, str>> for String<A> {
     fn extend<I: IntoIterator<Item = Cow<'a, str>>>(&mut self, iter: I) {
         iter.into_iter().for_each(move |s| self.push_str(&s));
     }
@@ -2279,7 +2662,7 @@ fn extend_one(&mut self, s: Cow<'a, str>) {
     reason = "API not fully fleshed out and ready to be stabilized",
     issue = "27721"
 )]
-impl<'a, 'b> Pattern<'a> for &'b String {
+impl<'a, 'b, A: Allocator> Pattern<'a> for &'b String<A> {
     type Searcher = <&'b str as Pattern<'a>>::Searcher;
 
     fn into_searcher(self, haystack: &'a str) -> <&'b str as Pattern<'a>>::Searcher {
@@ -2312,6 +2695,33 @@ fn strip_suffix_of(self, haystack: &'a str) -> Option<&'a str> {
     }
 }
 
+#[stable(feature = "rust1", since = "1.0.0")]
+impl<A: Allocator> PartialEq for String<A> {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.vec == other.vec
+    }
+}
+
+#[stable(feature = "rust1", since = "1.0.0")]
+impl<A: Allocator> Eq for String<A> {}
+
+#[stable(feature = "rust1", since = "1.0.0")]
+impl<A: Allocator> PartialOrd for String<A> {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.vec.partial_cmp(&other.vec)
+    }
+}
+
+#[stable(feature = "rust1", since = "1.0.0")]
+impl<A: Allocator> Ord for String<A> {
+    #[inline]
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.vec.cmp(&other.vec)
+    }
+}
+
 macro_rules! impl_eq {
     ($lhs:ty, $rhs: ty) => {
         #[stable(feature = "rust1", since = "1.0.0")]
@@ -2361,7 +2771,7 @@ fn default() -> String {
 }
 
 #[stable(feature = "rust1", since = "1.0.0")]
-impl fmt::Display for String {
+impl<A: Allocator> fmt::Display for String<A> {
     #[inline]
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         fmt::Display::fmt(&**self, f)
@@ -2369,7 +2779,7 @@ fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
 }
 
 #[stable(feature = "rust1", since = "1.0.0")]
-impl fmt::Debug for String {
+impl<A: Allocator> fmt::Debug for String<A> {
     #[inline]
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         fmt::Debug::fmt(&**self, f)
@@ -2377,7 +2787,7 @@ fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
 }
 
 #[stable(feature = "rust1", since = "1.0.0")]
-impl hash::Hash for String {
+impl<A: Allocator> hash::Hash for String<A> {
     #[inline]
     fn hash<H: hash::Hasher>(&self, hasher: &mut H) {
         (**self).hash(hasher)
@@ -2423,11 +2833,11 @@ fn hash<H: hash::Hasher>(&self, hasher: &mut H) {
 /// ```
 #[cfg(not(no_global_oom_handling))]
 #[stable(feature = "rust1", since = "1.0.0")]
-impl Add<&str> for String {
-    type Output = String;
+impl<A: Allocator> Add<&str> for String<A> {
+    type Output = String<A>;
 
     #[inline]
-    fn add(mut self, other: &str) -> String {
+    fn add(mut self, other: &str) -> String<A> {
         self.push_str(other);
         self
     }
@@ -2438,7 +2848,7 @@ fn add(mut self, other: &str) -> String {
 /// This has the same behavior as the [`push_str`][String::push_str] method.
 #[cfg(not(no_global_oom_handling))]
 #[stable(feature = "stringaddassign", since = "1.12.0")]
-impl AddAssign<&str> for String {
+impl<A: Allocator> AddAssign<&str> for String<A> {
     #[inline]
     fn add_assign(&mut self, other: &str) {
         self.push_str(other);
@@ -2446,7 +2856,7 @@ fn add_assign(&mut self, other: &str) {
 }
 
 #[stable(feature = "rust1", since = "1.0.0")]
-impl<I> ops::Index<I> for String
+impl<I, A: Allocator> ops::Index<I> for String<A>
 where
     I: slice::SliceIndex<str>,
 {
@@ -2459,7 +2869,7 @@ fn index(&self, index: I) -> &I::Output {
 }
 
 #[stable(feature = "rust1", since = "1.0.0")]
-impl<I> ops::IndexMut<I> for String
+impl<I, A: Allocator> ops::IndexMut<I> for String<A>
 where
     I: slice::SliceIndex<str>,
 {
@@ -2470,7 +2880,7 @@ fn index_mut(&mut self, index: I) -> &mut I::Output {
 }
 
 #[stable(feature = "rust1", since = "1.0.0")]
-impl ops::Deref for String {
+impl<A: Allocator> ops::Deref for String<A> {
     type Target = str;
 
     #[inline]
@@ -2480,10 +2890,10 @@ fn deref(&self) -> &str {
 }
 
 #[unstable(feature = "deref_pure_trait", issue = "87121")]
-unsafe impl ops::DerefPure for String {}
+unsafe impl<A: Allocator> ops::DerefPure for String<A> {}
 
 #[stable(feature = "derefmut_for_string", since = "1.3.0")]
-impl ops::DerefMut for String {
+impl<A: Allocator> ops::DerefMut for String<A> {
     #[inline]
     fn deref_mut(&mut self) -> &mut str {
         unsafe { str::from_utf8_unchecked_mut(&mut *self.vec) }
@@ -2675,7 +3085,7 @@ fn to_string(&self) -> String {
 }
 
 #[stable(feature = "rust1", since = "1.0.0")]
-impl AsRef<str> for String {
+impl<A: Allocator> AsRef<str> for String<A> {
     #[inline]
     fn as_ref(&self) -> &str {
         self
@@ -2683,7 +3093,7 @@ fn as_ref(&self) -> &str {
 }
 
 #[stable(feature = "string_as_mut", since = "1.43.0")]
-impl AsMut<str> for String {
+impl<A: Allocator> AsMut<str> for String<A> {
     #[inline]
     fn as_mut(&mut self) -> &mut str {
         self
@@ -2691,7 +3101,7 @@ fn as_mut(&mut self) -> &mut str {
 }
 
 #[stable(feature = "rust1", since = "1.0.0")]
-impl AsRef<[u8]> for String {
+impl<A: Allocator> AsRef<[u8]> for String<A> {
     #[inline]
     fn as_ref(&self) -> &[u8] {
         self.as_bytes()
@@ -2757,7 +3167,7 @@ fn from(s: Box<str>) -> String {
 
 #[cfg(not(no_global_oom_handling))]
 #[stable(feature = "box_from_str", since = "1.20.0")]
-impl From<String> for Box<str> {
+impl<A: Allocator> From<String<A>> for Box<str, A> {
     /// Converts the given [`String`] to a boxed `str` slice that is owned.
     ///
     /// # Examples
@@ -2769,7 +3179,7 @@ impl From<String> for Box<str> {
     ///
     /// assert_eq!("hello world", s3)
     /// ```
-    fn from(s: String) -> Box<str> {
+    fn from(s: String<A>) -> Box<str, A> {
         s.into_boxed_str()
     }
 }
@@ -2889,7 +3299,7 @@ fn from_iter<I: IntoIterator<Item = String>>(it: I) -> Cow<'a, str> {
 }
 
 #[stable(feature = "from_string_for_vec_u8", since = "1.14.0")]
-impl From<String> for Vec<u8> {
+impl<A: Allocator> From<String<A>> for Vec<u8, A> {
     /// Converts the given [`String`] to a vector [`Vec`] that holds values of type [`u8`].
     ///
     /// # Examples
@@ -2902,14 +3312,14 @@ impl From<String> for Vec<u8> {
     ///     println!("{b}");
     /// }
     /// ```
-    fn from(string: String) -> Vec<u8> {
+    fn from(string: String<A>) -> Vec<u8, A> {
         string.into_bytes()
     }
 }
 
 #[cfg(not(no_global_oom_handling))]
 #[stable(feature = "rust1", since = "1.0.0")]
-impl fmt::Write for String {
+impl<A: Allocator> fmt::Write for String<A> {
     #[inline]
     fn write_str(&mut self, s: &str) -> fmt::Result {
         self.push_str(s);
@@ -2930,9 +3340,12 @@ fn write_char(&mut self, c: char) -> fmt::Result {
 ///
 /// [`drain`]: String::drain
 #[stable(feature = "drain", since = "1.6.0")]
-pub struct Drain<'a> {
+pub struct Drain<
+    'a,
+    #[unstable(feature = "allocator_api", issue = "32838")] A: Allocator = Global,
+> {
     /// Will be used as &'a mut String in the destructor
-    string: *mut String,
+    string: *mut String<A>,
     /// Start of part to remove
     start: usize,
     /// End of part to remove
@@ -2942,19 +3355,19 @@ pub struct Drain<'a> {
 }
 
 #[stable(feature = "collection_debug", since = "1.17.0")]
-impl fmt::Debug for Drain<'_> {
+impl<A: Allocator> fmt::Debug for Drain<'_, A> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_tuple("Drain").field(&self.as_str()).finish()
     }
 }
 
 #[stable(feature = "drain", since = "1.6.0")]
-unsafe impl Sync for Drain<'_> {}
+unsafe impl<A: Allocator + Sync> Sync for Drain<'_, A> {}
 #[stable(feature = "drain", since = "1.6.0")]
-unsafe impl Send for Drain<'_> {}
+unsafe impl<A: Allocator + Send> Send for Drain<'_, A> {}
 
 #[stable(feature = "drain", since = "1.6.0")]
-impl Drop for Drain<'_> {
+impl<A: Allocator> Drop for Drain<'_, A> {
     fn drop(&mut self) {
         unsafe {
             // Use Vec::drain. "Reaffirm" the bounds checks to avoid
@@ -2967,7 +3380,7 @@ fn drop(&mut self) {
     }
 }
 
-impl<'a> Drain<'a> {
+impl<'a, A: Allocator> Drain<'a, A> {
     /// Returns the remaining (sub)string of this iterator as a slice.
     ///
     /// # Examples
@@ -2987,21 +3400,21 @@ pub fn as_str(&self) -> &str {
 }
 
 #[stable(feature = "string_drain_as_str", since = "1.55.0")]
-impl<'a> AsRef<str> for Drain<'a> {
+impl<'a, A: Allocator> AsRef<str> for Drain<'a, A> {
     fn as_ref(&self) -> &str {
         self.as_str()
     }
 }
 
 #[stable(feature = "string_drain_as_str", since = "1.55.0")]
-impl<'a> AsRef<[u8]> for Drain<'a> {
+impl<'a, A: Allocator> AsRef<[u8]> for Drain<'a, A> {
     fn as_ref(&self) -> &[u8] {
         self.as_str().as_bytes()
     }
 }
 
 #[stable(feature = "drain", since = "1.6.0")]
-impl Iterator for Drain<'_> {
+impl<A: Allocator> Iterator for Drain<'_, A> {
     type Item = char;
 
     #[inline]
@@ -3020,7 +3433,7 @@ fn last(mut self) -> Option<char> {
 }
 
 #[stable(feature = "drain", since = "1.6.0")]
-impl DoubleEndedIterator for Drain<'_> {
+impl<A: Allocator> DoubleEndedIterator for Drain<'_, A> {
     #[inline]
     fn next_back(&mut self) -> Option<char> {
         self.iter.next_back()
@@ -3028,7 +3441,7 @@ fn next_back(&mut self) -> Option<char> {
 }
 
 #[stable(feature = "fused", since = "1.26.0")]
-impl FusedIterator for Drain<'_> {}
+impl<A: Allocator> FusedIterator for Drain<'_, A> {}
 
 #[cfg(not(no_global_oom_handling))]
 #[stable(feature = "from_char_for_string", since = "1.46.0")]
@@ -497,6 +497,33 @@ pub fn try_with_capacity(capacity: usize) -> Result<Self, TryReserveError> {
         Self::try_with_capacity_in(capacity, Global)
     }
 
+    /// Creates a `Vec<T>` from the elements of an iterator, returning an error
+    /// instead of panicking or aborting if the allocator reports a failure.
+    ///
+    /// This is the fallible counterpart to [`collect`]ing into a `Vec<T>`.
+    ///
+    /// [`collect`]: Iterator::collect
+    ///
+    /// # Errors
+    ///
+    /// If the capacity overflows, or the allocator reports a failure, then an error
+    /// is returned and the elements collected so far are dropped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(fallible_allocation)]
+    ///
+    /// let vec = Vec::try_from_iter((1..=3).map(|x| x * 2)).unwrap();
+    /// assert_eq!(vec, [2, 4, 6]);
+    /// ```
+    #[unstable(feature = "fallible_allocation", issue = "none")]
+    pub fn try_from_iter<I: IntoIterator<Item = T>>(iter: I) -> Result<Self, TryReserveError> {
+        let mut vec = Vec::new();
+        vec.try_extend(iter)?;
+        Ok(vec)
+    }
+
     /// Creates a `Vec<T>` directly from a pointer, a length, and a capacity.
     ///
     /// # Safety
@@ -1595,6 +1622,63 @@ fn assert_failed(index: usize, len: usize) -> ! {
         }
     }
 
+    /// Inserts an element at position `index` within the vector, shifting all
+    /// elements after it to the right, or returns an error if the capacity
+    /// cannot be increased to make room for it.
+    ///
+    /// This is the fallible counterpart to [`insert`]. If an error is returned
+    /// the vector is left unchanged and `element` is dropped.
+    ///
+    /// [`insert`]: Vec::insert
+    ///
+    /// # Errors
+    ///
+    /// If the capacity overflows, or the allocator reports a failure, then an error
+    /// is returned.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index > len`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(fallible_allocation)]
+    ///
+    /// let mut vec = vec![1, 2, 3];
+    /// vec.try_insert(1, 4).unwrap();
+    /// assert_eq!(vec, [1, 4, 2, 3]);
+    /// ```
+    #[unstable(feature = "fallible_allocation", issue = "none")]
+    pub fn try_insert(&mut self, index: usize, element: T) -> Result<(), TryReserveError> {
+        #[cold]
+        #[cfg_attr(not(feature = "panic_immediate_abort"), inline(never))]
+        #[track_caller]
+        fn assert_failed(index: usize, len: usize) -> ! {
+            panic!("insertion index (is {index}) should be <= len (is {len})");
+        }
+
+        let len = self.len();
+        if index > len {
+            assert_failed(index, len);
+        }
+
+        // space for the new element
+        if len == self.buf.capacity() {
+            self.buf.try_reserve(len, 1)?;
+        }
+
+        unsafe {
+            let p = self.as_mut_ptr().add(index);
+            if index < len {
+                ptr::copy(p, p.add(1), len - index);
+            }
+            ptr::write(p, element);
+            self.set_len(len + 1);
+        }
+        Ok(())
+    }
+
     /// Removes and returns the element at position `index` within the vector,
     /// shifting all elements after it to the left.
     ///
@@ -2003,6 +2087,48 @@ pub fn push(&mut self, value: T) {
         }
     }
 
+    /// Appends an element to the back of a collection, or returns an error if
+    /// the capacity cannot be increased to make room for it.
+    ///
+    /// This is the fallible counterpart to [`push`]. If an error is returned
+    /// the vector is left unchanged and `value` is dropped; use
+    /// [`push_within_capacity`] to get the value back instead.
+    ///
+    /// [`push`]: Vec::push
+    /// [`push_within_capacity`]: Vec::push_within_capacity
+    ///
+    /// # Errors
+    ///
+    /// If the capacity overflows, or the allocator reports a failure, then an error
+    /// is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(fallible_allocation)]
+    ///
+    /// let mut vec = vec![1, 2];
+    /// vec.try_push(3).unwrap();
+    /// assert_eq!(vec, [1, 2, 3]);
+    /// ```
+    ///
+    /// # Time complexity
+    ///
+    /// Takes amortized *O*(1) time, as [`push`] does.
+    #[inline]
+    #[unstable(feature = "fallible_allocation", issue = "none")]
+    pub fn try_push(&mut self, value: T) -> Result<(), TryReserveError> {
+        if self.len == self.buf.capacity() {
+            self.buf.try_reserve(self.len, 1)?;
+        }
+        unsafe {
+            let end = self.as_mut_ptr().add(self.len);
+            ptr::write(end, value);
+            self.len += 1;
+        }
+        Ok(())
+    }
+
     /// Appends an element if there is sufficient spare capacity, otherwise an error is returned
     /// with the element.
     ///
@@ -2135,6 +2261,55 @@ pub fn append(&mut self, other: &mut Self) {
         }
     }
 
+    /// Appends the elements of an iterator to the vector, returning an error
+    /// instead of panicking or aborting if the capacity cannot be increased to
+    /// make room for them.
+    ///
+    /// This is the fallible counterpart to [`extend`]. Capacity for the iterator's
+    /// lower size bound is reserved up front.
+    ///
+    /// [`extend`]: Extend::extend
+    ///
+    /// # Errors
+    ///
+    /// If the capacity overflows, or the allocator reports a failure, then an error
+    /// is returned. The elements appended before the failure remain in the vector,
+    /// and the element that could not be stored is dropped along with the rest
+    /// of the iterator.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(fallible_allocation)]
+    ///
+    /// let mut vec = vec![1];
+    /// vec.try_extend([2, 3, 4]).unwrap();
+    /// assert_eq!(vec, [1, 2, 3, 4]);
+    /// ```
+    #[unstable(feature = "fallible_allocation", issue = "none")]
+    pub fn try_extend<I: IntoIterator<Item = T>>(
+        &mut self,
+        iter: I,
+    ) -> Result<(), TryReserveError> {
+        let mut iterator = iter.into_iter();
+        let (lower, _) = iterator.size_hint();
+        self.try_reserve(lower)?;
+        while let Some(element) = iterator.next() {
+            let len = self.len();
+            if len == self.capacity() {
+                let (lower, _) = iterator.size_hint();
+                self.try_reserve(lower.saturating_add(1))?;
+            }
+            unsafe {
+                ptr::write(self.as_mut_ptr().add(len), element);
+                // Since next() executes user code which can panic we have to bump the length
+                // after each step.
+                self.set_len(len + 1);
+            }
+        }
+        Ok(())
+    }
+
     /// Appends elements to `self` from other buffer.
     #[cfg(not(no_global_oom_handling))]
     #[inline]
@@ -2589,6 +2764,64 @@ pub fn extend_from_slice(&mut self, other: &[T]) {
         self.spec_extend(other.iter())
     }
 
+    /// Clones and appends all elements in a slice to the `Vec`, or returns an
+    /// error if the capacity cannot be increased to make room for them.
+    ///
+    /// This is the fallible counterpart to [`extend_from_slice`]. The capacity
+    /// for all of `other` is reserved before any element is cloned, so if an
+    /// error is returned the vector is left unchanged.
+    ///
+    /// [`extend_from_slice`]: Vec::extend_from_slice
+    ///
+    /// # Errors
+    ///
+    /// If the capacity overflows, or the allocator reports a failure, then an error
+    /// is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(fallible_allocation)]
+    ///
+    /// let mut vec = vec![1];
+    /// vec.try_extend_from_slice(&[2, 3, 4]).unwrap();
+    /// assert_eq!(vec, [1, 2, 3, 4]);
+    /// ```
+    #[unstable(feature = "fallible_allocation", issue = "none")]
+    pub fn try_extend_from_slice(&mut self, other: &[T]) -> Result<(), TryReserveError> {
+        self.try_extend(other.iter().cloned())
+    }
+
+    /// Returns a copy of the vector, or an error if the allocator cannot provide
+    /// the memory for it.
+    ///
+    /// This is the fallible counterpart to [`clone`].
+    ///
+    /// [`clone`]: Clone::clone
+    ///
+    /// # Errors
+    ///
+    /// If the allocator reports a failure, then an error is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(fallible_allocation)]
+    ///
+    /// let vec = vec![1, 2, 3];
+    /// let copy = vec.try_clone().unwrap();
+    /// assert_eq!(vec, copy);
+    /// ```
+    #[unstable(feature = "fallible_allocation", issue = "none")]
+    pub fn try_clone(&self) -> Result<Self, TryReserveError>
+    where
+        A: Clone,
+    {
+        let mut vec = Vec::try_with_capacity_in(self.len(), self.allocator().clone())?;
+        vec.try_extend_from_slice(self)?;
+        Ok(vec)
+    }
+
     /// Copies elements from `src` range to the end of the vector.
     ///
     /// # Panics
@@ -1,3 +1,4 @@
+use std::alloc::System;
 use std::assert_matches::assert_matches;
 use std::borrow::Cow;
 use std::cell::Cell;
@@ -734,6 +735,28 @@ fn test_try_with_capacity() {
     assert!(String::try_with_capacity(usize::MAX).is_err());
 }
 
+#[test]
+fn test_string_in_allocator() {
+    let mut s = String::new_in(System);
+    s.push_str("hello");
+    s += " world";
+    s.extend(['!', '!']);
+    assert_eq!(s.as_str(), "hello world!!");
+    assert!(s.try_reserve(16).is_ok());
+    assert!(s.capacity() >= s.len() + 16);
+
+    let tail = s.split_off(5);
+    assert_eq!(tail.as_str(), " world!!");
+    assert_eq!(s.clone(), s);
+
+    let boxed: Box<str, System> = s.into_boxed_str();
+    assert_eq!(&*boxed, "hello");
+
+    let (ptr, len, cap, alloc) = tail.into_raw_parts_with_alloc();
+    let rebuilt = unsafe { String::from_raw_parts_in(ptr, len, cap, alloc) };
+    assert_eq!(rebuilt.into_bytes(), b" world!!");
+}
+
 #[test]
 #[cfg_attr(miri, ignore)] // Miri does not support signalling OOM
 #[cfg_attr(target_os = "android", ignore)] // Android used in CI has a broken dlmalloc
@@ -866,6 +889,18 @@ fn test_try_reserve_exact() {
     }
 }
 
+#[test]
+fn test_try_push_insert_extend() {
+    let mut s = String::try_from_iter("ab".chars()).unwrap();
+    s.try_push('d').unwrap();
+    s.try_insert(2, 'c').unwrap();
+    s.try_push_str("ef").unwrap();
+    s.try_insert_str(0, "\u{e9}").unwrap();
+    s.try_extend(['g', 'h']).unwrap();
+    assert_eq!(s, "\u{e9}abcdefgh");
+    assert_eq!(s.try_clone().unwrap(), s);
+}
+
 #[test]
 fn test_from_char() {
     assert_eq!(String::from('a'), 'a'.to_string());
@@ -1892,6 +1892,46 @@ fn test_try_reserve_exact() {
     }
 }
 
+#[test]
+fn test_try_push_insert_extend() {
+    let mut vec = Vec::try_from_iter([1, 2]).unwrap();
+    vec.try_push(4).unwrap();
+    vec.try_insert(2, 3).unwrap();
+    vec.try_extend([5, 6]).unwrap();
+    vec.try_extend_from_slice(&[7, 8]).unwrap();
+    assert_eq!(vec, [1, 2, 3, 4, 5, 6, 7, 8]);
+    assert_eq!(vec.try_clone().unwrap(), vec);
+}
+
+#[test]
+fn test_try_methods_alloc_failure() {
+    use core::alloc::AllocError;
+
+    #[derive(Clone)]
+    struct NoAlloc;
+
+    unsafe impl Allocator for NoAlloc {
+        fn allocate(&self, _: Layout) -> Result<NonNull<[u8]>, AllocError> {
+            Err(AllocError)
+        }
+
+        unsafe fn deallocate(&self, _: NonNull<u8>, _: Layout) {
+            unreachable!()
+        }
+    }
+
+    let mut vec: Vec<u32, NoAlloc> = Vec::new_in(NoAlloc);
+    assert!(vec.try_push(1).is_err());
+    assert!(vec.try_insert(0, 1).is_err());
+    assert!(vec.try_extend([1, 2]).is_err());
+    assert!(vec.try_extend_from_slice(&[1, 2]).is_err());
+    assert!(vec.is_empty());
+
+    // Nothing needs to be allocated for an empty vector or an empty iterator.
+    assert!(vec.try_extend([]).is_ok());
+    assert!(vec.try_clone().unwrap().is_empty());
+}
+
 #[test]
 fn test_stable_pointers() {
     /// Pull an element from the iterator, then drop it.
@@ -1379,6 +1379,25 @@ fn test_try_reserve_exact() {
     }
 }
 
+#[test]
+fn test_try_push_insert_extend() {
+    let mut d = VecDeque::try_from_iter([3, 4]).unwrap();
+    d.try_push_front(1).unwrap();
+    d.try_push_back(6).unwrap();
+    d.try_insert(1, 2).unwrap();
+    d.try_insert(4, 5).unwrap();
+    d.try_extend([7, 8, 9]).unwrap();
+    assert_eq!(d, [1, 2, 3, 4, 5, 6, 7, 8, 9]);
+    assert_eq!(d.try_clone().unwrap(), d);
+
+    // Grow a deque whose contents wrap around the end of its buffer.
+    let mut d = VecDeque::with_capacity(4);
+    d.extend([3, 4, 5]);
+    d.try_push_front(2).unwrap();
+    d.try_push_front(1).unwrap();
+    assert_eq!(d, [1, 2, 3, 4, 5]);
+}
+
 #[test]
 fn test_rotate_nop() {
     let mut v: VecDeque<_> = (0..10).collect();
@@ -1650,7 +1650,19 @@ macro_rules! trace_macros {
         /* compiler built-in */
     }
 
-    /// An implementation detail of the `#[test]` and `#[bench]` macros.
+    /// Attribute macro applied to a function to turn it into a fuzz target.
+    ///
+    /// The function takes the input as a `&[u8]`. Normal test runs call it with
+    /// every input of its corpus, and `--fuzz` calls it with mutated inputs until
+    /// it panics or returns an error.
+    #[unstable(feature = "fuzz", issue = "none")]
+    #[allow_internal_unstable(test, rustc_attrs, coverage_attribute)]
+    #[rustc_builtin_macro]
+    pub macro fuzz($item:item) {
+        /* compiler built-in */
+    }
+
+    /// An implementation detail of the `#[test]`, `#[bench]` and `#[fuzz]` macros.
     #[unstable(
         feature = "custom_test_frameworks",
         issue = "50297",
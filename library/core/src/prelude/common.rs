@@ -76,6 +76,9 @@
 #[unstable(feature = "derive_const", issue = "none")]
 pub use crate::macros::builtin::derive_const;
 
+#[unstable(feature = "fuzz", issue = "none")]
+pub use crate::macros::builtin::fuzz;
+
 #[unstable(
     feature = "cfg_accessible",
     issue = "64797",
@@ -1,5 +1,6 @@
 use super::Entry::{Occupied, Vacant};
 use super::HashMap;
+use crate::alloc::System;
 use crate::assert_matches::assert_matches;
 use crate::cell::RefCell;
 use crate::hash::RandomState;
@@ -851,6 +852,35 @@ fn test_try_reserve() {
     }
 }
 
+#[test]
+fn test_new_in() {
+    let mut map = HashMap::new_in(System);
+    assert!(map.try_reserve(10).is_ok());
+    assert!(map.capacity() >= 10);
+
+    for i in 0..10 {
+        map.insert(i, i * 10);
+    }
+    match map.entry(3) {
+        Vacant(_) => unreachable!(),
+        Occupied(mut view) => *view.get_mut() += 1,
+    }
+    assert_eq!(map[&3], 31);
+
+    let cloned = map.clone();
+    assert_eq!(map, cloned);
+
+    let mut drained: Vec<_> = map.drain().collect();
+    drained.sort();
+    assert_eq!(drained.len(), 10);
+    assert_eq!(drained[3], (3, 31));
+    assert!(map.is_empty());
+
+    let mut keys: Vec<_> = cloned.into_keys().collect();
+    keys.sort();
+    assert_eq!(keys, (0..10).collect::<Vec<_>>());
+}
+
 #[test]
 fn test_raw_entry() {
     use super::RawEntryMut::{Occupied, Vacant};
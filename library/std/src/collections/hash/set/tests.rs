@@ -1,5 +1,6 @@
 use super::HashSet;
 
+use crate::alloc::System;
 use crate::hash::RandomState;
 use crate::panic::{catch_unwind, AssertUnwindSafe};
 use crate::sync::atomic::{AtomicU32, Ordering};
@@ -524,3 +525,23 @@ fn test_insert_does_not_overwrite_the_value() {
             must be the same as first value pointer we inserted"
     );
 }
+
+#[test]
+fn test_new_in() {
+    let mut a = HashSet::new_in(System);
+    let mut b = HashSet::with_capacity_in(4, System);
+    assert!(b.capacity() >= 4);
+
+    a.extend([1, 2, 3]);
+    b.extend([2, 3, 4]);
+
+    let mut union: Vec<_> = (&a | &b).into_iter().collect();
+    union.sort();
+    assert_eq!(union, [1, 2, 3, 4]);
+
+    let mut intersection: Vec<_> = a.intersection(&b).copied().collect();
+    intersection.sort();
+    assert_eq!(intersection, [2, 3]);
+
+    assert_eq!(a.clone(), a);
+}
@@ -407,6 +407,8 @@
 #[doc(hidden)]
 pub use crate::ops::Bound;
 
+#[unstable(feature = "persistent_btree_map", issue = "none")]
+pub use alloc_crate::collections::persistent_btree_map;
 #[stable(feature = "rust1", since = "1.0.0")]
 pub use alloc_crate::collections::{binary_heap, btree_map, btree_set};
 #[stable(feature = "rust1", since = "1.0.0")]
@@ -11,10 +11,12 @@
 #[cfg(all(test, not(any(target_os = "emscripten", target_env = "sgx", target_os = "xous"))))]
 mod tests;
 
-use crate::ffi::OsString;
+use crate::ffi::{OsStr, OsString};
 use crate::fmt;
 use crate::io::{self, BorrowedCursor, IoSlice, IoSliceMut, Read, Seek, SeekFrom, Write};
+use crate::mem;
 use crate::path::{Path, PathBuf};
+use crate::ptr;
 use crate::sealed::Sealed;
 use crate::sync::Arc;
 use crate::sys::fs as fs_imp;
@@ -343,6 +345,75 @@ fn inner(path: &Path, contents: &[u8]) -> io::Result<()> {
     inner(path.as_ref(), contents.as_ref())
 }
 
+/// Atomically replaces the entire contents of a file with a slice.
+///
+/// The contents are written to a new temporary file next to `path`, flushed to
+/// disk with [`File::sync_all`] and moved over `path` with [`rename`], after
+/// which the directory containing `path` is flushed as well. Other processes
+/// therefore see either the old or the new contents, never a mix of both, and
+/// once this function returns the new contents survive a crash or power loss.
+///
+/// If the function fails before the file is moved, `path` is left untouched and
+/// the temporary file is removed. If flushing the directory fails, the error is
+/// returned after `path` has been replaced: the new contents are visible, but
+/// may not survive a crash.
+///
+/// # Platform-specific behavior
+///
+/// On Unix, the permission bits of an existing file at `path` are applied to
+/// the new file, while a new file is created with mode `0o600`. The owner and
+/// other metadata are not preserved. If `path` is a symbolic link, the link
+/// itself is replaced by a regular file with mode `0o600`, and its target is
+/// left untouched. The directory is flushed with `fsync`.
+///
+/// On other platforms the new file has default permissions, and the directory
+/// is not flushed separately.
+/// Note that, this [may change in the future][changes].
+///
+/// [changes]: io#platform-specific-behavior
+///
+/// # Errors
+///
+/// This function fails if the directory containing `path` does not exist or
+/// is not writable, as well as in the cases [`rename`] fails in, such as when
+/// `path` is a directory.
+///
+/// # Examples
+///
+/// ```no_run
+/// #![feature(fs_write_atomic)]
+/// use std::fs;
+///
+/// fn main() -> std::io::Result<()> {
+///     fs::write_atomic("config.toml", "[server]\nport = 8080\n")?;
+///     Ok(())
+/// }
+/// ```
+#[unstable(feature = "fs_write_atomic", issue = "none")]
+pub fn write_atomic<P: AsRef<Path>, C: AsRef<[u8]>>(path: P, contents: C) -> io::Result<()> {
+    fn inner(path: &Path, contents: &[u8]) -> io::Result<()> {
+        let dir = match path.parent() {
+            Some(dir) if !dir.as_os_str().is_empty() => dir,
+            _ => Path::new("."),
+        };
+        let mut file = NamedTempFile::new_in(dir)?;
+        // The permissions of a symbolic link are meaningless, so it is replaced like
+        // a missing file.
+        #[cfg(unix)]
+        match symlink_metadata(path) {
+            Ok(metadata) if metadata.is_symlink() => {}
+            Ok(metadata) => file.as_file().set_permissions(metadata.permissions())?,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => {}
+            Err(e) => return Err(e),
+        }
+        file.write_all(contents)?;
+        file.as_file().sync_all()?;
+        file.persist(path)?;
+        fs_imp::sync_dir(dir)
+    }
+    inner(path.as_ref(), contents.as_ref())
+}
+
 impl File {
     /// Attempts to open a file in read-only mode.
     ///
@@ -570,6 +641,181 @@ pub fn set_len(&self, size: u64) -> io::Result<()> {
         self.inner.truncate(size)
     }
 
+    /// Acquires an exclusive advisory lock on the file, blocking until it can
+    /// be acquired.
+    ///
+    /// While a handle holds an exclusive lock, no other handle to the file can
+    /// acquire a shared or an exclusive lock on it. The lock is advisory: it
+    /// does not prevent other handles from reading or writing the file, it
+    /// only excludes other lockers. Calling this function on a handle that
+    /// already holds a lock is platform-specific: on Unix the existing lock is
+    /// converted, though not necessarily atomically, while on Windows the
+    /// handle ends up holding both locks, or deadlocks if it held a shared one.
+    ///
+    /// The lock is released by [`unlock`] or when the handle is closed. Handles
+    /// created by [`try_clone`] refer to the same lock, so it is only released
+    /// once all of them are closed.
+    ///
+    /// # Platform-specific behavior
+    ///
+    /// This function currently corresponds to `flock` with `LOCK_EX` on Linux,
+    /// Android, Apple platforms and the BSDs, to `fcntl` with `F_SETLKW` on
+    /// other Unix platforms, and to `LockFileEx` with `LOCKFILE_EXCLUSIVE_LOCK`
+    /// on Windows. Note that, [this may change in the future][changes].
+    ///
+    /// Locks taken with `fcntl` belong to the process rather than to the handle,
+    /// so two handles in the same process do not exclude each other there, and
+    /// closing any handle to the file releases the lock. On Windows the lock is
+    /// mandatory rather than advisory: other handles cannot read or write the
+    /// locked file.
+    ///
+    /// [changes]: io#platform-specific-behavior
+    ///
+    /// # Errors
+    ///
+    /// This function returns an error of kind [`io::ErrorKind::Unsupported`]
+    /// on platforms without file locking.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(file_lock)]
+    /// use std::fs::File;
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     let f = File::create("foo.txt")?;
+    ///     f.lock()?;
+    ///     Ok(())
+    /// }
+    /// ```
+    ///
+    /// [`unlock`]: File::unlock
+    /// [`try_clone`]: File::try_clone
+    #[unstable(feature = "file_lock", issue = "none")]
+    pub fn lock(&self) -> io::Result<()> {
+        self.inner.lock()
+    }
+
+    /// Acquires a shared advisory lock on the file, blocking until it can be
+    /// acquired.
+    ///
+    /// Any number of handles can hold a shared lock on the file at the same
+    /// time, but none of them while another handle holds an exclusive lock.
+    ///
+    /// See [`lock`] for when the lock is released, for locking a handle twice
+    /// and for the platform-specific behavior, with `LOCK_SH`, `F_RDLCK` and a
+    /// shared `LockFileEx` lock taking the place of their exclusive
+    /// counterparts. A shared lock taken with `fcntl` requires the file to be
+    /// opened for reading.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(file_lock)]
+    /// use std::fs::File;
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     let f = File::open("foo.txt")?;
+    ///     f.lock_shared()?;
+    ///     Ok(())
+    /// }
+    /// ```
+    ///
+    /// [`lock`]: File::lock
+    #[unstable(feature = "file_lock", issue = "none")]
+    pub fn lock_shared(&self) -> io::Result<()> {
+        self.inner.lock_shared()
+    }
+
+    /// Tries to acquire an exclusive advisory lock on the file without
+    /// blocking.
+    ///
+    /// This behaves like [`lock`], except that when another handle holds a
+    /// conflicting lock this function returns an error of kind
+    /// [`io::ErrorKind::WouldBlock`] instead of waiting for it to be released.
+    /// Any other error means that locking the file failed for another reason.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(file_lock)]
+    /// use std::fs::File;
+    /// use std::io::ErrorKind;
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     let f = File::create("foo.txt")?;
+    ///     match f.try_lock() {
+    ///         Ok(()) => println!("locked"),
+    ///         Err(e) if e.kind() == ErrorKind::WouldBlock => println!("already locked"),
+    ///         Err(e) => return Err(e),
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    ///
+    /// [`lock`]: File::lock
+    #[unstable(feature = "file_lock", issue = "none")]
+    pub fn try_lock(&self) -> io::Result<()> {
+        self.inner.try_lock()
+    }
+
+    /// Tries to acquire a shared advisory lock on the file without blocking.
+    ///
+    /// This behaves like [`lock_shared`], except that when another handle holds
+    /// an exclusive lock this function returns an error of kind
+    /// [`io::ErrorKind::WouldBlock`] instead of waiting for it to be released.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(file_lock)]
+    /// use std::fs::File;
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     let f = File::open("foo.txt")?;
+    ///     f.try_lock_shared()?;
+    ///     Ok(())
+    /// }
+    /// ```
+    ///
+    /// [`lock_shared`]: File::lock_shared
+    #[unstable(feature = "file_lock", issue = "none")]
+    pub fn try_lock_shared(&self) -> io::Result<()> {
+        self.inner.try_lock_shared()
+    }
+
+    /// Releases the lock held by this handle, if any.
+    ///
+    /// Calling this function on a handle that does not hold a lock succeeds
+    /// without doing anything.
+    ///
+    /// # Platform-specific behavior
+    ///
+    /// This function currently corresponds to `flock` with `LOCK_UN` on Linux,
+    /// Android, Apple platforms and the BSDs, to `fcntl` with `F_UNLCK` on other
+    /// Unix platforms, and to `UnlockFile` on Windows. Note that, [this may
+    /// change in the future][changes].
+    ///
+    /// [changes]: io#platform-specific-behavior
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(file_lock)]
+    /// use std::fs::File;
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     let f = File::create("foo.txt")?;
+    ///     f.lock()?;
+    ///     f.unlock()?;
+    ///     Ok(())
+    /// }
+    /// ```
+    #[unstable(feature = "file_lock", issue = "none")]
+    pub fn unlock(&self) -> io::Result<()> {
+        self.inner.unlock()
+    }
+
     /// Queries metadata about the underlying file.
     ///
     /// # Examples
@@ -2474,6 +2720,721 @@ pub fn read_dir<P: AsRef<Path>>(path: P) -> io::Result<ReadDir> {
     fs_imp::readdir(path.as_ref()).map(ReadDir)
 }
 
+/// A builder for recursively walking a directory tree.
+///
+/// The walk is started by turning the builder into an iterator with
+/// [`IntoIterator::into_iter`], or directly with a `for` loop. The resulting
+/// [`Walk`] yields the root itself first and then every entry below it, each
+/// directory being followed immediately by its contents (pre-order).
+///
+/// By default the walk has no depth limit, does not follow symbolic links
+/// found below the root and yields entries in the order the platform returns
+/// them.
+///
+/// # Errors
+///
+/// Errors are reported per entry: if a directory cannot be read, or the
+/// metadata of an entry cannot be queried, the iterator yields an [`Err`] for
+/// it and carries on with the rest of the tree rather than ending the walk.
+///
+/// # Platform-specific behavior
+///
+/// Where `openat` is available on Unix, subdirectories are opened relative to
+/// the descriptor of their already opened parent, in the same way as
+/// [`remove_dir_all`]. When symbolic links are not being followed, a
+/// directory that is replaced by a symbolic link while the walk is in progress
+/// is therefore reported as an error instead of being traversed. On other
+/// platforms subdirectories are opened by path.
+///
+/// One directory handle is kept open for every level between the root and the
+/// entry currently being visited.
+///
+/// # Examples
+///
+/// ```no_run
+/// #![feature(fs_walk_dir)]
+/// use std::fs::WalkDir;
+///
+/// fn main() -> std::io::Result<()> {
+///     for entry in WalkDir::new("src").max_depth(2).sort_by_file_name(true) {
+///         let entry = entry?;
+///         println!("{}{}", "  ".repeat(entry.depth()), entry.file_name().to_string_lossy());
+///     }
+///     Ok(())
+/// }
+/// ```
+#[unstable(feature = "fs_walk_dir", issue = "none")]
+#[derive(Clone, Debug)]
+pub struct WalkDir {
+    root: PathBuf,
+    min_depth: usize,
+    max_depth: usize,
+    follow_links: bool,
+    sort_by_file_name: bool,
+}
+
+/// Iterator over the entries of a directory tree.
+///
+/// This iterator is created from a [`WalkDir`] and yields instances of
+/// <code>[io::Result]<[WalkEntry]></code>.
+#[unstable(feature = "fs_walk_dir", issue = "none")]
+#[derive(Debug)]
+pub struct Walk {
+    opts: WalkDir,
+    /// The root, until it has been yielded.
+    start: Option<PathBuf>,
+    /// A directory which has been yielded and is to be opened next.
+    pending: Option<PendingDir>,
+    /// The directories currently being read, innermost last.
+    stack: Vec<WalkLevel>,
+}
+
+/// An entry returned by the [`Walk`] iterator.
+#[unstable(feature = "fs_walk_dir", issue = "none")]
+#[derive(Clone, Debug)]
+pub struct WalkEntry {
+    path: PathBuf,
+    depth: usize,
+    file_type: FileType,
+    path_is_symlink: bool,
+    follow_link: bool,
+}
+
+#[derive(Debug)]
+enum PendingDir {
+    Root(PathBuf, Option<DirId>),
+    Child(DirEntry, Option<DirId>),
+}
+
+#[derive(Debug)]
+struct WalkLevel {
+    dir: ReadDir,
+    sorted: Option<crate::vec::IntoIter<io::Result<DirEntry>>>,
+    /// Only known when following symbolic links, where it is used to detect
+    /// loops.
+    id: Option<DirId>,
+}
+
+#[cfg(unix)]
+type DirId = (u64, u64);
+#[cfg(not(unix))]
+type DirId = PathBuf;
+
+/// Identifies a directory for the purpose of loop detection.
+#[cfg(unix)]
+fn dir_id(_path: &Path, metadata: &Metadata) -> io::Result<DirId> {
+    use crate::os::unix::fs::MetadataExt;
+    Ok((metadata.dev(), metadata.ino()))
+}
+
+#[cfg(not(unix))]
+fn dir_id(path: &Path, _metadata: &Metadata) -> io::Result<DirId> {
+    canonicalize(path)
+}
+
+impl WalkDir {
+    /// Creates a builder for walking the directory tree rooted at `root`.
+    #[unstable(feature = "fs_walk_dir", issue = "none")]
+    pub fn new<P: AsRef<Path>>(root: P) -> WalkDir {
+        WalkDir {
+            root: root.as_ref().to_path_buf(),
+            min_depth: 0,
+            max_depth: usize::MAX,
+            follow_links: false,
+            sort_by_file_name: false,
+        }
+    }
+
+    /// Sets the minimum depth of the entries which are yielded.
+    ///
+    /// The root has depth 0 and its direct children have depth 1. Entries
+    /// which are shallower than `depth` are still traversed, but not yielded.
+    /// The default is 0.
+    #[unstable(feature = "fs_walk_dir", issue = "none")]
+    pub fn min_depth(mut self, depth: usize) -> WalkDir {
+        self.min_depth = depth;
+        self
+    }
+
+    /// Sets the maximum depth of the entries which are yielded.
+    ///
+    /// Directories at this depth are yielded but not descended into. A depth
+    /// of 0 yields only the root. There is no limit by default.
+    #[unstable(feature = "fs_walk_dir", issue = "none")]
+    pub fn max_depth(mut self, depth: usize) -> WalkDir {
+        self.max_depth = depth;
+        self
+    }
+
+    /// Sets whether symbolic links below the root are followed.
+    ///
+    /// When set, a symbolic link to a directory is descended into and the
+    /// entries report the type of the link's target. Symbolic links which
+    /// would lead back into one of their own ancestors are reported as an
+    /// error of kind [`io::ErrorKind::FilesystemLoop`] instead of being
+    /// followed; on Unix ancestors are identified by their device and inode
+    /// numbers.
+    ///
+    /// The root itself is always followed if it is a symbolic link. The
+    /// default is `false`.
+    #[unstable(feature = "fs_walk_dir", issue = "none")]
+    pub fn follow_links(mut self, follow: bool) -> WalkDir {
+        self.follow_links = follow;
+        self
+    }
+
+    /// Sets whether the entries of each directory are yielded sorted by file
+    /// name.
+    ///
+    /// This requires reading a directory completely before yielding its first
+    /// entry. When unset, entries are yielded in the order the platform
+    /// returns them, which is not guaranteed. The default is `false`.
+    #[unstable(feature = "fs_walk_dir", issue = "none")]
+    pub fn sort_by_file_name(mut self, sort: bool) -> WalkDir {
+        self.sort_by_file_name = sort;
+        self
+    }
+}
+
+#[unstable(feature = "fs_walk_dir", issue = "none")]
+impl IntoIterator for WalkDir {
+    type Item = io::Result<WalkEntry>;
+    type IntoIter = Walk;
+
+    fn into_iter(self) -> Walk {
+        Walk { start: Some(self.root.clone()), opts: self, pending: None, stack: Vec::new() }
+    }
+}
+
+impl Walk {
+    fn visit_root(&mut self, path: PathBuf) -> io::Result<WalkEntry> {
+        let path_is_symlink = symlink_metadata(&path)?.file_type().is_symlink();
+        let md = metadata(&path)?;
+        let file_type = md.file_type();
+        if file_type.is_dir() && self.opts.max_depth > 0 {
+            let id = if self.opts.follow_links { Some(dir_id(&path, &md)?) } else { None };
+            self.pending = Some(PendingDir::Root(path.clone(), id));
+        }
+        Ok(WalkEntry { path, depth: 0, file_type, path_is_symlink, follow_link: path_is_symlink })
+    }
+
+    fn open(&mut self, pending: PendingDir) -> io::Result<()> {
+        let (mut dir, id) = match pending {
+            PendingDir::Root(path, id) => (read_dir(path)?, id),
+            PendingDir::Child(entry, id) => {
+                // Children are always read through the parent opened just before
+                // them, which is the innermost directory on the stack.
+                let parent = &self.stack.last().unwrap().dir;
+                (ReadDir(fs_imp::readdir_child(&parent.0, &entry.0, self.opts.follow_links)?), id)
+            }
+        };
+        let sorted = if self.opts.sort_by_file_name {
+            let mut entries: Vec<_> = dir.by_ref().collect();
+            entries.sort_by_cached_key(|entry| entry.as_ref().ok().map(DirEntry::file_name));
+            Some(entries.into_iter())
+        } else {
+            None
+        };
+        self.stack.push(WalkLevel { dir, sorted, id });
+        Ok(())
+    }
+
+    /// Builds the entry for a child of the innermost open directory, and
+    /// schedules it to be opened if it is a directory to descend into.
+    fn visit(&mut self, entry: DirEntry) -> io::Result<WalkEntry> {
+        let depth = self.stack.len();
+        let path = entry.path();
+        let mut file_type = entry.file_type()?;
+        let path_is_symlink = file_type.is_symlink();
+        let mut target_metadata = None;
+        if path_is_symlink && self.opts.follow_links {
+            let md = metadata(&path)?;
+            file_type = md.file_type();
+            target_metadata = Some(md);
+        }
+
+        if file_type.is_dir() && depth < self.opts.max_depth {
+            let id = if self.opts.follow_links {
+                let md = match target_metadata {
+                    Some(md) => md,
+                    None => entry.metadata()?,
+                };
+                let id = dir_id(&path, &md)?;
+                if self.stack.iter().any(|level| level.id.as_ref() == Some(&id)) {
+                    return Err(io::const_io_error!(
+                        io::ErrorKind::FilesystemLoop,
+                        "symbolic link leads back to one of its ancestors",
+                    ));
+                }
+                Some(id)
+            } else {
+                None
+            };
+            self.pending = Some(PendingDir::Child(entry, id));
+        }
+
+        let follow_link = path_is_symlink && self.opts.follow_links;
+        Ok(WalkEntry { path, depth, file_type, path_is_symlink, follow_link })
+    }
+}
+
+#[unstable(feature = "fs_walk_dir", issue = "none")]
+impl Iterator for Walk {
+    type Item = io::Result<WalkEntry>;
+
+    fn next(&mut self) -> Option<io::Result<WalkEntry>> {
+        loop {
+            if let Some(pending) = self.pending.take() {
+                if let Err(e) = self.open(pending) {
+                    return Some(Err(e));
+                }
+            }
+
+            if let Some(root) = self.start.take() {
+                let entry = match self.visit_root(root) {
+                    Ok(entry) => entry,
+                    Err(e) => return Some(Err(e)),
+                };
+                if self.opts.min_depth == 0 {
+                    return Some(Ok(entry));
+                }
+                continue;
+            }
+
+            let level = self.stack.last_mut()?;
+            let next = match &mut level.sorted {
+                Some(entries) => entries.next(),
+                None => level.dir.next(),
+            };
+            let entry = match next {
+                Some(Ok(entry)) => entry,
+                Some(Err(e)) => return Some(Err(e)),
+                None => {
+                    self.stack.pop();
+                    continue;
+                }
+            };
+            match self.visit(entry) {
+                Ok(entry) if entry.depth < self.opts.min_depth => continue,
+                result => return Some(result),
+            }
+        }
+    }
+}
+
+impl WalkEntry {
+    /// Returns the path of this entry.
+    ///
+    /// The path is made by joining the root given to [`WalkDir::new`] with the
+    /// file names of the directories leading to this entry.
+    #[unstable(feature = "fs_walk_dir", issue = "none")]
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Consumes the entry, returning its path.
+    #[unstable(feature = "fs_walk_dir", issue = "none")]
+    pub fn into_path(self) -> PathBuf {
+        self.path
+    }
+
+    /// Returns the file name of this entry.
+    ///
+    /// For the root, this is the whole path if it has no file name, such as
+    /// for `.` or `/`.
+    #[unstable(feature = "fs_walk_dir", issue = "none")]
+    pub fn file_name(&self) -> &OsStr {
+        self.path.file_name().unwrap_or(self.path.as_os_str())
+    }
+
+    /// Returns the depth of this entry below the root, the root being at
+    /// depth 0.
+    #[unstable(feature = "fs_walk_dir", issue = "none")]
+    pub fn depth(&self) -> usize {
+        self.depth
+    }
+
+    /// Returns the file type of this entry.
+    ///
+    /// If this entry is a symbolic link which was followed, this is the type
+    /// of the link's target.
+    #[unstable(feature = "fs_walk_dir", issue = "none")]
+    pub fn file_type(&self) -> FileType {
+        self.file_type
+    }
+
+    /// Returns `true` if the path of this entry is a symbolic link, whether or
+    /// not it was followed.
+    #[unstable(feature = "fs_walk_dir", issue = "none")]
+    pub fn path_is_symlink(&self) -> bool {
+        self.path_is_symlink
+    }
+
+    /// Queries the metadata of this entry.
+    ///
+    /// This follows the entry if it is a symbolic link which the walk followed,
+    /// and is equivalent to [`symlink_metadata`] otherwise.
+    #[unstable(feature = "fs_walk_dir", issue = "none")]
+    pub fn metadata(&self) -> io::Result<Metadata> {
+        if self.follow_link { metadata(&self.path) } else { symlink_metadata(&self.path) }
+    }
+}
+
+/// Creates a new anonymous temporary file in [`env::temp_dir`].
+///
+/// See [`tempfile_in`] for details.
+///
+/// [`env::temp_dir`]: crate::env::temp_dir
+///
+/// # Examples
+///
+/// ```no_run
+/// #![feature(fs_temp)]
+/// use std::fs;
+/// use std::io::{Read, Seek, Write};
+///
+/// fn main() -> std::io::Result<()> {
+///     let mut file = fs::tempfile()?;
+///     file.write_all(b"scratch data")?;
+///     file.rewind()?;
+///     let mut data = String::new();
+///     file.read_to_string(&mut data)?;
+///     assert_eq!(data, "scratch data");
+///     Ok(())
+/// }
+/// ```
+#[unstable(feature = "fs_temp", issue = "none")]
+pub fn tempfile() -> io::Result<File> {
+    tempfile_in(crate::env::temp_dir())
+}
+
+/// Creates a new anonymous temporary file in `dir`, opened for reading and
+/// writing.
+///
+/// The file has no path that other processes could use to open it, or it is
+/// given one only for as long as it takes to remove it again. It is deleted
+/// once the returned [`File`] and any handles duplicated from it are closed,
+/// even if the process does not exit cleanly. Use [`NamedTempFile`] if the file
+/// needs a path.
+///
+/// # Platform-specific behavior
+///
+/// On Linux and Android this uses `open` with `O_TMPFILE` if both the kernel
+/// and the file system of `dir` support it. Otherwise on Unix the file is
+/// created with a random name, `O_EXCL` and mode `0o600` and is then unlinked
+/// immediately. On Windows the file is created with a random name and
+/// `FILE_FLAG_DELETE_ON_CLOSE`, and cannot be opened again while it exists.
+/// Note that, this [may change in the future][changes].
+///
+/// [changes]: io#platform-specific-behavior
+#[unstable(feature = "fs_temp", issue = "none")]
+pub fn tempfile_in<P: AsRef<Path>>(dir: P) -> io::Result<File> {
+    fs_imp::tempfile_in(dir.as_ref())
+}
+
+/// A temporary file with a path, which is removed when dropped.
+///
+/// The file is created atomically with a random name, failing rather than
+/// opening a file which already exists, and on Unix with mode `0o600` so that
+/// other users cannot access it.
+///
+/// Removing the file when the `NamedTempFile` is dropped may fail silently;
+/// use [`close`] to observe the error. Since nothing removes the file if the
+/// process does not exit cleanly, prefer [`tempfile`] where a path is not
+/// needed.
+///
+/// [`close`]: NamedTempFile::close
+///
+/// # Examples
+///
+/// Writing a file under a temporary name and atomically moving it into place
+/// once it is complete:
+///
+/// ```no_run
+/// #![feature(fs_temp)]
+/// use std::fs::NamedTempFile;
+/// use std::io::Write;
+///
+/// fn main() -> std::io::Result<()> {
+///     let mut file = NamedTempFile::new_in(".")?;
+///     file.write_all(b"[settings]\n")?;
+///     file.as_file().sync_all()?;
+///     file.persist("settings.toml")?;
+///     Ok(())
+/// }
+/// ```
+#[unstable(feature = "fs_temp", issue = "none")]
+#[derive(Debug)]
+pub struct NamedTempFile {
+    path: PathBuf,
+    file: File,
+}
+
+/// The error returned by [`NamedTempFile::persist`], which gives back the
+/// temporary file.
+#[unstable(feature = "fs_temp", issue = "none")]
+#[derive(Debug)]
+pub struct PersistError {
+    error: io::Error,
+    file: NamedTempFile,
+}
+
+/// A temporary directory, which is removed with all of its contents when
+/// dropped.
+///
+/// The directory is created atomically with a random name, and on Unix with
+/// mode `0o700` so that other users cannot access it.
+///
+/// Removing the directory when the `TempDir` is dropped may fail silently; use
+/// [`close`] to observe the error.
+///
+/// [`close`]: TempDir::close
+///
+/// # Examples
+///
+/// ```no_run
+/// #![feature(fs_temp)]
+/// use std::fs::{self, TempDir};
+///
+/// fn main() -> std::io::Result<()> {
+///     let dir = TempDir::new()?;
+///     fs::write(dir.path().join("input.txt"), "test input")?;
+///     // ...
+///     dir.close()
+/// }
+/// ```
+#[unstable(feature = "fs_temp", issue = "none")]
+#[derive(Debug)]
+pub struct TempDir {
+    path: PathBuf,
+}
+
+impl NamedTempFile {
+    /// Creates a new temporary file in [`env::temp_dir`].
+    ///
+    /// [`env::temp_dir`]: crate::env::temp_dir
+    #[unstable(feature = "fs_temp", issue = "none")]
+    pub fn new() -> io::Result<NamedTempFile> {
+        NamedTempFile::new_in(crate::env::temp_dir())
+    }
+
+    /// Creates a new temporary file in `dir`.
+    ///
+    /// To be able to [`persist`] the file by renaming it, `dir` should be on the
+    /// same file system as its final location.
+    ///
+    /// [`persist`]: NamedTempFile::persist
+    #[unstable(feature = "fs_temp", issue = "none")]
+    pub fn new_in<P: AsRef<Path>>(dir: P) -> io::Result<NamedTempFile> {
+        let (path, file) =
+            crate::sys_common::fs::create_temp(dir.as_ref(), fs_imp::create_temp_file)?;
+        Ok(NamedTempFile { path, file })
+    }
+
+    /// Returns the path of the file.
+    #[unstable(feature = "fs_temp", issue = "none")]
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Returns a reference to the open file.
+    #[unstable(feature = "fs_temp", issue = "none")]
+    pub fn as_file(&self) -> &File {
+        &self.file
+    }
+
+    /// Returns a mutable reference to the open file.
+    #[unstable(feature = "fs_temp", issue = "none")]
+    pub fn as_file_mut(&mut self) -> &mut File {
+        &mut self.file
+    }
+
+    /// Atomically moves the file to `new_path`, returning the open file which
+    /// is no longer removed.
+    ///
+    /// This uses [`rename`], so an existing file at `new_path` is replaced
+    /// and readers see either the old or the new file, never a partially
+    /// written one. Data written to the file should be flushed to disk first
+    /// with [`File::sync_all`] if it needs to survive a crash.
+    ///
+    /// # Errors
+    ///
+    /// If the file cannot be renamed, for example because `new_path` is on a
+    /// different file system, the error is returned along with the temporary
+    /// file, which has not been removed.
+    #[unstable(feature = "fs_temp", issue = "none")]
+    pub fn persist<P: AsRef<Path>>(self, new_path: P) -> Result<File, PersistError> {
+        match rename(&self.path, new_path) {
+            Ok(()) => Ok(self.keep().0),
+            Err(error) => Err(PersistError { error, file: self }),
+        }
+    }
+
+    /// Keeps the file at its current path rather than removing it, returning
+    /// the open file and its path.
+    #[unstable(feature = "fs_temp", issue = "none")]
+    pub fn keep(self) -> (File, PathBuf) {
+        let this = mem::ManuallyDrop::new(self);
+        // SAFETY: `this` is never used or dropped again, so each field is
+        // moved out exactly once.
+        unsafe { (ptr::read(&this.file), ptr::read(&this.path)) }
+    }
+
+    /// Closes and removes the file, reporting any error in removing it.
+    #[unstable(feature = "fs_temp", issue = "none")]
+    pub fn close(self) -> io::Result<()> {
+        let (file, path) = self.keep();
+        drop(file);
+        remove_file(path)
+    }
+}
+
+#[unstable(feature = "fs_temp", issue = "none")]
+impl Drop for NamedTempFile {
+    fn drop(&mut self) {
+        let _ = remove_file(&self.path);
+    }
+}
+
+#[unstable(feature = "fs_temp", issue = "none")]
+impl Read for NamedTempFile {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.file.read(buf)
+    }
+    fn read_vectored(&mut self, bufs: &mut [IoSliceMut<'_>]) -> io::Result<usize> {
+        self.file.read_vectored(bufs)
+    }
+    fn read_buf(&mut self, cursor: BorrowedCursor<'_>) -> io::Result<()> {
+        self.file.read_buf(cursor)
+    }
+    #[inline]
+    fn is_read_vectored(&self) -> bool {
+        self.file.is_read_vectored()
+    }
+}
+
+#[unstable(feature = "fs_temp", issue = "none")]
+impl Write for NamedTempFile {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.file.write(buf)
+    }
+    fn write_vectored(&mut self, bufs: &[IoSlice<'_>]) -> io::Result<usize> {
+        self.file.write_vectored(bufs)
+    }
+    #[inline]
+    fn is_write_vectored(&self) -> bool {
+        self.file.is_write_vectored()
+    }
+    fn flush(&mut self) -> io::Result<()> {
+        self.file.flush()
+    }
+}
+
+#[unstable(feature = "fs_temp", issue = "none")]
+impl Seek for NamedTempFile {
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        self.file.seek(pos)
+    }
+}
+
+impl PersistError {
+    /// Returns the error which caused the file not to be persisted.
+    #[unstable(feature = "fs_temp", issue = "none")]
+    pub fn error(&self) -> &io::Error {
+        &self.error
+    }
+
+    /// Returns the temporary file which could not be persisted.
+    #[unstable(feature = "fs_temp", issue = "none")]
+    pub fn into_file(self) -> NamedTempFile {
+        self.file
+    }
+
+    /// Consumes the error, returning the underlying error and removing the
+    /// temporary file.
+    #[unstable(feature = "fs_temp", issue = "none")]
+    pub fn into_error(self) -> io::Error {
+        self.error
+    }
+}
+
+#[unstable(feature = "fs_temp", issue = "none")]
+impl From<PersistError> for io::Error {
+    fn from(err: PersistError) -> io::Error {
+        err.error
+    }
+}
+
+#[unstable(feature = "fs_temp", issue = "none")]
+impl fmt::Display for PersistError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "failed to persist temporary file: {}", self.error)
+    }
+}
+
+#[unstable(feature = "fs_temp", issue = "none")]
+impl crate::error::Error for PersistError {
+    #[allow(deprecated)]
+    fn description(&self) -> &str {
+        "failed to persist temporary file"
+    }
+
+    fn source(&self) -> Option<&(dyn crate::error::Error + 'static)> {
+        Some(&self.error)
+    }
+}
+
+impl TempDir {
+    /// Creates a new temporary directory in [`env::temp_dir`].
+    ///
+    /// [`env::temp_dir`]: crate::env::temp_dir
+    #[unstable(feature = "fs_temp", issue = "none")]
+    pub fn new() -> io::Result<TempDir> {
+        TempDir::new_in(crate::env::temp_dir())
+    }
+
+    /// Creates a new temporary directory in `dir`.
+    #[unstable(feature = "fs_temp", issue = "none")]
+    pub fn new_in<P: AsRef<Path>>(dir: P) -> io::Result<TempDir> {
+        let (path, ()) = crate::sys_common::fs::create_temp(dir.as_ref(), fs_imp::create_temp_dir)?;
+        Ok(TempDir { path })
+    }
+
+    /// Returns the path of the directory.
+    #[unstable(feature = "fs_temp", issue = "none")]
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Keeps the directory rather than removing it, returning its path.
+    #[unstable(feature = "fs_temp", issue = "none")]
+    pub fn keep(self) -> PathBuf {
+        let mut this = mem::ManuallyDrop::new(self);
+        mem::take(&mut this.path)
+    }
+
+    /// Removes the directory and all of its contents, reporting any error in
+    /// doing so.
+    #[unstable(feature = "fs_temp", issue = "none")]
+    pub fn close(self) -> io::Result<()> {
+        remove_dir_all(self.keep())
+    }
+}
+
+#[unstable(feature = "fs_temp", issue = "none")]
+impl AsRef<Path> for TempDir {
+    fn as_ref(&self) -> &Path {
+        &self.path
+    }
+}
+
+#[unstable(feature = "fs_temp", issue = "none")]
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        let _ = remove_dir_all(&self.path);
+    }
+}
+
 /// Changes the permissions found on a file or a directory.
 ///
 /// # Platform-specific behavior
@@ -268,6 +268,69 @@ fn file_test_io_eof() {
     check!(fs::remove_file(&filename));
 }
 
+// Locks taken with `fcntl` don't exclude other handles in the same process.
+#[test]
+#[cfg(any(windows, target_os = "linux", target_os = "android", target_os = "macos"))]
+fn file_lock_multiple_shared() {
+    let tmpdir = tmpdir();
+    let filename = &tmpdir.join("file_lock_multiple_shared_test.txt");
+    let f1 = check!(File::create(filename));
+    let f2 = check!(OpenOptions::new().write(true).open(filename));
+
+    // Check that we can acquire concurrent shared locks
+    check!(f1.lock_shared());
+    check!(f2.lock_shared());
+    check!(f1.unlock());
+    check!(f2.unlock());
+    check!(f1.try_lock_shared());
+    check!(f2.try_lock_shared());
+}
+
+#[test]
+#[cfg(any(windows, target_os = "linux", target_os = "android", target_os = "macos"))]
+fn file_lock_blocking() {
+    let tmpdir = tmpdir();
+    let filename = &tmpdir.join("file_lock_blocking_test.txt");
+    let f1 = check!(File::create(filename));
+    let f2 = check!(OpenOptions::new().write(true).open(filename));
+
+    // Check that shared locks block exclusive locks
+    check!(f1.lock_shared());
+    assert_eq!(f2.try_lock().unwrap_err().kind(), ErrorKind::WouldBlock);
+    check!(f1.unlock());
+
+    // Check that exclusive locks block shared and exclusive locks
+    check!(f1.lock());
+    assert_eq!(f2.try_lock_shared().unwrap_err().kind(), ErrorKind::WouldBlock);
+    assert_eq!(f2.try_lock().unwrap_err().kind(), ErrorKind::WouldBlock);
+
+    // Check that a blocking lock waits for the lock to be released
+    let waiter = thread::spawn(move || {
+        check!(f2.lock());
+        check!(f2.unlock());
+    });
+    thread::sleep(Duration::from_millis(100));
+    check!(f1.unlock());
+    waiter.join().unwrap();
+}
+
+#[test]
+#[cfg(any(windows, target_os = "linux", target_os = "android", target_os = "macos"))]
+fn file_lock_drop() {
+    let tmpdir = tmpdir();
+    let filename = &tmpdir.join("file_lock_drop_test.txt");
+    let f1 = check!(File::create(filename));
+    let f2 = check!(OpenOptions::new().write(true).open(filename));
+
+    // Check that closing the handle releases its lock
+    check!(f1.lock());
+    drop(f1);
+    check!(f2.try_lock());
+    // Unlocking a handle twice is fine
+    check!(f2.unlock());
+    check!(f2.unlock());
+}
+
 #[test]
 #[cfg(unix)]
 fn file_test_io_read_write_at() {
@@ -1620,6 +1683,330 @@ fn test_read_dir_infinite_loop() {
     assert!(dir.filter(|e| e.is_err()).take(2).count() < 2);
 }
 
+#[test]
+fn walk_dir_sorted() {
+    let tmpdir = tmpdir();
+    let root = tmpdir.join("root");
+    check!(fs::create_dir_all(root.join("b/d")));
+    check!(fs::create_dir_all(root.join("a")));
+    check!(File::create(root.join("a/c")));
+    check!(File::create(root.join("b/d/e")));
+    check!(File::create(root.join("f")));
+
+    let entries: Vec<_> = fs::WalkDir::new(&root)
+        .sort_by_file_name(true)
+        .into_iter()
+        .map(|entry| {
+            let entry = check!(entry);
+            let path = entry.path().strip_prefix(&root).unwrap().to_path_buf();
+            (path, entry.depth(), entry.file_type().is_dir())
+        })
+        .collect();
+    let expected = [
+        ("", 0, true),
+        ("a", 1, true),
+        ("a/c", 2, false),
+        ("b", 1, true),
+        ("b/d", 2, true),
+        ("b/d/e", 3, false),
+        ("f", 1, false),
+    ];
+    let expected: Vec<_> = expected
+        .iter()
+        .map(|&(path, depth, is_dir)| (Path::new(path).to_path_buf(), depth, is_dir))
+        .collect();
+    assert_eq!(entries, expected);
+}
+
+#[test]
+fn walk_dir_depth_limits() {
+    let tmpdir = tmpdir();
+    check!(fs::create_dir_all(tmpdir.join("a/b/c/d")));
+
+    let depths = |walk: fs::WalkDir| -> Vec<usize> {
+        walk.into_iter().map(|entry| check!(entry).depth()).collect()
+    };
+    assert_eq!(depths(fs::WalkDir::new(tmpdir.path())), [0, 1, 2, 3, 4]);
+    assert_eq!(depths(fs::WalkDir::new(tmpdir.path()).max_depth(0)), [0]);
+    assert_eq!(depths(fs::WalkDir::new(tmpdir.path()).max_depth(2)), [0, 1, 2]);
+    assert_eq!(depths(fs::WalkDir::new(tmpdir.path()).min_depth(2).max_depth(3)), [2, 3]);
+}
+
+#[test]
+fn walk_dir_of_file() {
+    let tmpdir = tmpdir();
+    let file = tmpdir.join("file");
+    check!(File::create(&file));
+
+    let entries: Vec<_> = fs::WalkDir::new(&file).into_iter().map(|e| check!(e)).collect();
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0].path(), file);
+    assert!(entries[0].file_type().is_file());
+    assert!(fs::WalkDir::new(tmpdir.join("missing")).into_iter().next().unwrap().is_err());
+}
+
+#[test]
+fn walk_dir_symlinks() {
+    let tmpdir = tmpdir();
+    if !got_symlink_permission(&tmpdir) {
+        return;
+    };
+    let dir = tmpdir.join("dir");
+    check!(fs::create_dir_all(dir.join("sub")));
+    check!(File::create(dir.join("sub/file")));
+    check!(symlink_dir(&dir.join("sub"), &dir.join("link")));
+
+    // Not following links, the link is reported but not descended into.
+    let mut names: Vec<_> = fs::WalkDir::new(&dir)
+        .min_depth(1)
+        .into_iter()
+        .map(|e| {
+            let e = check!(e);
+            assert_eq!(e.path_is_symlink(), e.file_name() == "link");
+            e.path().strip_prefix(&dir).unwrap().to_path_buf()
+        })
+        .collect();
+    names.sort();
+    assert_eq!(names, [Path::new("link"), Path::new("sub"), &Path::new("sub").join("file")]);
+
+    // Following links, the link's target is walked through it as well.
+    let mut names: Vec<_> = fs::WalkDir::new(&dir)
+        .min_depth(1)
+        .follow_links(true)
+        .into_iter()
+        .map(|e| {
+            let e = check!(e);
+            assert!(!e.path_is_symlink() || e.file_type().is_dir());
+            e.path().strip_prefix(&dir).unwrap().to_path_buf()
+        })
+        .collect();
+    names.sort();
+    let expected = [
+        Path::new("link").to_path_buf(),
+        Path::new("link").join("file"),
+        Path::new("sub").to_path_buf(),
+        Path::new("sub").join("file"),
+    ];
+    assert_eq!(names, expected);
+}
+
+#[test]
+fn walk_dir_symlink_loop() {
+    let tmpdir = tmpdir();
+    if !got_symlink_permission(&tmpdir) {
+        return;
+    };
+    let dir = tmpdir.join("dir");
+    check!(fs::create_dir_all(dir.join("a")));
+    check!(symlink_dir(&dir, &dir.join("a/up")));
+    check!(File::create(dir.join("file")));
+
+    // The loop is reported as an error for the offending link, and the rest of
+    // the tree is still walked.
+    let mut loops = 0;
+    let mut entries = 0;
+    for entry in fs::WalkDir::new(&dir).follow_links(true) {
+        match entry {
+            Ok(_) => entries += 1,
+            Err(e) => {
+                assert_eq!(e.kind(), ErrorKind::FilesystemLoop);
+                loops += 1;
+            }
+        }
+    }
+    assert_eq!(loops, 1);
+    assert_eq!(entries, 3);
+}
+
+#[test]
+#[cfg(unix)]
+fn walk_dir_unreadable_dir() {
+    use crate::os::unix::fs::PermissionsExt;
+
+    let tmpdir = tmpdir();
+    let locked = tmpdir.join("locked");
+    check!(fs::create_dir_all(&locked));
+    check!(File::create(locked.join("file")));
+    check!(File::create(tmpdir.join("other")));
+    check!(fs::set_permissions(&locked, fs::Permissions::from_mode(0o000)));
+    if fs::read_dir(&locked).is_ok() {
+        // Running with privileges which ignore permissions.
+        check!(fs::set_permissions(&locked, fs::Permissions::from_mode(0o755)));
+        return;
+    }
+
+    let results: Vec<_> = fs::WalkDir::new(tmpdir.path()).into_iter().collect();
+    check!(fs::set_permissions(&locked, fs::Permissions::from_mode(0o755)));
+
+    // The locked directory itself is yielded, followed by an error for reading
+    // it, and the walk carries on to its siblings.
+    assert_eq!(results.len(), 4);
+    assert_eq!(results.iter().filter(|r| r.is_err()).count(), 1);
+    assert!(results.iter().any(|r| r.as_ref().is_ok_and(|e| e.file_name() == "other")));
+}
+
+#[test]
+fn tempfile_is_anonymous() {
+    let tmpdir = tmpdir();
+    let mut file = check!(fs::tempfile_in(tmpdir.path()));
+    check!(file.write_all(b"hello"));
+    check!(file.seek(SeekFrom::Start(0)));
+    let mut buf = String::new();
+    check!(file.read_to_string(&mut buf));
+    assert_eq!(buf, "hello");
+    assert_eq!(check!(fs::read_dir(tmpdir.path())).count(), 0);
+}
+
+#[test]
+fn named_temp_file_removed_on_drop() {
+    let tmpdir = tmpdir();
+    let mut file = check!(fs::NamedTempFile::new_in(tmpdir.path()));
+    let path = file.path().to_path_buf();
+    assert_eq!(path.parent(), Some(tmpdir.path()));
+    check!(file.write_all(b"hello"));
+    assert_eq!(check!(fs::read(&path)), b"hello");
+    drop(file);
+    assert!(!path.exists());
+
+    let file = check!(fs::NamedTempFile::new_in(tmpdir.path()));
+    let path = file.path().to_path_buf();
+    check!(file.close());
+    assert!(!path.exists());
+}
+
+#[test]
+fn named_temp_file_unique() {
+    let tmpdir = tmpdir();
+    let a = check!(fs::NamedTempFile::new_in(tmpdir.path()));
+    let b = check!(fs::NamedTempFile::new_in(tmpdir.path()));
+    assert_ne!(a.path(), b.path());
+}
+
+#[test]
+fn named_temp_file_persist() {
+    let tmpdir = tmpdir();
+    let target = tmpdir.join("target");
+    check!(fs::write(&target, b"old"));
+
+    let mut file = check!(fs::NamedTempFile::new_in(tmpdir.path()));
+    let temp_path = file.path().to_path_buf();
+    check!(file.write_all(b"new"));
+    let mut file = check!(file.persist(&target));
+    assert!(!temp_path.exists());
+    assert_eq!(check!(fs::read(&target)), b"new");
+    check!(file.write_all(b"er"));
+    drop(file);
+    assert_eq!(check!(fs::read(&target)), b"newer");
+
+    // A failed persist gives back the temporary file, which is still removed
+    // once dropped.
+    let file = check!(fs::NamedTempFile::new_in(tmpdir.path()));
+    let err = file.persist(tmpdir.join("missing").join("target")).unwrap_err();
+    assert_eq!(err.error().kind(), ErrorKind::NotFound);
+    let file = err.into_file();
+    let temp_path = file.path().to_path_buf();
+    assert!(temp_path.exists());
+    drop(file);
+    assert!(!temp_path.exists());
+}
+
+#[test]
+fn named_temp_file_keep() {
+    let tmpdir = tmpdir();
+    let file = check!(fs::NamedTempFile::new_in(tmpdir.path()));
+    let (mut file, path) = file.keep();
+    check!(file.write_all(b"kept"));
+    drop(file);
+    assert_eq!(check!(fs::read(&path)), b"kept");
+}
+
+#[test]
+fn temp_dir_removed_on_drop() {
+    let tmpdir = tmpdir();
+    let dir = check!(fs::TempDir::new_in(tmpdir.path()));
+    let path = dir.path().to_path_buf();
+    assert!(path.is_dir());
+    check!(fs::create_dir(path.join("sub")));
+    check!(fs::write(path.join("sub").join("file"), b"contents"));
+    drop(dir);
+    assert!(!path.exists());
+
+    let dir = check!(fs::TempDir::new_in(tmpdir.path()));
+    let path = dir.keep();
+    assert!(path.is_dir());
+    check!(fs::remove_dir(&path));
+}
+
+#[test]
+#[cfg(unix)]
+fn temp_permissions() {
+    use crate::os::unix::fs::PermissionsExt;
+
+    let tmpdir = tmpdir();
+    let file = check!(fs::NamedTempFile::new_in(tmpdir.path()));
+    assert_eq!(check!(file.as_file().metadata()).permissions().mode() & 0o777, 0o600);
+    let file = check!(fs::tempfile_in(tmpdir.path()));
+    assert_eq!(check!(file.metadata()).permissions().mode() & 0o777, 0o600);
+    let dir = check!(fs::TempDir::new_in(tmpdir.path()));
+    assert_eq!(check!(dir.path().metadata()).permissions().mode() & 0o777, 0o700);
+}
+
+#[test]
+fn write_atomic_replaces_contents() {
+    let tmpdir = tmpdir();
+    let path = tmpdir.join("config");
+    check!(fs::write_atomic(&path, b"first"));
+    assert_eq!(check!(fs::read(&path)), b"first");
+    check!(fs::write_atomic(&path, "second"));
+    assert_eq!(check!(fs::read(&path)), b"second");
+
+    // No temporary file is left behind.
+    assert_eq!(check!(fs::read_dir(tmpdir.path())).count(), 1);
+}
+
+#[test]
+fn write_atomic_fails_untouched() {
+    let tmpdir = tmpdir();
+    let dir = tmpdir.join("dir");
+    check!(fs::create_dir(&dir));
+    assert!(fs::write_atomic(&dir, b"contents").is_err());
+    assert!(dir.is_dir());
+    assert!(fs::write_atomic(tmpdir.join("missing").join("file"), b"contents").is_err());
+    assert_eq!(check!(fs::read_dir(tmpdir.path())).count(), 1);
+}
+
+#[test]
+#[cfg(unix)]
+fn write_atomic_preserves_permissions() {
+    use crate::os::unix::fs::PermissionsExt;
+
+    let tmpdir = tmpdir();
+    let path = tmpdir.join("script");
+    check!(fs::write(&path, b"old"));
+    check!(fs::set_permissions(&path, fs::Permissions::from_mode(0o751)));
+    check!(fs::write_atomic(&path, b"new"));
+    assert_eq!(check!(fs::read(&path)), b"new");
+    assert_eq!(check!(path.metadata()).permissions().mode() & 0o777, 0o751);
+}
+
+#[test]
+#[cfg(unix)]
+fn write_atomic_replaces_symlink() {
+    use crate::os::unix::fs::PermissionsExt;
+
+    let tmpdir = tmpdir();
+    let target = tmpdir.join("target");
+    let link = tmpdir.join("link");
+    check!(fs::write(&target, b"old"));
+    check!(fs::set_permissions(&target, fs::Permissions::from_mode(0o755)));
+    check!(crate::os::unix::fs::symlink(&target, &link));
+    check!(fs::write_atomic(&link, b"new"));
+    assert!(!check!(fs::symlink_metadata(&link)).is_symlink());
+    assert_eq!(check!(link.metadata()).permissions().mode() & 0o777, 0o600);
+    assert_eq!(check!(fs::read(&link)), b"new");
+    assert_eq!(check!(fs::read(&target)), b"old");
+}
+
 #[test]
 fn rename_directory() {
     let tmpdir = tmpdir();
@@ -329,6 +329,8 @@
     util::{empty, repeat, sink, Empty, Repeat, Sink},
 };
 
+#[unstable(feature = "anonymous_pipe", issue = "none")]
+pub use self::pipe::{pipe, PipeReader, PipeWriter};
 #[unstable(feature = "read_buf", issue = "78485")]
 pub use core::io::{BorrowedBuf, BorrowedCursor};
 pub(crate) use error::const_io_error;
@@ -338,6 +340,7 @@
 mod cursor;
 mod error;
 mod impls;
+mod pipe;
 pub mod prelude;
 mod stdio;
 mod util;
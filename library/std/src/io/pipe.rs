@@ -0,0 +1,210 @@
+#[cfg(all(test, not(any(target_os = "emscripten", target_env = "sgx", target_os = "xous"))))]
+mod tests;
+
+use crate::io::{self, IoSlice, IoSliceMut};
+use crate::sys::anonymous_pipe::{pipe as pipe_inner, AnonPipe};
+
+/// Creates an anonymous pipe.
+///
+/// Returns the two ends of a new pipe: bytes written to the [`PipeWriter`]
+/// can be read back from the [`PipeReader`], in order. Reading blocks until
+/// data is available, and returns end-of-file once every writer for the pipe
+/// has been dropped. Writing blocks while the pipe's buffer is full, and fails
+/// with [`io::ErrorKind::BrokenPipe`] once every reader has been dropped.
+///
+/// Both ends are most useful for talking to child processes: they can be
+/// turned into a [`Stdio`] to connect a child's standard streams, including
+/// those of several children at once to build a pipeline, and on Unix they
+/// can be handed to a child under any descriptor number with
+/// [`CommandExt::map_fd`].
+///
+/// Neither end is inherited by child processes unless explicitly passed.
+///
+/// [`Stdio`]: crate::process::Stdio
+/// [`CommandExt::map_fd`]: crate::os::unix::process::CommandExt::map_fd
+///
+/// # Platform-specific behavior
+///
+/// This currently corresponds to the `pipe2` function with `O_CLOEXEC` on
+/// Unix, and to a named pipe with a unique name on Windows. Platforms without
+/// pipes return an [`Unsupported`] error.
+///
+/// [`Unsupported`]: io::ErrorKind::Unsupported
+///
+/// # Examples
+///
+/// ```no_run
+/// #![feature(anonymous_pipe)]
+/// use std::io::{self, Read, Write};
+/// use std::process::Command;
+///
+/// // Connect `echo`'s stdout to `tr`'s stdin, like `echo hello | tr a-z A-Z`.
+/// let (reader, writer) = io::pipe()?;
+/// let mut echo = Command::new("echo").arg("hello").stdout(writer).spawn()?;
+/// let tr = Command::new("tr").args(["a-z", "A-Z"]).stdin(reader).output()?;
+/// echo.wait()?;
+///
+/// assert_eq!(tr.stdout, b"HELLO\n");
+/// # Ok::<_, io::Error>(())
+/// ```
+#[unstable(feature = "anonymous_pipe", issue = "none")]
+#[inline]
+pub fn pipe() -> io::Result<(PipeReader, PipeWriter)> {
+    pipe_inner().map(|(reader, writer)| (PipeReader(reader), PipeWriter(writer)))
+}
+
+/// The read end of an anonymous pipe, created by [`pipe`].
+#[unstable(feature = "anonymous_pipe", issue = "none")]
+#[derive(Debug)]
+pub struct PipeReader(pub(crate) AnonPipe);
+
+/// The write end of an anonymous pipe, created by [`pipe`].
+#[unstable(feature = "anonymous_pipe", issue = "none")]
+#[derive(Debug)]
+pub struct PipeWriter(pub(crate) AnonPipe);
+
+impl PipeReader {
+    /// Creates a new `PipeReader` sharing the same underlying pipe.
+    ///
+    /// Any of the clones can be used to read from the pipe, and end-of-file
+    /// is still only reached once every writer has been dropped.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(anonymous_pipe)]
+    /// use std::io::{self, Read, Write};
+    ///
+    /// let (reader, mut writer) = io::pipe()?;
+    /// let mut clone = reader.try_clone()?;
+    /// drop(reader);
+    ///
+    /// writer.write_all(b"hello")?;
+    /// drop(writer);
+    ///
+    /// let mut buf = String::new();
+    /// clone.read_to_string(&mut buf)?;
+    /// assert_eq!(buf, "hello");
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    #[unstable(feature = "anonymous_pipe", issue = "none")]
+    pub fn try_clone(&self) -> io::Result<Self> {
+        self.0.try_clone().map(Self)
+    }
+}
+
+impl PipeWriter {
+    /// Creates a new `PipeWriter` sharing the same underlying pipe.
+    ///
+    /// This is typically used to give several child processes the same
+    /// output pipe, for instance one for both their stdout and stderr. The
+    /// reader only sees end-of-file once every clone has been dropped.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(anonymous_pipe)]
+    /// use std::io::{self, Read};
+    /// use std::process::Command;
+    ///
+    /// let (mut reader, writer) = io::pipe()?;
+    /// let mut child = Command::new("sh")
+    ///     .arg("-c")
+    ///     .arg("echo out; echo err >&2")
+    ///     .stdout(writer.try_clone()?)
+    ///     .stderr(writer)
+    ///     .spawn()?;
+    ///
+    /// let mut output = String::new();
+    /// reader.read_to_string(&mut output)?;
+    /// child.wait()?;
+    /// assert_eq!(output, "out\nerr\n");
+    /// # Ok::<_, io::Error>(())
+    /// ```
+    #[unstable(feature = "anonymous_pipe", issue = "none")]
+    pub fn try_clone(&self) -> io::Result<Self> {
+        self.0.try_clone().map(Self)
+    }
+}
+
+#[unstable(feature = "anonymous_pipe", issue = "none")]
+impl io::Read for &PipeReader {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.0.read(buf)
+    }
+
+    fn read_vectored(&mut self, bufs: &mut [IoSliceMut<'_>]) -> io::Result<usize> {
+        self.0.read_vectored(bufs)
+    }
+
+    #[inline]
+    fn is_read_vectored(&self) -> bool {
+        self.0.is_read_vectored()
+    }
+
+    fn read_to_end(&mut self, buf: &mut Vec<u8>) -> io::Result<usize> {
+        self.0.read_to_end(buf)
+    }
+}
+
+#[unstable(feature = "anonymous_pipe", issue = "none")]
+impl io::Read for PipeReader {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        (&*self).read(buf)
+    }
+
+    fn read_vectored(&mut self, bufs: &mut [IoSliceMut<'_>]) -> io::Result<usize> {
+        (&*self).read_vectored(bufs)
+    }
+
+    #[inline]
+    fn is_read_vectored(&self) -> bool {
+        (&&*self).is_read_vectored()
+    }
+
+    fn read_to_end(&mut self, buf: &mut Vec<u8>) -> io::Result<usize> {
+        (&*self).read_to_end(buf)
+    }
+}
+
+#[unstable(feature = "anonymous_pipe", issue = "none")]
+impl io::Write for &PipeWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.write(buf)
+    }
+
+    #[inline]
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+
+    fn write_vectored(&mut self, bufs: &[IoSlice<'_>]) -> io::Result<usize> {
+        self.0.write_vectored(bufs)
+    }
+
+    #[inline]
+    fn is_write_vectored(&self) -> bool {
+        self.0.is_write_vectored()
+    }
+}
+
+#[unstable(feature = "anonymous_pipe", issue = "none")]
+impl io::Write for PipeWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        (&*self).write(buf)
+    }
+
+    #[inline]
+    fn flush(&mut self) -> io::Result<()> {
+        (&*self).flush()
+    }
+
+    fn write_vectored(&mut self, bufs: &[IoSlice<'_>]) -> io::Result<usize> {
+        (&*self).write_vectored(bufs)
+    }
+
+    #[inline]
+    fn is_write_vectored(&self) -> bool {
+        (&&*self).is_write_vectored()
+    }
+}
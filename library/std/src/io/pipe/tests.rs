@@ -0,0 +1,39 @@
+use crate::io::{pipe, Read, Write};
+use crate::thread;
+
+#[test]
+fn pipe_read_write() {
+    let (mut reader, mut writer) = pipe().unwrap();
+    writer.write_all(b"hello").unwrap();
+    drop(writer);
+
+    let mut buf = Vec::new();
+    reader.read_to_end(&mut buf).unwrap();
+    assert_eq!(buf, b"hello");
+}
+
+#[test]
+fn pipe_eof_once_all_writers_dropped() {
+    let (mut reader, writer) = pipe().unwrap();
+    let clone = writer.try_clone().unwrap();
+    drop(writer);
+
+    let writer = thread::spawn(move || {
+        let mut clone = clone;
+        clone.write_all(b"from the clone").unwrap();
+    });
+
+    let mut buf = String::new();
+    reader.read_to_string(&mut buf).unwrap();
+    writer.join().unwrap();
+    assert_eq!(buf, "from the clone");
+}
+
+#[test]
+fn pipe_write_to_closed_reader() {
+    let (reader, mut writer) = pipe().unwrap();
+    drop(reader);
+
+    let err = writer.write_all(b"nobody is listening").unwrap_err();
+    assert_eq!(err.kind(), crate::io::ErrorKind::BrokenPipe);
+}
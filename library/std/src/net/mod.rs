@@ -25,18 +25,23 @@
 
 #[stable(feature = "rust1", since = "1.0.0")]
 pub use self::ip_addr::{IpAddr, Ipv4Addr, Ipv6Addr, Ipv6MulticastScope};
+#[unstable(feature = "net_poll", issue = "none")]
+pub use self::poll::{Event, Events, EventsIter, Interest, Poller, Source, Token};
 #[stable(feature = "rust1", since = "1.0.0")]
 pub use self::socket_addr::{SocketAddr, SocketAddrV4, SocketAddrV6, ToSocketAddrs};
 #[unstable(feature = "tcplistener_into_incoming", issue = "88339")]
 pub use self::tcp::IntoIncoming;
 #[stable(feature = "rust1", since = "1.0.0")]
 pub use self::tcp::{Incoming, TcpListener, TcpStream};
+#[unstable(feature = "tcp_socket", issue = "none")]
+pub use self::tcp::{TcpKeepalive, TcpSocket};
 #[stable(feature = "rust1", since = "1.0.0")]
 pub use self::udp::UdpSocket;
 #[stable(feature = "rust1", since = "1.0.0")]
 pub use core::net::AddrParseError;
 
 mod ip_addr;
+mod poll;
 mod socket_addr;
 mod tcp;
 #[cfg(test)]
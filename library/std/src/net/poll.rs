@@ -0,0 +1,461 @@
+#[cfg(all(test, not(any(target_os = "emscripten", target_env = "sgx", target_os = "xous"))))]
+mod tests;
+
+use crate::fmt;
+use crate::io;
+use crate::ops::BitOr;
+use crate::sys::net_poll as sys;
+use crate::time::Duration;
+
+#[cfg(unix)]
+use crate::os::fd::AsFd;
+#[cfg(windows)]
+use crate::os::windows::io::AsSocket;
+
+/// A readiness poller for non-blocking sockets.
+///
+/// A `Poller` watches a set of registered sources, such as [`TcpListener`],
+/// [`TcpStream`] or [`UdpSocket`], and reports which of them are ready to be
+/// read from or written to. This is the building block of a simple event loop:
+/// put the sockets into non-blocking mode, register them, then repeatedly call
+/// [`poll`] and service the sources whose events come back.
+///
+/// Readiness is level-triggered: as long as a source stays readable (or
+/// writable), every call to [`poll`] keeps reporting it. A source is usually
+/// serviced until its operations fail with [`io::ErrorKind::WouldBlock`].
+///
+/// Each source is registered together with a [`Token`], an arbitrary `usize`
+/// which is handed back in the events for that source and is typically used
+/// as an index into the caller's own table of connections.
+///
+/// A `Poller` can be shared between threads, but a source registered while
+/// another thread is blocked in [`poll`] is only guaranteed to be watched by
+/// subsequent calls.
+///
+/// [`TcpListener`]: crate::net::TcpListener
+/// [`TcpStream`]: crate::net::TcpStream
+/// [`UdpSocket`]: crate::net::UdpSocket
+/// [`poll`]: Poller::poll
+///
+/// # Platform-specific behavior
+///
+/// This currently uses `epoll` on Linux and Android, `poll` on other Unix
+/// platforms and `WSAPoll` on Windows. On other platforms [`Poller::new`]
+/// returns an [`Unsupported`] error.
+///
+/// [`Unsupported`]: io::ErrorKind::Unsupported
+///
+/// # Examples
+///
+/// ```no_run
+/// #![feature(net_poll)]
+/// use std::io::{self, Read};
+/// use std::net::{Events, Interest, Poller, TcpListener, Token};
+///
+/// const LISTENER: Token = Token(usize::MAX);
+///
+/// let listener = TcpListener::bind("127.0.0.1:8080")?;
+/// listener.set_nonblocking(true)?;
+///
+/// let poller = Poller::new()?;
+/// poller.register(&listener, LISTENER, Interest::READABLE)?;
+///
+/// let mut connections = Vec::new();
+/// let mut events = Events::with_capacity(128);
+/// loop {
+///     poller.poll(&mut events, None)?;
+///     for event in &events {
+///         if event.token() == LISTENER {
+///             loop {
+///                 match listener.accept() {
+///                     Ok((stream, _)) => {
+///                         stream.set_nonblocking(true)?;
+///                         poller.register(&stream, Token(connections.len()), Interest::READABLE)?;
+///                         connections.push(stream);
+///                     }
+///                     Err(e) if e.kind() == io::ErrorKind::WouldBlock => break,
+///                     Err(e) => return Err(e),
+///                 }
+///             }
+///         } else {
+///             let mut buf = [0; 1024];
+///             match connections[event.token().0].read(&mut buf) {
+///                 Ok(n) => println!("read {n} bytes"),
+///                 Err(e) if e.kind() == io::ErrorKind::WouldBlock => {}
+///                 Err(e) => return Err(e),
+///             }
+///         }
+///     }
+/// }
+/// # #[allow(unreachable_code)]
+/// # Ok::<_, io::Error>(())
+/// ```
+#[unstable(feature = "net_poll", issue = "none")]
+pub struct Poller(sys::Poller);
+
+/// A source of readiness events that can be registered with a [`Poller`].
+///
+/// This is implemented for every type that exposes its underlying file
+/// descriptor on Unix ([`AsFd`]) or socket on Windows ([`AsSocket`]), which
+/// includes all of the socket types in this module.
+///
+/// [`AsFd`]: crate::os::fd::AsFd
+/// [`AsSocket`]: crate::os::windows::io::AsSocket
+#[cfg(unix)]
+#[unstable(feature = "net_poll", issue = "none")]
+pub trait Source: AsFd {}
+
+#[cfg(unix)]
+#[unstable(feature = "net_poll", issue = "none")]
+impl<T: AsFd + ?Sized> Source for T {}
+
+/// A source of readiness events that can be registered with a [`Poller`].
+///
+/// This is implemented for every type that exposes its underlying file
+/// descriptor on Unix ([`AsFd`]) or socket on Windows ([`AsSocket`]), which
+/// includes all of the socket types in this module.
+///
+/// [`AsFd`]: crate::os::fd::AsFd
+/// [`AsSocket`]: crate::os::windows::io::AsSocket
+#[cfg(windows)]
+#[unstable(feature = "net_poll", issue = "none")]
+pub trait Source: AsSocket {}
+
+#[cfg(windows)]
+#[unstable(feature = "net_poll", issue = "none")]
+impl<T: AsSocket + ?Sized> Source for T {}
+
+/// A source of readiness events that can be registered with a [`Poller`].
+///
+/// Polling is not supported on this platform, so nothing implements this
+/// trait.
+#[cfg(not(any(unix, windows)))]
+#[unstable(feature = "net_poll", issue = "none")]
+pub trait Source {}
+
+/// An identifier associated with a registered source.
+///
+/// The token passed to [`Poller::register`] is returned by
+/// [`Event::token`] for every event concerning that source.
+#[unstable(feature = "net_poll", issue = "none")]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Token(#[unstable(feature = "net_poll", issue = "none")] pub usize);
+
+/// The kinds of readiness a source is registered for.
+///
+/// Interests are combined with the `|` operator:
+///
+/// ```
+/// #![feature(net_poll)]
+/// use std::net::Interest;
+///
+/// let interest = Interest::READABLE | Interest::WRITABLE;
+/// assert!(interest.is_readable());
+/// assert!(interest.is_writable());
+/// ```
+#[unstable(feature = "net_poll", issue = "none")]
+#[derive(Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Interest(u8);
+
+impl Interest {
+    /// Interest in the source becoming readable, or a listener having a
+    /// connection to accept.
+    #[unstable(feature = "net_poll", issue = "none")]
+    pub const READABLE: Interest = Interest(0b01);
+
+    /// Interest in the source becoming writable, or a connection started in
+    /// non-blocking mode completing.
+    #[unstable(feature = "net_poll", issue = "none")]
+    pub const WRITABLE: Interest = Interest(0b10);
+
+    /// Returns `true` if this includes [`Interest::READABLE`].
+    #[unstable(feature = "net_poll", issue = "none")]
+    #[must_use]
+    #[inline]
+    pub const fn is_readable(self) -> bool {
+        self.0 & Self::READABLE.0 != 0
+    }
+
+    /// Returns `true` if this includes [`Interest::WRITABLE`].
+    #[unstable(feature = "net_poll", issue = "none")]
+    #[must_use]
+    #[inline]
+    pub const fn is_writable(self) -> bool {
+        self.0 & Self::WRITABLE.0 != 0
+    }
+}
+
+#[unstable(feature = "net_poll", issue = "none")]
+impl BitOr for Interest {
+    type Output = Interest;
+
+    #[inline]
+    fn bitor(self, rhs: Interest) -> Interest {
+        Interest(self.0 | rhs.0)
+    }
+}
+
+#[unstable(feature = "net_poll", issue = "none")]
+impl fmt::Debug for Interest {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (self.is_readable(), self.is_writable()) {
+            (true, true) => f.write_str("READABLE | WRITABLE"),
+            (true, false) => f.write_str("READABLE"),
+            (false, _) => f.write_str("WRITABLE"),
+        }
+    }
+}
+
+/// A readiness event reported by [`Poller::poll`].
+#[unstable(feature = "net_poll", issue = "none")]
+#[derive(Copy, Clone)]
+pub struct Event(sys::Event);
+
+impl Event {
+    /// Returns the token the source was registered with.
+    #[unstable(feature = "net_poll", issue = "none")]
+    #[must_use]
+    pub fn token(&self) -> Token {
+        Token(self.0.token())
+    }
+
+    /// Returns `true` if the source is readable.
+    ///
+    /// For a listener this means a connection is waiting to be accepted.
+    #[unstable(feature = "net_poll", issue = "none")]
+    #[must_use]
+    pub fn is_readable(&self) -> bool {
+        self.0.is_readable()
+    }
+
+    /// Returns `true` if the source is writable.
+    #[unstable(feature = "net_poll", issue = "none")]
+    #[must_use]
+    pub fn is_writable(&self) -> bool {
+        self.0.is_writable()
+    }
+
+    /// Returns `true` if an error is pending on the source.
+    ///
+    /// The error itself can be retrieved with `take_error` on the socket.
+    /// Errors are reported regardless of the registered [`Interest`].
+    #[unstable(feature = "net_poll", issue = "none")]
+    #[must_use]
+    pub fn is_error(&self) -> bool {
+        self.0.is_error()
+    }
+
+    /// Returns `true` if the peer has closed the connection.
+    ///
+    /// Hang-ups are reported regardless of the registered [`Interest`].
+    /// Data sent before the peer closed may still be waiting to be read.
+    #[unstable(feature = "net_poll", issue = "none")]
+    #[must_use]
+    pub fn is_hangup(&self) -> bool {
+        self.0.is_hangup()
+    }
+}
+
+#[unstable(feature = "net_poll", issue = "none")]
+impl fmt::Debug for Event {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Event")
+            .field("token", &self.token())
+            .field("readable", &self.is_readable())
+            .field("writable", &self.is_writable())
+            .field("error", &self.is_error())
+            .field("hangup", &self.is_hangup())
+            .finish()
+    }
+}
+
+/// A buffer of events filled in by [`Poller::poll`].
+///
+/// The capacity of the buffer bounds the number of events returned by a
+/// single call; any further ready sources are reported by the next call.
+#[unstable(feature = "net_poll", issue = "none")]
+pub struct Events(Vec<sys::Event>);
+
+impl Events {
+    /// Creates an empty buffer with room for `capacity` events.
+    ///
+    /// A capacity of zero is treated as one.
+    #[unstable(feature = "net_poll", issue = "none")]
+    #[must_use]
+    pub fn with_capacity(capacity: usize) -> Events {
+        Events(Vec::with_capacity(capacity.max(1)))
+    }
+
+    /// Returns the maximum number of events a single poll can return.
+    #[unstable(feature = "net_poll", issue = "none")]
+    #[must_use]
+    pub fn capacity(&self) -> usize {
+        self.0.capacity()
+    }
+
+    /// Returns the number of events in the buffer.
+    #[unstable(feature = "net_poll", issue = "none")]
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns `true` if the buffer holds no events, for instance because
+    /// the last poll timed out.
+    #[unstable(feature = "net_poll", issue = "none")]
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Returns an iterator over the events in the buffer.
+    #[unstable(feature = "net_poll", issue = "none")]
+    pub fn iter(&self) -> EventsIter<'_> {
+        EventsIter(self.0.iter())
+    }
+
+    /// Removes all events from the buffer, keeping its capacity.
+    #[unstable(feature = "net_poll", issue = "none")]
+    pub fn clear(&mut self) {
+        self.0.clear()
+    }
+}
+
+#[unstable(feature = "net_poll", issue = "none")]
+impl fmt::Debug for Events {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+#[unstable(feature = "net_poll", issue = "none")]
+impl<'a> IntoIterator for &'a Events {
+    type Item = Event;
+    type IntoIter = EventsIter<'a>;
+
+    fn into_iter(self) -> EventsIter<'a> {
+        self.iter()
+    }
+}
+
+/// An iterator over the events in an [`Events`] buffer.
+///
+/// This struct is created by [`Events::iter`].
+#[unstable(feature = "net_poll", issue = "none")]
+#[derive(Clone)]
+pub struct EventsIter<'a>(crate::slice::Iter<'a, sys::Event>);
+
+#[unstable(feature = "net_poll", issue = "none")]
+impl fmt::Debug for EventsIter<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.clone()).finish()
+    }
+}
+
+#[unstable(feature = "net_poll", issue = "none")]
+impl Iterator for EventsIter<'_> {
+    type Item = Event;
+
+    fn next(&mut self) -> Option<Event> {
+        self.0.next().map(|event| Event(*event))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+#[unstable(feature = "net_poll", issue = "none")]
+impl ExactSizeIterator for EventsIter<'_> {}
+
+impl Poller {
+    /// Creates a new poller with no registered sources.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Unsupported`] error on platforms without a polling
+    /// mechanism, or any error reported by the operating system while
+    /// allocating the poller.
+    ///
+    /// [`Unsupported`]: io::ErrorKind::Unsupported
+    #[unstable(feature = "net_poll", issue = "none")]
+    pub fn new() -> io::Result<Poller> {
+        sys::Poller::new().map(Poller)
+    }
+
+    /// Starts watching `source` for the readiness described by `interest`.
+    ///
+    /// Events for the source carry `token`. The source should be in
+    /// non-blocking mode, and must be [deregistered] before it is closed.
+    ///
+    /// [deregistered]: Poller::deregister
+    ///
+    /// # Errors
+    ///
+    /// Returns an error of kind [`AlreadyExists`] if the source is already
+    /// registered with this poller.
+    ///
+    /// [`AlreadyExists`]: io::ErrorKind::AlreadyExists
+    #[unstable(feature = "net_poll", issue = "none")]
+    pub fn register<S: Source + ?Sized>(
+        &self,
+        source: &S,
+        token: Token,
+        interest: Interest,
+    ) -> io::Result<()> {
+        self.0.register(source, token.0, interest)
+    }
+
+    /// Changes the token and interest of an already registered source.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error of kind [`NotFound`] if the source is not registered
+    /// with this poller.
+    ///
+    /// [`NotFound`]: io::ErrorKind::NotFound
+    #[unstable(feature = "net_poll", issue = "none")]
+    pub fn reregister<S: Source + ?Sized>(
+        &self,
+        source: &S,
+        token: Token,
+        interest: Interest,
+    ) -> io::Result<()> {
+        self.0.reregister(source, token.0, interest)
+    }
+
+    /// Stops watching `source`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error of kind [`NotFound`] if the source is not registered
+    /// with this poller.
+    ///
+    /// [`NotFound`]: io::ErrorKind::NotFound
+    #[unstable(feature = "net_poll", issue = "none")]
+    pub fn deregister<S: Source + ?Sized>(&self, source: &S) -> io::Result<()> {
+        self.0.deregister(source)
+    }
+
+    /// Waits for registered sources to become ready.
+    ///
+    /// `events` is cleared and then filled with up to [`Events::capacity`]
+    /// events. This blocks until at least one event is available or, if
+    /// `timeout` is `Some`, until the timeout elapses, in which case `events`
+    /// is left empty. A zero timeout checks for readiness without blocking.
+    ///
+    /// The timeout is rounded up to the resolution of the platform's timer,
+    /// so the call never returns early because of rounding.
+    #[unstable(feature = "net_poll", issue = "none")]
+    pub fn poll(&self, events: &mut Events, timeout: Option<Duration>) -> io::Result<()> {
+        events.clear();
+        self.0.poll(&mut events.0, timeout)
+    }
+}
+
+#[unstable(feature = "net_poll", issue = "none")]
+impl fmt::Debug for Poller {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Poller").finish_non_exhaustive()
+    }
+}
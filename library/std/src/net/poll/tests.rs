@@ -0,0 +1,156 @@
+use crate::io::prelude::*;
+use crate::io::ErrorKind;
+use crate::net::test::next_test_ip4;
+use crate::net::*;
+use crate::time::{Duration, Instant};
+
+macro_rules! t {
+    ($e:expr) => {
+        match $e {
+            Ok(t) => t,
+            Err(e) => panic!("received error for `{}`: {}", stringify!($e), e),
+        }
+    };
+}
+
+const LISTENER: Token = Token(0);
+const CLIENT: Token = Token(1);
+const SERVER: Token = Token(2);
+
+fn poll_tokens(poller: &Poller, events: &mut Events, timeout: Duration) -> Vec<Token> {
+    t!(poller.poll(events, Some(timeout)));
+    let mut tokens: Vec<_> = events.iter().map(|event| event.token()).collect();
+    tokens.sort();
+    tokens
+}
+
+#[test]
+fn timeout_without_events() {
+    let listener = t!(TcpListener::bind(next_test_ip4()));
+    let poller = t!(Poller::new());
+    t!(poller.register(&listener, LISTENER, Interest::READABLE));
+
+    let mut events = Events::with_capacity(8);
+    let start = Instant::now();
+    t!(poller.poll(&mut events, Some(Duration::from_millis(100))));
+    assert!(events.is_empty());
+    assert!(start.elapsed() >= Duration::from_millis(100));
+
+    t!(poller.poll(&mut events, Some(Duration::ZERO)));
+    assert!(events.is_empty());
+}
+
+#[test]
+fn accept_and_read_readiness() {
+    let addr = next_test_ip4();
+    let listener = t!(TcpListener::bind(addr));
+    t!(listener.set_nonblocking(true));
+
+    let poller = t!(Poller::new());
+    t!(poller.register(&listener, LISTENER, Interest::READABLE));
+
+    let mut client = t!(TcpStream::connect(addr));
+    let mut events = Events::with_capacity(8);
+    t!(poller.poll(&mut events, Some(Duration::from_secs(10))));
+    let event = events.iter().next().unwrap();
+    assert_eq!(event.token(), LISTENER);
+    assert!(event.is_readable());
+    assert!(!event.is_writable());
+
+    let (mut server, _) = t!(listener.accept());
+    t!(server.set_nonblocking(true));
+    t!(poller.register(&server, SERVER, Interest::READABLE));
+    t!(poller.register(&client, CLIENT, Interest::WRITABLE));
+
+    // Level-triggered: the client stays writable, the server has nothing to read.
+    assert_eq!(poll_tokens(&poller, &mut events, Duration::ZERO), [CLIENT]);
+    assert_eq!(poll_tokens(&poller, &mut events, Duration::ZERO), [CLIENT]);
+
+    t!(client.write_all(b"hello"));
+    assert_eq!(poll_tokens(&poller, &mut events, Duration::from_secs(10)), [CLIENT, SERVER]);
+
+    let mut buf = [0; 16];
+    assert_eq!(t!(server.read(&mut buf)), 5);
+    assert_eq!(server.read(&mut buf).unwrap_err().kind(), ErrorKind::WouldBlock);
+
+    t!(poller.deregister(&client));
+    t!(poller.poll(&mut events, Some(Duration::ZERO)));
+    assert!(events.is_empty());
+
+    drop(client);
+    t!(poller.poll(&mut events, Some(Duration::from_secs(10))));
+    let event = events.iter().next().unwrap();
+    assert_eq!(event.token(), SERVER);
+    assert!(event.is_readable());
+    assert_eq!(t!(server.read(&mut buf)), 0);
+}
+
+#[test]
+fn reregister_changes_token_and_interest() {
+    let addr = next_test_ip4();
+    let listener = t!(TcpListener::bind(addr));
+    let client = t!(TcpStream::connect(addr));
+    let _server = t!(listener.accept());
+
+    let poller = t!(Poller::new());
+    let mut events = Events::with_capacity(8);
+    t!(poller.register(&client, CLIENT, Interest::READABLE));
+    assert!(poll_tokens(&poller, &mut events, Duration::ZERO).is_empty());
+
+    t!(poller.reregister(&client, SERVER, Interest::READABLE | Interest::WRITABLE));
+    assert_eq!(poll_tokens(&poller, &mut events, Duration::ZERO), [SERVER]);
+    assert!(events.iter().next().unwrap().is_writable());
+}
+
+#[test]
+fn registration_errors() {
+    let socket = t!(UdpSocket::bind(next_test_ip4()));
+    let poller = t!(Poller::new());
+
+    let e = poller.reregister(&socket, Token(0), Interest::READABLE).unwrap_err();
+    assert_eq!(e.kind(), ErrorKind::NotFound);
+    assert_eq!(poller.deregister(&socket).unwrap_err().kind(), ErrorKind::NotFound);
+
+    t!(poller.register(&socket, Token(0), Interest::READABLE));
+    let e = poller.register(&socket, Token(1), Interest::READABLE).unwrap_err();
+    assert_eq!(e.kind(), ErrorKind::AlreadyExists);
+
+    t!(poller.deregister(&socket));
+    t!(poller.register(&socket, Token(1), Interest::WRITABLE));
+}
+
+#[test]
+fn events_capacity_bounds_results() {
+    let poller = t!(Poller::new());
+    let sockets: Vec<_> = (0..4).map(|_| t!(UdpSocket::bind(next_test_ip4()))).collect();
+    for (i, socket) in sockets.iter().enumerate() {
+        t!(poller.register(socket, Token(i), Interest::WRITABLE));
+    }
+
+    let mut events = Events::with_capacity(2);
+    t!(poller.poll(&mut events, Some(Duration::ZERO)));
+    assert_eq!(events.len(), 2);
+
+    let mut events = Events::with_capacity(8);
+    assert_eq!(
+        poll_tokens(&poller, &mut events, Duration::ZERO),
+        [Token(0), Token(1), Token(2), Token(3)]
+    );
+}
+
+#[test]
+fn ready_sources_beyond_capacity_are_not_starved() {
+    let poller = t!(Poller::new());
+    let sockets: Vec<_> = (0..4).map(|_| t!(UdpSocket::bind(next_test_ip4()))).collect();
+    for (i, socket) in sockets.iter().enumerate() {
+        t!(poller.register(socket, Token(i), Interest::WRITABLE));
+    }
+
+    // All four sockets stay writable, so two calls with room for two events
+    // each must between them report every socket.
+    let mut events = Events::with_capacity(2);
+    let mut tokens = poll_tokens(&poller, &mut events, Duration::ZERO);
+    tokens.extend(poll_tokens(&poller, &mut events, Duration::ZERO));
+    tokens.sort();
+    assert_eq!(tokens, [Token(0), Token(1), Token(2), Token(3)]);
+}
@@ -8,7 +8,7 @@
 use crate::fmt;
 use crate::io::{self, BorrowedCursor, IoSlice, IoSliceMut};
 use crate::iter::FusedIterator;
-use crate::net::{Shutdown, SocketAddr, ToSocketAddrs};
+use crate::net::{Ipv4Addr, Ipv6Addr, Shutdown, SocketAddr, ToSocketAddrs};
 use crate::sys_common::net as net_imp;
 use crate::sys_common::{AsInner, FromInner, IntoInner};
 use crate::time::Duration;
@@ -1069,3 +1069,315 @@ fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         self.0.fmt(f)
     }
 }
+
+/// A TCP socket that has not yet been connected or turned into a listener.
+///
+/// `TcpSocket` gives access to the socket options which only take effect, or
+/// can only be set, before a connection is established: address reuse, buffer
+/// sizes, keepalive tuning and the local address to bind to. Once configured,
+/// the socket is turned into a [`TcpStream`] with [`connect`] or into a
+/// [`TcpListener`] with [`listen`].
+///
+/// Unlike [`TcpListener::bind`], no options are set on the socket by default.
+/// In particular `SO_REUSEADDR` is left off unless [`set_reuseaddr`] is called.
+///
+/// [`connect`]: TcpSocket::connect
+/// [`listen`]: TcpSocket::listen
+/// [`set_reuseaddr`]: TcpSocket::set_reuseaddr
+///
+/// # Examples
+///
+/// Connecting from a fixed local address:
+///
+/// ```no_run
+/// #![feature(tcp_socket)]
+/// use std::net::TcpSocket;
+///
+/// let socket = TcpSocket::new_v4()?;
+/// socket.bind("127.0.0.1:9000".parse().unwrap())?;
+/// let stream = socket.connect("127.0.0.1:8080".parse().unwrap())?;
+/// # Ok::<_, std::io::Error>(())
+/// ```
+///
+/// A listener sharing its port with other processes:
+///
+/// ```no_run
+/// #![feature(tcp_socket)]
+/// use std::net::TcpSocket;
+///
+/// let socket = TcpSocket::new_v4()?;
+/// socket.set_reuseaddr(true)?;
+/// socket.set_reuseport(true)?;
+/// socket.bind("0.0.0.0:8080".parse().unwrap())?;
+/// let listener = socket.listen(1024)?;
+/// # Ok::<_, std::io::Error>(())
+/// ```
+#[unstable(feature = "tcp_socket", issue = "none")]
+pub struct TcpSocket(net_imp::TcpSocket);
+
+/// Parameters for TCP keepalive probes, set with
+/// [`TcpSocket::set_tcp_keepalive`].
+///
+/// Parameters left unset keep the system defaults.
+///
+/// # Examples
+///
+/// ```
+/// #![feature(tcp_socket)]
+/// use std::net::TcpKeepalive;
+/// use std::time::Duration;
+///
+/// let keepalive = TcpKeepalive::new()
+///     .with_time(Duration::from_secs(60))
+///     .with_interval(Duration::from_secs(10))
+///     .with_retries(5);
+/// ```
+#[unstable(feature = "tcp_socket", issue = "none")]
+#[derive(Clone, Debug, Default)]
+pub struct TcpKeepalive {
+    time: Option<Duration>,
+    interval: Option<Duration>,
+    retries: Option<u32>,
+}
+
+impl TcpKeepalive {
+    /// Creates keepalive parameters which leave every setting at the
+    /// system default.
+    #[unstable(feature = "tcp_socket", issue = "none")]
+    #[must_use]
+    pub const fn new() -> TcpKeepalive {
+        TcpKeepalive { time: None, interval: None, retries: None }
+    }
+
+    /// Sets how long the connection must be idle before the first probe is
+    /// sent.
+    ///
+    /// This corresponds to `TCP_KEEPIDLE`, or `TCP_KEEPALIVE` on Apple
+    /// platforms and Windows. The duration is rounded up to whole seconds.
+    #[unstable(feature = "tcp_socket", issue = "none")]
+    #[must_use]
+    pub const fn with_time(self, time: Duration) -> TcpKeepalive {
+        TcpKeepalive { time: Some(time), ..self }
+    }
+
+    /// Sets the time between two unanswered probes.
+    ///
+    /// This corresponds to `TCP_KEEPINTVL`. The duration is rounded up to
+    /// whole seconds.
+    #[unstable(feature = "tcp_socket", issue = "none")]
+    #[must_use]
+    pub const fn with_interval(self, interval: Duration) -> TcpKeepalive {
+        TcpKeepalive { interval: Some(interval), ..self }
+    }
+
+    /// Sets the number of unanswered probes after which the connection is
+    /// dropped.
+    ///
+    /// This corresponds to `TCP_KEEPCNT`.
+    #[unstable(feature = "tcp_socket", issue = "none")]
+    #[must_use]
+    pub const fn with_retries(self, retries: u32) -> TcpKeepalive {
+        TcpKeepalive { retries: Some(retries), ..self }
+    }
+}
+
+impl TcpSocket {
+    /// Creates a new IPv4 TCP socket.
+    #[unstable(feature = "tcp_socket", issue = "none")]
+    pub fn new_v4() -> io::Result<TcpSocket> {
+        net_imp::TcpSocket::new(&SocketAddr::from((Ipv4Addr::UNSPECIFIED, 0))).map(TcpSocket)
+    }
+
+    /// Creates a new IPv6 TCP socket.
+    #[unstable(feature = "tcp_socket", issue = "none")]
+    pub fn new_v6() -> io::Result<TcpSocket> {
+        net_imp::TcpSocket::new(&SocketAddr::from((Ipv6Addr::UNSPECIFIED, 0))).map(TcpSocket)
+    }
+
+    /// Sets the value of the `SO_REUSEADDR` option on this socket.
+    ///
+    /// On Unix this allows a listener to bind to a port which still has
+    /// connections in the `TIME_WAIT` state, which is what
+    /// [`TcpListener::bind`] does by default.
+    ///
+    /// # Platform-specific behavior
+    ///
+    /// On Windows `SO_REUSEADDR` also allows binding to a port that another
+    /// socket is actively listening on, which lets other processes steal its
+    /// connections. It should rarely be enabled there.
+    #[unstable(feature = "tcp_socket", issue = "none")]
+    pub fn set_reuseaddr(&self, reuseaddr: bool) -> io::Result<()> {
+        self.0.set_reuseaddr(reuseaddr)
+    }
+
+    /// Gets the value of the `SO_REUSEADDR` option on this socket.
+    ///
+    /// For more information about this option, see [`TcpSocket::set_reuseaddr`].
+    #[unstable(feature = "tcp_socket", issue = "none")]
+    pub fn reuseaddr(&self) -> io::Result<bool> {
+        self.0.reuseaddr()
+    }
+
+    /// Sets the value of the `SO_REUSEPORT` option on this socket.
+    ///
+    /// This allows several sockets to bind to the same address and port, as
+    /// long as all of them set this option. On Linux incoming connections are
+    /// then balanced between the listeners.
+    ///
+    /// # Platform-specific behavior
+    ///
+    /// This is only supported on Linux, Android, Fuchsia, Apple platforms
+    /// and the BSDs. Other platforms return an [`Unsupported`] error.
+    ///
+    /// [`Unsupported`]: io::ErrorKind::Unsupported
+    #[unstable(feature = "tcp_socket", issue = "none")]
+    pub fn set_reuseport(&self, reuseport: bool) -> io::Result<()> {
+        self.0.set_reuseport(reuseport)
+    }
+
+    /// Gets the value of the `SO_REUSEPORT` option on this socket.
+    ///
+    /// For more information about this option, see [`TcpSocket::set_reuseport`].
+    #[unstable(feature = "tcp_socket", issue = "none")]
+    pub fn reuseport(&self) -> io::Result<bool> {
+        self.0.reuseport()
+    }
+
+    /// Sets the size of the send buffer, in bytes, with `SO_SNDBUF`.
+    ///
+    /// The system may adjust the value; for instance Linux doubles it to
+    /// account for bookkeeping overhead. Use [`TcpSocket::send_buffer_size`]
+    /// to read back the effective size.
+    #[unstable(feature = "tcp_socket", issue = "none")]
+    pub fn set_send_buffer_size(&self, size: usize) -> io::Result<()> {
+        self.0.set_send_buffer_size(size)
+    }
+
+    /// Gets the size of the send buffer, in bytes.
+    #[unstable(feature = "tcp_socket", issue = "none")]
+    pub fn send_buffer_size(&self) -> io::Result<usize> {
+        self.0.send_buffer_size()
+    }
+
+    /// Sets the size of the receive buffer, in bytes, with `SO_RCVBUF`.
+    ///
+    /// Setting this before connecting or listening allows a larger TCP
+    /// window to be negotiated. As with [`TcpSocket::set_send_buffer_size`],
+    /// the system may adjust the value.
+    #[unstable(feature = "tcp_socket", issue = "none")]
+    pub fn set_recv_buffer_size(&self, size: usize) -> io::Result<()> {
+        self.0.set_recv_buffer_size(size)
+    }
+
+    /// Gets the size of the receive buffer, in bytes.
+    #[unstable(feature = "tcp_socket", issue = "none")]
+    pub fn recv_buffer_size(&self) -> io::Result<usize> {
+        self.0.recv_buffer_size()
+    }
+
+    /// Sets the value of the `SO_KEEPALIVE` option on this socket.
+    ///
+    /// When enabled, probes are sent on idle connections to detect peers that
+    /// went away. Their timing is controlled with
+    /// [`TcpSocket::set_tcp_keepalive`].
+    #[unstable(feature = "tcp_socket", issue = "none")]
+    pub fn set_keepalive(&self, keepalive: bool) -> io::Result<()> {
+        self.0.set_keepalive(keepalive)
+    }
+
+    /// Gets the value of the `SO_KEEPALIVE` option on this socket.
+    #[unstable(feature = "tcp_socket", issue = "none")]
+    pub fn keepalive(&self) -> io::Result<bool> {
+        self.0.keepalive()
+    }
+
+    /// Enables keepalive probes with the given parameters.
+    ///
+    /// # Platform-specific behavior
+    ///
+    /// Tuning the probes is supported on Linux, Android, Fuchsia, Apple
+    /// platforms, FreeBSD, NetBSD, DragonFly BSD, illumos, Solaris and
+    /// Windows. Elsewhere, setting any of the parameters returns an
+    /// [`Unsupported`] error, although keepalive itself is still enabled.
+    ///
+    /// [`Unsupported`]: io::ErrorKind::Unsupported
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(tcp_socket)]
+    /// use std::net::{TcpKeepalive, TcpSocket};
+    /// use std::time::Duration;
+    ///
+    /// let socket = TcpSocket::new_v4()?;
+    /// let keepalive = TcpKeepalive::new()
+    ///     .with_time(Duration::from_secs(30))
+    ///     .with_interval(Duration::from_secs(5))
+    ///     .with_retries(3);
+    /// socket.set_tcp_keepalive(&keepalive)?;
+    /// let stream = socket.connect("127.0.0.1:8080".parse().unwrap())?;
+    /// # Ok::<_, std::io::Error>(())
+    /// ```
+    #[unstable(feature = "tcp_socket", issue = "none")]
+    pub fn set_tcp_keepalive(&self, keepalive: &TcpKeepalive) -> io::Result<()> {
+        self.0.set_tcp_keepalive(keepalive.time, keepalive.interval, keepalive.retries)
+    }
+
+    /// Sets the value of the `TCP_NODELAY` option on this socket.
+    ///
+    /// For more information about this option, see [`TcpStream::set_nodelay`].
+    #[unstable(feature = "tcp_socket", issue = "none")]
+    pub fn set_nodelay(&self, nodelay: bool) -> io::Result<()> {
+        self.0.set_nodelay(nodelay)
+    }
+
+    /// Gets the value of the `TCP_NODELAY` option on this socket.
+    #[unstable(feature = "tcp_socket", issue = "none")]
+    pub fn nodelay(&self) -> io::Result<bool> {
+        self.0.nodelay()
+    }
+
+    /// Binds the socket to a local address.
+    ///
+    /// For a socket that is then connected, this picks the local address and
+    /// port the connection is made from. Binding to port 0 lets the system
+    /// choose a free port, which [`TcpSocket::local_addr`] then returns.
+    #[unstable(feature = "tcp_socket", issue = "none")]
+    pub fn bind(&self, addr: SocketAddr) -> io::Result<()> {
+        self.0.bind(&addr)
+    }
+
+    /// Returns the local address this socket is bound to.
+    #[unstable(feature = "tcp_socket", issue = "none")]
+    pub fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.0.socket_addr()
+    }
+
+    /// Connects the socket to a remote address, turning it into a
+    /// [`TcpStream`].
+    ///
+    /// If the socket was not bound, the system picks a local address first.
+    #[unstable(feature = "tcp_socket", issue = "none")]
+    pub fn connect(self, addr: SocketAddr) -> io::Result<TcpStream> {
+        self.0.connect(&addr).map(TcpStream)
+    }
+
+    /// Starts listening for connections, turning the socket into a
+    /// [`TcpListener`].
+    ///
+    /// `backlog` is the number of pending connections the system queues
+    /// before refusing new ones. It is a hint: the system may clamp it, for
+    /// instance to `somaxconn` on Linux. If the socket was not bound, it
+    /// listens on a port chosen by the system.
+    #[unstable(feature = "tcp_socket", issue = "none")]
+    pub fn listen(self, backlog: u32) -> io::Result<TcpListener> {
+        self.0.listen(backlog).map(TcpListener)
+    }
+}
+
+#[unstable(feature = "tcp_socket", issue = "none")]
+impl fmt::Debug for TcpSocket {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
@@ -917,3 +917,71 @@ fn connect_timeout_valid() {
     let addr = listener.local_addr().unwrap();
     TcpStream::connect_timeout(&addr, Duration::from_secs(2)).unwrap();
 }
+
+#[test]
+#[cfg_attr(target_env = "sgx", ignore)]
+fn socket_listen_and_connect() {
+    each_ip(&mut |addr| {
+        let new_socket = if addr.is_ipv4() { TcpSocket::new_v4 } else { TcpSocket::new_v6 };
+
+        let socket = t!(new_socket());
+        t!(socket.set_reuseaddr(true));
+        assert_eq!(true, t!(socket.reuseaddr()));
+        t!(socket.bind(addr));
+        assert_eq!(addr, t!(socket.local_addr()));
+        let listener = t!(socket.listen(16));
+
+        let client_addr = SocketAddr::new(addr.ip(), 0);
+        let socket = t!(new_socket());
+        t!(socket.bind(client_addr));
+        let client_addr = t!(socket.local_addr());
+        assert_ne!(client_addr.port(), 0);
+        let mut client = t!(socket.connect(addr));
+
+        let (mut server, peer) = t!(listener.accept());
+        assert_eq!(peer, client_addr);
+        t!(client.write_all(&[1, 2, 3]));
+        let mut buf = [0; 3];
+        t!(server.read_exact(&mut buf));
+        assert_eq!(buf, [1, 2, 3]);
+    })
+}
+
+#[test]
+#[cfg_attr(target_env = "sgx", ignore)]
+fn socket_options() {
+    let socket = t!(TcpSocket::new_v4());
+
+    t!(socket.set_send_buffer_size(64 * 1024));
+    assert!(t!(socket.send_buffer_size()) >= 64 * 1024);
+    t!(socket.set_recv_buffer_size(64 * 1024));
+    assert!(t!(socket.recv_buffer_size()) >= 64 * 1024);
+
+    t!(socket.set_nodelay(true));
+    assert_eq!(true, t!(socket.nodelay()));
+
+    assert_eq!(false, t!(socket.keepalive()));
+    let keepalive = TcpKeepalive::new()
+        .with_time(Duration::from_secs(30))
+        .with_interval(Duration::from_millis(2500))
+        .with_retries(3);
+    t!(socket.set_tcp_keepalive(&keepalive));
+    assert_eq!(true, t!(socket.keepalive()));
+    t!(socket.set_keepalive(false));
+    assert_eq!(false, t!(socket.keepalive()));
+}
+
+#[test]
+#[cfg(any(target_os = "linux", target_os = "android", target_vendor = "apple"))]
+fn socket_reuseport() {
+    let addr = next_test_ip4();
+    let bind = || {
+        let socket = t!(TcpSocket::new_v4());
+        t!(socket.set_reuseport(true));
+        assert_eq!(true, t!(socket.reuseport()));
+        t!(socket.bind(addr));
+        t!(socket.listen(16))
+    };
+    let _first = bind();
+    let _second = bind();
+}
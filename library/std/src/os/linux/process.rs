@@ -162,6 +162,35 @@ pub trait CommandExt: Sealed {
     /// [`pidfd`]: fn@ChildExt::pidfd
     /// [`take_pidfd`]: ChildExt::take_pidfd
     fn create_pidfd(&mut self, val: bool) -> &mut process::Command;
+
+    /// Sets the signal the child process receives when its parent exits.
+    /// Equivalent to a `prctl(PR_SET_PDEATHSIG)` call in the child process.
+    ///
+    /// This makes sure that a child does not outlive the process that spawned
+    /// it, for example by passing `SIGKILL`. If the parent has already exited
+    /// by the time the child sets this up, the child sends the signal to itself
+    /// right away.
+    ///
+    /// Note that the signal is sent when the *thread* which spawned the child
+    /// exits, not the whole parent process. It is also cleared when the child
+    /// executes a set-user-ID or set-group-ID program, and does not carry over
+    /// to the child's own children.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(linux_pidfd, process_parent_death_signal)]
+    /// use std::process::Command;
+    /// use std::os::linux::process::CommandExt;
+    ///
+    /// const SIGKILL: i32 = 9;
+    /// let mut child = Command::new("worker").parent_death_signal(SIGKILL).spawn()?;
+    /// child.wait()?;
+    /// #
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    #[unstable(feature = "process_parent_death_signal", issue = "none")]
+    fn parent_death_signal(&mut self, signal: i32) -> &mut process::Command;
 }
 
 impl CommandExt for process::Command {
@@ -169,4 +198,9 @@ fn create_pidfd(&mut self, val: bool) -> &mut process::Command {
         self.as_inner_mut().create_pidfd(val);
         self
     }
+
+    fn parent_death_signal(&mut self, signal: i32) -> &mut process::Command {
+        self.as_inner_mut().pdeathsig(signal);
+        self
+    }
 }
@@ -194,6 +194,81 @@ fn arg0<S>(&mut self, arg: S) -> &mut process::Command
     /// ```
     #[stable(feature = "process_set_process_group", since = "1.64.0")]
     fn process_group(&mut self, pgroup: i32) -> &mut process::Command;
+
+    /// Sets whether the child process should run in a new session. Equivalent
+    /// to a `setsid` call in the child process.
+    ///
+    /// The child becomes the leader of a new session and of a new process
+    /// group, both with its process ID as their ID, and is detached from the
+    /// controlling terminal of the parent. Its process group can then be
+    /// signaled as a whole with [`ChildExt::kill_process_group`].
+    ///
+    /// This cannot be combined with [`process_group`], as a session leader
+    /// cannot change its process group; spawning then fails.
+    ///
+    /// [`process_group`]: CommandExt::process_group
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(process_setsid)]
+    /// use std::process::Command;
+    /// use std::os::unix::process::CommandExt;
+    ///
+    /// Command::new("daemon").setsid(true).spawn()?;
+    /// #
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    #[unstable(feature = "process_setsid", issue = "none")]
+    fn setsid(&mut self, setsid: bool) -> &mut process::Command;
+
+    /// Makes `fd` available to the child process as file descriptor number
+    /// `child_fd`, in addition to stdin, stdout and stderr.
+    ///
+    /// This is how extra pipes or files are handed to a child, for instance
+    /// to use fd 3 for a side channel. Mapping the same `child_fd` again
+    /// replaces the earlier mapping. Mappings are installed after the child's
+    /// stdio, so mapping to 0, 1 or 2 overrides [`stdin`], [`stdout`] or
+    /// [`stderr`] respectively.
+    ///
+    /// The `Command` holds on to `fd` so that it can spawn any number of
+    /// children, which also means that the file stays open in this process
+    /// until the `Command` is dropped. For a pipe, that delays end-of-file
+    /// for the other end.
+    ///
+    /// Spawning fails if `child_fd` is negative, and on platforms which
+    /// don't support fd mappings.
+    ///
+    /// [`stdin`]: process::Command::stdin
+    /// [`stdout`]: process::Command::stdout
+    /// [`stderr`]: process::Command::stderr
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(process_map_fd, anonymous_pipe)]
+    /// use std::io::{self, Read};
+    /// use std::os::unix::process::CommandExt;
+    /// use std::process::Command;
+    ///
+    /// let (mut reader, writer) = io::pipe()?;
+    /// let mut child = Command::new("sh")
+    ///     .arg("-c")
+    ///     .arg("echo hello >&3")
+    ///     .map_fd(3, writer)
+    ///     .spawn()?;
+    ///
+    /// let mut output = String::new();
+    /// reader.read_to_string(&mut output)?;
+    /// child.wait()?;
+    /// assert_eq!(output, "hello\n");
+    /// #
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    #[unstable(feature = "process_map_fd", issue = "none")]
+    fn map_fd<F>(&mut self, child_fd: RawFd, fd: F) -> &mut process::Command
+    where
+        F: Into<OwnedFd>;
 }
 
 #[stable(feature = "rust1", since = "1.0.0")]
@@ -239,6 +314,76 @@ fn process_group(&mut self, pgroup: i32) -> &mut process::Command {
         self.as_inner_mut().pgroup(pgroup);
         self
     }
+
+    fn setsid(&mut self, setsid: bool) -> &mut process::Command {
+        self.as_inner_mut().setsid(setsid);
+        self
+    }
+
+    fn map_fd<F>(&mut self, child_fd: RawFd, fd: F) -> &mut process::Command
+    where
+        F: Into<OwnedFd>,
+    {
+        self.as_inner_mut().map_fd(child_fd, fd.into());
+        self
+    }
+}
+
+/// Unix-specific extensions to [`process::Child`].
+///
+/// This trait is sealed: it cannot be implemented outside the standard library.
+/// This is so that future additional methods are not breaking changes.
+#[unstable(feature = "process_group_kill", issue = "none")]
+pub trait ChildExt: Sealed {
+    /// Forces the child's whole process group to exit, by sending `SIGKILL` to
+    /// it with `killpg`.
+    ///
+    /// [`Child::kill`] only kills the child itself, leaving any processes it
+    /// has started running. If the child was spawned in a process group of its
+    /// own, with [`CommandExt::process_group`] or [`CommandExt::setsid`], this
+    /// kills all of them at once, unless they moved to another process group.
+    /// If the child was added to an existing process group instead, all of that
+    /// group is killed.
+    ///
+    /// The group can only be killed until the child has been waited on. As long
+    /// as the child is not reaped, it is still a member of the group, so the
+    /// process group ID cannot have been reused by the system for an unrelated
+    /// group. To clean up the descendants of a child, kill its group first and
+    /// then wait on it.
+    ///
+    /// [`Child::kill`]: process::Child::kill
+    ///
+    /// # Errors
+    ///
+    /// Returns an error of kind [`InvalidInput`] if the child was spawned in
+    /// the process group of the parent, or if it has already been waited on.
+    ///
+    /// [`InvalidInput`]: io::ErrorKind::InvalidInput
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(process_group_kill)]
+    /// use std::process::Command;
+    /// use std::os::unix::process::{ChildExt, CommandExt};
+    ///
+    /// let mut child = Command::new("sh")
+    ///     .args(["-c", "sleep 100 & sleep 100"])
+    ///     .process_group(0)
+    ///     .spawn()?;
+    /// child.kill_process_group()?;
+    /// child.wait()?;
+    /// #
+    /// # Ok::<_, Box<dyn std::error::Error>>(())
+    /// ```
+    fn kill_process_group(&mut self) -> io::Result<()>;
+}
+
+#[unstable(feature = "process_group_kill", issue = "none")]
+impl ChildExt for process::Child {
+    fn kill_process_group(&mut self) -> io::Result<()> {
+        self.handle.kill_process_group()
+    }
 }
 
 /// Unix-specific extensions to [`process::ExitStatus`] and
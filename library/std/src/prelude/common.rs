@@ -60,6 +60,9 @@
 #[unstable(feature = "derive_const", issue = "none")]
 pub use core::prelude::v1::derive_const;
 
+#[unstable(feature = "fuzz", issue = "none")]
+pub use core::prelude::v1::fuzz;
+
 // Do not `doc(no_inline)` either.
 #[unstable(
     feature = "cfg_accessible",
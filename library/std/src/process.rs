@@ -161,11 +161,12 @@
 use crate::num::NonZero;
 use crate::path::Path;
 use crate::str;
-use crate::sys::pipe::{read2, AnonPipe};
+use crate::sys::pipe::{read2, read2_deadline, AnonPipe};
 use crate::sys::process as imp;
 #[stable(feature = "command_access", since = "1.57.0")]
 pub use crate::sys_common::process::CommandEnvs;
 use crate::sys_common::{AsInner, AsInnerMut, FromInner, IntoInner};
+use crate::time::{Duration, Instant};
 
 /// Representation of a running or exited child process.
 ///
@@ -2189,6 +2190,96 @@ pub fn try_wait(&mut self) -> io::Result<Option<ExitStatus>> {
         Ok(self.handle.try_wait()?.map(ExitStatus))
     }
 
+    /// Waits for the child to exit for at most `timeout`, returning its exit
+    /// status if it did.
+    ///
+    /// The stdin handle to the child process, if any, will be closed before
+    /// waiting, as with [`wait`].
+    ///
+    /// If the child exits before the timeout elapses, then `Ok(Some(status))`
+    /// is returned and, on Unix, the process ID is reaped. If the child is
+    /// still running once the timeout elapses then `Ok(None)` is returned and
+    /// the child is left running: it can be waited on again, or [`kill`]ed.
+    ///
+    /// This is equivalent to calling [`wait_deadline`] with a deadline of
+    /// `timeout` from now.
+    ///
+    /// [`wait`]: Self::wait
+    /// [`kill`]: Self::kill
+    /// [`wait_deadline`]: Self::wait_deadline
+    ///
+    /// # Platform-specific behavior
+    ///
+    /// On Linux this blocks on a pidfd for the child, so it returns as soon as
+    /// the child exits. Where pidfds are unavailable, and on other Unix
+    /// platforms, the child is instead polled with [`try_wait`] at short
+    /// intervals, so the exit may be noticed a few milliseconds late. On
+    /// Windows this waits on the process handle.
+    ///
+    /// [`try_wait`]: Self::try_wait
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(child_wait_timeout)]
+    /// use std::process::Command;
+    /// use std::time::Duration;
+    ///
+    /// let mut child = Command::new("sleep").arg("60").spawn().unwrap();
+    ///
+    /// if child.wait_timeout(Duration::from_secs(1)).unwrap().is_none() {
+    ///     println!("child is taking too long, killing it");
+    ///     child.kill().unwrap();
+    ///     child.wait().unwrap();
+    /// }
+    /// ```
+    #[unstable(feature = "child_wait_timeout", issue = "none")]
+    pub fn wait_timeout(&mut self, timeout: Duration) -> io::Result<Option<ExitStatus>> {
+        match Instant::now().checked_add(timeout) {
+            Some(deadline) => self.wait_deadline(deadline),
+            // A deadline this far out will never be reached.
+            None => self.wait().map(Some),
+        }
+    }
+
+    /// Waits for the child to exit until `deadline`, returning its exit
+    /// status if it did.
+    ///
+    /// This behaves like [`wait_timeout`], but takes an absolute deadline,
+    /// which is more convenient when waiting on several children, or
+    /// retrying, within one overall time limit. A deadline which has already
+    /// passed makes this equivalent to [`try_wait`], except that stdin is
+    /// closed.
+    ///
+    /// [`wait_timeout`]: Self::wait_timeout
+    /// [`try_wait`]: Self::try_wait
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(child_wait_timeout)]
+    /// use std::process::Command;
+    /// use std::time::{Duration, Instant};
+    ///
+    /// let mut children = vec![
+    ///     Command::new("sleep").arg("1").spawn().unwrap(),
+    ///     Command::new("sleep").arg("2").spawn().unwrap(),
+    /// ];
+    ///
+    /// let deadline = Instant::now() + Duration::from_secs(5);
+    /// for child in &mut children {
+    ///     match child.wait_deadline(deadline).unwrap() {
+    ///         Some(status) => println!("exited with: {status}"),
+    ///         None => println!("still running at the deadline"),
+    ///     }
+    /// }
+    /// ```
+    #[unstable(feature = "child_wait_timeout", issue = "none")]
+    pub fn wait_deadline(&mut self, deadline: Instant) -> io::Result<Option<ExitStatus>> {
+        drop(self.stdin.take());
+        Ok(self.handle.wait_deadline(deadline)?.map(ExitStatus))
+    }
+
     /// Simultaneously waits for the child to exit and collect all remaining
     /// output on the stdout/stderr handles, returning an `Output`
     /// instance.
@@ -2245,6 +2336,77 @@ pub fn wait_with_output(mut self) -> io::Result<Output> {
         let status = self.wait()?;
         Ok(Output { status, stdout, stderr })
     }
+
+    /// Like [`wait_with_output`], but gives up if the child has not exited
+    /// and closed its stdout and stderr within `timeout`.
+    ///
+    /// Stdout and stderr are read concurrently, so a child that fills up the
+    /// pipe for one of them while the parent is waiting on the other cannot
+    /// cause a deadlock.
+    ///
+    /// If the child finishes in time, then `Ok(Some(output))` is returned. If
+    /// it does not, then the child is killed and reaped, and `Ok(None)` is
+    /// returned. The output read before the timeout is discarded, so it is not
+    /// available to diagnose a hung child.
+    ///
+    /// If reading the output or waiting fails, the child is killed and reaped
+    /// as well before the error is returned.
+    ///
+    /// Note that the output pipes can be held open by grandchildren which
+    /// inherited them, in which case this will time out even if the child
+    /// itself has exited.
+    ///
+    /// [`wait_with_output`]: Self::wait_with_output
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #![feature(child_wait_timeout)]
+    /// use std::process::{Command, Stdio};
+    /// use std::time::Duration;
+    ///
+    /// let child = Command::new("cargo")
+    ///     .arg("test")
+    ///     .stdout(Stdio::piped())
+    ///     .stderr(Stdio::piped())
+    ///     .spawn()
+    ///     .expect("failed to execute child");
+    ///
+    /// match child.wait_with_output_timeout(Duration::from_secs(600)) {
+    ///     Ok(Some(output)) => println!("tests finished: {}", output.status),
+    ///     Ok(None) => println!("tests timed out"),
+    ///     Err(e) => println!("error waiting for tests: {e}"),
+    /// }
+    /// ```
+    #[unstable(feature = "child_wait_timeout", issue = "none")]
+    pub fn wait_with_output_timeout(mut self, timeout: Duration) -> io::Result<Option<Output>> {
+        let Some(deadline) = Instant::now().checked_add(timeout) else {
+            return self.wait_with_output().map(Some);
+        };
+        drop(self.stdin.take());
+
+        let (mut stdout, mut stderr) = (Vec::new(), Vec::new());
+        let out = self.stdout.take().map(|out| out.inner);
+        let err = self.stderr.take().map(|err| err.inner);
+        let status = read2_deadline(out, &mut stdout, err, &mut stderr, deadline)
+            .and_then(|finished| if finished { self.wait_deadline(deadline) } else { Ok(None) });
+        match status {
+            Ok(Some(status)) => Ok(Some(Output { status, stdout, stderr })),
+            Ok(None) => {
+                self.kill()?;
+                self.wait()?;
+                Ok(None)
+            }
+            Err(e) => {
+                // Don't leave the child running. Waiting would block if it can't
+                // be killed, in which case it is left alone.
+                if self.kill().is_ok() {
+                    let _ = self.wait();
+                }
+                Err(e)
+            }
+        }
+    }
 }
 
 /// Terminates the current process with the specified exit code.
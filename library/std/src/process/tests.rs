@@ -4,6 +4,7 @@
 use crate::io::{BorrowedBuf, ErrorKind};
 use crate::mem::MaybeUninit;
 use crate::str;
+use crate::time::{Duration, Instant};
 
 fn known_command() -> Command {
     if cfg!(windows) { Command::new("help") } else { Command::new("echo") }
@@ -247,6 +248,80 @@ fn test_wait_with_output_once() {
     assert_eq!(stderr, Vec::new());
 }
 
+fn sleep_cmd() -> Command {
+    if cfg!(target_os = "windows") {
+        let mut cmd = Command::new("ping");
+        cmd.args(&["-n", "30", "127.0.0.1"]);
+        cmd
+    } else {
+        let mut cmd = shell_cmd();
+        cmd.args(&["-c", "exec sleep 30"]);
+        cmd
+    }
+}
+
+#[test]
+#[cfg_attr(any(target_os = "vxworks"), ignore)]
+fn test_wait_timeout() {
+    let mut prog = if cfg!(target_os = "windows") {
+        Command::new("cmd").args(&["/C", "exit 1"]).spawn().unwrap()
+    } else {
+        shell_cmd().arg("-c").arg("false").spawn().unwrap()
+    };
+    let status = prog.wait_timeout(Duration::from_secs(60)).unwrap();
+    assert_eq!(status.unwrap().code(), Some(1));
+    // The status is kept around once collected, as with `wait`.
+    let status = prog.wait_timeout(Duration::ZERO).unwrap();
+    assert_eq!(status.unwrap().code(), Some(1));
+}
+
+#[test]
+#[cfg_attr(any(target_os = "vxworks"), ignore)]
+fn test_wait_timeout_expires() {
+    let mut prog = sleep_cmd().stdout(Stdio::null()).spawn().unwrap();
+    let start = Instant::now();
+    assert!(prog.wait_timeout(Duration::from_millis(100)).unwrap().is_none());
+    assert!(start.elapsed() >= Duration::from_millis(100));
+    assert!(prog.wait_deadline(start).unwrap().is_none());
+    prog.kill().unwrap();
+    assert!(!prog.wait_timeout(Duration::from_secs(60)).unwrap().unwrap().success());
+}
+
+#[test]
+#[cfg_attr(any(target_os = "vxworks"), ignore)]
+fn test_wait_with_output_timeout() {
+    let prog = if cfg!(target_os = "windows") {
+        Command::new("cmd")
+            .args(&["/C", "echo hello & echo world 1>&2"])
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .unwrap()
+    } else {
+        shell_cmd()
+            .arg("-c")
+            .arg("echo hello; echo world 1>&2")
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .unwrap()
+    };
+
+    let output = prog.wait_with_output_timeout(Duration::from_secs(60)).unwrap().unwrap();
+    assert!(output.status.success());
+    assert_eq!(str::from_utf8(&output.stdout).unwrap().trim(), "hello");
+    assert_eq!(str::from_utf8(&output.stderr).unwrap().trim(), "world");
+}
+
+#[test]
+#[cfg_attr(any(target_os = "vxworks"), ignore)]
+fn test_wait_with_output_timeout_expires() {
+    let prog = sleep_cmd().stdout(Stdio::piped()).spawn().unwrap();
+    let start = Instant::now();
+    assert!(prog.wait_with_output_timeout(Duration::from_millis(100)).unwrap().is_none());
+    assert!(start.elapsed() < Duration::from_secs(30));
+}
+
 #[cfg(all(unix, not(target_os = "android")))]
 pub fn env_cmd() -> Command {
     Command::new("env")
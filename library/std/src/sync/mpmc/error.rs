@@ -1,7 +1,10 @@
 use crate::error;
 use crate::fmt;
 
-pub use crate::sync::mpsc::{RecvError, RecvTimeoutError, SendError, TryRecvError, TrySendError};
+pub use crate::sync::mpsc::{
+    RecvError, RecvTimeoutError, SelectTimeoutError, SendError, TryRecvError, TrySelectError,
+    TrySendError,
+};
 
 /// An error returned from the [`send_timeout`] method.
 ///
@@ -483,6 +483,25 @@ pub enum TrySendError<T> {
     Disconnected(#[stable(feature = "rust1", since = "1.0.0")] T),
 }
 
+/// An error returned from [`Select::try_select`] when none of the operations
+/// was ready.
+///
+/// Failed selection attempts leave the channels untouched, so no message has
+/// been sent or received.
+#[unstable(feature = "mpsc_select", issue = "none")]
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub struct TrySelectError;
+
+/// An error returned from [`Select::select_timeout`] and
+/// [`Select::select_deadline`] when none of the operations became ready in
+/// time.
+///
+/// Failed selection attempts leave the channels untouched, so no message has
+/// been sent or received.
+#[unstable(feature = "mpsc_select", issue = "none")]
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub struct SelectTimeoutError;
+
 /// Creates a new asynchronous channel, returning the sender/receiver halves.
 /// All data sent on the [`Sender`] will become available on the [`Receiver`] in
 /// the same order as it was sent, and no [`send`] will block the calling thread
@@ -1093,6 +1112,419 @@ fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
     }
 }
 
+////////////////////////////////////////////////////////////////////////////////
+// Select
+////////////////////////////////////////////////////////////////////////////////
+
+/// Waits on several channel operations at once.
+///
+/// A `Select` is a list of receive operations on [`Receiver`]s and send
+/// operations on [`SyncSender`]s. Each operation added to it is assigned an
+/// index, and selecting blocks the current thread until at least one of the
+/// operations can proceed. The returned [`SelectedOperation`] tells which one
+/// was picked and must then be completed by calling its [`recv`] or [`send`]
+/// method with the same receiver or sender.
+///
+/// A receive operation is ready when there is a message in the channel or when
+/// all senders have disconnected, in which case completing it returns a
+/// [`RecvError`]. A send operation is ready when there is room in the buffer, a
+/// receiver waiting on a rendezvous channel, or when the receiver has
+/// disconnected. If several operations are ready at the same time, one of them
+/// is picked at random.
+///
+/// Send operations on a [`Sender`] cannot take part in a `Select` because they
+/// never block: just call [`Sender::send`] instead.
+///
+/// The [`select!`] macro is a more convenient way of waiting on a fixed set of
+/// operations.
+///
+/// [`recv`]: SelectedOperation::recv
+/// [`send`]: SelectedOperation::send
+///
+/// # Examples
+///
+/// ```
+/// #![feature(mpsc_select)]
+/// use std::sync::mpsc::{channel, Select};
+/// use std::thread;
+///
+/// let (tx1, rx1) = channel();
+/// let (tx2, rx2) = channel();
+///
+/// thread::spawn(move || tx1.send("first").unwrap());
+/// thread::spawn(move || tx2.send("second").unwrap());
+///
+/// let mut sel = Select::new();
+/// let index1 = sel.recv(&rx1);
+/// let index2 = sel.recv(&rx2);
+///
+/// let oper = sel.select();
+/// match oper.index() {
+///     i if i == index1 => println!("{}", oper.recv(&rx1).unwrap()),
+///     i if i == index2 => println!("{}", oper.recv(&rx2).unwrap()),
+///     _ => unreachable!(),
+/// }
+/// ```
+#[unstable(feature = "mpsc_select", issue = "none")]
+#[derive(Clone, Default)]
+pub struct Select<'a> {
+    inner: mpmc::Select<'a>,
+}
+
+impl<'a> Select<'a> {
+    /// Creates an empty list of channel operations.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(mpsc_select)]
+    /// use std::sync::mpsc::Select;
+    ///
+    /// let mut sel = Select::new();
+    ///
+    /// // There are no operations, so nothing can be selected.
+    /// assert!(sel.try_select().is_err());
+    /// ```
+    #[unstable(feature = "mpsc_select", issue = "none")]
+    #[must_use]
+    pub fn new() -> Select<'a> {
+        Select { inner: mpmc::Select::new() }
+    }
+
+    /// Adds a receive operation on `r` and returns its index.
+    #[unstable(feature = "mpsc_select", issue = "none")]
+    pub fn recv<T>(&mut self, r: &'a Receiver<T>) -> usize {
+        self.inner.recv(&r.inner)
+    }
+
+    /// Adds a send operation on `s` and returns its index.
+    ///
+    /// The message is only provided once the operation has been selected, when
+    /// it is completed with [`SelectedOperation::send`].
+    #[unstable(feature = "mpsc_select", issue = "none")]
+    pub fn send<T>(&mut self, s: &'a SyncSender<T>) -> usize {
+        self.inner.send(&s.inner)
+    }
+
+    /// Removes the operation with the given index.
+    ///
+    /// This is useful to stop waiting on a receiver once all of its senders
+    /// have disconnected, as that receive operation would otherwise be
+    /// selected over and over again. Indices of the other operations are not
+    /// affected.
+    ///
+    /// # Panics
+    ///
+    /// Panics if no operation with this index was added or if it has already
+    /// been removed.
+    #[unstable(feature = "mpsc_select", issue = "none")]
+    pub fn remove(&mut self, index: usize) {
+        self.inner.remove(index)
+    }
+
+    /// Attempts to select one of the operations without blocking.
+    ///
+    /// Returns an error if none of the operations is ready.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(mpsc_select)]
+    /// use std::sync::mpsc::{channel, Select};
+    ///
+    /// let (tx, rx) = channel();
+    /// let mut sel = Select::new();
+    /// let index = sel.recv(&rx);
+    ///
+    /// assert!(sel.try_select().is_err());
+    ///
+    /// tx.send(1).unwrap();
+    /// let oper = sel.try_select().unwrap();
+    /// assert_eq!(oper.index(), index);
+    /// assert_eq!(oper.recv(&rx), Ok(1));
+    /// ```
+    #[unstable(feature = "mpsc_select", issue = "none")]
+    pub fn try_select(&mut self) -> Result<SelectedOperation<'a>, TrySelectError> {
+        self.inner.try_select().map(|inner| SelectedOperation { inner })
+    }
+
+    /// Blocks until one of the operations is ready and selects it.
+    ///
+    /// If the list of operations is empty, this blocks forever.
+    #[unstable(feature = "mpsc_select", issue = "none")]
+    pub fn select(&mut self) -> SelectedOperation<'a> {
+        SelectedOperation { inner: self.inner.select() }
+    }
+
+    /// Blocks until one of the operations is ready and selects it, but only
+    /// for a limited time.
+    ///
+    /// Returns an error if none of the operations became ready before the
+    /// timeout elapsed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #![feature(mpsc_select)]
+    /// use std::sync::mpsc::{channel, Select};
+    /// use std::thread;
+    /// use std::time::Duration;
+    ///
+    /// let (tx, rx) = channel();
+    /// thread::spawn(move || {
+    ///     thread::sleep(Duration::from_millis(50));
+    ///     tx.send(1).unwrap();
+    /// });
+    ///
+    /// let mut sel = Select::new();
+    /// sel.recv(&rx);
+    /// match sel.select_timeout(Duration::from_secs(10)) {
+    ///     Ok(oper) => assert_eq!(oper.recv(&rx), Ok(1)),
+    ///     Err(_) => println!("no message within ten seconds"),
+    /// }
+    /// ```
+    #[unstable(feature = "mpsc_select", issue = "none")]
+    pub fn select_timeout(
+        &mut self,
+        timeout: Duration,
+    ) -> Result<SelectedOperation<'a>, SelectTimeoutError> {
+        self.inner.select_timeout(timeout).map(|inner| SelectedOperation { inner })
+    }
+
+    /// Blocks until one of the operations is ready and selects it, but only
+    /// until the given deadline.
+    ///
+    /// Returns an error if none of the operations became ready before the
+    /// deadline.
+    #[unstable(feature = "mpsc_select", issue = "none")]
+    pub fn select_deadline(
+        &mut self,
+        deadline: Instant,
+    ) -> Result<SelectedOperation<'a>, SelectTimeoutError> {
+        self.inner.select_deadline(deadline).map(|inner| SelectedOperation { inner })
+    }
+}
+
+#[unstable(feature = "mpsc_select", issue = "none")]
+impl fmt::Debug for Select<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Select").finish_non_exhaustive()
+    }
+}
+
+/// An operation picked by a [`Select`] that still has to be completed.
+///
+/// The selected receive or send is completed by passing the same
+/// [`Receiver`] or [`SyncSender`] that was added to the [`Select`] to [`recv`]
+/// or [`send`]. Until then, the channel may hold a slot reserved for this
+/// operation, so other threads can be kept waiting on it.
+///
+/// # Panics
+///
+/// Dropping a `SelectedOperation` without completing it panics.
+///
+/// [`recv`]: SelectedOperation::recv
+/// [`send`]: SelectedOperation::send
+#[unstable(feature = "mpsc_select", issue = "none")]
+#[must_use = "a selected operation must be completed with `recv` or `send`"]
+pub struct SelectedOperation<'a> {
+    inner: mpmc::SelectedOperation<'a>,
+}
+
+impl SelectedOperation<'_> {
+    /// Returns the index of the selected operation, as returned by
+    /// [`Select::recv`] or [`Select::send`] when it was added.
+    #[unstable(feature = "mpsc_select", issue = "none")]
+    pub fn index(&self) -> usize {
+        self.inner.index()
+    }
+
+    /// Completes the selected receive operation.
+    ///
+    /// Returns a [`RecvError`] if the channel is empty and all of its senders
+    /// have disconnected.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `r` is not the receiver of the selected operation.
+    #[unstable(feature = "mpsc_select", issue = "none")]
+    pub fn recv<T>(self, r: &Receiver<T>) -> Result<T, RecvError> {
+        self.inner.recv(&r.inner)
+    }
+
+    /// Completes the selected send operation by sending `msg`.
+    ///
+    /// Returns a [`SendError`] containing the message if the receiver has
+    /// disconnected.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `s` is not the sender of the selected operation.
+    #[unstable(feature = "mpsc_select", issue = "none")]
+    pub fn send<T>(self, s: &SyncSender<T>, msg: T) -> Result<(), SendError<T>> {
+        self.inner.send(&s.inner, msg)
+    }
+}
+
+#[unstable(feature = "mpsc_select", issue = "none")]
+impl fmt::Debug for SelectedOperation<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SelectedOperation").field("index", &self.index()).finish_non_exhaustive()
+    }
+}
+
+/// Waits on several channel operations at once and runs the code of the one
+/// that completes.
+///
+/// Each case names a receive or send operation, a pattern for its result and
+/// an expression to evaluate when it is selected:
+///
+/// ```text
+/// select! {
+///     recv(receiver) -> result => expression,
+///     send(sync_sender, message) -> result => expression,
+///     default => expression,
+///     default(timeout) => expression,
+/// }
+/// ```
+///
+/// The macro blocks until one of the operations can proceed, completes it and
+/// evaluates the expression of its case with the pattern bound to the result:
+/// a `Result<T, RecvError>` for `recv` and a `Result<(), SendError<T>>` for
+/// `send`. If several operations are ready, one of them is picked at random.
+/// The message of a `send` case is only evaluated if that case is selected.
+///
+/// An optional `default` case must come last. Without a timeout, it is
+/// evaluated if none of the operations is ready right away; with a
+/// [`Duration`] timeout, it is evaluated if none of them became ready before
+/// the timeout elapsed.
+///
+/// The expressions are evaluated in the enclosing function rather than a
+/// closure, so they can use `return`, `break`, `continue` and `?`. See
+/// [`Select`] for the semantics of the individual operations.
+///
+/// [`Duration`]: crate::time::Duration
+///
+/// # Examples
+///
+/// ```
+/// #![feature(mpsc_select)]
+/// use std::sync::mpsc::{channel, select, sync_channel};
+/// use std::thread;
+/// use std::time::Duration;
+///
+/// let (tx1, rx1) = channel();
+/// let (tx2, rx2) = sync_channel(0);
+///
+/// thread::spawn(move || tx1.send(1).unwrap());
+/// thread::spawn(move || tx2.send(2).unwrap());
+///
+/// select! {
+///     recv(rx1) -> msg => assert_eq!(msg, Ok(1)),
+///     recv(rx2) -> msg => assert_eq!(msg, Ok(2)),
+///     default(Duration::from_secs(10)) => panic!("no message within ten seconds"),
+/// }
+/// ```
+///
+/// Sending without blocking:
+///
+/// ```
+/// #![feature(mpsc_select)]
+/// use std::sync::mpsc::{select, sync_channel};
+///
+/// let (tx, rx) = sync_channel(1);
+///
+/// for i in 0..2 {
+///     select! {
+///         send(tx, i) -> res => res.unwrap(),
+///         default => println!("the buffer is full, dropping {i}"),
+///     }
+/// }
+/// assert_eq!(rx.try_recv(), Ok(0));
+/// ```
+#[unstable(feature = "mpsc_select", issue = "none")]
+#[allow_internal_unstable(mpsc_select)]
+#[rustc_macro_transparency = "semitransparent"]
+pub macro select {
+    (@cases $sel:ident; recv($r:expr) -> $res:pat => $body:expr, $($rest:tt)*) => {
+        $crate::sync::mpsc::select!(@recv $sel; ($r) ($res) ($body) $($rest)*)
+    },
+    (@cases $sel:ident; recv($r:expr) -> $res:pat => $body:expr) => {
+        $crate::sync::mpsc::select!(@recv $sel; ($r) ($res) ($body))
+    },
+    (@cases $sel:ident; recv($r:expr) -> $res:pat => $body:block $($rest:tt)*) => {
+        $crate::sync::mpsc::select!(@recv $sel; ($r) ($res) ($body) $($rest)*)
+    },
+    (@cases $sel:ident; send($s:expr, $m:expr) -> $res:pat => $body:expr, $($rest:tt)*) => {
+        $crate::sync::mpsc::select!(@send $sel; ($s) ($m) ($res) ($body) $($rest)*)
+    },
+    (@cases $sel:ident; send($s:expr, $m:expr) -> $res:pat => $body:expr) => {
+        $crate::sync::mpsc::select!(@send $sel; ($s) ($m) ($res) ($body))
+    },
+    (@cases $sel:ident; send($s:expr, $m:expr) -> $res:pat => $body:block $($rest:tt)*) => {
+        $crate::sync::mpsc::select!(@send $sel; ($s) ($m) ($res) ($body) $($rest)*)
+    },
+    (@cases $sel:ident; default => $body:expr $(,)?) => {
+        match $sel.try_select() {
+            $crate::result::Result::Ok(oper) => $crate::result::Result::Err(oper),
+            $crate::result::Result::Err(_) => $crate::result::Result::Ok($body),
+        }
+    },
+    (@cases $sel:ident; default($timeout:expr) => $body:expr $(,)?) => {
+        match $sel.select_timeout($timeout) {
+            $crate::result::Result::Ok(oper) => $crate::result::Result::Err(oper),
+            $crate::result::Result::Err(_) => $crate::result::Result::Ok($body),
+        }
+    },
+    (@cases $sel:ident;) => {
+        $crate::result::Result::Err($sel.select())
+    },
+    (@cases $sel:ident; $($tokens:tt)*) => {
+        $crate::compile_error!(
+            "expected `recv(..) -> .. => ..` or `send(.., ..) -> .. => ..` cases, \
+             optionally followed by a `default` case"
+        )
+    },
+    // Every case adds its operation, nests the remaining cases, and then
+    // completes the operation if it was the one they selected.
+    (@recv $sel:ident; ($r:expr) ($res:pat) ($body:expr) $($rest:tt)*) => {{
+        let r = &$r;
+        let index = $sel.recv(r);
+        // The body may diverge, which must not warn about wrapping its value.
+        #[allow(unreachable_code)]
+        let result = match $crate::sync::mpsc::select!(@cases $sel; $($rest)*) {
+            $crate::result::Result::Err(oper) if oper.index() == index => {
+                let $res = oper.recv(r);
+                $crate::result::Result::Ok($body)
+            }
+            result => result,
+        };
+        result
+    }},
+    (@send $sel:ident; ($s:expr) ($m:expr) ($res:pat) ($body:expr) $($rest:tt)*) => {{
+        let s = &$s;
+        let index = $sel.send(s);
+        // The body may diverge, which must not warn about wrapping its value.
+        #[allow(unreachable_code)]
+        let result = match $crate::sync::mpsc::select!(@cases $sel; $($rest)*) {
+            $crate::result::Result::Err(oper) if oper.index() == index => {
+                let $res = oper.send(s, $m);
+                $crate::result::Result::Ok($body)
+            }
+            result => result,
+        };
+        result
+    }},
+    ($($cases:tt)*) => {{
+        let mut sel = $crate::sync::mpsc::Select::new();
+        let result = $crate::sync::mpsc::select!(@cases sel; $($cases)*);
+        match result {
+            $crate::result::Result::Ok(value) => value,
+            $crate::result::Result::Err(_) => $crate::unreachable!(),
+        }
+    }},
+}
+
 #[stable(feature = "rust1", since = "1.0.0")]
 impl<T> fmt::Debug for SendError<T> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
@@ -1244,3 +1676,23 @@ fn from(err: RecvError) -> RecvTimeoutError {
         }
     }
 }
+
+#[unstable(feature = "mpsc_select", issue = "none")]
+impl fmt::Display for TrySelectError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        "all operations in select would block".fmt(f)
+    }
+}
+
+#[unstable(feature = "mpsc_select", issue = "none")]
+impl error::Error for TrySelectError {}
+
+#[unstable(feature = "mpsc_select", issue = "none")]
+impl fmt::Display for SelectTimeoutError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        "timed out waiting on select".fmt(f)
+    }
+}
+
+#[unstable(feature = "mpsc_select", issue = "none")]
+impl error::Error for SelectTimeoutError {}
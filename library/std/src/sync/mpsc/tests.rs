@@ -720,3 +720,246 @@ fn issue_39364() {
     let _tx = t.join().unwrap(); // delay dropping until end of test
     let _ = rx.recv_timeout(Duration::from_millis(500));
 }
+
+#[test]
+fn select_smoke() {
+    let (tx1, rx1) = channel::<i32>();
+    let (tx2, rx2) = channel::<i32>();
+
+    let mut sel = Select::new();
+    let i1 = sel.recv(&rx1);
+    let i2 = sel.recv(&rx2);
+    assert_eq!(sel.try_select().unwrap_err(), TrySelectError);
+
+    tx2.send(2).unwrap();
+    let oper = sel.select();
+    assert_eq!(oper.index(), i2);
+    assert_eq!(oper.recv(&rx2), Ok(2));
+
+    tx1.send(1).unwrap();
+    let oper = sel.try_select().unwrap();
+    assert_eq!(oper.index(), i1);
+    assert_eq!(oper.recv(&rx1), Ok(1));
+}
+
+#[test]
+fn select_blocks_until_ready() {
+    let (tx1, rx1) = channel::<i32>();
+    let (tx2, rx2) = sync_channel::<i32>(0);
+    let t = thread::spawn(move || {
+        thread::sleep(Duration::from_millis(50));
+        tx2.send(2).unwrap();
+        tx1
+    });
+
+    select! {
+        recv(rx1) -> _ => panic!(),
+        recv(rx2) -> msg => assert_eq!(msg, Ok(2)),
+    }
+    drop(t.join().unwrap());
+}
+
+#[test]
+fn select_send() {
+    let (tx1, rx1) = sync_channel::<i32>(1);
+    let (tx2, rx2) = sync_channel::<i32>(0);
+
+    let mut sel = Select::new();
+    let i1 = sel.send(&tx1);
+    sel.send(&tx2);
+    let oper = sel.select();
+    assert_eq!(oper.index(), i1);
+    oper.send(&tx1, 1).unwrap();
+    assert!(sel.try_select().is_err());
+
+    // A rendezvous send is selected once a receiver shows up.
+    let t = thread::spawn(move || rx2.recv().unwrap());
+    select! {
+        send(tx1, 10) -> _ => panic!(),
+        send(tx2, 2) -> res => assert_eq!(res, Ok(())),
+    }
+    assert_eq!(t.join().unwrap(), 2);
+    assert_eq!(rx1.recv(), Ok(1));
+}
+
+#[test]
+fn select_rendezvous_recv() {
+    let (tx, rx) = sync_channel::<i32>(0);
+    let t = thread::spawn(move || {
+        for i in 0..100 {
+            tx.send(i).unwrap();
+        }
+    });
+
+    for i in 0..100 {
+        let mut sel = Select::new();
+        sel.recv(&rx);
+        assert_eq!(sel.select().recv(&rx), Ok(i));
+    }
+    t.join().unwrap();
+}
+
+#[test]
+fn select_disconnected() {
+    let (tx1, rx1) = channel::<i32>();
+    let (tx2, rx2) = sync_channel::<i32>(1);
+    drop(tx1);
+
+    let mut sel = Select::new();
+    let i1 = sel.recv(&rx1);
+    let oper = sel.select();
+    assert_eq!(oper.index(), i1);
+    assert_eq!(oper.recv(&rx1), Err(RecvError));
+
+    drop(rx2);
+    select! {
+        send(tx2, 1) -> res => assert_eq!(res, Err(SendError(1))),
+    }
+}
+
+#[test]
+fn select_timeout() {
+    let (_tx, rx) = channel::<i32>();
+
+    let mut sel = Select::new();
+    sel.recv(&rx);
+    let start = Instant::now();
+    assert_eq!(sel.select_timeout(Duration::from_millis(50)).unwrap_err(), SelectTimeoutError);
+    assert!(start.elapsed() >= Duration::from_millis(50));
+    assert!(sel.select_deadline(Instant::now()).is_err());
+
+    // An empty `Select` sleeps until the timeout.
+    let mut sel = Select::new();
+    assert!(sel.select_timeout(Duration::from_millis(10)).is_err());
+}
+
+#[test]
+fn select_remove() {
+    let (tx1, rx1) = channel::<i32>();
+    let (tx2, rx2) = channel::<i32>();
+    drop(tx1);
+    tx2.send(2).unwrap();
+
+    let mut sel = Select::new();
+    let i1 = sel.recv(&rx1);
+    let i2 = sel.recv(&rx2);
+    sel.remove(i1);
+    let oper = sel.select();
+    assert_eq!(oper.index(), i2);
+    assert_eq!(oper.recv(&rx2), Ok(2));
+}
+
+#[test]
+#[should_panic = "passed a receiver that wasn't selected"]
+fn select_wrong_receiver() {
+    let (tx1, rx1) = channel::<i32>();
+    let (_tx2, rx2) = channel::<i32>();
+    tx1.send(1).unwrap();
+
+    let mut sel = Select::new();
+    sel.recv(&rx1);
+    sel.recv(&rx2);
+    let _ = sel.select().recv(&rx2);
+}
+
+#[test]
+fn select_macro_default() {
+    let (tx, rx) = channel::<i32>();
+
+    let mut value = select! {
+        recv(rx) -> _ => 1,
+        default => 0,
+    };
+    assert_eq!(value, 0);
+
+    tx.send(5).unwrap();
+    for _ in 0..2 {
+        value = select! {
+            recv(rx) -> msg => { msg.unwrap() }
+            default(Duration::from_millis(10)) => { -1 }
+        };
+    }
+    assert_eq!(value, -1);
+
+    // Case bodies run in the enclosing function.
+    let double = || -> Result<i32, RecvError> {
+        tx.send(21).unwrap();
+        select! {
+            recv(rx) -> msg => return Ok(msg? * 2),
+        }
+    };
+    assert_eq!(double(), Ok(42));
+}
+
+#[test]
+fn select_stress() {
+    let stress = stress_factor() + 1;
+    let amt = 1000 * stress;
+    let (tx1, rx1) = channel::<usize>();
+    let (tx2, rx2) = sync_channel::<usize>(0);
+    let (tx3, rx3) = sync_channel::<usize>(4);
+
+    let t1 = thread::spawn(move || {
+        for i in 0..amt {
+            tx1.send(i).unwrap();
+        }
+    });
+    let t2 = thread::spawn(move || {
+        for i in 0..amt {
+            tx2.send(i).unwrap();
+        }
+    });
+    let t3 = thread::spawn(move || {
+        let mut sum = 0;
+        for i in rx3.iter() {
+            sum += i;
+        }
+        sum
+    });
+
+    let (mut n1, mut n2, mut sent) = (0, 0, 0);
+    let mut sel = Select::new();
+    let i1 = sel.recv(&rx1);
+    let i2 = sel.recv(&rx2);
+    let i3 = sel.send(&tx3);
+    let mut live = 3;
+    while live > 0 {
+        let oper = sel.select();
+        match oper.index() {
+            i if i == i1 => match oper.recv(&rx1) {
+                Ok(i) => {
+                    assert_eq!(i, n1);
+                    n1 += 1;
+                }
+                Err(_) => {
+                    sel.remove(i1);
+                    live -= 1;
+                }
+            },
+            i if i == i2 => match oper.recv(&rx2) {
+                Ok(i) => {
+                    assert_eq!(i, n2);
+                    n2 += 1;
+                }
+                Err(_) => {
+                    sel.remove(i2);
+                    live -= 1;
+                }
+            },
+            i if i == i3 => {
+                oper.send(&tx3, sent).unwrap();
+                sent += 1;
+                if sent == amt {
+                    sel.remove(i3);
+                    live -= 1;
+                }
+            }
+            _ => unreachable!(),
+        }
+    }
+    assert_eq!((n1, n2), (amt, amt));
+    drop(tx3);
+    t1.join().unwrap();
+    t2.join().unwrap();
+    assert_eq!(t3.join().unwrap(), amt * (amt - 1) / 2);
+}
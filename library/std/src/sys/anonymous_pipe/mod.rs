@@ -0,0 +1,14 @@
+#![forbid(unsafe_op_in_unsafe_fn)]
+
+cfg_if::cfg_if! {
+    if #[cfg(unix)] {
+        mod unix;
+        pub use unix::{pipe, AnonPipe};
+    } else if #[cfg(windows)] {
+        mod windows;
+        pub use windows::{pipe, AnonPipe};
+    } else {
+        mod unsupported;
+        pub use unsupported::{pipe, AnonPipe};
+    }
+}
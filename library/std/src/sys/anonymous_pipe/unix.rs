@@ -0,0 +1,68 @@
+use crate::io::{self, PipeReader, PipeWriter};
+use crate::os::fd::{AsFd, AsRawFd, BorrowedFd, FromRawFd, IntoRawFd, OwnedFd, RawFd};
+use crate::process::Stdio;
+use crate::sys::fd::FileDesc;
+use crate::sys::pipe::anon_pipe;
+use crate::sys_common::{FromInner, IntoInner};
+
+pub use crate::sys::pipe::AnonPipe;
+
+#[inline]
+pub fn pipe() -> io::Result<(AnonPipe, AnonPipe)> {
+    anon_pipe()
+}
+
+macro_rules! impl_traits {
+    ($name:ident) => {
+        #[unstable(feature = "anonymous_pipe", issue = "none")]
+        impl AsFd for $name {
+            fn as_fd(&self) -> BorrowedFd<'_> {
+                self.0.as_fd()
+            }
+        }
+
+        #[unstable(feature = "anonymous_pipe", issue = "none")]
+        impl AsRawFd for $name {
+            fn as_raw_fd(&self) -> RawFd {
+                self.0.as_raw_fd()
+            }
+        }
+
+        #[unstable(feature = "anonymous_pipe", issue = "none")]
+        impl From<$name> for OwnedFd {
+            fn from(pipe: $name) -> Self {
+                pipe.0.into_inner().into_inner()
+            }
+        }
+
+        #[unstable(feature = "anonymous_pipe", issue = "none")]
+        impl From<OwnedFd> for $name {
+            fn from(fd: OwnedFd) -> Self {
+                Self(AnonPipe::from_inner(FileDesc::from_inner(fd)))
+            }
+        }
+
+        #[unstable(feature = "anonymous_pipe", issue = "none")]
+        impl FromRawFd for $name {
+            unsafe fn from_raw_fd(raw_fd: RawFd) -> Self {
+                Self(unsafe { AnonPipe::from_raw_fd(raw_fd) })
+            }
+        }
+
+        #[unstable(feature = "anonymous_pipe", issue = "none")]
+        impl IntoRawFd for $name {
+            fn into_raw_fd(self) -> RawFd {
+                self.0.into_raw_fd()
+            }
+        }
+
+        #[unstable(feature = "anonymous_pipe", issue = "none")]
+        impl From<$name> for Stdio {
+            fn from(pipe: $name) -> Self {
+                Self::from(OwnedFd::from(pipe))
+            }
+        }
+    };
+}
+impl_traits!(PipeReader);
+impl_traits!(PipeWriter);
@@ -0,0 +1,72 @@
+use crate::io::{self, PipeReader, PipeWriter};
+use crate::os::windows::io::{
+    AsHandle, AsRawHandle, BorrowedHandle, FromRawHandle, IntoRawHandle, OwnedHandle, RawHandle,
+};
+use crate::process::Stdio;
+use crate::sys::handle::Handle;
+use crate::sys::pipe::{anon_pipe, Pipes};
+use crate::sys_common::{FromInner, IntoInner};
+
+pub type AnonPipe = Handle;
+
+// `ours` is opened in overlapped mode, which `Handle`'s synchronous reads and
+// writes deal with, and neither end is inheritable.
+#[inline]
+pub fn pipe() -> io::Result<(AnonPipe, AnonPipe)> {
+    anon_pipe(true, false).map(|Pipes { ours, theirs }| (ours.into_inner(), theirs.into_inner()))
+}
+
+macro_rules! impl_traits {
+    ($name:ident) => {
+        #[unstable(feature = "anonymous_pipe", issue = "none")]
+        impl AsHandle for $name {
+            fn as_handle(&self) -> BorrowedHandle<'_> {
+                self.0.as_handle()
+            }
+        }
+
+        #[unstable(feature = "anonymous_pipe", issue = "none")]
+        impl AsRawHandle for $name {
+            fn as_raw_handle(&self) -> RawHandle {
+                self.0.as_raw_handle()
+            }
+        }
+
+        #[unstable(feature = "anonymous_pipe", issue = "none")]
+        impl From<$name> for OwnedHandle {
+            fn from(pipe: $name) -> Self {
+                pipe.0.into_inner()
+            }
+        }
+
+        #[unstable(feature = "anonymous_pipe", issue = "none")]
+        impl From<OwnedHandle> for $name {
+            fn from(handle: OwnedHandle) -> Self {
+                Self(Handle::from_inner(handle))
+            }
+        }
+
+        #[unstable(feature = "anonymous_pipe", issue = "none")]
+        impl FromRawHandle for $name {
+            unsafe fn from_raw_handle(raw_handle: RawHandle) -> Self {
+                Self(unsafe { Handle::from_raw_handle(raw_handle) })
+            }
+        }
+
+        #[unstable(feature = "anonymous_pipe", issue = "none")]
+        impl IntoRawHandle for $name {
+            fn into_raw_handle(self) -> RawHandle {
+                self.0.into_raw_handle()
+            }
+        }
+
+        #[unstable(feature = "anonymous_pipe", issue = "none")]
+        impl From<$name> for Stdio {
+            fn from(pipe: $name) -> Self {
+                Self::from(OwnedHandle::from(pipe))
+            }
+        }
+    };
+}
+impl_traits!(PipeReader);
+impl_traits!(PipeWriter);
@@ -5,7 +5,9 @@
 
 mod personality;
 
+pub mod anonymous_pipe;
 pub mod cmath;
+pub mod net_poll;
 pub mod os_str;
 pub mod path;
 pub mod sync;
@@ -0,0 +1,116 @@
+use super::Timeout;
+use crate::io;
+use crate::net::{Interest, Source};
+use crate::os::fd::{AsRawFd, FromRawFd, OwnedFd};
+use crate::sys::cvt;
+use crate::time::Duration;
+
+pub struct Poller {
+    epoll: OwnedFd,
+}
+
+#[derive(Copy, Clone)]
+#[repr(transparent)]
+pub struct Event(libc::epoll_event);
+
+impl Poller {
+    pub fn new() -> io::Result<Poller> {
+        let epoll = cvt(unsafe { libc::epoll_create1(libc::EPOLL_CLOEXEC) })?;
+        Ok(Poller { epoll: unsafe { OwnedFd::from_raw_fd(epoll) } })
+    }
+
+    pub fn register<S: Source + ?Sized>(
+        &self,
+        source: &S,
+        token: usize,
+        interest: Interest,
+    ) -> io::Result<()> {
+        self.ctl(libc::EPOLL_CTL_ADD, source, Some((token, interest)))
+    }
+
+    pub fn reregister<S: Source + ?Sized>(
+        &self,
+        source: &S,
+        token: usize,
+        interest: Interest,
+    ) -> io::Result<()> {
+        self.ctl(libc::EPOLL_CTL_MOD, source, Some((token, interest)))
+    }
+
+    pub fn deregister<S: Source + ?Sized>(&self, source: &S) -> io::Result<()> {
+        self.ctl(libc::EPOLL_CTL_DEL, source, None)
+    }
+
+    fn ctl<S: Source + ?Sized>(
+        &self,
+        op: libc::c_int,
+        source: &S,
+        registration: Option<(usize, Interest)>,
+    ) -> io::Result<()> {
+        let mut event = libc::epoll_event { events: 0, u64: 0 };
+        if let Some((token, interest)) = registration {
+            let mut events = 0;
+            if interest.is_readable() {
+                events |= libc::EPOLLIN | libc::EPOLLRDHUP;
+            }
+            if interest.is_writable() {
+                events |= libc::EPOLLOUT;
+            }
+            event = libc::epoll_event { events: events as u32, u64: token as u64 };
+        }
+        let fd = source.as_fd().as_raw_fd();
+        cvt(unsafe { libc::epoll_ctl(self.epoll.as_raw_fd(), op, fd, &mut event) })?;
+        Ok(())
+    }
+
+    pub fn poll(&self, events: &mut Vec<Event>, timeout: Option<Duration>) -> io::Result<()> {
+        let timeout = Timeout::new(timeout);
+        let max_events = events.capacity().min(libc::c_int::MAX as usize) as libc::c_int;
+        loop {
+            let n = unsafe {
+                libc::epoll_wait(
+                    self.epoll.as_raw_fd(),
+                    // `Event` is a transparent wrapper around `epoll_event`.
+                    events.as_mut_ptr().cast::<libc::epoll_event>(),
+                    max_events,
+                    timeout.remaining_ms(),
+                )
+            };
+            match cvt(n) {
+                Ok(n) => {
+                    // SAFETY: the kernel initialized the first `n` events.
+                    unsafe { events.set_len(n as usize) };
+                    return Ok(());
+                }
+                Err(e) if e.is_interrupted() => continue,
+                Err(e) => return Err(e),
+            }
+        }
+    }
+}
+
+impl Event {
+    pub fn token(&self) -> usize {
+        self.0.u64 as usize
+    }
+
+    pub fn is_readable(&self) -> bool {
+        self.has(libc::EPOLLIN)
+    }
+
+    pub fn is_writable(&self) -> bool {
+        self.has(libc::EPOLLOUT)
+    }
+
+    pub fn is_error(&self) -> bool {
+        self.has(libc::EPOLLERR)
+    }
+
+    pub fn is_hangup(&self) -> bool {
+        self.has(libc::EPOLLHUP | libc::EPOLLRDHUP)
+    }
+
+    fn has(&self, flags: libc::c_int) -> bool {
+        self.0.events & flags as u32 != 0
+    }
+}
@@ -0,0 +1,45 @@
+#![forbid(unsafe_op_in_unsafe_fn)]
+
+cfg_if::cfg_if! {
+    if #[cfg(any(target_os = "linux", target_os = "android"))] {
+        mod epoll;
+        pub use epoll::{Event, Poller};
+    } else if #[cfg(unix)] {
+        mod poll;
+        pub use poll::{Event, Poller};
+    } else if #[cfg(windows)] {
+        mod windows;
+        pub use windows::{Event, Poller};
+    } else {
+        mod unsupported;
+        pub use unsupported::{Event, Poller};
+    }
+}
+
+#[cfg(any(unix, windows))]
+use crate::time::{Duration, Instant};
+
+/// Tracks the time left until a poll times out, so that a wait interrupted
+/// by a signal can be resumed without extending the overall timeout.
+#[cfg(any(unix, windows))]
+struct Timeout(Option<Instant>);
+
+#[cfg(any(unix, windows))]
+impl Timeout {
+    fn new(timeout: Option<Duration>) -> Timeout {
+        // A timeout too large to represent is as good as no timeout at all.
+        Timeout(timeout.and_then(|timeout| Instant::now().checked_add(timeout)))
+    }
+
+    /// Returns the remaining time in milliseconds, rounded up so the wait
+    /// never ends early, with `-1` meaning to wait forever.
+    fn remaining_ms(&self) -> i32 {
+        match self.0 {
+            None => -1,
+            Some(deadline) => {
+                let remaining = deadline.saturating_duration_since(Instant::now());
+                remaining.as_nanos().div_ceil(1_000_000).min(i32::MAX as u128) as i32
+            }
+        }
+    }
+}
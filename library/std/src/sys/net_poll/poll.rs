@@ -0,0 +1,143 @@
+//! A `poll(2)` based poller for Unix platforms without `epoll`.
+//!
+//! The registered sources are kept in a table which is copied into a fresh
+//! `pollfd` array for every call, so the table's lock is not held while
+//! blocked and other threads can keep registering sources.
+
+use super::Timeout;
+use crate::io;
+use crate::net::{Interest, Source};
+use crate::os::fd::{AsRawFd, RawFd};
+use crate::sync::atomic::{AtomicUsize, Ordering};
+use crate::sync::Mutex;
+use crate::sys::cvt;
+use crate::time::Duration;
+
+pub struct Poller {
+    registrations: Mutex<Vec<Registration>>,
+    /// Where the next scan for ready sources starts, so that sources late in
+    /// the table are not starved when more are ready than fit in `events`.
+    next: AtomicUsize,
+}
+
+struct Registration {
+    fd: RawFd,
+    token: usize,
+    interest: Interest,
+}
+
+#[derive(Copy, Clone)]
+pub struct Event {
+    token: usize,
+    revents: libc::c_short,
+}
+
+impl Poller {
+    pub fn new() -> io::Result<Poller> {
+        Ok(Poller { registrations: Mutex::new(Vec::new()), next: AtomicUsize::new(0) })
+    }
+
+    pub fn register<S: Source + ?Sized>(
+        &self,
+        source: &S,
+        token: usize,
+        interest: Interest,
+    ) -> io::Result<()> {
+        let fd = source.as_fd().as_raw_fd();
+        let mut registrations = self.registrations.lock().unwrap();
+        if registrations.iter().any(|r| r.fd == fd) {
+            return Err(io::const_io_error!(
+                io::ErrorKind::AlreadyExists,
+                "source is already registered",
+            ));
+        }
+        registrations.push(Registration { fd, token, interest });
+        Ok(())
+    }
+
+    pub fn reregister<S: Source + ?Sized>(
+        &self,
+        source: &S,
+        token: usize,
+        interest: Interest,
+    ) -> io::Result<()> {
+        let fd = source.as_fd().as_raw_fd();
+        let mut registrations = self.registrations.lock().unwrap();
+        let registration = registrations.iter_mut().find(|r| r.fd == fd).ok_or(NOT_REGISTERED)?;
+        registration.token = token;
+        registration.interest = interest;
+        Ok(())
+    }
+
+    pub fn deregister<S: Source + ?Sized>(&self, source: &S) -> io::Result<()> {
+        let fd = source.as_fd().as_raw_fd();
+        let mut registrations = self.registrations.lock().unwrap();
+        let index = registrations.iter().position(|r| r.fd == fd).ok_or(NOT_REGISTERED)?;
+        registrations.swap_remove(index);
+        Ok(())
+    }
+
+    pub fn poll(&self, events: &mut Vec<Event>, timeout: Option<Duration>) -> io::Result<()> {
+        let (mut fds, tokens): (Vec<libc::pollfd>, Vec<usize>) = self
+            .registrations
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|r| {
+                let mut events = 0;
+                if r.interest.is_readable() {
+                    events |= libc::POLLIN;
+                }
+                if r.interest.is_writable() {
+                    events |= libc::POLLOUT;
+                }
+                (libc::pollfd { fd: r.fd, events, revents: 0 }, r.token)
+            })
+            .unzip();
+
+        let timeout = Timeout::new(timeout);
+        loop {
+            let n = unsafe {
+                libc::poll(fds.as_mut_ptr(), fds.len() as libc::nfds_t, timeout.remaining_ms())
+            };
+            match cvt(n) {
+                Ok(_) => break,
+                Err(e) if e.is_interrupted() => continue,
+                Err(e) => return Err(e),
+            }
+        }
+
+        let start = self.next.load(Ordering::Relaxed) % fds.len().max(1);
+        let ready = (start..fds.len()).chain(0..start).filter(|&i| fds[i].revents != 0);
+        for i in ready.take(events.capacity()) {
+            events.push(Event { token: tokens[i], revents: fds[i].revents });
+            self.next.store(i + 1, Ordering::Relaxed);
+        }
+        Ok(())
+    }
+}
+
+const NOT_REGISTERED: io::Error =
+    io::const_io_error!(io::ErrorKind::NotFound, "source is not registered");
+
+impl Event {
+    pub fn token(&self) -> usize {
+        self.token
+    }
+
+    pub fn is_readable(&self) -> bool {
+        self.revents & libc::POLLIN != 0
+    }
+
+    pub fn is_writable(&self) -> bool {
+        self.revents & libc::POLLOUT != 0
+    }
+
+    pub fn is_error(&self) -> bool {
+        self.revents & (libc::POLLERR | libc::POLLNVAL) != 0
+    }
+
+    pub fn is_hangup(&self) -> bool {
+        self.revents & libc::POLLHUP != 0
+    }
+}
@@ -0,0 +1,52 @@
+use crate::io;
+use crate::net::{Interest, Source};
+use crate::time::Duration;
+
+pub struct Poller(!);
+
+#[derive(Copy, Clone)]
+pub struct Event(!);
+
+impl Poller {
+    pub fn new() -> io::Result<Poller> {
+        Err(io::Error::UNSUPPORTED_PLATFORM)
+    }
+
+    pub fn register<S: Source + ?Sized>(&self, _: &S, _: usize, _: Interest) -> io::Result<()> {
+        self.0
+    }
+
+    pub fn reregister<S: Source + ?Sized>(&self, _: &S, _: usize, _: Interest) -> io::Result<()> {
+        self.0
+    }
+
+    pub fn deregister<S: Source + ?Sized>(&self, _: &S) -> io::Result<()> {
+        self.0
+    }
+
+    pub fn poll(&self, _: &mut Vec<Event>, _: Option<Duration>) -> io::Result<()> {
+        self.0
+    }
+}
+
+impl Event {
+    pub fn token(&self) -> usize {
+        self.0
+    }
+
+    pub fn is_readable(&self) -> bool {
+        self.0
+    }
+
+    pub fn is_writable(&self) -> bool {
+        self.0
+    }
+
+    pub fn is_error(&self) -> bool {
+        self.0
+    }
+
+    pub fn is_hangup(&self) -> bool {
+        self.0
+    }
+}
@@ -0,0 +1,143 @@
+//! A `WSAPoll` based poller.
+//!
+//! The registered sockets are kept in a table which is copied into a fresh
+//! `WSAPOLLFD` array for every call, so the table's lock is not held while
+//! blocked and other threads can keep registering sockets.
+
+use super::Timeout;
+use crate::io;
+use crate::net::{Interest, Source};
+use crate::os::windows::io::AsRawSocket;
+use crate::sync::atomic::{AtomicUsize, Ordering};
+use crate::sync::Mutex;
+use crate::sys::c;
+use crate::sys::net::{cvt, init};
+use crate::time::Duration;
+
+pub struct Poller {
+    registrations: Mutex<Vec<Registration>>,
+    /// Where the next scan for ready sources starts, so that sources late in
+    /// the table are not starved when more are ready than fit in `events`.
+    next: AtomicUsize,
+}
+
+struct Registration {
+    socket: c::SOCKET,
+    token: usize,
+    interest: Interest,
+}
+
+#[derive(Copy, Clone)]
+pub struct Event {
+    token: usize,
+    revents: c::WSAPOLL_EVENT_FLAGS,
+}
+
+impl Poller {
+    pub fn new() -> io::Result<Poller> {
+        init();
+        Ok(Poller { registrations: Mutex::new(Vec::new()), next: AtomicUsize::new(0) })
+    }
+
+    pub fn register<S: Source + ?Sized>(
+        &self,
+        source: &S,
+        token: usize,
+        interest: Interest,
+    ) -> io::Result<()> {
+        let socket = source.as_socket().as_raw_socket() as c::SOCKET;
+        let mut registrations = self.registrations.lock().unwrap();
+        if registrations.iter().any(|r| r.socket == socket) {
+            return Err(io::const_io_error!(
+                io::ErrorKind::AlreadyExists,
+                "source is already registered",
+            ));
+        }
+        registrations.push(Registration { socket, token, interest });
+        Ok(())
+    }
+
+    pub fn reregister<S: Source + ?Sized>(
+        &self,
+        source: &S,
+        token: usize,
+        interest: Interest,
+    ) -> io::Result<()> {
+        let socket = source.as_socket().as_raw_socket() as c::SOCKET;
+        let mut registrations = self.registrations.lock().unwrap();
+        let registration =
+            registrations.iter_mut().find(|r| r.socket == socket).ok_or(NOT_REGISTERED)?;
+        registration.token = token;
+        registration.interest = interest;
+        Ok(())
+    }
+
+    pub fn deregister<S: Source + ?Sized>(&self, source: &S) -> io::Result<()> {
+        let socket = source.as_socket().as_raw_socket() as c::SOCKET;
+        let mut registrations = self.registrations.lock().unwrap();
+        let index = registrations.iter().position(|r| r.socket == socket).ok_or(NOT_REGISTERED)?;
+        registrations.swap_remove(index);
+        Ok(())
+    }
+
+    pub fn poll(&self, events: &mut Vec<Event>, timeout: Option<Duration>) -> io::Result<()> {
+        let (mut fds, tokens): (Vec<c::WSAPOLLFD>, Vec<usize>) = self
+            .registrations
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|r| {
+                let mut events = 0;
+                if r.interest.is_readable() {
+                    events |= c::POLLRDNORM;
+                }
+                if r.interest.is_writable() {
+                    events |= c::POLLWRNORM;
+                }
+                (c::WSAPOLLFD { fd: r.socket, events, revents: 0 }, r.token)
+            })
+            .unzip();
+
+        let timeout = Timeout::new(timeout);
+        if fds.is_empty() {
+            // `WSAPoll` rejects an empty array, so just wait out the timeout.
+            // `-1` becomes `INFINITE`.
+            unsafe { c::Sleep(timeout.remaining_ms() as u32) };
+            return Ok(());
+        }
+        cvt(unsafe { c::WSAPoll(fds.as_mut_ptr(), fds.len() as u32, timeout.remaining_ms()) })?;
+
+        let start = self.next.load(Ordering::Relaxed) % fds.len().max(1);
+        let ready = (start..fds.len()).chain(0..start).filter(|&i| fds[i].revents != 0);
+        for i in ready.take(events.capacity()) {
+            events.push(Event { token: tokens[i], revents: fds[i].revents });
+            self.next.store(i + 1, Ordering::Relaxed);
+        }
+        Ok(())
+    }
+}
+
+const NOT_REGISTERED: io::Error =
+    io::const_io_error!(io::ErrorKind::NotFound, "source is not registered");
+
+impl Event {
+    pub fn token(&self) -> usize {
+        self.token
+    }
+
+    pub fn is_readable(&self) -> bool {
+        self.revents & c::POLLRDNORM != 0
+    }
+
+    pub fn is_writable(&self) -> bool {
+        self.revents & c::POLLWRNORM != 0
+    }
+
+    pub fn is_error(&self) -> bool {
+        self.revents & (c::POLLERR | c::POLLNVAL) != 0
+    }
+
+    pub fn is_hangup(&self) -> bool {
+        self.revents & c::POLLHUP != 0
+    }
+}
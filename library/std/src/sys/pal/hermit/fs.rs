@@ -18,7 +18,9 @@
 use crate::sys::unsupported;
 use crate::sys_common::{AsInner, AsInnerMut, FromInner, IntoInner};
 
-pub use crate::sys_common::fs::{copy, try_exists};
+pub use crate::sys_common::fs::{
+    copy, create_temp_dir, create_temp_file, sync_dir, tempfile_in, try_exists,
+};
 
 #[derive(Debug)]
 pub struct File(FileDesc);
@@ -387,6 +389,26 @@ pub fn truncate(&self, _size: u64) -> io::Result<()> {
         Err(Error::from_raw_os_error(22))
     }
 
+    pub fn lock(&self) -> io::Result<()> {
+        unsupported()
+    }
+
+    pub fn lock_shared(&self) -> io::Result<()> {
+        unsupported()
+    }
+
+    pub fn try_lock(&self) -> io::Result<()> {
+        unsupported()
+    }
+
+    pub fn try_lock_shared(&self) -> io::Result<()> {
+        unsupported()
+    }
+
+    pub fn unlock(&self) -> io::Result<()> {
+        unsupported()
+    }
+
     pub fn read(&self, buf: &mut [u8]) -> io::Result<usize> {
         self.0.read(buf)
     }
@@ -546,6 +568,10 @@ pub fn readdir(path: &Path) -> io::Result<ReadDir> {
     Ok(ReadDir::new(InnerReadDir::new(root, vec)))
 }
 
+pub fn readdir_child(_parent: &ReadDir, entry: &DirEntry, _follow: bool) -> io::Result<ReadDir> {
+    readdir(&entry.path())
+}
+
 pub fn unlink(path: &Path) -> io::Result<()> {
     run_path_with_cstr(path, &|path| cvt(unsafe { abi::unlink(path.as_ptr()) }).map(|_| ()))
 }
@@ -12,7 +12,9 @@
     sys::unsupported,
 };
 
-pub use crate::sys_common::fs::try_exists;
+pub use crate::sys_common::fs::{
+    create_temp_dir, create_temp_file, sync_dir, tempfile_in, try_exists,
+};
 
 /// A file descriptor.
 #[derive(Clone, Copy)]
@@ -163,6 +165,10 @@ pub fn readdir(p: &Path) -> io::Result<ReadDir> {
     }
 }
 
+pub fn readdir_child(_parent: &ReadDir, entry: &DirEntry, _follow: bool) -> io::Result<ReadDir> {
+    readdir(&entry.path())
+}
+
 impl fmt::Debug for ReadDir {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         // This will only be called from std::fs::ReadDir, which will add a "ReadDir()" frame.
@@ -356,6 +362,26 @@ pub fn truncate(&self, _size: u64) -> io::Result<()> {
         unsupported()
     }
 
+    pub fn lock(&self) -> io::Result<()> {
+        unsupported()
+    }
+
+    pub fn lock_shared(&self) -> io::Result<()> {
+        unsupported()
+    }
+
+    pub fn try_lock(&self) -> io::Result<()> {
+        unsupported()
+    }
+
+    pub fn try_lock_shared(&self) -> io::Result<()> {
+        unsupported()
+    }
+
+    pub fn unlock(&self) -> io::Result<()> {
+        unsupported()
+    }
+
     pub fn read(&self, buf: &mut [u8]) -> io::Result<usize> {
         unsafe {
             let mut out_num_bytes = MaybeUninit::uninit();
@@ -116,6 +116,13 @@
 
 pub struct File(FileDesc);
 
+#[derive(Clone, Copy)]
+enum LockKind {
+    Shared,
+    Exclusive,
+    Unlock,
+}
+
 // FIXME: This should be available on Linux with all `target_env`.
 // But currently only glibc exposes `statx` fn and structs.
 // We don't want to import unverified raw C structs here directly.
@@ -1285,6 +1292,96 @@ pub fn truncate(&self, size: u64) -> io::Result<()> {
         cvt_r(|| unsafe { ftruncate64(self.as_raw_fd(), size) }).map(drop)
     }
 
+    pub fn lock(&self) -> io::Result<()> {
+        self.lock_op(LockKind::Exclusive, false)
+    }
+
+    pub fn lock_shared(&self) -> io::Result<()> {
+        self.lock_op(LockKind::Shared, false)
+    }
+
+    pub fn try_lock(&self) -> io::Result<()> {
+        self.lock_op(LockKind::Exclusive, true)
+    }
+
+    pub fn try_lock_shared(&self) -> io::Result<()> {
+        self.lock_op(LockKind::Shared, true)
+    }
+
+    pub fn unlock(&self) -> io::Result<()> {
+        self.lock_op(LockKind::Unlock, true)
+    }
+
+    fn lock_op(&self, kind: LockKind, nonblocking: bool) -> io::Result<()> {
+        cfg_if::cfg_if! {
+            if #[cfg(any(
+                target_os = "linux",
+                target_os = "android",
+                target_os = "freebsd",
+                target_os = "fuchsia",
+                target_os = "netbsd",
+                target_os = "openbsd",
+                target_os = "dragonfly",
+                target_os = "hurd",
+                target_os = "macos",
+                target_os = "ios",
+                target_os = "tvos",
+                target_os = "watchos",
+                target_os = "visionos",
+            ))] {
+                let mut operation = match kind {
+                    LockKind::Shared => libc::LOCK_SH,
+                    LockKind::Exclusive => libc::LOCK_EX,
+                    LockKind::Unlock => libc::LOCK_UN,
+                };
+                if nonblocking {
+                    operation |= libc::LOCK_NB;
+                }
+                // `flock` fails with `EWOULDBLOCK` when a non-blocking request
+                // conflicts with another lock, which maps to `WouldBlock`.
+                cvt_r(|| unsafe { libc::flock(self.as_raw_fd(), operation) }).map(drop)
+            } else if #[cfg(any(
+                target_os = "solaris",
+                target_os = "illumos",
+                target_os = "aix",
+                target_os = "nto",
+                target_os = "haiku",
+                target_os = "vxworks",
+                target_os = "redox",
+            ))] {
+                // These targets have no `flock`, so lock the whole file with
+                // a POSIX record lock instead. Those belong to the process
+                // rather than to the open file description, and are released
+                // as soon as the process closes any descriptor for the file.
+                let mut lock: libc::flock = unsafe { mem::zeroed() };
+                lock.l_type = match kind {
+                    LockKind::Shared => libc::F_RDLCK,
+                    LockKind::Exclusive => libc::F_WRLCK,
+                    LockKind::Unlock => libc::F_UNLCK,
+                } as _;
+                lock.l_whence = libc::SEEK_SET as _;
+                let cmd = if nonblocking { libc::F_SETLK } else { libc::F_SETLKW };
+                match cvt_r(|| unsafe { libc::fcntl(self.as_raw_fd(), cmd, &lock) }) {
+                    Ok(_) => Ok(()),
+                    // POSIX allows either error for a conflicting `F_SETLK`.
+                    Err(e) if matches!(e.raw_os_error(), Some(libc::EACCES | libc::EAGAIN)) => {
+                        Err(io::const_io_error!(
+                            io::ErrorKind::WouldBlock,
+                            "the file is locked by another process",
+                        ))
+                    }
+                    Err(e) => Err(e),
+                }
+            } else {
+                let _ = (kind, nonblocking);
+                Err(io::const_io_error!(
+                    io::ErrorKind::Unsupported,
+                    "file locking is not supported on this platform",
+                ))
+            }
+        }
+    }
+
     pub fn read(&self, buf: &mut [u8]) -> io::Result<usize> {
         self.0.read(buf)
     }
@@ -2059,7 +2156,48 @@ pub fn chroot(dir: &Path) -> io::Result<()> {
     run_path_with_cstr(dir, &|dir| cvt(unsafe { libc::chroot(dir.as_ptr()) }).map(|_| ()))
 }
 
-pub use remove_dir_impl::remove_dir_all;
+pub fn create_temp_file(path: &Path) -> io::Result<crate::fs::File> {
+    use crate::os::unix::fs::OpenOptionsExt;
+    crate::fs::OpenOptions::new().read(true).write(true).create_new(true).mode(0o600).open(path)
+}
+
+pub fn create_temp_dir(path: &Path) -> io::Result<()> {
+    use crate::os::unix::fs::DirBuilderExt;
+    crate::fs::DirBuilder::new().mode(0o700).create(path)
+}
+
+pub fn sync_dir(path: &Path) -> io::Result<()> {
+    let mut opts = OpenOptions::new();
+    opts.read(true);
+    File::open(path, &opts)?.fsync()
+}
+
+pub fn tempfile_in(dir: &Path) -> io::Result<crate::fs::File> {
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    {
+        use crate::os::unix::fs::OpenOptionsExt;
+        // An O_TMPFILE file has no name at all, so it cannot be opened by anyone else and is
+        // removed by the kernel when closed, even if the process is killed.
+        let file = crate::fs::OpenOptions::new()
+            .read(true)
+            .write(true)
+            .mode(0o600)
+            .custom_flags(libc::O_TMPFILE)
+            .open(dir);
+        match file {
+            // Kernels without O_TMPFILE support see an O_DIRECTORY open for writing and fail
+            // with EISDIR, while file systems without support fail with EOPNOTSUPP.
+            Err(e) if matches!(e.raw_os_error(), Some(libc::EISDIR | libc::EOPNOTSUPP)) => {}
+            result => return result,
+        }
+    }
+
+    let (path, file) = crate::sys_common::fs::create_temp(dir, create_temp_file)?;
+    unlink(&path)?;
+    Ok(file)
+}
+
+pub use remove_dir_impl::{readdir_child, remove_dir_all};
 
 // Fallback for REDOX, ESP-ID, Horizon, Vita and Miri
 #[cfg(any(
@@ -2071,7 +2209,17 @@ pub fn chroot(dir: &Path) -> io::Result<()> {
     miri
 ))]
 mod remove_dir_impl {
+    use super::{readdir, DirEntry, ReadDir};
+    use crate::io;
     pub use crate::sys_common::fs::remove_dir_all;
+
+    pub fn readdir_child(
+        _parent: &ReadDir,
+        entry: &DirEntry,
+        _follow: bool,
+    ) -> io::Result<ReadDir> {
+        readdir(&entry.path())
+    }
 }
 
 // Modern implementation using openat(), unlinkat() and fdopendir()
@@ -2155,7 +2303,7 @@ pub fn openat_nofollow_dironly(parent_fd: Option<RawFd>, p: &CStr) -> io::Result
         Ok(unsafe { OwnedFd::from_raw_fd(fd) })
     }
 
-    fn fdreaddir(dir_fd: OwnedFd) -> io::Result<(ReadDir, RawFd)> {
+    fn fdreaddir(dir_fd: OwnedFd, root: PathBuf) -> io::Result<(ReadDir, RawFd)> {
         let ptr = unsafe { fdopendir(dir_fd.as_raw_fd()) };
         if ptr.is_null() {
             return Err(io::Error::last_os_error());
@@ -2163,13 +2311,29 @@ fn fdreaddir(dir_fd: OwnedFd) -> io::Result<(ReadDir, RawFd)> {
         let dirp = Dir(ptr);
         // file descriptor is automatically closed by libc::closedir() now, so give up ownership
         let new_parent_fd = dir_fd.into_raw_fd();
-        // a valid root is not needed because we do not call any functions involving the full path
-        // of the `DirEntry`s.
-        let dummy_root = PathBuf::new();
-        let inner = InnerReadDir { dirp, root: dummy_root };
+        let inner = InnerReadDir { dirp, root };
         Ok((ReadDir::new(inner), new_parent_fd))
     }
 
+    /// Opens the directory `entry` of `parent` relative to the descriptor of `parent`, so that
+    /// the directory being read is the one `parent` contains even if the path to `parent` has
+    /// been changed in the meantime. Unless `follow` is set, this fails if `entry` has been
+    /// replaced by a symlink.
+    fn readdir_child_modern(
+        parent: &ReadDir,
+        entry: &DirEntry,
+        follow: bool,
+    ) -> io::Result<ReadDir> {
+        let parent_fd = unsafe { libc::dirfd(parent.inner.dirp.0) };
+        let mut flags = libc::O_CLOEXEC | libc::O_RDONLY | libc::O_DIRECTORY;
+        if !follow {
+            flags |= libc::O_NOFOLLOW;
+        }
+        let fd = cvt_r(|| unsafe { openat(parent_fd, entry.name_cstr().as_ptr(), flags) })?;
+        let fd = unsafe { OwnedFd::from_raw_fd(fd) };
+        fdreaddir(fd, entry.path()).map(|(dir, _)| dir)
+    }
+
     #[cfg(any(
         target_os = "solaris",
         target_os = "illumos",
@@ -2214,8 +2378,9 @@ fn remove_dir_all_recursive(parent_fd: Option<RawFd>, path: &CStr) -> io::Result
             result => result?,
         };
 
-        // open the directory passing ownership of the fd
-        let (dir, fd) = fdreaddir(fd)?;
+        // open the directory passing ownership of the fd; a valid root is not needed because we
+        // do not call any functions involving the full path of the `DirEntry`s.
+        let (dir, fd) = fdreaddir(fd, PathBuf::new())?;
         for child in dir {
             let child = child?;
             let child_name = child.name_cstr();
@@ -2260,6 +2425,11 @@ pub fn remove_dir_all(p: &Path) -> io::Result<()> {
         remove_dir_all_modern(p)
     }
 
+    #[cfg(not(all(target_os = "macos", not(target_arch = "aarch64"))))]
+    pub fn readdir_child(parent: &ReadDir, entry: &DirEntry, follow: bool) -> io::Result<ReadDir> {
+        readdir_child_modern(parent, entry, follow)
+    }
+
     #[cfg(all(target_os = "macos", not(target_arch = "aarch64")))]
     pub fn remove_dir_all(p: &Path) -> io::Result<()> {
         if macos_weak::has_openat() {
@@ -2270,4 +2440,13 @@ pub fn remove_dir_all(p: &Path) -> io::Result<()> {
             crate::sys_common::fs::remove_dir_all(p)
         }
     }
+
+    #[cfg(all(target_os = "macos", not(target_arch = "aarch64")))]
+    pub fn readdir_child(parent: &ReadDir, entry: &DirEntry, follow: bool) -> io::Result<ReadDir> {
+        if macos_weak::has_openat() {
+            readdir_child_modern(parent, entry, follow)
+        } else {
+            super::readdir(&entry.path())
+        }
+    }
 }
@@ -370,6 +370,104 @@ fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         }
     }
 
+    pub struct TcpSocket {
+        inner: Socket,
+    }
+
+    impl TcpSocket {
+        pub fn new(_: &SocketAddr) -> io::Result<TcpSocket> {
+            unimpl!();
+        }
+
+        #[inline]
+        pub fn socket(&self) -> &Socket {
+            &self.inner
+        }
+
+        pub fn into_socket(self) -> Socket {
+            self.inner
+        }
+
+        pub fn set_reuseaddr(&self, _: bool) -> io::Result<()> {
+            unimpl!();
+        }
+
+        pub fn reuseaddr(&self) -> io::Result<bool> {
+            unimpl!();
+        }
+
+        pub fn set_reuseport(&self, _: bool) -> io::Result<()> {
+            unimpl!();
+        }
+
+        pub fn reuseport(&self) -> io::Result<bool> {
+            unimpl!();
+        }
+
+        pub fn set_send_buffer_size(&self, _: usize) -> io::Result<()> {
+            unimpl!();
+        }
+
+        pub fn send_buffer_size(&self) -> io::Result<usize> {
+            unimpl!();
+        }
+
+        pub fn set_recv_buffer_size(&self, _: usize) -> io::Result<()> {
+            unimpl!();
+        }
+
+        pub fn recv_buffer_size(&self) -> io::Result<usize> {
+            unimpl!();
+        }
+
+        pub fn set_keepalive(&self, _: bool) -> io::Result<()> {
+            unimpl!();
+        }
+
+        pub fn keepalive(&self) -> io::Result<bool> {
+            unimpl!();
+        }
+
+        pub fn set_tcp_keepalive(
+            &self,
+            _: Option<Duration>,
+            _: Option<Duration>,
+            _: Option<u32>,
+        ) -> io::Result<()> {
+            unimpl!();
+        }
+
+        pub fn set_nodelay(&self, _: bool) -> io::Result<()> {
+            unimpl!();
+        }
+
+        pub fn nodelay(&self) -> io::Result<bool> {
+            unimpl!();
+        }
+
+        pub fn bind(&self, _: &SocketAddr) -> io::Result<()> {
+            unimpl!();
+        }
+
+        pub fn socket_addr(&self) -> io::Result<SocketAddr> {
+            unimpl!();
+        }
+
+        pub fn connect(self, _: &SocketAddr) -> io::Result<TcpStream> {
+            unimpl!();
+        }
+
+        pub fn listen(self, _: u32) -> io::Result<TcpListener> {
+            unimpl!();
+        }
+    }
+
+    impl fmt::Debug for TcpSocket {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(f, "No networking support available on L4Re")
+        }
+    }
+
     pub struct UdpSocket {
         inner: Socket,
     }
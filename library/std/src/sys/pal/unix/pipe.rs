@@ -4,11 +4,13 @@
 use crate::sys::fd::FileDesc;
 use crate::sys::{cvt, cvt_r};
 use crate::sys_common::{FromInner, IntoInner};
+use crate::time::Instant;
 
 ////////////////////////////////////////////////////////////////////////////////
 // Anonymous pipes
 ////////////////////////////////////////////////////////////////////////////////
 
+#[derive(Debug)]
 pub struct AnonPipe(FileDesc);
 
 pub fn anon_pipe() -> io::Result<(AnonPipe, AnonPipe)> {
@@ -46,6 +48,10 @@ pub fn anon_pipe() -> io::Result<(AnonPipe, AnonPipe)> {
 }
 
 impl AnonPipe {
+    pub fn try_clone(&self) -> io::Result<Self> {
+        self.0.duplicate().map(Self)
+    }
+
     pub fn read(&self, buf: &mut [u8]) -> io::Result<usize> {
         self.0.read(buf)
     }
@@ -135,6 +141,64 @@ fn read(fd: &FileDesc, dst: &mut Vec<u8>) -> Result<bool, io::Error> {
     }
 }
 
+/// Like `read2`, but either pipe may be missing and reading gives up once
+/// `deadline` passes. Returns whether both pipes were read to EOF.
+pub fn read2_deadline(
+    p1: Option<AnonPipe>,
+    v1: &mut Vec<u8>,
+    p2: Option<AnonPipe>,
+    v2: &mut Vec<u8>,
+    deadline: Instant,
+) -> io::Result<bool> {
+    let pipes = [p1.map(AnonPipe::into_inner), p2.map(AnonPipe::into_inner)];
+    let mut dsts = [v1, v2];
+
+    // `poll` ignores entries with a negative fd, which is how missing pipes
+    // and pipes that already hit EOF are taken out of the loop.
+    let mut fds: [libc::pollfd; 2] = unsafe { mem::zeroed() };
+    for (pollfd, pipe) in fds.iter_mut().zip(&pipes) {
+        pollfd.events = libc::POLLIN;
+        pollfd.fd = match pipe {
+            Some(pipe) => {
+                pipe.set_nonblocking(true)?;
+                pipe.as_raw_fd()
+            }
+            None => -1,
+        };
+    }
+
+    while fds.iter().any(|pollfd| pollfd.fd >= 0) {
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        if remaining.is_zero() {
+            return Ok(false);
+        }
+        let timeout = remaining.as_nanos().div_ceil(1_000_000).min(libc::c_int::MAX as u128);
+        cvt_r(|| unsafe { libc::poll(fds.as_mut_ptr(), 2, timeout as libc::c_int) })?;
+
+        for ((pollfd, pipe), dst) in fds.iter_mut().zip(&pipes).zip(&mut dsts) {
+            if pollfd.revents != 0 && read_chunk(pipe.as_ref().unwrap(), dst)? {
+                pollfd.fd = -1;
+            }
+        }
+    }
+    Ok(true)
+}
+
+// Reads whatever a single `read` call returns, and whether that was EOF.
+// Unlike `read_to_end`, this gets back to checking the deadline even if the
+// child never stops writing.
+fn read_chunk(fd: &FileDesc, dst: &mut Vec<u8>) -> io::Result<bool> {
+    let mut buf = [0; 32 * 1024];
+    match fd.read(&mut buf) {
+        Ok(n) => {
+            dst.extend_from_slice(&buf[..n]);
+            Ok(n == 0)
+        }
+        Err(e) if e.kind() == io::ErrorKind::WouldBlock || e.is_interrupted() => Ok(false),
+        Err(e) => Err(e),
+    }
+}
+
 impl AsRawFd for AnonPipe {
     #[inline]
     fn as_raw_fd(&self) -> RawFd {
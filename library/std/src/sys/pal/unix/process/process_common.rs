@@ -9,6 +9,7 @@
 use crate::io;
 use crate::path::Path;
 use crate::ptr;
+use crate::sys::cvt;
 use crate::sys::fd::FileDesc;
 use crate::sys::fs::File;
 use crate::sys::pipe::{self, AnonPipe};
@@ -105,9 +106,13 @@ pub struct Command {
     stdin: Option<Stdio>,
     stdout: Option<Stdio>,
     stderr: Option<Stdio>,
+    fd_mappings: Vec<(c_int, OwnedFd)>,
     #[cfg(target_os = "linux")]
     create_pidfd: bool,
     pgroup: Option<pid_t>,
+    setsid: bool,
+    #[cfg(target_os = "linux")]
+    pdeathsig: Option<c_int>,
 }
 
 // Create a new type for argv, so that we can make it `Send` and `Sync`
@@ -132,6 +137,9 @@ pub struct ChildPipes {
     pub stdin: ChildStdio,
     pub stdout: ChildStdio,
     pub stderr: ChildStdio,
+    /// Target fd numbers in the child, and close-on-exec duplicates of the
+    /// fds to install there, numbered above every target.
+    pub fds: Vec<(c_int, OwnedFd)>,
 }
 
 pub enum ChildStdio {
@@ -198,7 +206,9 @@ pub fn new(program: &OsStr) -> Command {
             stdin: None,
             stdout: None,
             stderr: None,
+            fd_mappings: Vec::new(),
             pgroup: None,
+            setsid: false,
         }
     }
 
@@ -222,8 +232,11 @@ pub fn new(program: &OsStr) -> Command {
             stdin: None,
             stdout: None,
             stderr: None,
+            fd_mappings: Vec::new(),
             create_pidfd: false,
             pgroup: None,
+            setsid: false,
+            pdeathsig: None,
         }
     }
 
@@ -262,6 +275,18 @@ pub fn groups(&mut self, groups: &[gid_t]) {
     pub fn pgroup(&mut self, pgroup: pid_t) {
         self.pgroup = Some(pgroup);
     }
+    pub fn setsid(&mut self, setsid: bool) {
+        self.setsid = setsid;
+    }
+    pub fn map_fd(&mut self, child_fd: c_int, fd: OwnedFd) {
+        self.fd_mappings.retain(|&(target, _)| target != child_fd);
+        self.fd_mappings.push((child_fd, fd));
+    }
+
+    #[cfg(target_os = "linux")]
+    pub fn pdeathsig(&mut self, signal: c_int) {
+        self.pdeathsig = Some(signal);
+    }
 
     #[cfg(target_os = "linux")]
     pub fn create_pidfd(&mut self, val: bool) {
@@ -334,6 +359,26 @@ pub fn get_groups(&self) -> Option<&[gid_t]> {
     pub fn get_pgroup(&self) -> Option<pid_t> {
         self.pgroup
     }
+    #[allow(dead_code)]
+    pub fn get_fd_mappings(&self) -> &[(c_int, OwnedFd)] {
+        &self.fd_mappings
+    }
+
+    #[allow(dead_code)]
+    pub fn get_setsid(&self) -> bool {
+        self.setsid
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    #[allow(dead_code)]
+    pub fn get_pdeathsig(&self) -> Option<c_int> {
+        None
+    }
+
+    #[cfg(target_os = "linux")]
+    pub fn get_pdeathsig(&self) -> Option<c_int> {
+        self.pdeathsig
+    }
 
     pub fn get_closures(&mut self) -> &mut Vec<Box<dyn FnMut() -> io::Result<()> + Send + Sync>> {
         &mut self.closures
@@ -387,10 +432,43 @@ pub fn setup_io(
         let (their_stdin, our_stdin) = stdin.to_child_stdio(true)?;
         let (their_stdout, our_stdout) = stdout.to_child_stdio(false)?;
         let (their_stderr, our_stderr) = stderr.to_child_stdio(false)?;
+        let fds = self.dup_mapped_fds()?;
         let ours = StdioPipes { stdin: our_stdin, stdout: our_stdout, stderr: our_stderr };
-        let theirs = ChildPipes { stdin: their_stdin, stdout: their_stdout, stderr: their_stderr };
+        let theirs =
+            ChildPipes { stdin: their_stdin, stdout: their_stdout, stderr: their_stderr, fds };
         Ok((ours, theirs))
     }
+
+    // The child installs the fd mappings one after the other with `dup2`, so
+    // a mapping whose source is the target of an earlier one would pick up
+    // the wrong file. Working from duplicates numbered above every target
+    // (and above stdio) avoids that, and as they are close-on-exec only the
+    // targets end up being inherited.
+    fn dup_mapped_fds(&self) -> io::Result<Vec<(c_int, OwnedFd)>> {
+        let Some(max_target) = self.fd_mappings.iter().map(|&(target, _)| target).max() else {
+            return Ok(Vec::new());
+        };
+        if self.fd_mappings.iter().any(|&(target, _)| target < 0) {
+            return Err(io::const_io_error!(
+                io::ErrorKind::InvalidInput,
+                "cannot map a file descriptor to a negative number",
+            ));
+        }
+
+        #[cfg(not(any(target_os = "espidf", target_os = "vita")))]
+        let cmd = libc::F_DUPFD_CLOEXEC;
+        #[cfg(any(target_os = "espidf", target_os = "vita"))]
+        let cmd = libc::F_DUPFD;
+
+        let min = max_target.saturating_add(1).max(3);
+        self.fd_mappings
+            .iter()
+            .map(|(target, fd)| {
+                let dup = cvt(unsafe { libc::fcntl(fd.as_raw_fd(), cmd, min) })?;
+                Ok((*target, unsafe { OwnedFd::from_raw_fd(dup) }))
+            })
+            .collect()
+    }
 }
 
 fn os2c(s: &OsStr, saw_nul: &mut bool) -> CString {
@@ -572,13 +650,22 @@ fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
             if self.stderr.is_some() {
                 debug_command.field("stderr", &self.stderr);
             }
+            if !self.fd_mappings.is_empty() {
+                debug_command.field("fd_mappings", &self.fd_mappings);
+            }
             if self.pgroup.is_some() {
                 debug_command.field("pgroup", &self.pgroup);
             }
+            if self.setsid {
+                debug_command.field("setsid", &self.setsid);
+            }
 
             #[cfg(target_os = "linux")]
             {
                 debug_command.field("create_pidfd", &self.create_pidfd);
+                if self.pdeathsig.is_some() {
+                    debug_command.field("pdeathsig", &self.pdeathsig);
+                }
             }
 
             debug_command.finish()
@@ -136,6 +136,136 @@ fn test_process_group_no_posix_spawn() {
     }
 }
 
+#[test]
+#[cfg_attr(
+    any(
+        // See test_process_mask
+        target_os = "macos",
+        target_arch = "arm",
+        target_arch = "aarch64",
+        target_arch = "riscv64",
+    ),
+    ignore
+)]
+fn test_kill_process_group() {
+    // The shell and its background job both hold the write end of the pipe,
+    // so reading it to the end only finishes once both have been killed.
+    let mut cmd = Command::new(OsStr::new("sh"));
+    cmd.arg(OsStr::new("-c"));
+    cmd.arg(OsStr::new("sleep 1000 & echo ready; wait"));
+    cmd.pgroup(0);
+    cmd.stdout(Stdio::MakePipe);
+    let (mut sh, mut pipes) = t!(cmd.spawn(Stdio::Null, false));
+    let stdout = pipes.stdout.take().unwrap();
+    let mut buf = [0; 6];
+    assert_eq!(t!(stdout.read(&mut buf)), 6);
+    assert_eq!(&buf, b"ready\n");
+
+    t!(sh.kill_process_group());
+    let mut rest = Vec::new();
+    t!(stdout.read_to_end(&mut rest));
+    assert!(rest.is_empty());
+    t!(sh.wait());
+
+    // The process group ID may have been reused once the child has been reaped.
+    let err = sh.kill_process_group().unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+}
+
+#[test]
+fn test_kill_process_group_of_parent() {
+    let mut cmd = Command::new(OsStr::new("true"));
+    let (mut child, _pipes) = t!(cmd.spawn(Stdio::Null, false));
+    let err = child.kill_process_group().unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+    t!(child.wait());
+}
+
+#[test]
+#[cfg_attr(
+    any(
+        // See test_process_mask
+        target_os = "macos",
+        target_arch = "arm",
+        target_arch = "aarch64",
+        target_arch = "riscv64",
+    ),
+    ignore
+)]
+fn test_setsid() {
+    let mut cmd = Command::new(OsStr::new("cat"));
+    cmd.setsid(true);
+    cmd.stdin(Stdio::MakePipe);
+    let (mut cat, pipes) = t!(cmd.spawn(Stdio::Null, true));
+
+    let pid = cat.id() as libc::pid_t;
+    assert_eq!(unsafe { libc::getsid(pid) }, pid);
+    assert_eq!(unsafe { libc::getpgid(pid) }, pid);
+
+    drop(pipes);
+    t!(cat.wait());
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn test_parent_death_signal() {
+    // The signal is tied to the thread which spawned the child, so the child
+    // is killed as soon as that thread exits.
+    let (mut cat, pipes) = crate::thread::spawn(|| {
+        let mut cmd = Command::new(OsStr::new("cat"));
+        cmd.pdeathsig(libc::SIGKILL);
+        cmd.stdin(Stdio::MakePipe);
+        t!(cmd.spawn(Stdio::Null, true))
+    })
+    .join()
+    .unwrap();
+
+    // Stdin is still open, so `cat` did not exit by itself.
+    assert_eq!(t!(cat.wait()).signal(), Some(libc::SIGKILL));
+    drop(pipes);
+}
+
+#[test]
+fn test_map_fd() {
+    fn test_inner(mut cmd: Command) {
+        // Swap the write ends of two pipes between their own fd numbers, which
+        // only works if installing one mapping leaves the other's source alone.
+        let (r1, w1) = t!(pipe::anon_pipe());
+        let (r2, w2) = t!(pipe::anon_pipe());
+        let (n1, n2) = (w1.as_raw_fd(), w2.as_raw_fd());
+        cmd.arg(OsStr::new("-c"));
+        cmd.arg(OsStr::new(&format!("echo one >&{n2}; echo two >&{n1}")));
+        cmd.map_fd(n2, w1.into_inner().into_inner());
+        cmd.map_fd(n1, w2.into_inner().into_inner());
+        let (mut sh, _pipes) = t!(cmd.spawn(Stdio::Null, false));
+        // The command holds on to the write ends.
+        drop(cmd);
+
+        let (mut one, mut two) = (Vec::new(), Vec::new());
+        t!(r1.read_to_end(&mut one));
+        t!(r2.read_to_end(&mut two));
+        assert_eq!(one, b"one\n");
+        assert_eq!(two, b"two\n");
+        assert_eq!(t!(sh.wait()).code(), Some(0));
+    }
+
+    test_inner(Command::new(OsStr::new("sh")));
+
+    let mut cmd = Command::new(OsStr::new("sh"));
+    // pre_exec forces fork + exec
+    unsafe { cmd.pre_exec(Box::new(|| Ok(()))) };
+    test_inner(cmd);
+}
+
+#[test]
+fn test_map_fd_negative() {
+    let (_r, w) = t!(pipe::anon_pipe());
+    let mut cmd = Command::new(OsStr::new("true"));
+    cmd.map_fd(-1, w.into_inner().into_inner());
+    let err = cmd.spawn(Stdio::Null, false).err().unwrap();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+}
+
 #[test]
 fn test_program_kind() {
     let vectors = &[
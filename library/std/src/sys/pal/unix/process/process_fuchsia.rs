@@ -3,6 +3,7 @@
 use crate::mem;
 use crate::num::NonZero;
 use crate::ptr;
+use crate::time::Instant;
 
 use crate::sys::process::process_common::*;
 use crate::sys::process::zircon::{zx_handle_t, Handle};
@@ -27,6 +28,12 @@ pub fn spawn(
                 "nul byte found in provided data",
             ));
         }
+        if !self.get_fd_mappings().is_empty() {
+            return Err(io::const_io_error!(
+                io::ErrorKind::Unsupported,
+                "fd mappings are not supported on this platform",
+            ));
+        }
 
         let (ours, theirs) = self.setup_io(default, needs_stdin)?;
 
@@ -165,6 +172,13 @@ pub fn kill(&mut self) -> io::Result<()> {
         Ok(())
     }
 
+    pub fn kill_process_group(&mut self) -> io::Result<()> {
+        Err(io::const_io_error!(
+            io::ErrorKind::Unsupported,
+            "process groups are not supported on this platform",
+        ))
+    }
+
     pub fn wait(&mut self) -> io::Result<ExitStatus> {
         use crate::sys::process::zircon::*;
 
@@ -233,6 +247,10 @@ pub fn try_wait(&mut self) -> io::Result<Option<ExitStatus>> {
         }
         Ok(Some(ExitStatus(proc_info.return_code)))
     }
+
+    pub fn wait_deadline(&mut self, deadline: Instant) -> io::Result<Option<ExitStatus>> {
+        crate::sys_common::process::wait_deadline_by_polling(self, deadline)
+    }
 }
 
 #[derive(PartialEq, Eq, Clone, Copy, Debug, Default)]
@@ -5,11 +5,11 @@
 use crate::sys;
 use crate::sys::cvt;
 use crate::sys::process::process_common::*;
+use crate::time::Instant;
 
 #[cfg(target_os = "linux")]
 use crate::os::linux::process::PidFd;
-#[cfg(target_os = "linux")]
-use crate::os::unix::io::AsRawFd;
+use crate::os::unix::io::{AsRawFd, FromRawFd};
 
 #[cfg(any(
     target_os = "macos",
@@ -97,6 +97,18 @@ pub fn spawn(
         #[cfg(not(target_os = "linux"))]
         let (input, output) = sys::pipe::anon_pipe()?;
 
+        // The child reports exec failures through `output` after installing
+        // its fd mappings, so it must not be replaced by one of them.
+        let output = match theirs.fds.iter().map(|&(target, _)| target).max() {
+            Some(max_target) if output.as_raw_fd() <= max_target => {
+                let fd = cvt(unsafe {
+                    libc::fcntl(output.as_raw_fd(), libc::F_DUPFD_CLOEXEC, max_target + 1)
+                })?;
+                unsafe { FromRawFd::from_raw_fd(fd) }
+            }
+            _ => output,
+        };
+
         // Whatever happens after the fork is almost for sure going to touch or
         // look at the environment in one way or another (PATH in `execvp` or
         // accessing the `environ` pointer ourselves). Make sure no other thread
@@ -108,6 +120,7 @@ pub fn spawn(
         // The child calls `mem::forget` to leak the lock, which is crucial because
         // releasing a lock is not async-signal-safe.
         let env_lock = sys::os::env_read_lock();
+        let parent = unsafe { libc::getpid() };
         let pid = unsafe { self.do_fork()? };
 
         if pid == 0 {
@@ -118,7 +131,7 @@ pub fn spawn(
             if self.get_create_pidfd() {
                 self.send_pidfd(&output);
             }
-            let Err(err) = unsafe { self.do_exec(theirs, envp.as_ref()) };
+            let Err(err) = unsafe { self.do_exec(theirs, envp.as_ref(), parent) };
             let errno = err.raw_os_error().unwrap_or(libc::EINVAL) as u32;
             let errno = errno.to_be_bytes();
             let bytes = [
@@ -149,6 +162,7 @@ pub fn spawn(
 
         // Safety: We obtained the pidfd (on Linux) using SOCK_SEQPACKET, so it's valid.
         let mut p = unsafe { Process::new(pid, pidfd) };
+        p.pgroup = self.child_pgroup(pid);
         let mut bytes = [0; 8];
 
         // loop to handle EINTR
@@ -262,7 +276,7 @@ pub fn exec(&mut self, default: Stdio) -> io::Error {
                     // environment lock before we try to exec.
                     let _lock = sys::os::env_read_lock();
 
-                    let Err(e) = self.do_exec(theirs, envp.as_ref());
+                    let Err(e) = self.do_exec(theirs, envp.as_ref(), libc::getppid());
                     e
                 }
             }
@@ -300,11 +314,16 @@ pub fn exec(&mut self, default: Stdio) -> io::Error {
     // allocation). Instead we just close it manually. This will never
     // have the drop glue anyway because this code never returns (the
     // child will either exec() or invoke libc::exit)
+    //
+    // `parent` is the process which is expected to be the parent of the
+    // process image being executed.
     #[cfg(not(any(target_os = "tvos", target_os = "watchos")))]
+    #[cfg_attr(not(target_os = "linux"), allow(unused_variables))]
     unsafe fn do_exec(
         &mut self,
         stdio: ChildPipes,
         maybe_envp: Option<&CStringArray>,
+        parent: pid_t,
     ) -> Result<!, io::Error> {
         use crate::sys::{self, cvt_r};
 
@@ -317,6 +336,9 @@ unsafe fn do_exec(
         if let Some(fd) = stdio.stderr.fd() {
             cvt_r(|| libc::dup2(fd, libc::STDERR_FILENO))?;
         }
+        for (target, fd) in &stdio.fds {
+            cvt_r(|| libc::dup2(fd.as_raw_fd(), *target))?;
+        }
 
         #[cfg(not(target_os = "l4re"))]
         {
@@ -355,10 +377,24 @@ unsafe fn do_exec(
             cvt(libc::chdir(cwd.as_ptr()))?;
         }
 
+        if self.get_setsid() {
+            cvt(libc::setsid())?;
+        }
+
         if let Some(pgroup) = self.get_pgroup() {
             cvt(libc::setpgid(0, pgroup))?;
         }
 
+        #[cfg(target_os = "linux")]
+        if let Some(signal) = self.get_pdeathsig() {
+            cvt(libc::prctl(libc::PR_SET_PDEATHSIG, signal as libc::c_ulong))?;
+            // If the parent exited before the death signal was set up, it will
+            // never be delivered, so deliver it now instead.
+            if libc::getppid() != parent {
+                cvt(libc::raise(signal))?;
+            }
+        }
+
         // emscripten has no signal support.
         #[cfg(not(target_os = "emscripten"))]
         {
@@ -427,6 +463,7 @@ unsafe fn do_exec(
         &mut self,
         _stdio: ChildPipes,
         _maybe_envp: Option<&CStringArray>,
+        _parent: pid_t,
     ) -> Result<!, io::Error> {
         return Err(Self::ERR_APPLE_TV_WATCH_NO_FORK_EXEC);
     }
@@ -474,6 +511,8 @@ fn posix_spawn(
             || !self.get_closures().is_empty()
             || self.get_groups().is_some()
             || self.get_create_pidfd()
+            || self.get_setsid()
+            || self.get_pdeathsig().is_some()
         {
             return Ok(None);
         }
@@ -616,6 +655,13 @@ fn drop(&mut self) {
                     libc::STDERR_FILENO,
                 ))?;
             }
+            for (target, fd) in &stdio.fds {
+                cvt_nz(libc::posix_spawn_file_actions_adddup2(
+                    file_actions.0.as_mut_ptr(),
+                    fd.as_raw_fd(),
+                    *target,
+                ))?;
+            }
             if let Some((f, cwd)) = addchdir {
                 cvt_nz(f(file_actions.0.as_mut_ptr(), cwd.as_ptr()))?;
             }
@@ -671,10 +717,21 @@ fn drop(&mut self) {
             let spawn_res = spawn_res?;
 
             cvt_nz(spawn_res)?;
+            p.pgroup = self.child_pgroup(p.pid);
             Ok(Some(p))
         }
     }
 
+    /// Returns the process group the child with the given pid has been placed
+    /// in, if it is not the one of the parent.
+    fn child_pgroup(&self, pid: pid_t) -> Option<pid_t> {
+        match self.get_pgroup() {
+            _ if self.get_setsid() => Some(pid),
+            Some(0) => Some(pid),
+            pgroup => pgroup,
+        }
+    }
+
     #[cfg(target_os = "linux")]
     fn send_pidfd(&self, sock: &crate::sys::net::Socket) {
         use crate::io::IoSlice;
@@ -800,21 +857,22 @@ pub struct Process {
     // (e.g. the `pidfd_open` syscall was not available).
     #[cfg(target_os = "linux")]
     pidfd: Option<PidFd>,
+    // The process group the child was placed in, if not the parent's.
+    pgroup: Option<pid_t>,
 }
 
 impl Process {
     #[cfg(target_os = "linux")]
     unsafe fn new(pid: pid_t, pidfd: pid_t) -> Self {
-        use crate::os::unix::io::FromRawFd;
         use crate::sys_common::FromInner;
         // Safety: If `pidfd` is nonnegative, we assume it's valid and otherwise unowned.
         let pidfd = (pidfd >= 0).then(|| PidFd::from_inner(sys::fd::FileDesc::from_raw_fd(pidfd)));
-        Process { pid, status: None, pidfd }
+        Process { pid, status: None, pidfd, pgroup: None }
     }
 
     #[cfg(not(target_os = "linux"))]
     unsafe fn new(pid: pid_t, _pidfd: pid_t) -> Self {
-        Process { pid, status: None }
+        Process { pid, status: None, pgroup: None }
     }
 
     pub fn id(&self) -> u32 {
@@ -845,6 +903,30 @@ pub fn kill(&mut self) -> io::Result<()> {
         cvt(unsafe { libc::kill(self.pid, libc::SIGKILL) }).map(drop)
     }
 
+    pub fn kill_process_group(&mut self) -> io::Result<()> {
+        let Some(pgroup) = self.pgroup else {
+            return Err(io::const_io_error!(
+                ErrorKind::InvalidInput,
+                "process was not spawned in a process group of its own",
+            ));
+        };
+        // The child is a member of the group until it is reaped, so the process
+        // group ID cannot have been reused for another group before then. Once
+        // the child has been waited on, there is no such guarantee anymore.
+        if self.status.is_some() {
+            return Err(io::const_io_error!(
+                ErrorKind::InvalidInput,
+                "process group cannot be killed after the process has been waited on",
+            ));
+        }
+        match cvt(unsafe { libc::killpg(pgroup, libc::SIGKILL) }) {
+            // Only the child is left in the group, as a zombie, which some
+            // systems do not signal.
+            Err(e) if e.raw_os_error() == Some(libc::ESRCH) => Ok(()),
+            result => result.map(drop),
+        }
+    }
+
     pub fn wait(&mut self) -> io::Result<ExitStatus> {
         use crate::sys::cvt_r;
         if let Some(status) = self.status {
@@ -899,6 +981,49 @@ pub fn try_wait(&mut self) -> io::Result<Option<ExitStatus>> {
             Ok(Some(ExitStatus::new(status)))
         }
     }
+
+    pub fn wait_deadline(&mut self, deadline: Instant) -> io::Result<Option<ExitStatus>> {
+        if let Some(status) = self.try_wait()? {
+            return Ok(Some(status));
+        }
+        #[cfg(target_os = "linux")]
+        {
+            use crate::os::unix::io::OwnedFd;
+
+            // A pidfd becomes readable once the process exits, so it can be
+            // polled with a timeout. If the child wasn't spawned with one,
+            // open one just for this wait: the child hasn't been reaped yet,
+            // so its pid can't have been reused by another process.
+            let opened;
+            let pidfd = match self.pidfd.as_ref() {
+                Some(pidfd) => Some(pidfd.as_raw_fd()),
+                None => match cvt(unsafe { libc::syscall(libc::SYS_pidfd_open, self.pid, 0) }) {
+                    Ok(fd) => {
+                        opened = unsafe { OwnedFd::from_raw_fd(fd as c_int) };
+                        Some(opened.as_raw_fd())
+                    }
+                    // ENOSYS on kernels older than 5.3, or EPERM if blocked by
+                    // a seccomp filter, so fall back to polling.
+                    Err(_) => None,
+                },
+            };
+            if let Some(pidfd) = pidfd {
+                let mut pollfd = libc::pollfd { fd: pidfd, events: libc::POLLIN, revents: 0 };
+                loop {
+                    let remaining = deadline.saturating_duration_since(Instant::now());
+                    let timeout = remaining.as_nanos().div_ceil(1_000_000).min(c_int::MAX as u128);
+                    match cvt(unsafe { libc::poll(&mut pollfd, 1, timeout as c_int) }) {
+                        Ok(0) if remaining.is_zero() => return Ok(None),
+                        Ok(0) => {}
+                        Ok(_) => return self.try_wait(),
+                        Err(e) if e.is_interrupted() => {}
+                        Err(e) => return Err(e),
+                    }
+                }
+            }
+        }
+        crate::sys_common::process::wait_deadline_by_polling(self, deadline)
+    }
 }
 
 /// Unix exit statuses
@@ -3,6 +3,7 @@
 use crate::num::NonZero;
 use crate::sys::pal::unix::unsupported::*;
 use crate::sys::process::process_common::*;
+use crate::time::Instant;
 
 use libc::{c_int, pid_t};
 
@@ -45,6 +46,10 @@ pub fn kill(&mut self) -> io::Result<()> {
         unsupported()
     }
 
+    pub fn kill_process_group(&mut self) -> io::Result<()> {
+        unsupported()
+    }
+
     pub fn wait(&mut self) -> io::Result<ExitStatus> {
         unsupported()
     }
@@ -52,6 +57,10 @@ pub fn wait(&mut self) -> io::Result<ExitStatus> {
     pub fn try_wait(&mut self) -> io::Result<Option<ExitStatus>> {
         unsupported()
     }
+
+    pub fn wait_deadline(&mut self, _deadline: Instant) -> io::Result<Option<ExitStatus>> {
+        unsupported()
+    }
 }
 
 mod wait_status;
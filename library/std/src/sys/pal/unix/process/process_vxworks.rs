@@ -5,6 +5,7 @@
 use crate::sys::cvt;
 use crate::sys::process::process_common::*;
 use crate::sys_common::thread;
+use crate::time::Instant;
 use libc::RTP_ID;
 use libc::{self, c_char, c_int};
 
@@ -27,6 +28,12 @@ pub fn spawn(
                 "nul byte found in provided data",
             ));
         }
+        if !self.get_fd_mappings().is_empty() {
+            return Err(io::const_io_error!(
+                io::ErrorKind::Unsupported,
+                "fd mappings are not supported on this platform",
+            ));
+        }
         let (ours, theirs) = self.setup_io(default, needs_stdin)?;
         let mut p = Process { pid: 0, status: None };
 
@@ -151,6 +158,13 @@ pub fn kill(&mut self) -> io::Result<()> {
         }
     }
 
+    pub fn kill_process_group(&mut self) -> io::Result<()> {
+        Err(io::const_io_error!(
+            io::ErrorKind::Unsupported,
+            "process groups are not supported on this platform",
+        ))
+    }
+
     pub fn wait(&mut self) -> io::Result<ExitStatus> {
         use crate::sys::cvt_r;
         if let Some(status) = self.status {
@@ -175,6 +189,10 @@ pub fn try_wait(&mut self) -> io::Result<Option<ExitStatus>> {
             Ok(Some(ExitStatus::new(status)))
         }
     }
+
+    pub fn wait_deadline(&mut self, deadline: Instant) -> io::Result<Option<ExitStatus>> {
+        crate::sys_common::process::wait_deadline_by_polling(self, deadline)
+    }
 }
 
 /// Unix exit statuses
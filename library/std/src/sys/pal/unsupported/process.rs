@@ -7,6 +7,7 @@
 use crate::sys::pipe::AnonPipe;
 use crate::sys::unsupported;
 use crate::sys_common::process::{CommandEnv, CommandEnvs};
+use crate::time::Instant;
 
 pub use crate::ffi::OsString as EnvKey;
 
@@ -295,6 +296,10 @@ pub fn wait(&mut self) -> io::Result<ExitStatus> {
     pub fn try_wait(&mut self) -> io::Result<Option<ExitStatus>> {
         self.0
     }
+
+    pub fn wait_deadline(&mut self, _deadline: Instant) -> io::Result<Option<ExitStatus>> {
+        self.0
+    }
 }
 
 pub struct CommandArgs<'a> {
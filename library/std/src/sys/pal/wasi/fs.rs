@@ -17,7 +17,9 @@
 use crate::sys::unsupported;
 use crate::sys_common::{AsInner, FromInner, IntoInner};
 
-pub use crate::sys_common::fs::try_exists;
+pub use crate::sys_common::fs::{
+    create_temp_dir, create_temp_file, sync_dir, tempfile_in, try_exists,
+};
 
 pub struct File {
     fd: WasiFd,
@@ -427,6 +429,26 @@ pub fn truncate(&self, size: u64) -> io::Result<()> {
         self.fd.filestat_set_size(size)
     }
 
+    pub fn lock(&self) -> io::Result<()> {
+        unsupported()
+    }
+
+    pub fn lock_shared(&self) -> io::Result<()> {
+        unsupported()
+    }
+
+    pub fn try_lock(&self) -> io::Result<()> {
+        unsupported()
+    }
+
+    pub fn try_lock_shared(&self) -> io::Result<()> {
+        unsupported()
+    }
+
+    pub fn unlock(&self) -> io::Result<()> {
+        unsupported()
+    }
+
     pub fn read(&self, buf: &mut [u8]) -> io::Result<usize> {
         self.read_vectored(&mut [IoSliceMut::new(buf)])
     }
@@ -567,6 +589,10 @@ pub fn readdir(p: &Path) -> io::Result<ReadDir> {
     Ok(ReadDir::new(dir, p.to_path_buf()))
 }
 
+pub fn readdir_child(_parent: &ReadDir, entry: &DirEntry, _follow: bool) -> io::Result<ReadDir> {
+    readdir(&entry.path())
+}
+
 pub fn unlink(p: &Path) -> io::Result<()> {
     let (dir, file) = open_parent(p)?;
     dir.unlink_file(osstr2str(file.as_ref())?)
@@ -386,6 +386,17 @@ pub fn InitializeProcThreadAttributeList(
     pub fn LocalFree(hmem: HLOCAL) -> HLOCAL;
 }
 #[link(name = "kernel32")]
+extern "system" {
+    pub fn LockFileEx(
+        hfile: HANDLE,
+        dwflags: LOCK_FILE_FLAGS,
+        dwreserved: u32,
+        nnumberofbytestolocklow: u32,
+        nnumberofbytestolockhigh: u32,
+        lpoverlapped: *mut OVERLAPPED,
+    ) -> BOOL;
+}
+#[link(name = "kernel32")]
 extern "system" {
     pub fn MoveFileExW(
         lpexistingfilename: PCWSTR,
@@ -569,6 +580,16 @@ pub fn SleepConditionVariableSRW(
     pub fn TryAcquireSRWLockShared(srwlock: *mut SRWLOCK) -> BOOLEAN;
 }
 #[link(name = "kernel32")]
+extern "system" {
+    pub fn UnlockFile(
+        hfile: HANDLE,
+        dwfileoffsetlow: u32,
+        dwfileoffsethigh: u32,
+        nnumberofbytestounlocklow: u32,
+        nnumberofbytestounlockhigh: u32,
+    ) -> BOOL;
+}
+#[link(name = "kernel32")]
 extern "system" {
     pub fn UpdateProcThreadAttribute(
         lpattributelist: LPPROC_THREAD_ATTRIBUTE_LIST,
@@ -707,6 +728,10 @@ pub fn WSADuplicateSocketW(
     pub fn WSAGetLastError() -> WSA_ERROR;
 }
 #[link(name = "ws2_32")]
+extern "system" {
+    pub fn WSAPoll(fdarray: *mut WSAPOLLFD, fds: u32, timeout: i32) -> i32;
+}
+#[link(name = "ws2_32")]
 extern "system" {
     pub fn WSARecv(
         s: SOCKET,
@@ -3640,6 +3665,9 @@ fn clone(&self) -> Self {
         *self
     }
 }
+pub const LOCKFILE_EXCLUSIVE_LOCK: LOCK_FILE_FLAGS = 2u32;
+pub const LOCKFILE_FAIL_IMMEDIATELY: LOCK_FILE_FLAGS = 1u32;
+pub type LOCK_FILE_FLAGS = u32;
 pub type LPOVERLAPPED_COMPLETION_ROUTINE = Option<
     unsafe extern "system" fn(
         dwerrorcode: u32,
@@ -3784,6 +3812,11 @@ fn clone(&self) -> Self {
 pub const PIPE_TYPE_BYTE: NAMED_PIPE_MODE = 0u32;
 pub const PIPE_TYPE_MESSAGE: NAMED_PIPE_MODE = 4u32;
 pub const PIPE_WAIT: NAMED_PIPE_MODE = 0u32;
+pub const POLLERR: WSAPOLL_EVENT_FLAGS = 1i16;
+pub const POLLHUP: WSAPOLL_EVENT_FLAGS = 2i16;
+pub const POLLNVAL: WSAPOLL_EVENT_FLAGS = 4i16;
+pub const POLLRDNORM: WSAPOLL_EVENT_FLAGS = 256i16;
+pub const POLLWRNORM: WSAPOLL_EVENT_FLAGS = 16i16;
 pub type PRIORITY_HINT = i32;
 pub type PROCESSOR_ARCHITECTURE = u16;
 pub type PROCESS_CREATION_FLAGS = u32;
@@ -3874,8 +3907,12 @@ fn clone(&self) -> Self {
 pub const SOL_SOCKET: i32 = 65535i32;
 pub const SO_BROADCAST: i32 = 32i32;
 pub const SO_ERROR: i32 = 4103i32;
+pub const SO_KEEPALIVE: i32 = 8i32;
 pub const SO_LINGER: i32 = 128i32;
+pub const SO_RCVBUF: i32 = 4098i32;
 pub const SO_RCVTIMEO: i32 = 4102i32;
+pub const SO_REUSEADDR: i32 = 4i32;
+pub const SO_SNDBUF: i32 = 4097i32;
 pub const SO_SNDTIMEO: i32 = 4101i32;
 pub const SPECIFIC_RIGHTS_ALL: FILE_ACCESS_RIGHTS = 65535u32;
 #[repr(C)]
@@ -4004,6 +4041,9 @@ fn clone(&self) -> Self {
         *self
     }
 }
+pub const TCP_KEEPALIVE: i32 = 3i32;
+pub const TCP_KEEPCNT: i32 = 16i32;
+pub const TCP_KEEPINTVL: i32 = 17i32;
 pub const TCP_NODELAY: i32 = 1i32;
 pub const THREAD_CREATE_RUN_IMMEDIATELY: THREAD_CREATION_FLAGS = 0u32;
 pub const THREAD_CREATE_SUSPENDED: THREAD_CREATION_FLAGS = 4u32;
@@ -4198,6 +4238,19 @@ fn clone(&self) -> Self {
 pub const WSANO_DATA: WSA_ERROR = 11004i32;
 pub const WSANO_RECOVERY: WSA_ERROR = 11003i32;
 #[repr(C)]
+pub struct WSAPOLLFD {
+    pub fd: SOCKET,
+    pub events: WSAPOLL_EVENT_FLAGS,
+    pub revents: WSAPOLL_EVENT_FLAGS,
+}
+impl Copy for WSAPOLLFD {}
+impl Clone for WSAPOLLFD {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+pub type WSAPOLL_EVENT_FLAGS = i16;
+#[repr(C)]
 pub struct WSAPROTOCOLCHAIN {
     pub ChainLen: i32,
     pub ChainEntries: [u32; 7],
@@ -21,6 +21,8 @@
 use super::{api, to_u16s, IoResult};
 use crate::sys::path::maybe_verbatim;
 
+pub use crate::sys_common::fs::{create_temp_dir, create_temp_file, sync_dir};
+
 pub struct File {
     handle: Handle,
 }
@@ -354,6 +356,71 @@ pub fn truncate(&self, size: u64) -> io::Result<()> {
         api::set_file_information_by_handle(self.handle.as_raw_handle(), &info).io_result()
     }
 
+    pub fn lock(&self) -> io::Result<()> {
+        self.lock_file_ex(c::LOCKFILE_EXCLUSIVE_LOCK)
+    }
+
+    pub fn lock_shared(&self) -> io::Result<()> {
+        self.lock_file_ex(0)
+    }
+
+    pub fn try_lock(&self) -> io::Result<()> {
+        self.lock_file_ex(c::LOCKFILE_EXCLUSIVE_LOCK | c::LOCKFILE_FAIL_IMMEDIATELY)
+    }
+
+    pub fn try_lock_shared(&self) -> io::Result<()> {
+        self.lock_file_ex(c::LOCKFILE_FAIL_IMMEDIATELY)
+    }
+
+    pub fn unlock(&self) -> io::Result<()> {
+        // A handle may hold both a shared and an exclusive lock on the same
+        // range, in which case each `UnlockFile` call only releases one of
+        // them, so keep unlocking until there is nothing left to release.
+        for _ in 0..2 {
+            let result = cvt(unsafe {
+                c::UnlockFile(self.handle.as_raw_handle(), 0, 0, u32::MAX, u32::MAX)
+            });
+            match result {
+                Ok(_) => {}
+                Err(e) if e.raw_os_error() == Some(c::ERROR_NOT_LOCKED as i32) => break,
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(())
+    }
+
+    fn lock_file_ex(&self, flags: c::LOCK_FILE_FLAGS) -> io::Result<()> {
+        let handle = self.handle.as_raw_handle();
+        let mut overlapped: c::OVERLAPPED = unsafe { mem::zeroed() };
+        let result =
+            cvt(unsafe { c::LockFileEx(handle, flags, 0, u32::MAX, u32::MAX, &mut overlapped) });
+        match result {
+            Ok(_) => Ok(()),
+            Err(e) if e.raw_os_error() == Some(c::ERROR_LOCK_VIOLATION as i32) => {
+                Err(io::const_io_error!(
+                    io::ErrorKind::WouldBlock,
+                    "the file is locked by another handle",
+                ))
+            }
+            // Handles opened for asynchronous I/O report a lock that is not
+            // immediately available as pending.
+            Err(e) if e.raw_os_error() == Some(c::ERROR_IO_PENDING as i32) => {
+                if flags & c::LOCKFILE_FAIL_IMMEDIATELY != 0 {
+                    return Err(io::const_io_error!(
+                        io::ErrorKind::WouldBlock,
+                        "the file is locked by another handle",
+                    ));
+                }
+                let mut transferred = 0;
+                cvt(unsafe {
+                    c::GetOverlappedResult(handle, &overlapped, &mut transferred, c::TRUE)
+                })
+                .map(drop)
+            }
+            Err(e) => Err(e),
+        }
+    }
+
     #[cfg(not(target_vendor = "uwp"))]
     pub fn file_attr(&self) -> io::Result<FileAttr> {
         unsafe {
@@ -1114,6 +1181,25 @@ pub fn readdir(p: &Path) -> io::Result<ReadDir> {
     }
 }
 
+pub fn readdir_child(_parent: &ReadDir, entry: &DirEntry, _follow: bool) -> io::Result<ReadDir> {
+    readdir(&entry.path())
+}
+
+pub fn tempfile_in(dir: &Path) -> io::Result<crate::fs::File> {
+    // The file is deleted when its only handle is closed, which is why it is not shared either.
+    let (_, file) = crate::sys_common::fs::create_temp(dir, |path| {
+        crate::fs::OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create_new(true)
+            .share_mode(0)
+            .custom_flags(c::FILE_FLAG_DELETE_ON_CLOSE)
+            .attributes(c::FILE_ATTRIBUTE_HIDDEN | c::FILE_ATTRIBUTE_TEMPORARY)
+            .open(path)
+    })?;
+    Ok(file)
+}
+
 pub fn unlink(p: &Path) -> io::Result<()> {
     let p_u16s = maybe_verbatim(p)?;
     cvt(unsafe { c::DeleteFileW(p_u16s.as_ptr()) })?;
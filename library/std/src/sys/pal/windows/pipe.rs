@@ -9,10 +9,12 @@
 use crate::sync::atomic::AtomicUsize;
 use crate::sync::atomic::Ordering::Relaxed;
 use crate::sys::c;
+use crate::sys::dur2timeout;
 use crate::sys::fs::{File, OpenOptions};
 use crate::sys::handle::Handle;
 use crate::sys::hashmap_random_keys;
 use crate::sys_common::{FromInner, IntoInner};
+use crate::time::Instant;
 
 ////////////////////////////////////////////////////////////////////////////////
 // Anonymous pipes
@@ -436,6 +438,61 @@ pub fn read2(p1: AnonPipe, v1: &mut Vec<u8>, p2: AnonPipe, v2: &mut Vec<u8>) ->
     }
 }
 
+/// Like `read2`, but either pipe may be missing and reading gives up once
+/// `deadline` passes. Returns whether both pipes were read to EOF.
+pub fn read2_deadline(
+    p1: Option<AnonPipe>,
+    v1: &mut Vec<u8>,
+    p2: Option<AnonPipe>,
+    v2: &mut Vec<u8>,
+    deadline: Instant,
+) -> io::Result<bool> {
+    let p1 = p1.map(|p| AsyncPipe::new(p.into_handle(), v1)).transpose()?;
+    let p2 = p2.map(|p| AsyncPipe::new(p.into_handle(), v2)).transpose()?;
+    let mut pipes = [p1, p2];
+
+    // Same loop as in `read2`, except that a pipe which hits EOF is dropped
+    // from the set being waited on instead of being finished off with a
+    // blocking read.
+    loop {
+        let mut objs = [ptr::null_mut(); 2];
+        let mut indices = [0; 2];
+        let mut len = 0;
+        for (i, pipe) in pipes.iter().enumerate() {
+            if let Some(pipe) = pipe {
+                objs[len] = pipe.event.as_raw_handle();
+                indices[len] = i;
+                len += 1;
+            }
+        }
+        if len == 0 {
+            return Ok(true);
+        }
+
+        // Checked up front so that a child which never stops writing can't
+        // keep us here past the deadline.
+        let now = Instant::now();
+        if now >= deadline {
+            return Ok(false);
+        }
+        let timeout = dur2timeout(deadline - now);
+        let res =
+            unsafe { c::WaitForMultipleObjects(len as u32, objs.as_ptr(), c::FALSE, timeout) };
+        if res == c::WAIT_TIMEOUT {
+            continue;
+        }
+        let i = res.wrapping_sub(c::WAIT_OBJECT_0) as usize;
+        if i >= len {
+            return Err(io::Error::last_os_error());
+        }
+        let slot = &mut pipes[indices[i]];
+        let pipe = slot.as_mut().unwrap();
+        if !pipe.result()? || !pipe.schedule_read()? {
+            *slot = None;
+        }
+    }
+}
+
 struct AsyncPipe<'a> {
     pipe: Handle,
     event: Handle,
@@ -21,6 +21,7 @@
 use crate::sys::args::{self, Arg};
 use crate::sys::c::{self, NonZeroDWORD, EXIT_FAILURE, EXIT_SUCCESS};
 use crate::sys::cvt;
+use crate::sys::dur2timeout;
 use crate::sys::fs::{File, OpenOptions};
 use crate::sys::handle::Handle;
 use crate::sys::path;
@@ -28,6 +29,7 @@
 use crate::sys::stdio;
 use crate::sys_common::process::{CommandEnv, CommandEnvs};
 use crate::sys_common::IntoInner;
+use crate::time::Instant;
 
 use core::ffi::c_void;
 
@@ -691,6 +693,24 @@ pub fn try_wait(&mut self) -> io::Result<Option<ExitStatus>> {
         }
     }
 
+    pub fn wait_deadline(&mut self, deadline: Instant) -> io::Result<Option<ExitStatus>> {
+        // Deadlines more than `u32::MAX` milliseconds away are rounded up to
+        // `INFINITE`, so this never times out early.
+        let timeout = dur2timeout(deadline.saturating_duration_since(Instant::now()));
+        unsafe {
+            match c::WaitForSingleObject(self.handle.as_raw_handle(), timeout) {
+                c::WAIT_OBJECT_0 => {}
+                c::WAIT_TIMEOUT => {
+                    return Ok(None);
+                }
+                _ => return Err(io::Error::last_os_error()),
+            }
+            let mut status = 0;
+            cvt(c::GetExitCodeProcess(self.handle.as_raw_handle(), &mut status))?;
+            Ok(Some(ExitStatus(status)))
+        }
+    }
+
     pub fn handle(&self) -> &Handle {
         &self.handle
     }
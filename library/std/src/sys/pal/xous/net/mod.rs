@@ -6,6 +6,9 @@
 mod tcplistener;
 pub use tcplistener::*;
 
+mod tcpsocket;
+pub use tcpsocket::*;
+
 mod udp;
 pub use udp::*;
 
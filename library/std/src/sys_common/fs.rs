@@ -2,7 +2,7 @@
 
 use crate::fs;
 use crate::io::{self, Error, ErrorKind};
-use crate::path::Path;
+use crate::path::{Path, PathBuf};
 
 pub(crate) const NOT_FILE_ERROR: Error = io::const_io_error!(
     ErrorKind::InvalidInput,
@@ -49,3 +49,56 @@ pub fn try_exists(path: &Path) -> io::Result<bool> {
         Err(error) => Err(error),
     }
 }
+
+/// Creates an entry with a fresh random name in `dir`, calling `create` with candidate paths
+/// until it does not fail with `AlreadyExists`. `create` must fail if the path already exists,
+/// which makes the whole operation atomic.
+pub fn create_temp<R>(
+    dir: &Path,
+    create: impl Fn(&Path) -> io::Result<R>,
+) -> io::Result<(PathBuf, R)> {
+    const ATTEMPTS: u32 = 1 << 16;
+    const CHARS: &[u8] = b"abcdefghijklmnopqrstuvwxyz0123456789";
+
+    for _ in 0..ATTEMPTS {
+        let (k0, k1) = crate::sys::hashmap_random_keys();
+        let mut bits = k0 ^ k1.rotate_left(32);
+        let mut name = String::from(".tmp");
+        for _ in 0..12 {
+            name.push(CHARS[(bits % CHARS.len() as u64) as usize] as char);
+            bits /= CHARS.len() as u64;
+        }
+        let path = dir.join(name);
+        match create(&path) {
+            Ok(r) => return Ok((path, r)),
+            Err(e) if e.kind() == ErrorKind::AlreadyExists => {}
+            Err(e) => return Err(e),
+        }
+    }
+    Err(io::const_io_error!(ErrorKind::AlreadyExists, "too many temporary files exist"))
+}
+
+pub fn create_temp_file(path: &Path) -> io::Result<fs::File> {
+    fs::OpenOptions::new().read(true).write(true).create_new(true).open(path)
+}
+
+pub fn create_temp_dir(path: &Path) -> io::Result<()> {
+    fs::create_dir(path)
+}
+
+pub fn tempfile_in(dir: &Path) -> io::Result<fs::File> {
+    let (path, file) = create_temp(dir, create_temp_file)?;
+    if let Err(e) = fs::remove_file(&path) {
+        // Don't leave the file behind if it can be removed once closed.
+        drop(file);
+        let _ = fs::remove_file(&path);
+        return Err(e);
+    }
+    Ok(file)
+}
+
+pub fn sync_dir(_path: &Path) -> io::Result<()> {
+    // Not all platforms can open a directory to flush it, and those which have no way to do
+    // so make renames durable together with the file system's other metadata.
+    Ok(())
+}
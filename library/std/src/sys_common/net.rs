@@ -503,6 +503,209 @@ fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
     }
 }
 
+////////////////////////////////////////////////////////////////////////////////
+// TCP sockets
+////////////////////////////////////////////////////////////////////////////////
+
+// Socket options which are not available everywhere are `None` on the
+// platforms that lack them, and fail with `Unsupported` when used.
+
+cfg_if::cfg_if! {
+    if #[cfg(any(unix, windows))] {
+        const SO_KEEPALIVE: Option<c_int> = Some(c::SO_KEEPALIVE);
+        const SO_RCVBUF: Option<c_int> = Some(c::SO_RCVBUF);
+        const SO_SNDBUF: Option<c_int> = Some(c::SO_SNDBUF);
+    } else {
+        const SO_KEEPALIVE: Option<c_int> = None;
+        const SO_RCVBUF: Option<c_int> = None;
+        const SO_SNDBUF: Option<c_int> = None;
+    }
+}
+
+cfg_if::cfg_if! {
+    if #[cfg(any(
+        target_os = "android", target_os = "dragonfly", target_os = "freebsd",
+        target_os = "fuchsia", target_os = "linux", target_os = "netbsd",
+        target_os = "openbsd", target_vendor = "apple"))] {
+        const SO_REUSEPORT: Option<c_int> = Some(c::SO_REUSEPORT);
+    } else {
+        const SO_REUSEPORT: Option<c_int> = None;
+    }
+}
+
+cfg_if::cfg_if! {
+    if #[cfg(any(target_vendor = "apple", windows))] {
+        const TCP_KEEPIDLE: Option<c_int> = Some(c::TCP_KEEPALIVE);
+        const TCP_KEEPINTVL: Option<c_int> = Some(c::TCP_KEEPINTVL);
+        const TCP_KEEPCNT: Option<c_int> = Some(c::TCP_KEEPCNT);
+    } else if #[cfg(any(
+        target_os = "android", target_os = "dragonfly", target_os = "freebsd",
+        target_os = "fuchsia", target_os = "illumos", target_os = "linux",
+        target_os = "netbsd", target_os = "solaris"))] {
+        const TCP_KEEPIDLE: Option<c_int> = Some(c::TCP_KEEPIDLE);
+        const TCP_KEEPINTVL: Option<c_int> = Some(c::TCP_KEEPINTVL);
+        const TCP_KEEPCNT: Option<c_int> = Some(c::TCP_KEEPCNT);
+    } else {
+        const TCP_KEEPIDLE: Option<c_int> = None;
+        const TCP_KEEPINTVL: Option<c_int> = None;
+        const TCP_KEEPCNT: Option<c_int> = None;
+    }
+}
+
+fn set_int_option(
+    sock: &Socket,
+    level: c_int,
+    option_name: Option<c_int>,
+    value: c_int,
+) -> io::Result<()> {
+    let option_name = option_name.ok_or(UNSUPPORTED_OPTION)?;
+    setsockopt(sock, level, option_name, value)
+}
+
+fn int_option(sock: &Socket, level: c_int, option_name: Option<c_int>) -> io::Result<c_int> {
+    let option_name = option_name.ok_or(UNSUPPORTED_OPTION)?;
+    getsockopt(sock, level, option_name)
+}
+
+const UNSUPPORTED_OPTION: io::Error =
+    io::const_io_error!(ErrorKind::Unsupported, "socket option not supported on this platform");
+
+/// Converts a keepalive duration to whole seconds, rounding up so that a
+/// non-zero duration never turns into zero.
+fn keepalive_secs(dur: Duration) -> c_int {
+    let secs = dur.as_secs().saturating_add((dur.subsec_nanos() != 0) as u64);
+    cmp::min(secs, c_int::MAX as u64) as c_int
+}
+
+pub struct TcpSocket {
+    inner: Socket,
+}
+
+impl TcpSocket {
+    /// Creates a TCP socket of the same address family as `addr`.
+    pub fn new(addr: &SocketAddr) -> io::Result<TcpSocket> {
+        init();
+
+        let sock = Socket::new(addr, c::SOCK_STREAM)?;
+        Ok(TcpSocket { inner: sock })
+    }
+
+    #[inline]
+    pub fn socket(&self) -> &Socket {
+        &self.inner
+    }
+
+    pub fn into_socket(self) -> Socket {
+        self.inner
+    }
+
+    pub fn set_reuseaddr(&self, reuseaddr: bool) -> io::Result<()> {
+        setsockopt(&self.inner, c::SOL_SOCKET, c::SO_REUSEADDR, reuseaddr as c_int)
+    }
+
+    pub fn reuseaddr(&self) -> io::Result<bool> {
+        let raw: c_int = getsockopt(&self.inner, c::SOL_SOCKET, c::SO_REUSEADDR)?;
+        Ok(raw != 0)
+    }
+
+    pub fn set_reuseport(&self, reuseport: bool) -> io::Result<()> {
+        set_int_option(&self.inner, c::SOL_SOCKET, SO_REUSEPORT, reuseport as c_int)
+    }
+
+    pub fn reuseport(&self) -> io::Result<bool> {
+        Ok(int_option(&self.inner, c::SOL_SOCKET, SO_REUSEPORT)? != 0)
+    }
+
+    pub fn set_send_buffer_size(&self, size: usize) -> io::Result<()> {
+        let size = cmp::min(size, c_int::MAX as usize) as c_int;
+        set_int_option(&self.inner, c::SOL_SOCKET, SO_SNDBUF, size)
+    }
+
+    pub fn send_buffer_size(&self) -> io::Result<usize> {
+        Ok(int_option(&self.inner, c::SOL_SOCKET, SO_SNDBUF)? as usize)
+    }
+
+    pub fn set_recv_buffer_size(&self, size: usize) -> io::Result<()> {
+        let size = cmp::min(size, c_int::MAX as usize) as c_int;
+        set_int_option(&self.inner, c::SOL_SOCKET, SO_RCVBUF, size)
+    }
+
+    pub fn recv_buffer_size(&self) -> io::Result<usize> {
+        Ok(int_option(&self.inner, c::SOL_SOCKET, SO_RCVBUF)? as usize)
+    }
+
+    pub fn set_keepalive(&self, keepalive: bool) -> io::Result<()> {
+        set_int_option(&self.inner, c::SOL_SOCKET, SO_KEEPALIVE, keepalive as c_int)
+    }
+
+    pub fn keepalive(&self) -> io::Result<bool> {
+        Ok(int_option(&self.inner, c::SOL_SOCKET, SO_KEEPALIVE)? != 0)
+    }
+
+    pub fn set_tcp_keepalive(
+        &self,
+        time: Option<Duration>,
+        interval: Option<Duration>,
+        retries: Option<u32>,
+    ) -> io::Result<()> {
+        self.set_keepalive(true)?;
+        if let Some(time) = time {
+            set_int_option(&self.inner, c::IPPROTO_TCP, TCP_KEEPIDLE, keepalive_secs(time))?;
+        }
+        if let Some(interval) = interval {
+            let interval = keepalive_secs(interval);
+            set_int_option(&self.inner, c::IPPROTO_TCP, TCP_KEEPINTVL, interval)?;
+        }
+        if let Some(retries) = retries {
+            let retries = cmp::min(retries, c_int::MAX as u32) as c_int;
+            set_int_option(&self.inner, c::IPPROTO_TCP, TCP_KEEPCNT, retries)?;
+        }
+        Ok(())
+    }
+
+    pub fn set_nodelay(&self, nodelay: bool) -> io::Result<()> {
+        self.inner.set_nodelay(nodelay)
+    }
+
+    pub fn nodelay(&self) -> io::Result<bool> {
+        self.inner.nodelay()
+    }
+
+    pub fn bind(&self, addr: &SocketAddr) -> io::Result<()> {
+        let (addr, len) = addr.into_inner();
+        cvt(unsafe { c::bind(self.inner.as_raw(), addr.as_ptr(), len as _) })?;
+        Ok(())
+    }
+
+    pub fn socket_addr(&self) -> io::Result<SocketAddr> {
+        sockname(|buf, len| unsafe { c::getsockname(self.inner.as_raw(), buf, len) })
+    }
+
+    pub fn connect(self, addr: &SocketAddr) -> io::Result<TcpStream> {
+        self.inner.connect(addr)?;
+        Ok(TcpStream { inner: self.inner })
+    }
+
+    pub fn listen(self, backlog: u32) -> io::Result<TcpListener> {
+        let backlog = cmp::min(backlog, c_int::MAX as u32) as c_int;
+        cvt(unsafe { c::listen(self.inner.as_raw(), backlog) })?;
+        Ok(TcpListener { inner: self.inner })
+    }
+}
+
+impl fmt::Debug for TcpSocket {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut res = f.debug_struct("TcpSocket");
+
+        if let Ok(addr) = self.socket_addr() {
+            res.field("addr", &addr);
+        }
+
+        let name = if cfg!(windows) { "socket" } else { "fd" };
+        res.field(name, &self.inner.as_raw()).finish()
+    }
+}
+
 ////////////////////////////////////////////////////////////////////////////////
 // UDP
 ////////////////////////////////////////////////////////////////////////////////
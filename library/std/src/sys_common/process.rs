@@ -8,6 +8,8 @@
 use crate::io;
 use crate::sys::pipe::read2;
 use crate::sys::process::{EnvKey, ExitStatus, Process, StdioPipes};
+use crate::thread;
+use crate::time::{Duration, Instant};
 
 // Stores a set of changes to an environment
 #[derive(Clone)]
@@ -159,3 +161,25 @@ pub fn wait_with_output(
     let status = process.wait()?;
     Ok((status, stdout, stderr))
 }
+
+/// Waits for `process` to exit by calling `try_wait` until `deadline`, for
+/// targets with no way of blocking on a child with a timeout.
+pub fn wait_deadline_by_polling(
+    process: &mut Process,
+    deadline: Instant,
+) -> io::Result<Option<ExitStatus>> {
+    // Start off polling often so that short-lived children are noticed
+    // quickly, then back off to avoid burning CPU on long-running ones.
+    let mut interval = Duration::from_millis(1);
+    loop {
+        if let Some(status) = process.try_wait()? {
+            return Ok(Some(status));
+        }
+        let now = Instant::now();
+        if now >= deadline {
+            return Ok(None);
+        }
+        thread::sleep(interval.min(deadline - now));
+        interval = (interval * 2).min(Duration::from_millis(50));
+    }
+}
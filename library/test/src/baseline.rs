@@ -0,0 +1,204 @@
+//! Saving benchmark results as named baselines, and comparing against them.
+
+use std::collections::BTreeMap;
+use std::env;
+use std::fs;
+use std::io::{self, BufRead, BufReader, BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+use super::stats::{self, Summary};
+
+/// Differences with a p-value below this level are reported as regressions or
+/// improvements.
+pub const SIGNIFICANCE_LEVEL: f64 = 0.05;
+
+const HEADER: &str = "# libtest benchmark baseline v1";
+
+/// Summaries of a set of benchmarks, by benchmark name.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Baseline(BTreeMap<String, BenchStats>);
+
+/// Summary of a benchmark, and the number of samples it summarizes.
+#[derive(Copy, Clone, Debug, PartialEq)]
+struct BenchStats {
+    summary: Summary,
+    samples: usize,
+}
+
+impl Baseline {
+    pub fn new() -> Baseline {
+        Baseline(BTreeMap::new())
+    }
+
+    pub fn insert(&mut self, name: &str, summary: Summary, samples: usize) {
+        self.0.insert(name.to_owned(), BenchStats { summary, samples });
+    }
+
+    /// Path of the baseline named `name` for the running test binary.
+    ///
+    /// Baselines are stored in `$RUST_TEST_BASELINE_DIR`, or by default in a
+    /// `libtest-baselines` directory next to the test binary, with one
+    /// subdirectory per baseline name and one file per test binary.
+    pub fn path(name: &str) -> io::Result<PathBuf> {
+        let exe = env::current_exe()?;
+        let dir = match env::var_os("RUST_TEST_BASELINE_DIR") {
+            Some(dir) => PathBuf::from(dir),
+            None => exe.with_file_name("libtest-baselines"),
+        };
+        let binary = exe.file_stem().unwrap_or_default();
+        Ok(dir.join(name).join(binary))
+    }
+
+    pub fn load(path: &Path) -> io::Result<Baseline> {
+        let file = fs::File::open(path).map_err(|e| {
+            io::Error::new(e.kind(), format!("failed to read baseline {}: {e}", path.display()))
+        })?;
+        let malformed = |line: usize| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("malformed baseline {} at line {line}", path.display()),
+            )
+        };
+
+        let mut baseline = Baseline::new();
+        for (i, line) in BufReader::new(file).lines().enumerate() {
+            let line = line?;
+            if i == 0 {
+                if line != HEADER {
+                    return Err(malformed(1));
+                }
+                continue;
+            }
+            let (name, stats) = line
+                .split_once('\t')
+                .and_then(|(name, fields)| Some((name, parse_stats(fields)?)))
+                .ok_or_else(|| malformed(i + 1))?;
+            baseline.0.insert(name.to_owned(), stats);
+        }
+        Ok(baseline)
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let mut out = BufWriter::new(fs::File::create(path)?);
+        writeln!(out, "{HEADER}")?;
+        for (name, &BenchStats { summary: ref s, samples }) in &self.0 {
+            let (q1, q2, q3) = s.quartiles;
+            writeln!(
+                out,
+                "{name}\t{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}",
+                samples,
+                s.sum,
+                s.min,
+                s.max,
+                s.mean,
+                s.median,
+                s.var,
+                s.std_dev,
+                s.std_dev_pct,
+                s.median_abs_dev,
+                s.median_abs_dev_pct,
+                q1,
+                q2,
+                q3,
+                s.iqr,
+            )?;
+        }
+        out.flush()
+    }
+
+    /// Compares the benchmarks of `new` which are also part of this baseline.
+    pub fn compare(&self, new: &Baseline) -> Vec<BenchComparison> {
+        new.0
+            .iter()
+            .filter_map(|(name, new)| {
+                let old = self.0.get(name)?;
+                let p_value =
+                    stats::welch_t_test(&old.summary, old.samples, &new.summary, new.samples);
+                Some(BenchComparison {
+                    name: name.clone(),
+                    old: old.summary,
+                    new: new.summary,
+                    p_value,
+                })
+            })
+            .collect()
+    }
+}
+
+fn parse_stats(fields: &str) -> Option<BenchStats> {
+    let mut fields = fields.split('\t');
+    let samples = fields.next()?.parse().ok()?;
+    let values: Vec<f64> = fields.map(|f| f.parse().ok()).collect::<Option<_>>()?;
+    let &[sum, min, max, mean, median, var, std_dev, std_dev_pct, mad, mad_pct, q1, q2, q3, iqr] =
+        &values[..]
+    else {
+        return None;
+    };
+    let summary = Summary {
+        sum,
+        min,
+        max,
+        mean,
+        median,
+        var,
+        std_dev,
+        std_dev_pct,
+        median_abs_dev: mad,
+        median_abs_dev_pct: mad_pct,
+        quartiles: (q1, q2, q3),
+        iqr,
+    };
+    Some(BenchStats { summary, samples })
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum BenchChange {
+    Regressed,
+    Improved,
+    Unchanged,
+}
+
+impl BenchChange {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            BenchChange::Regressed => "regressed",
+            BenchChange::Improved => "improved",
+            BenchChange::Unchanged => "unchanged",
+        }
+    }
+}
+
+/// Result of comparing a benchmark against its baseline.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BenchComparison {
+    pub name: String,
+    pub old: Summary,
+    pub new: Summary,
+    /// Two-sided p-value of Welch's t-test of the old and new samples.
+    pub p_value: f64,
+}
+
+// The change is judged by the mean, which is what the t-test compares, so that the
+// reported direction of a significant change always agrees with the reported numbers.
+impl BenchComparison {
+    /// Relative change of the mean time per iteration, in percent.
+    pub fn mean_change_pct(&self) -> f64 {
+        if self.old.mean == 0.0 {
+            return 0.0;
+        }
+        (self.new.mean - self.old.mean) / self.old.mean * 100.0
+    }
+
+    pub fn change(&self) -> BenchChange {
+        if self.p_value >= SIGNIFICANCE_LEVEL {
+            BenchChange::Unchanged
+        } else if self.new.mean > self.old.mean {
+            BenchChange::Regressed
+        } else {
+            BenchChange::Improved
+        }
+    }
+}
@@ -2,9 +2,10 @@
 
 use std::env;
 use std::path::PathBuf;
+use std::time::Duration;
 
-use super::options::{ColorConfig, Options, OutputFormat, RunIgnored};
-use super::time::TestTimeOptions;
+use super::options::{ColorConfig, Options, OutputCapture, OutputFormat, RunIgnored, TestShard};
+use super::time::{self, TestTimeOptions};
 use std::io::{self, IsTerminal};
 
 #[derive(Debug)]
@@ -13,19 +14,35 @@ pub struct TestOpts {
     pub filters: Vec<String>,
     pub filter_exact: bool,
     pub force_run_in_process: bool,
+    /// Run every test in its own child process.
+    pub isolate: bool,
     pub exclude_should_panic: bool,
     pub run_ignored: RunIgnored,
     pub run_tests: bool,
     pub bench_benchmarks: bool,
+    /// Fuzz the `#[fuzz]` targets instead of running their corpus as tests.
+    pub fuzz: bool,
+    /// Time spent fuzzing every target.
+    pub fuzz_time: Option<Duration>,
     pub logfile: Option<PathBuf>,
     pub nocapture: bool,
+    pub capture: OutputCapture,
     pub color: ColorConfig,
     pub format: OutputFormat,
     pub shuffle: bool,
     pub shuffle_seed: Option<u64>,
+    pub shard: Option<TestShard>,
     pub test_threads: Option<usize>,
     pub skip: Vec<String>,
     pub time_options: Option<TestTimeOptions>,
+    /// Hard time limit of tests which don't set their own with `#[test_timeout]`.
+    pub timeout: Option<Duration>,
+    /// Number of times a failing test is run again before it is reported as failed.
+    pub retries: usize,
+    /// Name of the baseline to save the benchmark results as.
+    pub save_baseline: Option<String>,
+    /// Name of the saved baseline to compare the benchmark results against.
+    pub baseline: Option<String>,
     /// Stop at first failing test.
     /// May run a few more tests due to threading, but will
     /// abort as soon as possible.
@@ -53,9 +70,29 @@ fn optgroups() -> getopts::Options {
     opts.optflag("", "include-ignored", "Run ignored and not ignored tests")
         .optflag("", "ignored", "Run only ignored tests")
         .optflag("", "force-run-in-process", "Forces tests to run in-process when panic=abort")
+        .optflag(
+            "",
+            "isolate",
+            "Run every test in its own child process, so that tests which exit,
+            crash or change the state of the process don't affect other tests.
+            At most --test-threads child processes run at the same time. Tests
+            created at runtime, such as doctests, still run in-process",
+        )
         .optflag("", "exclude-should-panic", "Excludes tests marked as should_panic")
         .optflag("", "test", "Run tests and not benchmarks")
         .optflag("", "bench", "Run benchmarks instead of tests")
+        .optflag(
+            "",
+            "fuzz",
+            "Fuzz the #[fuzz] targets instead of running tests, one after the
+            other, until an input makes them crash",
+        )
+        .optopt(
+            "",
+            "fuzz-time",
+            "Fuzz every target for MILLIS milliseconds (default: 60000)",
+            "MILLIS",
+        )
         .optflag("", "list", "List all tests and benchmarks")
         .optflag("h", "help", "Display this message")
         .optopt("", "logfile", "Write logs to the specified file", "PATH")
@@ -65,6 +102,15 @@ fn optgroups() -> getopts::Options {
             "don't capture stdout/stderr of each \
              task, allow printing directly",
         )
+        .optopt(
+            "",
+            "capture",
+            "Configure how the output of tests is captured:
+            print = capture the output of the print macros (default);
+            fd    = run every test in its own child process and capture
+                    everything it writes to stdout and stderr, in order",
+            "print|fd",
+        )
         .optopt(
             "",
             "test-threads",
@@ -102,8 +148,9 @@ fn optgroups() -> getopts::Options {
             pretty = Print verbose output;
             terse  = Display one character per test;
             json   = Output a json document;
-            junit  = Output a JUnit document",
-            "pretty|terse|json|junit",
+            junit  = Output a JUnit document;
+            tap    = Output a TAP version 14 stream",
+            "pretty|terse|json|junit|tap",
         )
         .optflag("", "show-output", "Show captured stdout of successful tests")
         .optopt(
@@ -142,12 +189,50 @@ fn optgroups() -> getopts::Options {
             `CRITICAL_TIME` here means the limit that should not be exceeded by test.
             ",
         )
+        .optopt(
+            "",
+            "timeout",
+            "Fail tests which run for longer than MILLIS milliseconds.
+
+            Tests running in a subprocess are killed, tests running in-process are
+            abandoned. The `#[test_timeout]` attribute overrides this value.",
+            "MILLIS",
+        )
+        .optopt(
+            "",
+            "retries",
+            "Run failing tests again up to N times. Tests which pass on a later
+            attempt are reported as flaky instead of passed. Tests created at
+            runtime, such as doctests, are only run once",
+            "N",
+        )
+        .optopt(
+            "",
+            "save-baseline",
+            "Save the benchmark results as the baseline named NAME",
+            "NAME",
+        )
+        .optopt(
+            "",
+            "baseline",
+            "Compare the benchmark results against the saved baseline named NAME,
+            reporting significant regressions and improvements",
+            "NAME",
+        )
         .optflag("", "shuffle", "Run tests in random order")
         .optopt(
             "",
             "shuffle-seed",
             "Run tests in random order; seed the random number generator with SEED",
             "SEED",
+        )
+        .optopt(
+            "",
+            "shard",
+            "Only run the tests belonging to shard INDEX out of COUNT shards.
+            Shards are zero-based and are assigned by hashing the test name,
+            so every shard of the same test binary runs a disjoint set of tests",
+            "INDEX/COUNT",
         );
     opts
 }
@@ -171,9 +256,33 @@ fn usage(binary: &str, options: &getopts::Options) {
 tests in the same order again. Note that --shuffle and --shuffle-seed do not
 affect whether the tests are run in parallel.
 
+A test run can be split across several processes or machines with
+--shard INDEX/COUNT. Each test is assigned to exactly one of the COUNT shards
+based on its name, so running every INDEX from 0 to COUNT - 1 runs every test
+exactly once. --list only lists the tests of the given shard.
+
+Unless tests are compiled with panic=abort, they run on threads of the test
+process. With --isolate, every test runs in its own child process instead, so
+a test which exits, crashes or changes the environment or the current directory
+doesn't affect the other tests. A test which crashes is reported as failed with
+the signal which killed it.
+
 All tests have their standard output and standard error captured by default.
 This can be overridden with the --nocapture flag or setting RUST_TEST_NOCAPTURE
 environment variable to a value other than "0". Logging is not captured by default.
+Only the output of the print macros is captured, unless --capture=fd is passed,
+in which case every test runs in its own child process and everything written
+to its stdout and stderr is captured, including the output of C libraries and
+child processes.
+
+Fuzz targets are run with every input of their corpus, which is read from
+fuzz/corpus/PATH/TO/TARGET, or from PATH/TO/TARGET under RUST_TEST_FUZZ_CORPUS_DIR.
+With --fuzz, they are instead run with mutations of these inputs for
+--fuzz-time each, and the inputs reaching new code are added to the corpus.
+Coverage is measured with the counters of -Cinstrument-coverage or of
+SanitizerCoverage's inline 8-bit counters, once the fuzz crate registers them
+with the functions of test::fuzz. An input which makes a
+target crash is written to its corpus, so that it is run by later test runs.
 
 Test Attributes:
 
@@ -181,6 +290,8 @@ fn usage(binary: &str, options: &getopts::Options) {
                        takes no arguments.
     `#[bench]`       - Indicates a function is a benchmark to be run. This
                        function takes one argument (test::Bencher).
+    `#[fuzz]`        - Indicates a function is a fuzz target. This function
+                       takes one argument (&[u8]).
     `#[should_panic]` - This function (also labeled with `#[test]`) will only pass if
                         the code causes a panic (an assertion failure or panic!)
                         A message may be provided, which the failure string must
@@ -257,10 +368,20 @@ fn parse_opts_impl(matches: getopts::Matches) -> OptRes {
 
     // Unstable flags
     let force_run_in_process = unstable_optflag!(matches, allow_unstable, "force-run-in-process");
+    let isolate = unstable_optflag!(matches, allow_unstable, "isolate");
+    if isolate && force_run_in_process {
+        return Err(
+            "the options --isolate and --force-run-in-process are mutually exclusive".into()
+        );
+    }
     let exclude_should_panic = unstable_optflag!(matches, allow_unstable, "exclude-should-panic");
     let time_options = get_time_options(&matches, allow_unstable)?;
+    let timeout = get_timeout(&matches, allow_unstable)?;
+    let retries = get_retries(&matches, allow_unstable)?;
+    let (save_baseline, baseline) = get_baselines(&matches, allow_unstable)?;
     let shuffle = get_shuffle(&matches, allow_unstable)?;
     let shuffle_seed = get_shuffle_seed(&matches, allow_unstable)?;
+    let shard = get_shard(&matches, allow_unstable)?;
 
     let include_ignored = matches.opt_present("include-ignored");
     let quiet = matches.opt_present("quiet");
@@ -269,12 +390,20 @@ fn parse_opts_impl(matches: getopts::Matches) -> OptRes {
     let skip = matches.opt_strs("skip");
 
     let bench_benchmarks = matches.opt_present("bench");
-    let run_tests = !bench_benchmarks || matches.opt_present("test");
+    let fuzz = unstable_optflag!(matches, allow_unstable, "fuzz");
+    let fuzz_time = get_fuzz_time(&matches, allow_unstable)?;
+    let run_tests = !(bench_benchmarks || fuzz) || matches.opt_present("test");
 
     let logfile = get_log_file(&matches)?;
     let run_ignored = get_run_ignored(&matches, include_ignored)?;
     let filters = matches.free.clone();
     let nocapture = get_nocapture(&matches)?;
+    let capture = get_capture(&matches, nocapture, allow_unstable)?;
+    if capture == OutputCapture::Fd && force_run_in_process {
+        return Err(
+            "the options --capture=fd and --force-run-in-process are mutually exclusive".into()
+        );
+    }
     let test_threads = get_test_threads(&matches)?;
     let color = get_color_config(&matches)?;
     let format = get_format(&matches, quiet, allow_unstable)?;
@@ -286,19 +415,28 @@ fn parse_opts_impl(matches: getopts::Matches) -> OptRes {
         filters,
         filter_exact: exact,
         force_run_in_process,
+        isolate,
         exclude_should_panic,
         run_ignored,
         run_tests,
         bench_benchmarks,
+        fuzz,
+        fuzz_time,
         logfile,
         nocapture,
+        capture,
         color,
         format,
         shuffle,
         shuffle_seed,
+        shard,
         test_threads,
         skip,
         time_options,
+        timeout,
+        retries,
+        save_baseline,
+        baseline,
         options,
         fail_fast: false,
     };
@@ -336,6 +474,58 @@ fn get_time_options(
     Ok(options)
 }
 
+fn get_timeout(matches: &getopts::Matches, allow_unstable: bool) -> OptPartRes<Option<Duration>> {
+    match unstable_optopt!(matches, allow_unstable, "timeout") {
+        Some(timeout) => match time::parse_timeout(&timeout) {
+            Ok(timeout) => Ok(Some(timeout)),
+            Err(e) => Err(format!("argument for --timeout is invalid: {e}")),
+        },
+        None => Ok(None),
+    }
+}
+
+fn get_fuzz_time(matches: &getopts::Matches, allow_unstable: bool) -> OptPartRes<Option<Duration>> {
+    match unstable_optopt!(matches, allow_unstable, "fuzz-time") {
+        Some(time) => match time::parse_timeout(&time) {
+            Ok(time) => Ok(Some(time)),
+            Err(e) => Err(format!("argument for --fuzz-time is invalid: {e}")),
+        },
+        None => Ok(None),
+    }
+}
+
+fn get_retries(matches: &getopts::Matches, allow_unstable: bool) -> OptPartRes<usize> {
+    match unstable_optopt!(matches, allow_unstable, "retries") {
+        Some(n_str) => n_str
+            .parse::<usize>()
+            .map_err(|e| format!("argument for --retries must be a number (error: {e})")),
+        None => Ok(0),
+    }
+}
+
+// Gets the names of the benchmark baselines to save and to compare against.
+fn get_baselines(
+    matches: &getopts::Matches,
+    allow_unstable: bool,
+) -> OptPartRes<(Option<String>, Option<String>)> {
+    let save_baseline = unstable_optopt!(matches, allow_unstable, "save-baseline");
+    let baseline = unstable_optopt!(matches, allow_unstable, "baseline");
+
+    // Baselines are stored in a directory named after them.
+    let check_name = |option_name: &str, name: Option<String>| match name {
+        Some(name)
+            if name.is_empty()
+                || name == "."
+                || name == ".."
+                || name.contains(std::path::is_separator) =>
+        {
+            Err(format!("argument for --{option_name} must be a name, not a path (was {name})"))
+        }
+        name => Ok(name),
+    };
+    Ok((check_name("save-baseline", save_baseline)?, check_name("baseline", baseline)?))
+}
+
 fn get_shuffle(matches: &getopts::Matches, allow_unstable: bool) -> OptPartRes<bool> {
     let mut shuffle = unstable_optflag!(matches, allow_unstable, "shuffle");
     if !shuffle && allow_unstable {
@@ -375,6 +565,27 @@ fn get_shuffle_seed(matches: &getopts::Matches, allow_unstable: bool) -> OptPart
     Ok(shuffle_seed)
 }
 
+fn get_shard(matches: &getopts::Matches, allow_unstable: bool) -> OptPartRes<Option<TestShard>> {
+    let Some(shard_str) = unstable_optopt!(matches, allow_unstable, "shard") else {
+        return Ok(None);
+    };
+
+    let parsed = shard_str.split_once('/').and_then(|(index, count)| {
+        Some((index.parse::<usize>().ok()?, count.parse::<usize>().ok()?))
+    });
+    match parsed {
+        Some((_, 0)) => Err("the shard count passed to --shard must not be 0".to_string()),
+        Some((index, count)) if index >= count => Err(format!(
+            "the shard index passed to --shard must be less than the shard count \
+             (was {index}/{count})"
+        )),
+        Some((index, count)) => Ok(Some(TestShard { index, count })),
+        None => {
+            Err(format!("argument for --shard must be of the form INDEX/COUNT (was {shard_str})"))
+        }
+    }
+}
+
 fn get_test_threads(matches: &getopts::Matches) -> OptPartRes<Option<usize>> {
     let test_threads = match matches.opt_str("test-threads") {
         Some(n_str) => match n_str.parse::<usize>() {
@@ -414,9 +625,15 @@ fn get_format(
             }
             OutputFormat::Junit
         }
+        Some("tap") => {
+            if !allow_unstable {
+                return Err("The \"tap\" format is only accepted on the nightly compiler with -Z unstable-options".into());
+            }
+            OutputFormat::Tap
+        }
         Some(v) => {
             return Err(format!(
-                "argument for --format must be pretty, terse, json or junit (was \
+                "argument for --format must be pretty, terse, json, junit or tap (was \
                  {v})"
             ));
         }
@@ -454,6 +671,25 @@ fn get_nocapture(matches: &getopts::Matches) -> OptPartRes<bool> {
     Ok(nocapture)
 }
 
+fn get_capture(
+    matches: &getopts::Matches,
+    nocapture: bool,
+    allow_unstable: bool,
+) -> OptPartRes<OutputCapture> {
+    let capture = match unstable_optopt!(matches, allow_unstable, "capture").as_deref() {
+        Some("print") | None => OutputCapture::Print,
+        Some("fd") => OutputCapture::Fd,
+        Some(v) => return Err(format!("argument for --capture must be print or fd (was {v})")),
+    };
+    if nocapture && capture != OutputCapture::Print {
+        return Err("--capture can't be used when output capture is disabled with --nocapture \
+                    or RUST_TEST_NOCAPTURE"
+            .into());
+    }
+
+    Ok(capture)
+}
+
 fn get_run_ignored(matches: &getopts::Matches, include_ignored: bool) -> OptPartRes<RunIgnored> {
     let run_ignored = match (include_ignored, matches.opt_present("ignored")) {
         (true, true) => {
@@ -6,11 +6,16 @@
 use std::time::Instant;
 
 use super::{
+    baseline::Baseline,
     bench::fmt_bench_samples,
     cli::TestOpts,
     event::{CompletedTest, TestEvent},
     filter_tests,
-    formatters::{JsonFormatter, JunitFormatter, OutputFormatter, PrettyFormatter, TerseFormatter},
+    fixture::TeardownFailure,
+    formatters::{
+        JsonFormatter, JunitFormatter, OutputFormatter, PrettyFormatter, TapFormatter,
+        TerseFormatter,
+    },
     helpers::{concurrency::get_concurrency, metrics::MetricMap},
     options::{Options, OutputFormat},
     run_tests, term,
@@ -78,16 +83,20 @@ pub struct ConsoleTestState {
     pub log_out: Option<File>,
     pub total: usize,
     pub passed: usize,
+    pub flaky: usize,
     pub failed: usize,
     pub ignored: usize,
     pub filtered_out: usize,
     pub measured: usize,
     pub exec_time: Option<TestSuiteExecTime>,
     pub metrics: MetricMap,
+    pub benchmarks: Baseline,
     pub failures: Vec<(TestDesc, Vec<u8>)>,
     pub not_failures: Vec<(TestDesc, Vec<u8>)>,
+    pub flakes: Vec<(TestDesc, Vec<u8>)>,
     pub ignores: Vec<(TestDesc, Vec<u8>)>,
     pub time_failures: Vec<(TestDesc, Vec<u8>)>,
+    pub teardown_failures: Vec<TeardownFailure>,
     pub options: Options,
 }
 
@@ -102,16 +111,20 @@ pub fn new(opts: &TestOpts) -> io::Result<ConsoleTestState> {
             log_out,
             total: 0,
             passed: 0,
+            flaky: 0,
             failed: 0,
             ignored: 0,
             filtered_out: 0,
             measured: 0,
             exec_time: None,
             metrics: MetricMap::new(),
+            benchmarks: Baseline::new(),
             failures: Vec::new(),
             not_failures: Vec::new(),
+            flakes: Vec::new(),
             ignores: Vec::new(),
             time_failures: Vec::new(),
+            teardown_failures: Vec::new(),
             options: opts.options,
         })
     }
@@ -154,6 +167,10 @@ pub fn write_log_result(
                     }
                     TestResult::TrBench(ref bs) => fmt_bench_samples(bs),
                     TestResult::TrTimedFail => "failed (time limit exceeded)".to_owned(),
+                    TestResult::TrTimedOut => "failed (timed out)".to_owned(),
+                    TestResult::TrFlaky(failed_attempts) => {
+                        format!("flaky (failed attempts: {failed_attempts})")
+                    }
                 },
                 name,
             )
@@ -165,7 +182,12 @@ pub fn write_log_result(
     }
 
     fn current_test_count(&self) -> usize {
-        self.passed + self.failed + self.ignored + self.measured
+        self.passed + self.flaky + self.failed + self.ignored + self.measured
+    }
+
+    /// Whether all tests passed and all fixtures were torn down.
+    pub fn is_success(&self) -> bool {
+        self.failed == 0 && self.teardown_failures.is_empty()
     }
 }
 
@@ -177,7 +199,7 @@ pub fn list_tests_console(opts: &TestOpts, tests: Vec<TestDescAndFn>) -> io::Res
     };
 
     let mut out: Box<dyn OutputFormatter> = match opts.format {
-        OutputFormat::Pretty | OutputFormat::Junit => {
+        OutputFormat::Pretty | OutputFormat::Junit | OutputFormat::Tap => {
             Box::new(PrettyFormatter::new(output, false, 0, false, None))
         }
         OutputFormat::Terse => Box::new(TerseFormatter::new(output, false, 0, false)),
@@ -200,6 +222,11 @@ pub fn list_tests_console(opts: &TestOpts, tests: Vec<TestDescAndFn>) -> io::Res
                 st.benchmarks += 1;
                 "benchmark"
             }
+            // Fuzz targets run as tests unless --fuzz is passed.
+            StaticFuzzFn(..) | StaticFuzzAsTestFn(..) => {
+                st.tests += 1;
+                "fuzz"
+            }
         };
 
         st.ignored += if desc.ignore { 1 } else { 0 };
@@ -220,6 +247,10 @@ fn handle_test_result(st: &mut ConsoleTestState, completed_test: CompletedTest)
             st.passed += 1;
             st.not_failures.push((test, stdout));
         }
+        TestResult::TrFlaky(_) => {
+            st.flaky += 1;
+            st.flakes.push((test, stdout));
+        }
         TestResult::TrIgnored => {
             st.ignored += 1;
             st.ignores.push((test, stdout));
@@ -230,6 +261,7 @@ fn handle_test_result(st: &mut ConsoleTestState, completed_test: CompletedTest)
                 bs.ns_iter_summ.median,
                 bs.ns_iter_summ.max - bs.ns_iter_summ.min,
             );
+            st.benchmarks.insert(test.name.as_slice(), bs.ns_iter_summ, bs.samples);
             st.measured += 1
         }
         TestResult::TrFailed => {
@@ -246,6 +278,17 @@ fn handle_test_result(st: &mut ConsoleTestState, completed_test: CompletedTest)
             st.failed += 1;
             st.time_failures.push((test, stdout));
         }
+        TestResult::TrTimedOut => {
+            st.failed += 1;
+            let mut stdout = stdout;
+            match completed_test.exec_time {
+                Some(exec_time) => stdout.extend_from_slice(
+                    format!("note: test timed out after {exec_time}").as_bytes(),
+                ),
+                None => stdout.extend_from_slice(b"note: test timed out"),
+            }
+            st.failures.push((test, stdout));
+        }
     }
 }
 
@@ -276,6 +319,11 @@ fn on_test_event(
             out.write_result(test, result, exec_time.as_ref(), stdout, st)?;
             handle_test_result(st, completed_test);
         }
+        TestEvent::TeTeardownFailed(failure) => {
+            st.write_log(|| format!("teardown failed {failure}: {}\n", failure.message))?;
+            out.write_teardown_failure(&failure)?;
+            st.teardown_failures.push(failure);
+        }
     }
 
     Ok(())
@@ -310,9 +358,29 @@ pub fn run_tests_console(opts: &TestOpts, tests: Vec<TestDescAndFn>) -> io::Resu
         }
         OutputFormat::Json => Box::new(JsonFormatter::new(output)),
         OutputFormat::Junit => Box::new(JunitFormatter::new(output)),
+        OutputFormat::Tap => Box::new(TapFormatter::new(output)),
     };
     let mut st = ConsoleTestState::new(opts)?;
 
+    // Load the baseline up front, so that a missing one is reported before running the benchmarks.
+    let baseline = match opts.baseline {
+        Some(ref name) => {
+            let path = Baseline::path(name)?;
+            match Baseline::load(&path) {
+                Ok(baseline) => Some(baseline),
+                Err(e) if e.kind() == io::ErrorKind::NotFound => {
+                    eprintln!(
+                        "warning: baseline `{name}` not found at {}, nothing to compare with",
+                        path.display()
+                    );
+                    None
+                }
+                Err(e) => return Err(e),
+            }
+        }
+        None => None,
+    };
+
     // Prevent the usage of `Instant` in some cases:
     // - It's currently not supported for wasm targets.
     // - We disable it for miri because it's not available when isolation is enabled.
@@ -326,6 +394,13 @@ pub fn run_tests_console(opts: &TestOpts, tests: Vec<TestDescAndFn>) -> io::Resu
 
     assert!(opts.fail_fast || st.current_test_count() == st.total);
 
+    if let (Some(name), Some(baseline)) = (&opts.baseline, &baseline) {
+        out.write_baseline_comparison(name, &baseline.compare(&st.benchmarks))?;
+    }
+    if let Some(ref name) = opts.save_baseline {
+        st.benchmarks.save(&Baseline::path(name)?)?;
+    }
+
     out.write_run_finish(&st)
 }
 
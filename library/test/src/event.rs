@@ -1,6 +1,7 @@
 //! Module containing different events that can occur
 //! during tests execution process.
 
+use super::fixture::TeardownFailure;
 use super::test_result::TestResult;
 use super::time::TestExecTime;
 use super::types::{TestDesc, TestId};
@@ -33,4 +34,5 @@ pub enum TestEvent {
     TeResult(CompletedTest),
     TeTimeout(TestDesc),
     TeFilteredOut(usize),
+    TeTeardownFailed(TeardownFailure),
 }
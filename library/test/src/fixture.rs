@@ -0,0 +1,232 @@
+//! Fixtures, which are set up before the tests using them and torn down after.
+//!
+//! A `#[test]` function can take shared references to fixtures as arguments:
+//!
+//! ```rust,ignore (requires the test harness)
+//! #[test]
+//! fn writes_config(dir: &TempDir, server: &LocalServer) { ... }
+//! ```
+//!
+//! Each argument type implements [`Fixture`], whose [`SCOPE`](Fixture::SCOPE)
+//! decides whether every test gets its own instance, or whether the tests of a
+//! module share one. A test-scoped fixture is torn down when its test returns or
+//! panics, and a module-scoped one once the last test of its module completes.
+//! Teardown failures are reported separately from the results of the tests.
+//!
+//! When tests run in subprocesses, with `panic=abort` or `--isolate`, teardown
+//! failures fail the test instead. With `panic=abort`, the fixtures of a test which
+//! panics are not torn down.
+
+use std::any::{self, Any, TypeId};
+use std::fmt;
+use std::ops::Deref;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::sync::{Arc, Mutex, PoisonError};
+use std::thread;
+
+/// How widely an instance of a fixture is shared.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum FixtureScope {
+    /// Every test gets its own instance of the fixture.
+    Test,
+    /// The tests of a module share an instance of the fixture.
+    Module,
+}
+
+/// A resource which is set up for the tests taking a reference to it.
+pub trait Fixture: Send + Sync + Sized + 'static {
+    /// Which tests share an instance of the fixture.
+    const SCOPE: FixtureScope = FixtureScope::Test;
+
+    /// Creates the fixture. A panic fails the test which needed the fixture.
+    fn setup() -> Self;
+
+    /// Tears the fixture down. This also runs when a test using it panicked, so
+    /// errors should be returned rather than raised as panics.
+    fn teardown(self) -> Result<(), String> {
+        Ok(())
+    }
+}
+
+/// A failure to tear a fixture down.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TeardownFailure {
+    /// Type name of the fixture.
+    pub fixture: &'static str,
+    pub scope: FixtureScope,
+    /// Name of the test or path of the module the fixture was set up for.
+    pub owner: &'static str,
+    pub message: String,
+}
+
+impl fmt::Display for TeardownFailure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.scope {
+            FixtureScope::Test => write!(f, "fixture `{}` of test `{}`", self.fixture, self.owner),
+            FixtureScope::Module if self.owner.is_empty() => {
+                write!(f, "fixture `{}` of the root module", self.fixture)
+            }
+            FixtureScope::Module => {
+                write!(f, "fixture `{}` of module `{}`", self.fixture, self.owner)
+            }
+        }
+    }
+}
+
+/// A module-scoped fixture which has been set up.
+struct ModuleFixture {
+    module: &'static str,
+    type_id: TypeId,
+    value: Arc<dyn Any + Send + Sync>,
+    teardown: fn(Arc<dyn Any + Send + Sync>) -> Result<(), String>,
+    failure: fn(&'static str, String) -> TeardownFailure,
+}
+
+static MODULE_FIXTURES: Mutex<Vec<ModuleFixture>> = Mutex::new(Vec::new());
+static TEARDOWN_FAILURES: Mutex<Vec<TeardownFailure>> = Mutex::new(Vec::new());
+
+/// Access to a fixture for the duration of a test.
+#[doc(hidden)]
+pub struct FixtureGuard<T: Fixture>(Instance<T>);
+
+enum Instance<T> {
+    Test { value: Option<T>, test: &'static str },
+    Module(Arc<T>),
+}
+
+impl<T: Fixture> Deref for FixtureGuard<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        match self.0 {
+            Instance::Test { ref value, .. } => value.as_ref().unwrap(),
+            Instance::Module(ref value) => value,
+        }
+    }
+}
+
+impl<T: Fixture> Drop for FixtureGuard<T> {
+    fn drop(&mut self) {
+        if let Instance::Test { ref mut value, test } = self.0 {
+            let value = value.take().unwrap();
+            // A panic while the test is already panicking aborts the process, so
+            // there is nothing to catch in that case.
+            let result = if thread::panicking() {
+                value.teardown()
+            } else {
+                catch_unwind(AssertUnwindSafe(|| value.teardown()))
+                    .unwrap_or_else(|payload| Err(panic_message(&*payload)))
+            };
+            if let Err(message) = result {
+                record_failure(teardown_failure::<T>(FixtureScope::Test, test, message));
+            }
+        }
+    }
+}
+
+/// Sets up the fixture `T` for the test `test` of module `module`, or returns the
+/// instance shared by the module. Called by the code generated for `#[test]`.
+#[doc(hidden)]
+pub fn acquire<T: Fixture>(module: &'static str, test: &'static str) -> FixtureGuard<T> {
+    match T::SCOPE {
+        FixtureScope::Test => FixtureGuard(Instance::Test { value: Some(T::setup()), test }),
+        FixtureScope::Module => {
+            // The lock is held during the setup, so that concurrent tests of the
+            // module don't set up the fixture twice.
+            let mut fixtures = MODULE_FIXTURES.lock().unwrap_or_else(PoisonError::into_inner);
+            let type_id = TypeId::of::<T>();
+            let value = match fixtures.iter().find(|f| f.module == module && f.type_id == type_id) {
+                Some(fixture) => fixture.value.clone(),
+                None => {
+                    let value: Arc<dyn Any + Send + Sync> = Arc::new(T::setup());
+                    fixtures.push(ModuleFixture {
+                        module,
+                        type_id,
+                        value: value.clone(),
+                        teardown: teardown_shared::<T>,
+                        failure: |module, message| {
+                            teardown_failure::<T>(FixtureScope::Module, module, message)
+                        },
+                    });
+                    value
+                }
+            };
+            FixtureGuard(Instance::Module(value.downcast().unwrap()))
+        }
+    }
+}
+
+fn teardown_shared<T: Fixture>(value: Arc<dyn Any + Send + Sync>) -> Result<(), String> {
+    match Arc::try_unwrap(value.downcast::<T>().unwrap()) {
+        Ok(value) => catch_unwind(AssertUnwindSafe(|| value.teardown()))
+            .unwrap_or_else(|payload| Err(panic_message(&*payload))),
+        Err(_) => Err("the fixture is still in use by a test which has not finished".to_string()),
+    }
+}
+
+/// Returns the path of the module of a test, which is the module whose fixtures
+/// the test shares.
+///
+/// The cases of a `#[test_params]` test are named `path::to::test::case_N`, and
+/// belong to the module of `path::to::test`.
+pub(crate) fn test_module(name: &str) -> &str {
+    let name = match name.rsplit_once("::case_") {
+        Some((test, n)) if !n.is_empty() && n.bytes().all(|b| b.is_ascii_digit()) => test,
+        _ => name,
+    };
+    name.rsplit_once("::").map_or("", |(module, _)| module)
+}
+
+/// Tears down the fixtures shared by the tests of `module`.
+pub(crate) fn teardown_module(module: &str) {
+    teardown_module_fixtures(|f| f.module == module)
+}
+
+/// Tears down the fixtures shared by the tests of every module.
+pub(crate) fn teardown_all() {
+    teardown_module_fixtures(|_| true)
+}
+
+fn teardown_module_fixtures(mut predicate: impl FnMut(&ModuleFixture) -> bool) {
+    let fixtures = {
+        let mut fixtures = MODULE_FIXTURES.lock().unwrap_or_else(PoisonError::into_inner);
+        let (torn_down, kept): (Vec<_>, Vec<_>) = fixtures.drain(..).partition(|f| predicate(f));
+        *fixtures = kept;
+        torn_down
+    };
+    // Tear the fixtures down in the reverse order of their setup.
+    for fixture in fixtures.into_iter().rev() {
+        let ModuleFixture { module, value, teardown, failure, .. } = fixture;
+        if let Err(message) = teardown(value) {
+            record_failure(failure(module, message));
+        }
+    }
+}
+
+fn teardown_failure<T>(
+    scope: FixtureScope,
+    owner: &'static str,
+    message: String,
+) -> TeardownFailure {
+    TeardownFailure { fixture: any::type_name::<T>(), scope, owner, message }
+}
+
+fn record_failure(failure: TeardownFailure) {
+    TEARDOWN_FAILURES.lock().unwrap_or_else(PoisonError::into_inner).push(failure);
+}
+
+/// Returns the teardown failures which have occurred since the last call.
+pub(crate) fn take_teardown_failures() -> Vec<TeardownFailure> {
+    std::mem::take(&mut *TEARDOWN_FAILURES.lock().unwrap_or_else(PoisonError::into_inner))
+}
+
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    let message = payload
+        .downcast_ref::<String>()
+        .map(|s| s.as_str())
+        .or_else(|| payload.downcast_ref::<&str>().copied());
+    match message {
+        Some(message) => format!("teardown panicked: {message}"),
+        None => "teardown panicked".to_string(),
+    }
+}
@@ -2,7 +2,10 @@
 
 use super::OutputFormatter;
 use crate::{
+    baseline::BenchComparison,
+    bench::BenchSamples,
     console::{ConsoleTestDiscoveryState, ConsoleTestState, OutputLocation},
+    fixture::{FixtureScope, TeardownFailure},
     test_result::TestResult,
     time,
     types::TestDesc,
@@ -133,6 +136,15 @@ fn write_result(
                 self.write_event("test", desc.name.as_slice(), "ok", exec_time, stdout, None)
             }
 
+            TestResult::TrFlaky(failed_attempts) => self.write_event(
+                "test",
+                desc.name.as_slice(),
+                "flaky",
+                exec_time,
+                stdout,
+                Some(&*format!(r#""failed_attempts": {failed_attempts}"#)),
+            ),
+
             TestResult::TrFailed => {
                 self.write_event("test", desc.name.as_slice(), "failed", exec_time, stdout, None)
             }
@@ -146,6 +158,15 @@ fn write_result(
                 Some(r#""reason": "time limit exceeded""#),
             ),
 
+            TestResult::TrTimedOut => self.write_event(
+                "test",
+                desc.name.as_slice(),
+                "failed",
+                exec_time,
+                stdout,
+                Some(r#""reason": "timed out""#),
+            ),
+
             TestResult::TrFailedMsg(ref m) => self.write_event(
                 "test",
                 desc.name.as_slice(),
@@ -176,12 +197,14 @@ fn write_result(
                     format!(r#", "mib_per_second": {}"#, bs.mb_s)
                 };
                 let name = EscapedString(desc.name.as_slice());
+                let summary = JsonSummary(bs);
 
                 self.writeln_message(&format!(
                     "{{ \"type\": \"bench\", \
                      \"name\": \"{name}\", \
                      \"median\": {median}, \
-                     \"deviation\": {deviation}{mbps} }}\n",
+                     \"deviation\": {deviation}{mbps}, \
+                     \"summary\": {summary} }}\n",
                 ))
             }
         }
@@ -195,10 +218,58 @@ fn write_timeout(&mut self, desc: &TestDesc) -> io::Result<()> {
         ))
     }
 
+    fn write_teardown_failure(&mut self, failure: &TeardownFailure) -> io::Result<()> {
+        let fixture = EscapedString(failure.fixture);
+        let scope = match failure.scope {
+            FixtureScope::Test => "test",
+            FixtureScope::Module => "module",
+        };
+        let owner = EscapedString(failure.owner);
+        let message = EscapedString(&failure.message);
+        self.writeln_message(&format!(
+            "{{ \"type\": \"fixture\", \"event\": \"teardown_failed\", \"name\": \"{fixture}\", \
+             \"scope\": \"{scope}\", \"owner\": \"{owner}\", \"message\": \"{message}\" }}\n",
+        ))
+    }
+
+    fn write_baseline_comparison(
+        &mut self,
+        baseline: &str,
+        comparisons: &[BenchComparison],
+    ) -> io::Result<()> {
+        let baseline = EscapedString(baseline);
+        for comparison in comparisons {
+            let name = EscapedString(&comparison.name);
+            let old_mean = JsonFloat(comparison.old.mean);
+            let new_mean = JsonFloat(comparison.new.mean);
+            let change_pct = JsonFloat(comparison.mean_change_pct());
+            let p_value = JsonFloat(comparison.p_value);
+            let change = comparison.change().as_str();
+            self.writeln_message(&format!(
+                "{{ \"type\": \"bench_comparison\", \
+                 \"name\": \"{name}\", \
+                 \"baseline\": \"{baseline}\", \
+                 \"old_mean\": {old_mean}, \
+                 \"new_mean\": {new_mean}, \
+                 \"change_pct\": {change_pct}, \
+                 \"p_value\": {p_value}, \
+                 \"change\": \"{change}\" }}\n",
+            ))?;
+        }
+        Ok(())
+    }
+
     fn write_run_finish(&mut self, state: &ConsoleTestState) -> io::Result<bool> {
-        let event = if state.failed == 0 { "ok" } else { "failed" };
+        let event = if state.is_success() { "ok" } else { "failed" };
         let passed = state.passed;
+        let flaky_json =
+            if state.flaky > 0 { format!(r#", "flaky": {}"#, state.flaky) } else { String::new() };
         let failed = state.failed;
+        let teardown_failed_json = if state.teardown_failures.is_empty() {
+            String::new()
+        } else {
+            format!(r#", "teardown_failed": {}"#, state.teardown_failures.len())
+        };
         let ignored = state.ignored;
         let measured = state.measured;
         let filtered_out = state.filtered_out;
@@ -210,10 +281,46 @@ fn write_run_finish(&mut self, state: &ConsoleTestState) -> io::Result<bool> {
         let newline = "\n";
 
         self.writeln_message(&format!(
-            r#"{{ "type": "suite", "event": "{event}", "passed": {passed}, "failed": {failed}, "ignored": {ignored}, "measured": {measured}, "filtered_out": {filtered_out}{exec_time_json} }}{newline}"#
+            r#"{{ "type": "suite", "event": "{event}", "passed": {passed}{flaky_json}, "failed": {failed}{teardown_failed_json}, "ignored": {ignored}, "measured": {measured}, "filtered_out": {filtered_out}{exec_time_json} }}{newline}"#
         ))?;
 
-        Ok(state.failed == 0)
+        Ok(state.is_success())
+    }
+}
+
+/// A formatting utility used to print floats as JSON numbers, or as `null` if they
+/// aren't finite.
+struct JsonFloat(f64);
+
+impl std::fmt::Display for JsonFloat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.0.is_finite() { write!(f, "{}", self.0) } else { f.write_str("null") }
+    }
+}
+
+/// A formatting utility used to print the statistics of a benchmark as a JSON object.
+struct JsonSummary<'a>(&'a BenchSamples);
+
+impl std::fmt::Display for JsonSummary<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = &self.0.ns_iter_summ;
+        let (q1, q2, q3) = s.quartiles;
+        write!(
+            f,
+            "{{ \"samples\": {}, \"min\": {}, \"max\": {}, \"mean\": {}, \"median\": {}, \
+             \"std_dev\": {}, \"median_abs_dev\": {}, \"quartiles\": [{}, {}, {}], \"iqr\": {} }}",
+            self.0.samples,
+            JsonFloat(s.min),
+            JsonFloat(s.max),
+            JsonFloat(s.mean),
+            JsonFloat(s.median),
+            JsonFloat(s.std_dev),
+            JsonFloat(s.median_abs_dev),
+            JsonFloat(q1),
+            JsonFloat(q2),
+            JsonFloat(q3),
+            JsonFloat(s.iqr),
+        )
     }
 }
 
@@ -3,7 +3,9 @@
 
 use super::OutputFormatter;
 use crate::{
+    baseline::BenchComparison,
     console::{ConsoleTestDiscoveryState, ConsoleTestState, OutputLocation},
+    fixture::TeardownFailure,
     test_result::TestResult,
     time,
     types::{TestDesc, TestType},
@@ -85,17 +87,34 @@ fn write_result(
         self.results.push((desc.clone(), result.clone(), duration, stdout.to_vec()));
         Ok(())
     }
+    fn write_teardown_failure(&mut self, _failure: &TeardownFailure) -> io::Result<()> {
+        // Teardown failures are written as errors of the test suite at the end.
+        Ok(())
+    }
+
+    fn write_baseline_comparison(
+        &mut self,
+        _baseline: &str,
+        _comparisons: &[BenchComparison],
+    ) -> io::Result<()> {
+        // JUnit documents have no place for benchmark comparisons.
+        Ok(())
+    }
+
     fn write_run_finish(&mut self, state: &ConsoleTestState) -> io::Result<bool> {
         self.write_message("<testsuites>")?;
 
         self.write_message(&format!(
             "<testsuite name=\"test\" package=\"test\" id=\"0\" \
-             errors=\"0\" \
+             errors=\"{}\" \
              failures=\"{}\" \
              tests=\"{}\" \
              skipped=\"{}\" \
              >",
-            state.failed, state.total, state.ignored
+            state.teardown_failures.len(),
+            state.failed,
+            state.total,
+            state.ignored
         ))?;
         for (desc, result, duration, stdout) in std::mem::take(&mut self.results) {
             let (class_name, test_name) = parse_class_name(&desc);
@@ -147,6 +166,42 @@ fn write_run_finish(&mut self, state: &ConsoleTestState) -> io::Result<bool> {
                     self.write_message("</testcase>")?;
                 }
 
+                TestResult::TrTimedOut => {
+                    self.write_message(&format!(
+                        "<testcase classname=\"{}\" \
+                         name=\"{}\" time=\"{}\">",
+                        class_name,
+                        test_name,
+                        duration.as_secs_f64()
+                    ))?;
+                    self.write_message("<failure message=\"timed out\" type=\"timeout\"/>")?;
+                    if !stdout.is_empty() {
+                        self.write_message("<system-out>")?;
+                        self.write_message(&str_to_cdata(&String::from_utf8_lossy(&stdout)))?;
+                        self.write_message("</system-out>")?;
+                    }
+                    self.write_message("</testcase>")?;
+                }
+
+                TestResult::TrFlaky(failed_attempts) => {
+                    self.write_message(&format!(
+                        "<testcase classname=\"{}\" \
+                         name=\"{}\" time=\"{}\">",
+                        class_name,
+                        test_name,
+                        duration.as_secs_f64()
+                    ))?;
+                    for _ in 0..failed_attempts {
+                        self.write_message("<flakyFailure type=\"assert\"/>")?;
+                    }
+                    if !stdout.is_empty() {
+                        self.write_message("<system-out>")?;
+                        self.write_message(&str_to_cdata(&String::from_utf8_lossy(&stdout)))?;
+                        self.write_message("</system-out>")?;
+                    }
+                    self.write_message("</testcase>")?;
+                }
+
                 TestResult::TrBench(ref b) => {
                     self.write_message(&format!(
                         "<testcase classname=\"benchmark::{}\" \
@@ -174,6 +229,21 @@ fn write_run_finish(&mut self, state: &ConsoleTestState) -> io::Result<bool> {
                 }
             }
         }
+        for failure in &state.teardown_failures {
+            // Fixtures of the root module have an empty owner.
+            let class_name = if failure.owner.is_empty() { "crate" } else { failure.owner };
+            // The type name of the fixture may contain `<`, `>` and `&`.
+            let fixture =
+                failure.fixture.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;");
+            self.write_message(&format!(
+                "<testcase classname=\"{}\" name=\"teardown of {}\" time=\"0\">",
+                class_name, fixture
+            ))?;
+            self.write_message("<error message=\"teardown failed\" type=\"teardown\">")?;
+            self.write_message(&str_to_cdata(&failure.message))?;
+            self.write_message("</error>")?;
+            self.write_message("</testcase>")?;
+        }
         self.write_message("<system-out/>")?;
         self.write_message("<system-err/>")?;
         self.write_message("</testsuite>")?;
@@ -181,7 +251,7 @@ fn write_run_finish(&mut self, state: &ConsoleTestState) -> io::Result<bool> {
 
         self.out.write_all(b"\n")?;
 
-        Ok(state.failed == 0)
+        Ok(state.is_success())
     }
 }
 
@@ -1,7 +1,10 @@
 use std::{io, io::prelude::Write};
 
 use crate::{
+    baseline::{BenchChange, BenchComparison},
+    bench::fmt_thousands_sep,
     console::{ConsoleTestDiscoveryState, ConsoleTestState},
+    fixture::TeardownFailure,
     test_result::TestResult,
     time,
     types::{TestDesc, TestName},
@@ -10,11 +13,13 @@
 mod json;
 mod junit;
 mod pretty;
+mod tap;
 mod terse;
 
 pub(crate) use self::json::JsonFormatter;
 pub(crate) use self::junit::JunitFormatter;
 pub(crate) use self::pretty::PrettyFormatter;
+pub(crate) use self::tap::TapFormatter;
 pub(crate) use self::terse::TerseFormatter;
 
 pub(crate) trait OutputFormatter {
@@ -33,9 +38,46 @@ fn write_result(
         stdout: &[u8],
         state: &ConsoleTestState,
     ) -> io::Result<()>;
+    fn write_teardown_failure(&mut self, failure: &TeardownFailure) -> io::Result<()>;
+    fn write_baseline_comparison(
+        &mut self,
+        baseline: &str,
+        comparisons: &[BenchComparison],
+    ) -> io::Result<()>;
     fn write_run_finish(&mut self, state: &ConsoleTestState) -> io::Result<bool>;
 }
 
+/// Formats the report of a comparison against a baseline as lines of text.
+pub(crate) fn fmt_baseline_comparison(
+    baseline: &str,
+    comparisons: &[BenchComparison],
+) -> Vec<String> {
+    let mut lines = vec![format!("benchmarks compared with baseline `{baseline}`:")];
+    let (mut regressed, mut improved, mut unchanged) = (0, 0, 0);
+    for comparison in comparisons {
+        let change = comparison.change();
+        match change {
+            BenchChange::Regressed => regressed += 1,
+            BenchChange::Improved => improved += 1,
+            BenchChange::Unchanged => unchanged += 1,
+        }
+        lines.push(format!(
+            "    {}: mean {} -> {} ns/iter ({:+.2}%, p = {:.3}) {}",
+            comparison.name,
+            fmt_thousands_sep(comparison.old.mean as usize, ','),
+            fmt_thousands_sep(comparison.new.mean as usize, ','),
+            comparison.mean_change_pct(),
+            comparison.p_value,
+            change.as_str(),
+        ));
+    }
+    lines.push(format!(
+        "{} compared; {regressed} regressed; {improved} improved; {unchanged} unchanged",
+        comparisons.len()
+    ));
+    lines
+}
+
 pub(crate) fn write_stderr_delimiter(test_output: &mut Vec<u8>, test_name: &TestName) {
     match test_output.last() {
         Some(b'\n') => (),
@@ -1,9 +1,11 @@
 use std::{io, io::prelude::Write};
 
-use super::OutputFormatter;
+use super::{fmt_baseline_comparison, OutputFormatter};
 use crate::{
+    baseline::BenchComparison,
     bench::fmt_bench_samples,
     console::{ConsoleTestDiscoveryState, ConsoleTestState, OutputLocation},
+    fixture::TeardownFailure,
     term,
     test_result::TestResult,
     time,
@@ -41,6 +43,10 @@ pub fn write_ok(&mut self) -> io::Result<()> {
         self.write_short_result("ok", term::color::GREEN)
     }
 
+    pub fn write_flaky(&mut self) -> io::Result<()> {
+        self.write_short_result("ok (flaky)", term::color::YELLOW)
+    }
+
     pub fn write_failed(&mut self) -> io::Result<()> {
         self.write_short_result("FAILED", term::color::RED)
     }
@@ -57,6 +63,10 @@ pub fn write_time_failed(&mut self) -> io::Result<()> {
         self.write_short_result("FAILED (time limit exceeded)", term::color::RED)
     }
 
+    pub fn write_timed_out(&mut self) -> io::Result<()> {
+        self.write_short_result("FAILED (timed out)", term::color::RED)
+    }
+
     pub fn write_bench(&mut self) -> io::Result<()> {
         self.write_pretty("bench", term::color::CYAN)
     }
@@ -160,6 +170,10 @@ pub fn write_successes(&mut self, state: &ConsoleTestState) -> io::Result<()> {
         self.write_results(&state.not_failures, "successes")
     }
 
+    pub fn write_flakes(&mut self, state: &ConsoleTestState) -> io::Result<()> {
+        self.write_results(&state.flakes, "flaky tests")
+    }
+
     pub fn write_failures(&mut self, state: &ConsoleTestState) -> io::Result<()> {
         self.write_results(&state.failures, "failures")
     }
@@ -168,6 +182,14 @@ pub fn write_time_failures(&mut self, state: &ConsoleTestState) -> io::Result<()
         self.write_results(&state.time_failures, "failures (time limit exceeded)")
     }
 
+    pub fn write_teardown_failures(&mut self, state: &ConsoleTestState) -> io::Result<()> {
+        self.write_plain("\nteardown failures:\n")?;
+        for failure in &state.teardown_failures {
+            self.write_plain(format!("    {failure}: {}\n", failure.message))?;
+        }
+        Ok(())
+    }
+
     fn write_test_name(&mut self, desc: &TestDesc) -> io::Result<()> {
         let name = desc.padded_name(self.max_name_len, desc.name.padding());
         if let Some(test_mode) = desc.test_mode() {
@@ -244,6 +266,7 @@ fn write_result(
 
         match *result {
             TestResult::TrOk => self.write_ok()?,
+            TestResult::TrFlaky(_) => self.write_flaky()?,
             TestResult::TrFailed | TestResult::TrFailedMsg(_) => self.write_failed()?,
             TestResult::TrIgnored => self.write_ignored(desc.ignore_message)?,
             TestResult::TrBench(ref bs) => {
@@ -251,6 +274,7 @@ fn write_result(
                 self.write_plain(format!(": {}", fmt_bench_samples(bs)))?;
             }
             TestResult::TrTimedFail => self.write_time_failed()?,
+            TestResult::TrTimedOut => self.write_timed_out()?,
         }
 
         self.write_time(desc, exec_time)?;
@@ -265,11 +289,32 @@ fn write_timeout(&mut self, desc: &TestDesc) -> io::Result<()> {
         ))
     }
 
+    fn write_teardown_failure(&mut self, failure: &TeardownFailure) -> io::Result<()> {
+        self.write_plain(format!("teardown of {failure} ... "))?;
+        self.write_failed()?;
+        self.write_plain("\n")
+    }
+
+    fn write_baseline_comparison(
+        &mut self,
+        baseline: &str,
+        comparisons: &[BenchComparison],
+    ) -> io::Result<()> {
+        self.write_plain("\n")?;
+        for line in fmt_baseline_comparison(baseline, comparisons) {
+            self.write_plain(format!("{line}\n"))?;
+        }
+        Ok(())
+    }
+
     fn write_run_finish(&mut self, state: &ConsoleTestState) -> io::Result<bool> {
         if state.options.display_output {
             self.write_successes(state)?;
         }
-        let success = state.failed == 0;
+        if !state.flakes.is_empty() {
+            self.write_flakes(state)?;
+        }
+        let success = state.is_success();
         if !success {
             if !state.failures.is_empty() {
                 self.write_failures(state)?;
@@ -278,6 +323,10 @@ fn write_run_finish(&mut self, state: &ConsoleTestState) -> io::Result<bool> {
             if !state.time_failures.is_empty() {
                 self.write_time_failures(state)?;
             }
+
+            if !state.teardown_failures.is_empty() {
+                self.write_teardown_failures(state)?;
+            }
         }
 
         self.write_plain("\ntest result: ")?;
@@ -289,13 +338,19 @@ fn write_run_finish(&mut self, state: &ConsoleTestState) -> io::Result<bool> {
             self.write_pretty("FAILED", term::color::RED)?;
         }
 
+        let flaky =
+            if state.flaky > 0 { format!("{} flaky; ", state.flaky) } else { String::new() };
         let s = format!(
-            ". {} passed; {} failed; {} ignored; {} measured; {} filtered out",
-            state.passed, state.failed, state.ignored, state.measured, state.filtered_out
+            ". {} passed; {}{} failed; {} ignored; {} measured; {} filtered out",
+            state.passed, flaky, state.failed, state.ignored, state.measured, state.filtered_out
         );
 
         self.write_plain(s)?;
 
+        if !state.teardown_failures.is_empty() {
+            self.write_plain(format!("; {} teardown failed", state.teardown_failures.len()))?;
+        }
+
         if let Some(ref exec_time) = state.exec_time {
             let time_str = format!("; finished in {exec_time}");
             self.write_plain(time_str)?;
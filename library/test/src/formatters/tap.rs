@@ -0,0 +1,272 @@
+use std::{io, io::prelude::Write};
+
+use super::{fmt_baseline_comparison, OutputFormatter};
+use crate::{
+    baseline::BenchComparison,
+    console::{ConsoleTestDiscoveryState, ConsoleTestState, OutputLocation},
+    fixture::TeardownFailure,
+    test_result::TestResult,
+    time,
+    types::TestDesc,
+};
+
+#[cfg(test)]
+mod tests;
+
+/// Formatter producing a TAP version 14 stream, with YAML diagnostics
+/// attached to the test points.
+pub(crate) struct TapFormatter<T> {
+    out: OutputLocation<T>,
+    /// Number of the last test point written.
+    test_number: usize,
+}
+
+impl<T: Write> TapFormatter<T> {
+    pub fn new(out: OutputLocation<T>) -> Self {
+        Self { out, test_number: 0 }
+    }
+
+    fn write_lines(&mut self, s: &str) -> io::Result<()> {
+        // Like the JSON formatter, issue a single `write_all` call per message so that
+        // a test point and its diagnostics are not interleaved with other output.
+        assert_eq!(s.chars().last(), Some('\n'));
+
+        self.out.write_all(s.as_ref())
+    }
+
+    fn write_test_point(
+        &mut self,
+        ok: bool,
+        desc: &TestDesc,
+        directive: Option<&str>,
+        diagnostics: &[(&str, String)],
+    ) -> io::Result<()> {
+        self.test_number += 1;
+        let status = if ok { "ok" } else { "not ok" };
+        let name = EscapedDescription(desc.name.as_slice());
+        let mut s = format!("{status} {} - {name}", self.test_number);
+        if let Some(directive) = directive {
+            s.push_str(&format!(" # {directive}"));
+        }
+        s.push('\n');
+
+        if !diagnostics.is_empty() {
+            s.push_str("  ---\n");
+            for (key, value) in diagnostics {
+                s.push_str(&format!("  {key}: {value}\n"));
+            }
+            s.push_str("  ...\n");
+        }
+
+        self.write_lines(&s)
+    }
+}
+
+impl<T: Write> OutputFormatter for TapFormatter<T> {
+    fn write_discovery_start(&mut self) -> io::Result<()> {
+        Err(io::Error::new(io::ErrorKind::NotFound, "Not yet implemented!"))
+    }
+
+    fn write_test_discovered(&mut self, _desc: &TestDesc, _test_type: &str) -> io::Result<()> {
+        Err(io::Error::new(io::ErrorKind::NotFound, "Not yet implemented!"))
+    }
+
+    fn write_discovery_finish(&mut self, _state: &ConsoleTestDiscoveryState) -> io::Result<()> {
+        Err(io::Error::new(io::ErrorKind::NotFound, "Not yet implemented!"))
+    }
+
+    fn write_run_start(&mut self, _test_count: usize, shuffle_seed: Option<u64>) -> io::Result<()> {
+        // The plan is written at the end, since a run stopping early with `--fail-fast`
+        // only runs some of the tests.
+        let mut s = String::from("TAP version 14\n");
+        if let Some(shuffle_seed) = shuffle_seed {
+            s.push_str(&format!("# shuffle seed: {shuffle_seed}\n"));
+        }
+        self.write_lines(&s)
+    }
+
+    fn write_test_start(&mut self, _desc: &TestDesc) -> io::Result<()> {
+        // Test points are only written once the result is known.
+        Ok(())
+    }
+
+    fn write_timeout(&mut self, desc: &TestDesc) -> io::Result<()> {
+        self.write_lines(&format!(
+            "# test {} has been running for over {} seconds\n",
+            desc.name,
+            time::TEST_WARN_TIMEOUT_S
+        ))
+    }
+
+    fn write_result(
+        &mut self,
+        desc: &TestDesc,
+        result: &TestResult,
+        exec_time: Option<&time::TestExecTime>,
+        stdout: &[u8],
+        state: &ConsoleTestState,
+    ) -> io::Result<()> {
+        let mut diagnostics = Vec::new();
+        match *result {
+            TestResult::TrFailedMsg(ref msg) => {
+                diagnostics.push(("message", yaml_quoted(msg)));
+            }
+            TestResult::TrTimedFail => {
+                diagnostics.push(("message", yaml_quoted("time limit exceeded")));
+            }
+            TestResult::TrTimedOut => {
+                diagnostics.push(("message", yaml_quoted("timed out")));
+            }
+            TestResult::TrFlaky(failed_attempts) => {
+                diagnostics.push(("failed_attempts", failed_attempts.to_string()));
+            }
+            TestResult::TrBench(ref bs) => {
+                let median = bs.ns_iter_summ.median as usize;
+                let deviation = (bs.ns_iter_summ.max - bs.ns_iter_summ.min) as usize;
+                diagnostics.push(("median", median.to_string()));
+                diagnostics.push(("deviation", deviation.to_string()));
+                if bs.mb_s != 0 {
+                    diagnostics.push(("mib_per_second", bs.mb_s.to_string()));
+                }
+            }
+            TestResult::TrOk | TestResult::TrFailed | TestResult::TrIgnored => {}
+        }
+
+        let ok = match *result {
+            TestResult::TrOk
+            | TestResult::TrFlaky(_)
+            | TestResult::TrIgnored
+            | TestResult::TrBench(_) => true,
+            TestResult::TrFailed
+            | TestResult::TrFailedMsg(_)
+            | TestResult::TrTimedFail
+            | TestResult::TrTimedOut => false,
+        };
+        if !ok {
+            diagnostics.push(("severity", "fail".to_string()));
+        }
+
+        if let Some(exec_time) = exec_time {
+            diagnostics.push(("exec_time", exec_time.0.as_secs_f64().to_string()));
+        }
+
+        let display_stdout = state.options.display_output || !ok;
+        if display_stdout && !stdout.is_empty() {
+            diagnostics.push(("stdout", yaml_block(&String::from_utf8_lossy(stdout))));
+        }
+
+        let directive = match *result {
+            TestResult::TrIgnored => Some(match desc.ignore_message {
+                Some(msg) => format!("SKIP {}", EscapedDescription(msg)),
+                None => "SKIP".to_string(),
+            }),
+            _ => None,
+        };
+
+        self.write_test_point(ok, desc, directive.as_deref(), &diagnostics)
+    }
+
+    fn write_teardown_failure(&mut self, failure: &TeardownFailure) -> io::Result<()> {
+        // Teardowns aren't test points, so their failures are reported as comments.
+        let message = failure.message.replace('\n', " ");
+        self.write_lines(&format!("# teardown of {failure} failed: {message}\n"))
+    }
+
+    fn write_baseline_comparison(
+        &mut self,
+        baseline: &str,
+        comparisons: &[BenchComparison],
+    ) -> io::Result<()> {
+        let mut s = String::new();
+        for line in fmt_baseline_comparison(baseline, comparisons) {
+            s.push_str(&format!("# {line}\n"));
+        }
+        self.write_lines(&s)
+    }
+
+    fn write_run_finish(&mut self, state: &ConsoleTestState) -> io::Result<bool> {
+        let success = state.is_success();
+        let flaky =
+            if state.flaky > 0 { format!("{} flaky; ", state.flaky) } else { String::new() };
+        let teardown_failed = if state.teardown_failures.is_empty() {
+            String::new()
+        } else {
+            format!("; {} teardown failed", state.teardown_failures.len())
+        };
+        let exec_time = if let Some(ref exec_time) = state.exec_time {
+            format!("; finished in {exec_time}")
+        } else {
+            String::new()
+        };
+
+        self.write_lines(&format!(
+            "1..{}\n# test result: {}. {} passed; {}{} failed; {} ignored; {} measured; {} filtered out{}{}\n",
+            self.test_number,
+            if success { "ok" } else { "FAILED" },
+            state.passed,
+            flaky,
+            state.failed,
+            state.ignored,
+            state.measured,
+            state.filtered_out,
+            teardown_failed,
+            exec_time,
+        ))?;
+
+        Ok(success)
+    }
+}
+
+/// Formats a string as a double-quoted YAML scalar.
+fn yaml_quoted(s: &str) -> String {
+    let mut quoted = String::with_capacity(s.len() + 2);
+    quoted.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            c if c.is_control() => quoted.push_str(&format!("\\x{:02x}", c as u32)),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+/// Formats a multi-line string as a literal YAML block scalar, indented to be
+/// the value of a key in a diagnostic block.
+fn yaml_block(s: &str) -> String {
+    // The indentation indicator allows the first line to start with spaces, and
+    // the chomping indicator keeps the final newline only if there is one.
+    let mut block = String::from(if s.ends_with('\n') { "|2" } else { "|2-" });
+    for line in s.lines() {
+        block.push('\n');
+        if !line.is_empty() {
+            block.push_str("    ");
+            // Control characters other than tabs aren't allowed in YAML documents.
+            block.extend(line.chars().map(|c| if c.is_control() && c != '\t' { '?' } else { c }));
+        }
+    }
+    block
+}
+
+/// A formatting utility escaping the characters with a special meaning in the
+/// description of a TAP test point.
+struct EscapedDescription<S: AsRef<str>>(S);
+
+impl<S: AsRef<str>> std::fmt::Display for EscapedDescription<S> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for c in self.0.as_ref().chars() {
+            match c {
+                '\\' => f.write_str("\\\\")?,
+                '#' => f.write_str("\\#")?,
+                '\n' => f.write_str(" ")?,
+                c => write!(f, "{c}")?,
+            }
+        }
+        Ok(())
+    }
+}
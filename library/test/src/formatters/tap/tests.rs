@@ -0,0 +1,33 @@
+use super::*;
+
+#[test]
+fn yaml_quoted_escapes() {
+    assert_eq!(yaml_quoted("plain"), r#""plain""#);
+    assert_eq!(yaml_quoted(r#"say "hi""#), r#""say \"hi\"""#);
+    assert_eq!(yaml_quoted(r"C:\dir"), r#""C:\\dir""#);
+    assert_eq!(yaml_quoted("one\ntwo\r\n"), r#""one\ntwo\r\n""#);
+    assert_eq!(yaml_quoted("a\tb\x07"), r#""a\tb\x07""#);
+    assert_eq!(yaml_quoted("  leading"), r#""  leading""#);
+}
+
+#[test]
+fn yaml_block_indents_lines() {
+    assert_eq!(yaml_block("one\ntwo\n"), "|2\n    one\n    two");
+    assert_eq!(yaml_block("one\ntwo"), "|2-\n    one\n    two");
+    assert_eq!(yaml_block("one\n\ntwo\n"), "|2\n    one\n\n    two");
+}
+
+#[test]
+fn yaml_block_keeps_leading_spaces() {
+    assert_eq!(yaml_block("  indented\nnot\n"), "|2\n      indented\n    not");
+}
+
+#[test]
+fn yaml_block_leaves_quotes_and_backslashes() {
+    assert_eq!(yaml_block("\"quoted\" \\ text\n"), "|2\n    \"quoted\" \\ text");
+}
+
+#[test]
+fn yaml_block_replaces_control_characters() {
+    assert_eq!(yaml_block("bell\x07\ttab\n"), "|2\n    bell?\ttab");
+}
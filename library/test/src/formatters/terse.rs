@@ -1,9 +1,11 @@
 use std::{io, io::prelude::Write};
 
-use super::OutputFormatter;
+use super::{fmt_baseline_comparison, OutputFormatter};
 use crate::{
+    baseline::BenchComparison,
     bench::fmt_bench_samples,
     console::{ConsoleTestDiscoveryState, ConsoleTestState, OutputLocation},
+    fixture::TeardownFailure,
     term,
     test_result::TestResult,
     time,
@@ -50,8 +52,21 @@ pub fn write_ok(&mut self) -> io::Result<()> {
     }
 
     pub fn write_failed(&mut self, name: &str) -> io::Result<()> {
-        // Put failed tests on their own line and include the test name, so that it's faster
-        // to see which test failed without having to wait for them all to run.
+        self.write_named_result(name, "FAILED", term::color::RED)
+    }
+
+    pub fn write_flaky(&mut self, name: &str) -> io::Result<()> {
+        self.write_named_result(name, "flaky", term::color::YELLOW)
+    }
+
+    fn write_named_result(
+        &mut self,
+        name: &str,
+        result: &str,
+        color: term::color::Color,
+    ) -> io::Result<()> {
+        // Put failed and flaky tests on their own line and include the test name, so that it's
+        // faster to see which test failed without having to wait for them all to run.
 
         // normally, we write the progress unconditionally, even if the previous line was cut short.
         // but if this is the very first column, no short results will have been printed and we'll end up with *only* the progress on the line.
@@ -61,7 +76,7 @@ pub fn write_failed(&mut self, name: &str) -> io::Result<()> {
         }
         self.test_count += 1;
         self.write_plain(format!("{name} --- "))?;
-        self.write_pretty("FAILED", term::color::RED)?;
+        self.write_pretty(result, color)?;
         self.write_plain("\n")
     }
 
@@ -175,6 +190,14 @@ pub fn write_failures(&mut self, state: &ConsoleTestState) -> io::Result<()> {
         Ok(())
     }
 
+    pub fn write_teardown_failures(&mut self, state: &ConsoleTestState) -> io::Result<()> {
+        self.write_plain("\nteardown failures:\n")?;
+        for failure in &state.teardown_failures {
+            self.write_plain(format!("    {failure}: {}\n", failure.message))?;
+        }
+        Ok(())
+    }
+
     fn write_test_name(&mut self, desc: &TestDesc) -> io::Result<()> {
         let name = desc.padded_name(self.max_name_len, desc.name.padding());
         if let Some(test_mode) = desc.test_mode() {
@@ -233,9 +256,11 @@ fn write_result(
     ) -> io::Result<()> {
         match *result {
             TestResult::TrOk => self.write_ok(),
-            TestResult::TrFailed | TestResult::TrFailedMsg(_) | TestResult::TrTimedFail => {
-                self.write_failed(desc.name.as_slice())
-            }
+            TestResult::TrFlaky(_) => self.write_flaky(desc.name.as_slice()),
+            TestResult::TrFailed
+            | TestResult::TrFailedMsg(_)
+            | TestResult::TrTimedFail
+            | TestResult::TrTimedOut => self.write_failed(desc.name.as_slice()),
             TestResult::TrIgnored => self.write_ignored(),
             TestResult::TrBench(ref bs) => {
                 if self.is_multithreaded {
@@ -255,14 +280,39 @@ fn write_timeout(&mut self, desc: &TestDesc) -> io::Result<()> {
         ))
     }
 
+    fn write_teardown_failure(&mut self, failure: &TeardownFailure) -> io::Result<()> {
+        // Like failed tests, teardown failures are put on their own line.
+        if self.test_column != 0 {
+            self.write_progress()?;
+        }
+        self.write_plain(format!("teardown of {failure} --- "))?;
+        self.write_pretty("FAILED", term::color::RED)?;
+        self.write_plain("\n")
+    }
+
+    fn write_baseline_comparison(
+        &mut self,
+        baseline: &str,
+        comparisons: &[BenchComparison],
+    ) -> io::Result<()> {
+        self.write_plain("\n")?;
+        for line in fmt_baseline_comparison(baseline, comparisons) {
+            self.write_plain(format!("{line}\n"))?;
+        }
+        Ok(())
+    }
+
     fn write_run_finish(&mut self, state: &ConsoleTestState) -> io::Result<bool> {
         if state.options.display_output {
             self.write_outputs(state)?;
         }
-        let success = state.failed == 0;
-        if !success {
+        let success = state.is_success();
+        if state.failed > 0 {
             self.write_failures(state)?;
         }
+        if !state.teardown_failures.is_empty() {
+            self.write_teardown_failures(state)?;
+        }
 
         self.write_plain("\ntest result: ")?;
 
@@ -273,13 +323,19 @@ fn write_run_finish(&mut self, state: &ConsoleTestState) -> io::Result<bool> {
             self.write_pretty("FAILED", term::color::RED)?;
         }
 
+        let flaky =
+            if state.flaky > 0 { format!("{} flaky; ", state.flaky) } else { String::new() };
         let s = format!(
-            ". {} passed; {} failed; {} ignored; {} measured; {} filtered out",
-            state.passed, state.failed, state.ignored, state.measured, state.filtered_out
+            ". {} passed; {}{} failed; {} ignored; {} measured; {} filtered out",
+            state.passed, flaky, state.failed, state.ignored, state.measured, state.filtered_out
         );
 
         self.write_plain(s)?;
 
+        if !state.teardown_failures.is_empty() {
+            self.write_plain(format!("; {} teardown failed", state.teardown_failures.len()))?;
+        }
+
         if let Some(ref exec_time) = state.exec_time {
             let time_str = format!("; finished in {exec_time}");
             self.write_plain(time_str)?;
@@ -0,0 +1,492 @@
+//! Fuzz targets, which are functions declared with `#[fuzz]` taking an input as a `&[u8]`.
+//!
+//! Normal test runs call a fuzz target with every input of its corpus, so that the
+//! inputs which made it crash once are kept as regression tests. With `--fuzz`, the
+//! target is called with inputs derived from its corpus by random mutations instead.
+//! Inputs which reach new code are added to the corpus, and fuzzing stops at the first
+//! input which makes the target panic or return an error, which is written to the
+//! corpus as well.
+//!
+//! Whether an input reaches new code is measured with the counters of the coverage
+//! instrumentation the code under test is built with, which the fuzz crate
+//! registers before fuzzing:
+//!
+//! * SanitizerCoverage's 8-bit counters, e.g. with `-Cpasses=sancov-module
+//!   -Cllvm-args=-sanitizer-coverage-level=3
+//!   -Cllvm-args=-sanitizer-coverage-inline-8bit-counters`, with
+//!   [`register_8bit_counters`];
+//! * the counters of `-Cinstrument-coverage`, with [`register_profile_counters`].
+//!
+//! Without registered counters, the inputs of the corpus are mutated blindly, which
+//! the results of fuzzing say.
+//!
+//! For instance, a fuzz crate built with SanitizerCoverage registers its counters
+//! from the callback of the instrumentation:
+//!
+//! ```ignore (requires SanitizerCoverage)
+//! #[no_mangle]
+//! pub extern "C" fn __sanitizer_cov_8bit_counters_init(start: *mut u8, stop: *mut u8) {
+//!     // SAFETY: the instrumentation passes the bounds of its counters.
+//!     unsafe { test::fuzz::register_8bit_counters(start, stop) }
+//! }
+//! ```
+
+use std::env;
+use std::fs;
+use std::io;
+use std::mem;
+use std::panic::{self, catch_unwind, AssertUnwindSafe};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, Once, PoisonError};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use super::{
+    __rust_begin_short_backtrace,
+    event::CompletedTest,
+    helpers::shuffle::calculate_hash,
+    test_result::TestResult,
+    types::{TestDesc, TestId, TestName},
+    Sender,
+};
+
+/// Time spent fuzzing every target, unless `--fuzz-time` is passed.
+pub const DEFAULT_FUZZ_TIME: Duration = Duration::from_secs(60);
+
+/// Maximum length of the inputs created by mutations.
+const MAX_INPUT_LEN: usize = 4096;
+
+/// Directory holding the corpus of the fuzz target `name`.
+///
+/// The corpus of `path::to::target` is stored in `path/to/target` under
+/// `$RUST_TEST_FUZZ_CORPUS_DIR`, or by default under `fuzz/corpus` relative to the
+/// current directory, which is where Cargo runs tests from the package root.
+pub fn corpus_dir(name: &TestName) -> PathBuf {
+    let mut dir = match env::var_os("RUST_TEST_FUZZ_CORPUS_DIR") {
+        Some(dir) => PathBuf::from(dir),
+        None => PathBuf::from("fuzz").join("corpus"),
+    };
+    dir.extend(name.as_slice().split("::"));
+    dir
+}
+
+/// Reads the inputs of the corpus in `dir`, in the order of their file names. A
+/// missing directory is an empty corpus.
+fn load_corpus(dir: &Path) -> io::Result<Vec<(PathBuf, Vec<u8>)>> {
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e),
+    };
+    let mut paths = Vec::new();
+    for entry in entries {
+        let entry = entry?;
+        if entry.file_type()?.is_file() {
+            paths.push(entry.path());
+        }
+    }
+    paths.sort();
+    paths
+        .into_iter()
+        .map(|path| {
+            let input = fs::read(&path)?;
+            Ok((path, input))
+        })
+        .collect()
+}
+
+/// Writes `input` to the corpus in `dir`, in a file named after its hash.
+fn save_input(dir: &Path, prefix: &str, input: &[u8]) -> io::Result<PathBuf> {
+    fs::create_dir_all(dir)?;
+    let path = dir.join(format!("{prefix}{:016x}", calculate_hash(&input)));
+    fs::write(&path, input)?;
+    Ok(path)
+}
+
+/// Calls the fuzz target `f` with every input of the corpus in `dir`, which is how
+/// fuzz targets run as tests. A target without a corpus is called with an empty input.
+pub(crate) fn run_corpus(dir: &Path, f: fn(&[u8]) -> Result<(), String>) -> Result<(), String> {
+    let corpus =
+        load_corpus(dir).map_err(|e| format!("failed to read corpus {}: {e}", dir.display()))?;
+    if corpus.is_empty() {
+        return __rust_begin_short_backtrace(|| f(&[]));
+    }
+    for (path, input) in corpus {
+        match catch_unwind(AssertUnwindSafe(|| __rust_begin_short_backtrace(|| f(&input)))) {
+            Ok(Ok(())) => {}
+            Ok(Err(message)) => return Err(format!("{message}\ninput: {}", path.display())),
+            Err(payload) => {
+                eprintln!("input: {}", path.display());
+                panic::resume_unwind(payload);
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Settings of a fuzzing session.
+#[derive(Copy, Clone, Debug)]
+pub(crate) struct FuzzOptions {
+    /// How long each target is fuzzed, unless it crashes earlier.
+    pub time: Duration,
+    /// Whether panics abort the process, in which case crashing inputs are saved by
+    /// the panic hook.
+    pub panic_abort: bool,
+}
+
+/// Fuzzes the target `f` and reports the result on `monitor_ch`.
+pub(crate) fn fuzz(
+    id: TestId,
+    desc: TestDesc,
+    monitor_ch: Sender<CompletedTest>,
+    nocapture: bool,
+    options: FuzzOptions,
+    f: fn(&[u8]) -> Result<(), String>,
+) {
+    let dir = corpus_dir(&desc.name);
+    let data = Arc::new(Mutex::new(Vec::new()));
+
+    if !nocapture {
+        io::set_output_capture(Some(data.clone()));
+    }
+
+    // Only the output of the last input is kept, which is the crashing one if any.
+    let clear_output = || data.lock().unwrap_or_else(PoisonError::into_inner).clear();
+    let result = fuzz_loop(&dir, options, f, clear_output);
+    *ABORTING_INPUT.lock().unwrap_or_else(PoisonError::into_inner) = None;
+
+    io::set_output_capture(None);
+
+    let mut stdout = mem::take(&mut *data.lock().unwrap_or_else(PoisonError::into_inner));
+    let test_result = match result {
+        Ok(stats) => {
+            stdout.clear();
+            stdout.extend_from_slice(stats.to_string().as_bytes());
+            TestResult::TrOk
+        }
+        Err(message) => TestResult::TrFailedMsg(message),
+    };
+    let message = CompletedTest::new(id, desc, test_result, None, stdout);
+    monitor_ch.send(message).unwrap();
+}
+
+/// Statistics of a fuzzing session which didn't find a crash.
+pub(crate) struct FuzzStats {
+    time: Duration,
+    runs: u64,
+    corpus_len: usize,
+    features: Option<usize>,
+}
+
+impl std::fmt::Display for FuzzStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "fuzzed for {:.1}s: {} runs, {} inputs in the corpus, ",
+            self.time.as_secs_f64(),
+            self.runs,
+            self.corpus_len
+        )?;
+        match self.features {
+            Some(features) => writeln!(f, "{features} coverage features"),
+            None => writeln!(f, "mutated blindly without coverage instrumentation"),
+        }
+    }
+}
+
+/// Fuzzes the target `f` starting from the corpus in `dir`, and returns the
+/// statistics of the session, or an error naming the crashing input.
+pub(crate) fn fuzz_loop(
+    dir: &Path,
+    options: FuzzOptions,
+    f: fn(&[u8]) -> Result<(), String>,
+    mut clear_output: impl FnMut(),
+) -> Result<FuzzStats, String> {
+    let start = Instant::now();
+    let deadline = start + options.time;
+
+    let seeds =
+        load_corpus(dir).map_err(|e| format!("failed to read corpus {}: {e}", dir.display()))?;
+    // The seeds are run first, and are mutated later on whether or not they reach
+    // new code. Without any seed, fuzzing starts from an empty input.
+    let mut seeds: Vec<(Option<PathBuf>, Vec<u8>)> =
+        seeds.into_iter().rev().map(|(path, input)| (Some(path), input)).collect();
+    if seeds.is_empty() {
+        seeds.push((None, Vec::new()));
+    }
+    let mut corpus: Vec<Vec<u8>> = Vec::new();
+
+    if options.panic_abort {
+        install_abort_hook();
+    }
+
+    let mut coverage = Coverage::new();
+    let mut rng =
+        Rng::new(SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |d| d.as_nanos() as u64));
+    let mut runs = 0;
+    loop {
+        let (input, is_seed, seed_path) = match seeds.pop() {
+            Some((path, input)) => (input, true, path),
+            None if Instant::now() >= deadline => break,
+            None => {
+                let mut input = corpus[rng.below(corpus.len())].clone();
+                mutate(&mut rng, &mut input, &corpus);
+                (input, false, None)
+            }
+        };
+
+        runs += 1;
+        clear_output();
+        if options.panic_abort {
+            *ABORTING_INPUT.lock().unwrap_or_else(PoisonError::into_inner) =
+                Some((dir.to_owned(), input.clone()));
+        }
+        coverage.start_run();
+        let result = catch_unwind(AssertUnwindSafe(|| __rust_begin_short_backtrace(|| f(&input))));
+        let reached_new_code = coverage.end_run();
+
+        let message = match result {
+            Ok(Ok(())) => None,
+            Ok(Err(message)) => Some(message),
+            Err(_) => Some("the fuzz target panicked".to_string()),
+        };
+        if let Some(message) = message {
+            if let Some(path) = seed_path {
+                return Err(format!("{message}\ninput: {}", path.display()));
+            }
+            let path = save_input(dir, "crash-", &input)
+                .map_err(|e| format!("{message}\nfailed to save the crashing input: {e}"))?;
+            return Err(format!("{message}\ncrashing input written to {}", path.display()));
+        }
+
+        if is_seed {
+            corpus.push(input);
+        } else if reached_new_code {
+            save_input(dir, "", &input)
+                .map_err(|e| format!("failed to add an input to the corpus: {e}"))?;
+            corpus.push(input);
+        }
+    }
+
+    Ok(FuzzStats {
+        time: start.elapsed(),
+        runs,
+        corpus_len: corpus.len(),
+        features: coverage.is_available().then_some(coverage.features),
+    })
+}
+
+/// The input being run and the corpus it belongs to, for the panic hook to save it
+/// when panics abort the process.
+static ABORTING_INPUT: Mutex<Option<(PathBuf, Vec<u8>)>> = Mutex::new(None);
+
+fn install_abort_hook() {
+    static INSTALL: Once = Once::new();
+    INSTALL.call_once(|| {
+        let previous_hook = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            previous_hook(info);
+            if info.can_unwind() {
+                return;
+            }
+            // Don't wait for the lock, as the panic may have happened while holding it.
+            let Ok(input) = ABORTING_INPUT.try_lock() else { return };
+            if let Some((dir, input)) = &*input {
+                match save_input(dir, "crash-", input) {
+                    Ok(path) => eprintln!("crashing input written to {}", path.display()),
+                    Err(e) => eprintln!("failed to save the crashing input: {e}"),
+                }
+            }
+        }));
+    });
+}
+
+/// 8-bit counters of SanitizerCoverage, as address ranges.
+static SANCOV_COUNTERS: Mutex<Vec<(usize, usize)>> = Mutex::new(Vec::new());
+
+/// Counters of `-Cinstrument-coverage`, as an address range.
+static PROFILE_COUNTERS: Mutex<Option<(usize, usize)>> = Mutex::new(None);
+
+/// Registers the 8-bit counters of a module instrumented with
+/// `-sanitizer-coverage-inline-8bit-counters`, which are passed to the
+/// `__sanitizer_cov_8bit_counters_init` callback of the instrumentation.
+///
+/// # Safety
+///
+/// `start..stop` must be a range of counters which stays valid until the process
+/// exits, and which the fuzz targets may reset at any time.
+pub unsafe fn register_8bit_counters(start: *mut u8, stop: *mut u8) {
+    let mut counters = SANCOV_COUNTERS.lock().unwrap_or_else(PoisonError::into_inner);
+    counters.push((start as usize, stop as usize));
+}
+
+/// Registers the counters of `-Cinstrument-coverage`, which are returned by the
+/// `__llvm_profile_begin_counters` and `__llvm_profile_end_counters` functions of
+/// the profiler runtime.
+///
+/// # Safety
+///
+/// `start..stop` must be the range of 64-bit counters of the profiler runtime,
+/// which stays valid until the process exits.
+pub unsafe fn register_profile_counters(start: *const u64, stop: *const u64) {
+    *PROFILE_COUNTERS.lock().unwrap_or_else(PoisonError::into_inner) =
+        Some((start as usize, stop as usize));
+}
+
+/// Coverage of the runs of a fuzz target, as "features": pairs of a counter and the
+/// magnitude of its number of hits in a run.
+struct Coverage {
+    /// 8-bit counters, which are reset after every run.
+    sancov: Vec<(*mut u8, usize)>,
+    /// The counters of `-Cinstrument-coverage`, which are compared to a snapshot
+    /// taken before every run, as they must keep counting for the coverage report.
+    profile: Option<(*const u64, usize)>,
+    snapshot: Vec<u64>,
+    /// For every counter, the set of magnitudes it has been seen with.
+    seen: Vec<u8>,
+    features: usize,
+}
+
+impl Coverage {
+    fn new() -> Coverage {
+        let sancov: Vec<_> = SANCOV_COUNTERS
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .iter()
+            .map(|&(start, stop)| (start as *mut u8, stop - start))
+            .collect();
+        let profile = PROFILE_COUNTERS
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .map(|(start, stop)| (start as *const u64, (stop - start) / mem::size_of::<u64>()))
+            .filter(|&(_, len)| len > 0);
+        let len =
+            sancov.iter().map(|&(_, len)| len).sum::<usize>() + profile.map_or(0, |(_, len)| len);
+        Coverage { sancov, profile, snapshot: Vec::new(), seen: vec![0; len], features: 0 }
+    }
+
+    fn is_available(&self) -> bool {
+        !self.seen.is_empty()
+    }
+
+    fn start_run(&mut self) {
+        for &(start, len) in &self.sancov {
+            // SAFETY: the range was registered by the instrumentation as its counters.
+            unsafe { start.write_bytes(0, len) };
+        }
+        if let Some((start, len)) = self.profile {
+            self.snapshot.clear();
+            // SAFETY: the range is the counters section of the profiler runtime.
+            self.snapshot.extend((0..len).map(|i| unsafe { start.add(i).read_volatile() }));
+        }
+    }
+
+    /// Records the coverage of the run which just ended, and returns whether it
+    /// found new features.
+    fn end_run(&mut self) -> bool {
+        let mut seen = self.seen.iter_mut();
+        let mut new_features = 0;
+        let mut record = |hits: u64| {
+            let seen = seen.next().unwrap();
+            let magnitude = match hits {
+                0 => return,
+                1 => 1 << 0,
+                2 => 1 << 1,
+                3 => 1 << 2,
+                4..=7 => 1 << 3,
+                8..=15 => 1 << 4,
+                16..=31 => 1 << 5,
+                32..=127 => 1 << 6,
+                _ => 1 << 7,
+            };
+            if *seen & magnitude == 0 {
+                *seen |= magnitude;
+                new_features += 1;
+            }
+        };
+
+        for &(start, len) in &self.sancov {
+            for i in 0..len {
+                // SAFETY: the range was registered by the instrumentation as its counters.
+                record(unsafe { start.add(i).read_volatile() }.into());
+            }
+        }
+        if let Some((start, len)) = self.profile {
+            for i in 0..len {
+                // SAFETY: the range is the counters section of the profiler runtime.
+                let now = unsafe { start.add(i).read_volatile() };
+                record(now.wrapping_sub(self.snapshot[i]));
+            }
+        }
+
+        self.features += new_features;
+        new_features > 0
+    }
+}
+
+/// A xorshift pseudo-random number generator, which is plenty for picking mutations.
+struct Rng(u64);
+
+impl Rng {
+    fn new(seed: u64) -> Rng {
+        // The state must not be zero.
+        Rng(seed | 1)
+    }
+
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    /// Returns a number in `0..n`.
+    fn below(&mut self, n: usize) -> usize {
+        (self.next() % n as u64) as usize
+    }
+}
+
+/// Applies a few random mutations to `input`, possibly splicing in parts of the
+/// other inputs of the corpus.
+fn mutate(rng: &mut Rng, input: &mut Vec<u8>, corpus: &[Vec<u8>]) {
+    const INTERESTING: [u8; 7] = [0, 1, 0x7f, 0x80, 0xfe, 0xff, b'\n'];
+
+    for _ in 0..1 + rng.below(4) {
+        let len = input.len();
+        match rng.below(7) {
+            // Flip a bit.
+            0 if len > 0 => input[rng.below(len)] ^= 1 << rng.below(8),
+            // Set a byte to a random value.
+            1 if len > 0 => input[rng.below(len)] = rng.next() as u8,
+            // Set a byte to an interesting value.
+            2 if len > 0 => {
+                input[rng.below(len)] = INTERESTING[rng.below(INTERESTING.len())];
+            }
+            // Remove a range of bytes.
+            3 if len > 0 => {
+                let start = rng.below(len);
+                let end = start + 1 + rng.below(len - start);
+                input.drain(start..end);
+            }
+            // Copy a range of bytes over another one.
+            4 if len > 1 => {
+                let src = rng.below(len);
+                let dst = rng.below(len);
+                let n = 1 + rng.below(len - src.max(dst));
+                input.copy_within(src..src + n, dst);
+            }
+            // Insert a range of bytes of another input.
+            5 => {
+                let other = &corpus[rng.below(corpus.len())];
+                if !other.is_empty() {
+                    let start = rng.below(other.len());
+                    let end = start + 1 + rng.below(other.len() - start);
+                    let at = rng.below(len + 1);
+                    input.splice(at..at, other[start..end].iter().copied());
+                }
+            }
+            // Insert a random byte.
+            _ => input.insert(rng.below(len + 1), rng.next() as u8),
+        }
+    }
+    input.truncate(MAX_INPUT_LEN);
+}
@@ -0,0 +1,14 @@
+use super::shuffle::calculate_hash;
+use crate::options::TestShard;
+use crate::types::TestName;
+
+/// Returns whether the test with the given name belongs to `shard`.
+///
+/// The assignment only depends on the name of the test, so it is stable
+/// across processes and machines running the same test binary, and doesn't
+/// change when other tests are added or removed.
+pub fn is_in_shard(shard: TestShard, name: &TestName) -> bool {
+    // Hash the name itself rather than the `TestName`, whose variant
+    // (static, dynamic or padded) is an implementation detail.
+    calculate_hash(&name.as_slice()) % shard.count as u64 == shard.index as u64
+}
@@ -23,13 +23,16 @@
 #![feature(staged_api)]
 #![feature(process_exitcode_internals)]
 #![feature(panic_can_unwind)]
+#![feature(child_wait_timeout)]
 #![feature(test)]
 #![allow(internal_features)]
 
 // Public reexports
 pub use self::bench::{black_box, Bencher};
 pub use self::console::run_tests_console;
-pub use self::options::{ColorConfig, Options, OutputFormat, RunIgnored, ShouldPanic};
+pub use self::options::{
+    ColorConfig, Options, OutputCapture, OutputFormat, RunIgnored, ShouldPanic, TestShard,
+};
 pub use self::types::TestName::*;
 pub use self::types::*;
 pub use self::ColorConfig::*;
@@ -43,13 +46,13 @@ pub mod test {
         cli::{parse_opts, TestOpts},
         filter_tests,
         helpers::metrics::{Metric, MetricMap},
-        options::{Options, RunIgnored, RunStrategy, ShouldPanic},
+        options::{Options, RunIgnored, RunStrategy, ShouldPanic, TestShard},
         run_test, test_main, test_main_static,
         test_result::{TestResult, TrFailed, TrFailedMsg, TrIgnored, TrOk},
         time::{TestExecTime, TestTimeOptions},
         types::{
-            DynTestFn, DynTestName, StaticBenchFn, StaticTestFn, StaticTestName, TestDesc,
-            TestDescAndFn, TestId, TestName, TestType,
+            DynTestFn, DynTestName, StaticBenchFn, StaticFuzzFn, StaticTestFn, StaticTestName,
+            TestDesc, TestDescAndFn, TestId, TestName, TestType,
         },
     };
 }
@@ -67,11 +70,14 @@ pub mod test {
     time::{Duration, Instant},
 };
 
+mod baseline;
 pub mod bench;
 mod cli;
 mod console;
 mod event;
+pub mod fixture;
 mod formatters;
+pub mod fuzz;
 mod helpers;
 mod options;
 pub mod stats;
@@ -86,6 +92,7 @@ pub mod test {
 use core::any::Any;
 use event::{CompletedTest, TestEvent};
 use helpers::concurrency::get_concurrency;
+use helpers::shard::is_in_shard;
 use helpers::shuffle::{get_shuffle_seed, shuffle_tests};
 use options::RunStrategy;
 use test_result::*;
@@ -96,10 +103,19 @@ pub mod test {
 
 const SECONDARY_TEST_INVOKER_VAR: &str = "__RUST_TEST_INVOKE";
 const SECONDARY_TEST_BENCH_BENCHMARKS_VAR: &str = "__RUST_TEST_BENCH_BENCHMARKS";
+const SECONDARY_TEST_MERGE_OUTPUT_VAR: &str = "__RUST_TEST_MERGE_OUTPUT";
 
 // The default console test runner. It accepts the command line
 // arguments and a vector of test_descs.
 pub fn test_main(args: &[String], tests: Vec<TestDescAndFn>, options: Option<Options>) {
+    // If we're being run in SpawnedSecondary mode, run the test here. The test
+    // then exits the process.
+    if let Ok(name) = env::var(SECONDARY_TEST_INVOKER_VAR) {
+        env::remove_var(SECONDARY_TEST_INVOKER_VAR);
+        let panic_abort = options.is_some_and(|options| options.panic_abort);
+        run_secondary_test(&name, tests, panic_abort);
+    }
+
     let mut opts = match cli::parse_opts(args) {
         Some(Ok(o)) => o,
         Some(Err(msg)) => {
@@ -174,40 +190,43 @@ pub fn test_main_static(tests: &[&TestDescAndFn]) {
 /// This is the entry point for the main function generated by `rustc --test`
 /// when panic=abort.
 pub fn test_main_static_abort(tests: &[&TestDescAndFn]) {
-    // If we're being run in SpawnedSecondary mode, run the test here. run_test
-    // will then exit the process.
-    if let Ok(name) = env::var(SECONDARY_TEST_INVOKER_VAR) {
-        env::remove_var(SECONDARY_TEST_INVOKER_VAR);
+    let args = env::args().collect::<Vec<_>>();
+    let owned_tests: Vec<_> = tests.iter().map(make_owned_test).collect();
+    test_main(&args, owned_tests, Some(Options::new().panic_abort(true)))
+}
 
-        // Convert benchmarks to tests if we're not benchmarking.
-        let mut tests = tests.iter().map(make_owned_test).collect::<Vec<_>>();
-        if env::var(SECONDARY_TEST_BENCH_BENCHMARKS_VAR).is_ok() {
-            env::remove_var(SECONDARY_TEST_BENCH_BENCHMARKS_VAR);
-        } else {
-            tests = convert_benchmarks_to_tests(tests);
-        };
+/// Runs the test named `name` in this process, which was spawned to run only
+/// this test, and exits with its result.
+fn run_secondary_test(name: &str, mut tests: Vec<TestDescAndFn>, panic_abort: bool) -> ! {
+    if env::var(SECONDARY_TEST_MERGE_OUTPUT_VAR).is_ok() {
+        env::remove_var(SECONDARY_TEST_MERGE_OUTPUT_VAR);
+        merge_output();
+    }
 
-        let test = tests
-            .into_iter()
-            .find(|test| test.desc.name.as_slice() == name)
-            .unwrap_or_else(|| panic!("couldn't find a test with the provided name '{name}'"));
-        let TestDescAndFn { desc, testfn } = test;
-        match testfn.into_runnable() {
-            Runnable::Test(runnable_test) => {
-                if runnable_test.is_dynamic() {
-                    panic!("only static tests are supported");
-                }
-                run_test_in_spawned_subprocess(desc, runnable_test);
-            }
-            Runnable::Bench(_) => {
-                panic!("benchmarks should not be executed into child processes")
-            }
+    // Convert benchmarks to tests if we're not benchmarking.
+    if env::var(SECONDARY_TEST_BENCH_BENCHMARKS_VAR).is_ok() {
+        env::remove_var(SECONDARY_TEST_BENCH_BENCHMARKS_VAR);
+    } else {
+        tests = convert_benchmarks_to_tests(tests);
+    };
+    // Fuzz targets are only fuzzed in the primary process.
+    tests = convert_fuzz_targets_to_tests(tests);
+
+    let test = tests
+        .into_iter()
+        .find(|test| test.desc.name.as_slice() == name)
+        .unwrap_or_else(|| panic!("couldn't find a test with the provided name '{name}'"));
+    let TestDescAndFn { desc, testfn } = test;
+    match testfn.into_runnable() {
+        Runnable::Test(runnable_test) if panic_abort => {
+            run_test_in_spawned_subprocess(desc, runnable_test)
+        }
+        Runnable::Test(runnable_test) => run_test_in_isolated_subprocess(desc, runnable_test),
+        Runnable::Bench(_) => {
+            panic!("benchmarks should not be executed into child processes")
         }
+        Runnable::Fuzz(_) => unreachable!("fuzz targets are converted to tests"),
     }
-
-    let args = env::args().collect::<Vec<_>>();
-    let owned_tests: Vec<_> = tests.iter().map(make_owned_test).collect();
-    test_main(&args, owned_tests, Some(Options::new().panic_abort(true)))
 }
 
 /// Clones static values for putting into a dynamic vector, which test_main()
@@ -218,6 +237,7 @@ fn make_owned_test(test: &&TestDescAndFn) -> TestDescAndFn {
     match test.testfn {
         StaticTestFn(f) => TestDescAndFn { testfn: StaticTestFn(f), desc: test.desc.clone() },
         StaticBenchFn(f) => TestDescAndFn { testfn: StaticBenchFn(f), desc: test.desc.clone() },
+        StaticFuzzFn(f) => TestDescAndFn { testfn: StaticFuzzFn(f), desc: test.desc.clone() },
         _ => panic!("non-static tests passed to test::test_main_static"),
     }
 }
@@ -273,6 +293,7 @@ pub fn run_tests<F>(
 
     struct RunningTest {
         join_handle: Option<thread::JoinHandle<()>>,
+        hard_timeout: Option<HardTimeout>,
     }
 
     impl RunningTest {
@@ -286,10 +307,36 @@ fn join(self, completed_test: &mut CompletedTest) {
                 }
             }
         }
+
+        // Gives up on a test which exceeded its hard time limit: its thread is
+        // detached, and its result is discarded if it ever completes.
+        fn abandon(self, id: TestId) -> CompletedTest {
+            let HardTimeout { desc, start, .. } = self.hard_timeout.unwrap();
+            let exec_time = TestExecTime(start.elapsed());
+            CompletedTest::new(id, desc, TrTimedOut, Some(exec_time), Vec::new())
+        }
+    }
+
+    // Hard time limit of a test running in-process. Tests running in a subprocess
+    // enforce their own limit by killing the child process.
+    struct HardTimeout {
+        desc: TestDesc,
+        start: Instant,
+        deadline: Instant,
+    }
+
+    // A copy of a test which is run again if it fails, along with the output of
+    // its failed attempts.
+    struct Retry {
+        test: TestDescAndFn,
+        failed_attempts: usize,
+        stdout: Vec<u8>,
     }
 
     // Use a deterministic hasher
     type TestMap = HashMap<TestId, RunningTest, BuildHasherDefault<DefaultHasher>>;
+    type RetryMap = HashMap<TestId, Retry, BuildHasherDefault<DefaultHasher>>;
+    type ModuleMap = HashMap<String, usize, BuildHasherDefault<DefaultHasher>>;
 
     struct TimeoutEntry {
         id: TestId,
@@ -305,13 +352,18 @@ struct TimeoutEntry {
     if !opts.bench_benchmarks {
         filtered_tests = convert_benchmarks_to_tests(filtered_tests);
     }
+    if !opts.fuzz {
+        filtered_tests = convert_fuzz_targets_to_tests(filtered_tests);
+    }
 
     for test in filtered_tests {
         let mut desc = test.desc;
         desc.name = desc.name.with_padding(test.testfn.padding());
 
         match test.testfn {
-            DynBenchFn(_) | StaticBenchFn(_) => {
+            // Fuzz targets run serially like benchmarks, as they use the whole machine
+            // for as long as they are given.
+            DynBenchFn(_) | StaticBenchFn(_) | StaticFuzzFn(_) => {
                 filtered.add_bench(desc, test.testfn);
             }
             testfn => {
@@ -338,6 +390,13 @@ struct TimeoutEntry {
     // Store the tests in a VecDeque so we can efficiently remove the first element to run the
     // tests in the order they were passed (unless shuffled).
     let mut remaining = VecDeque::from(remaining);
+    // Number of tests of each module which have yet to complete, so that the fixtures
+    // shared by a module are torn down once its last test completes.
+    let mut module_tests: ModuleMap = HashMap::default();
+    for (_, test) in &remaining {
+        let module = fixture::test_module(test.desc.name.as_slice());
+        *module_tests.entry(module.to_owned()).or_default() += 1;
+    }
     let mut pending = 0;
 
     let (tx, rx) = channel::<CompletedTest>();
@@ -349,6 +408,24 @@ struct TimeoutEntry {
 
     let mut running_tests: TestMap = HashMap::default();
     let mut timeout_queue: VecDeque<TimeoutEntry> = VecDeque::new();
+    let mut retries: RetryMap = HashMap::default();
+
+    // With `--isolate` or `--capture=fd`, every test runs in a subprocess, which
+    // looks the test up by name. Tests created at runtime can't be found that way,
+    // so they still run in-process.
+    let get_run_strategy = |test: &TestDescAndFn| match test.testfn {
+        DynTestFn(_) | DynBenchFn(_) | DynBenchAsTestFn(_) => run_strategy,
+        _ if opts.isolate || opts.capture == OutputCapture::Fd => RunStrategy::SpawnPrimary,
+        _ => run_strategy,
+    };
+
+    let get_hard_timeout = |desc: &TestDesc, run_strategy| match run_strategy {
+        RunStrategy::InProcess => time::get_test_hard_timeout(desc, opts.timeout).map(|timeout| {
+            let start = Instant::now();
+            HardTimeout { desc: desc.clone(), start, deadline: start + timeout }
+        }),
+        RunStrategy::SpawnPrimary => None,
+    };
 
     fn get_timed_out_tests(
         running_tests: &TestMap,
@@ -368,8 +445,88 @@ fn get_timed_out_tests(
         timed_out
     }
 
-    fn calc_timeout(timeout_queue: &VecDeque<TimeoutEntry>) -> Option<Duration> {
-        timeout_queue.front().map(|&TimeoutEntry { timeout: next_timeout, .. }| {
+    fn get_hard_timed_out_tests(running_tests: &mut TestMap) -> Vec<CompletedTest> {
+        let now = Instant::now();
+        let timed_out: Vec<TestId> = running_tests
+            .iter()
+            .filter(|(_, test)| test.hard_timeout.as_ref().is_some_and(|t| t.deadline <= now))
+            .map(|(&id, _)| id)
+            .collect();
+        timed_out.into_iter().map(|id| running_tests.remove(&id).unwrap().abandon(id)).collect()
+    }
+
+    // Remembers a copy of the test so that it can be run again if it fails. Dynamic
+    // tests can only be run once.
+    fn track_retries(retries: &mut RetryMap, id: TestId, test: &TestDescAndFn) {
+        if let Some(testfn) = test.testfn.try_clone() {
+            let test = TestDescAndFn { desc: test.desc.clone(), testfn };
+            retries.insert(id, Retry { test, failed_attempts: 0, stdout: Vec::new() });
+        }
+    }
+
+    // Returns the test to run again if the completed test failed and has retries
+    // left. Otherwise returns the final result, which is `TrFlaky` if the test
+    // passed after failing.
+    fn retry_if_failed(
+        max_retries: usize,
+        retries: &mut RetryMap,
+        mut completed_test: CompletedTest,
+    ) -> Result<TestDescAndFn, CompletedTest> {
+        let Some(retry) = retries.get_mut(&completed_test.id) else {
+            return Err(completed_test);
+        };
+        // Tests which hit their hard time limit are not retried, as an abandoned test
+        // may still be running and would report its result under the same id.
+        let failed = matches!(completed_test.result, TrFailed | TrFailedMsg(_) | TrTimedFail);
+        if failed && retry.failed_attempts < max_retries {
+            retry.failed_attempts += 1;
+            retry.stdout.extend_from_slice(&completed_test.stdout);
+            let note = match completed_test.result {
+                TrFailedMsg(ref msg) => {
+                    format!("note: attempt {} failed: {msg}\n", retry.failed_attempts)
+                }
+                _ => format!("note: attempt {} failed\n", retry.failed_attempts),
+            };
+            retry.stdout.extend_from_slice(note.as_bytes());
+            let testfn = retry.test.testfn.try_clone().unwrap();
+            return Ok(TestDescAndFn { desc: retry.test.desc.clone(), testfn });
+        }
+
+        let retry = retries.remove(&completed_test.id).unwrap();
+        if retry.failed_attempts > 0 {
+            if let TrOk = completed_test.result {
+                completed_test.result = TrFlaky(retry.failed_attempts);
+            }
+            let mut stdout = retry.stdout;
+            stdout.append(&mut completed_test.stdout);
+            completed_test.stdout = stdout;
+        }
+        Err(completed_test)
+    }
+
+    // Tears down the fixtures of the module of a completed test if it was the last
+    // test of the module.
+    fn complete_module_test(module_tests: &mut ModuleMap, desc: &TestDesc) {
+        let module = fixture::test_module(desc.name.as_slice());
+        if let Some(count) = module_tests.get_mut(module) {
+            *count -= 1;
+            if *count == 0 {
+                module_tests.remove(module);
+                fixture::teardown_module(module);
+            }
+        }
+    }
+
+    fn calc_timeout(
+        running_tests: &TestMap,
+        timeout_queue: &VecDeque<TimeoutEntry>,
+    ) -> Option<Duration> {
+        let next_warning = timeout_queue.front().map(|entry| entry.timeout);
+        let next_deadline = running_tests
+            .values()
+            .filter_map(|test| test.hard_timeout.as_ref().map(|t| t.deadline))
+            .min();
+        next_warning.into_iter().chain(next_deadline).min().map(|next_timeout| {
             let now = Instant::now();
             if next_timeout >= now { next_timeout - now } else { Duration::new(0, 0) }
         })
@@ -377,24 +534,56 @@ fn calc_timeout(timeout_queue: &VecDeque<TimeoutEntry>) -> Option<Duration> {
 
     if concurrency == 1 {
         while !remaining.is_empty() {
-            let (id, test) = remaining.pop_front().unwrap();
+            let (id, mut test) = remaining.pop_front().unwrap();
             let event = TestEvent::TeWait(test.desc.clone());
             notify_about_test_event(event)?;
-            let join_handle = run_test(opts, !opts.run_tests, id, test, run_strategy, tx.clone());
-            // Wait for the test to complete.
-            let mut completed_test = rx.recv().unwrap();
-            RunningTest { join_handle }.join(&mut completed_test);
+            if opts.retries > 0 {
+                track_retries(&mut retries, id, &test);
+            }
+            let completed_test = loop {
+                let strategy = get_run_strategy(&test);
+                let hard_timeout = get_hard_timeout(&test.desc, strategy);
+                let join_handle = run_test(opts, !opts.run_tests, id, test, strategy, tx.clone());
+                let running_test = RunningTest { join_handle, hard_timeout };
+                // Wait for the test to complete.
+                let completed_test = loop {
+                    let res = match running_test.hard_timeout {
+                        Some(HardTimeout { deadline, .. }) => {
+                            rx.recv_timeout(deadline.saturating_duration_since(Instant::now()))
+                        }
+                        None => rx.recv().map_err(|_| RecvTimeoutError::Disconnected),
+                    };
+                    match res {
+                        // Late results of previously abandoned tests are discarded.
+                        Ok(completed_test) if completed_test.id != id => {}
+                        Ok(mut completed_test) => {
+                            running_test.join(&mut completed_test);
+                            break completed_test;
+                        }
+                        Err(RecvTimeoutError::Timeout) => break running_test.abandon(id),
+                        Err(RecvTimeoutError::Disconnected) => {
+                            panic!("test result channel closed")
+                        }
+                    }
+                };
+                match retry_if_failed(opts.retries, &mut retries, completed_test) {
+                    Ok(retried_test) => test = retried_test,
+                    Err(completed_test) => break completed_test,
+                }
+            };
 
             let fail_fast = match completed_test.result {
-                TrIgnored | TrOk | TrBench(_) => false,
-                TrFailed | TrFailedMsg(_) | TrTimedFail => opts.fail_fast,
+                TrIgnored | TrOk | TrFlaky(_) | TrBench(_) => false,
+                TrFailed | TrFailedMsg(_) | TrTimedFail | TrTimedOut => opts.fail_fast,
             };
 
+            complete_module_test(&mut module_tests, &completed_test.desc);
             let event = TestEvent::TeResult(completed_test);
             notify_about_test_event(event)?;
+            report_teardown_failures(&mut notify_about_test_event)?;
 
             if fail_fast {
-                return Ok(());
+                return teardown_remaining_fixtures(&mut notify_about_test_event);
             }
         }
     } else {
@@ -406,72 +595,120 @@ fn calc_timeout(timeout_queue: &VecDeque<TimeoutEntry>) -> Option<Duration> {
 
                 let event = TestEvent::TeWait(desc.clone());
                 notify_about_test_event(event)?; //here no pad
-                let join_handle =
-                    run_test(opts, !opts.run_tests, id, test, run_strategy, tx.clone());
-                running_tests.insert(id, RunningTest { join_handle });
+                if opts.retries > 0 {
+                    track_retries(&mut retries, id, &test);
+                }
+                let strategy = get_run_strategy(&test);
+                let hard_timeout = get_hard_timeout(&desc, strategy);
+                let join_handle = run_test(opts, !opts.run_tests, id, test, strategy, tx.clone());
+                running_tests.insert(id, RunningTest { join_handle, hard_timeout });
                 timeout_queue.push_back(TimeoutEntry { id, desc, timeout });
                 pending += 1;
             }
 
-            let mut res;
-            loop {
-                if let Some(timeout) = calc_timeout(&timeout_queue) {
-                    res = rx.recv_timeout(timeout);
-                    for test in get_timed_out_tests(&running_tests, &mut timeout_queue) {
-                        let event = TestEvent::TeTimeout(test);
-                        notify_about_test_event(event)?;
-                    }
-
-                    match res {
-                        Err(RecvTimeoutError::Timeout) => {
-                            // Result is not yet ready, continue waiting.
-                        }
-                        _ => {
-                            // We've got a result, stop the loop.
-                            break;
+            let mut completed_tests = Vec::new();
+            while completed_tests.is_empty() {
+                let res = match calc_timeout(&running_tests, &timeout_queue) {
+                    Some(timeout) => rx.recv_timeout(timeout),
+                    None => rx.recv().map_err(|_| RecvTimeoutError::Disconnected),
+                };
+                for test in get_timed_out_tests(&running_tests, &mut timeout_queue) {
+                    let event = TestEvent::TeTimeout(test);
+                    notify_about_test_event(event)?;
+                }
+                completed_tests.extend(get_hard_timed_out_tests(&mut running_tests));
+
+                match res {
+                    Ok(mut completed_test) => {
+                        // Late results of abandoned tests are discarded.
+                        if let Some(running_test) = running_tests.remove(&completed_test.id) {
+                            running_test.join(&mut completed_test);
+                            completed_tests.push(completed_test);
                         }
                     }
-                } else {
-                    res = rx.recv().map_err(|_| RecvTimeoutError::Disconnected);
-                    break;
+                    Err(RecvTimeoutError::Timeout) => {
+                        // Result is not yet ready, continue waiting.
+                    }
+                    Err(RecvTimeoutError::Disconnected) => panic!("test result channel closed"),
                 }
             }
 
-            let mut completed_test = res.unwrap();
-            let running_test = running_tests.remove(&completed_test.id).unwrap();
-            running_test.join(&mut completed_test);
-
-            let fail_fast = match completed_test.result {
-                TrIgnored | TrOk | TrBench(_) => false,
-                TrFailed | TrFailedMsg(_) | TrTimedFail => opts.fail_fast,
-            };
+            for completed_test in completed_tests {
+                let id = completed_test.id;
+                let completed_test =
+                    match retry_if_failed(opts.retries, &mut retries, completed_test) {
+                        Ok(test) => {
+                            let strategy = get_run_strategy(&test);
+                            let hard_timeout = get_hard_timeout(&test.desc, strategy);
+                            let join_handle =
+                                run_test(opts, !opts.run_tests, id, test, strategy, tx.clone());
+                            running_tests.insert(id, RunningTest { join_handle, hard_timeout });
+                            continue;
+                        }
+                        Err(completed_test) => completed_test,
+                    };
+                let fail_fast = match completed_test.result {
+                    TrIgnored | TrOk | TrFlaky(_) | TrBench(_) => false,
+                    TrFailed | TrFailedMsg(_) | TrTimedFail | TrTimedOut => opts.fail_fast,
+                };
 
-            let event = TestEvent::TeResult(completed_test);
-            notify_about_test_event(event)?;
-            pending -= 1;
+                complete_module_test(&mut module_tests, &completed_test.desc);
+                let event = TestEvent::TeResult(completed_test);
+                notify_about_test_event(event)?;
+                report_teardown_failures(&mut notify_about_test_event)?;
+                pending -= 1;
 
-            if fail_fast {
-                // Prevent remaining test threads from panicking
-                std::mem::forget(rx);
-                return Ok(());
+                if fail_fast {
+                    // Prevent remaining test threads from panicking
+                    std::mem::forget(rx);
+                    return teardown_remaining_fixtures(&mut notify_about_test_event);
+                }
             }
         }
     }
 
-    if opts.bench_benchmarks {
-        // All benchmarks run at the end, in serial.
+    if opts.bench_benchmarks || opts.fuzz {
+        // All benchmarks and fuzz targets run at the end, in serial.
         for (id, b) in filtered.benches {
             let event = TestEvent::TeWait(b.desc.clone());
             notify_about_test_event(event)?;
             let join_handle = run_test(opts, false, id, b, run_strategy, tx.clone());
-            // Wait for the test to complete.
-            let mut completed_test = rx.recv().unwrap();
-            RunningTest { join_handle }.join(&mut completed_test);
+            // Wait for the benchmark to complete, discarding late results of
+            // abandoned tests.
+            let mut completed_test = loop {
+                let completed_test = rx.recv().unwrap();
+                if completed_test.id == id {
+                    break completed_test;
+                }
+            };
+            RunningTest { join_handle, hard_timeout: None }.join(&mut completed_test);
 
             let event = TestEvent::TeResult(completed_test);
             notify_about_test_event(event)?;
         }
     }
+
+    teardown_remaining_fixtures(&mut notify_about_test_event)
+}
+
+/// Tears down the fixtures which are left when the run ends, e.g. ones set up by
+/// dynamically created tests whose names don't match their module, or by the
+/// modules whose tests were cut short by `--fail-fast`.
+fn teardown_remaining_fixtures<F>(notify_about_test_event: &mut F) -> io::Result<()>
+where
+    F: FnMut(TestEvent) -> io::Result<()>,
+{
+    fixture::teardown_all();
+    report_teardown_failures(notify_about_test_event)
+}
+
+fn report_teardown_failures<F>(notify_about_test_event: &mut F) -> io::Result<()>
+where
+    F: FnMut(TestEvent) -> io::Result<()>,
+{
+    for failure in fixture::take_teardown_failures() {
+        notify_about_test_event(TestEvent::TeTeardownFailed(failure))?;
+    }
     Ok(())
 }
 
@@ -513,6 +750,11 @@ pub fn filter_tests(opts: &TestOpts, tests: Vec<TestDescAndFn>) -> Vec<TestDescA
         RunIgnored::No => {}
     }
 
+    // Only keep the tests belonging to the requested shard
+    if let Some(shard) = opts.shard {
+        filtered.retain(|test| is_in_shard(shard, &test.desc.name));
+    }
+
     filtered
 }
 
@@ -531,6 +773,20 @@ pub fn convert_benchmarks_to_tests(tests: Vec<TestDescAndFn>) -> Vec<TestDescAnd
         .collect()
 }
 
+/// Converts fuzz targets to tests running their corpus, unless we're fuzzing them.
+fn convert_fuzz_targets_to_tests(tests: Vec<TestDescAndFn>) -> Vec<TestDescAndFn> {
+    tests
+        .into_iter()
+        .map(|x| {
+            let testfn = match x.testfn {
+                StaticFuzzFn(f) => StaticFuzzAsTestFn(f),
+                f => f,
+            };
+            TestDescAndFn { desc: x.desc, testfn }
+        })
+        .collect()
+}
+
 pub fn run_test(
     opts: &TestOpts,
     force_ignore: bool,
@@ -563,8 +819,11 @@ pub fn run_test(
 
             let name = desc.name.clone();
             let nocapture = opts.nocapture;
+            let merge_output = opts.capture == OutputCapture::Fd;
             let time_options = opts.time_options;
             let bench_benchmarks = opts.bench_benchmarks;
+            let panic_abort = opts.options.panic_abort;
+            let hard_timeout = time::get_test_hard_timeout(&desc, opts.timeout);
 
             let runtest = move || match strategy {
                 RunStrategy::InProcess => run_test_in_process(
@@ -580,10 +839,13 @@ pub fn run_test(
                     id,
                     desc,
                     nocapture,
+                    merge_output,
                     time_options.is_some(),
                     monitor_ch,
                     time_options,
                     bench_benchmarks,
+                    panic_abort,
+                    hard_timeout,
                 ),
             };
 
@@ -615,6 +877,16 @@ pub fn run_test(
             runnable_bench.run(id, &desc, &monitor_ch, opts.nocapture);
             None
         }
+        Runnable::Fuzz(f) => {
+            // Fuzz targets catch their panics to save the crashing input, so they run
+            // in-process too.
+            let options = fuzz::FuzzOptions {
+                time: opts.fuzz_time.unwrap_or(fuzz::DEFAULT_FUZZ_TIME),
+                panic_abort: opts.options.panic_abort,
+            };
+            fuzz::fuzz(id, desc, monitor_ch, opts.nocapture, options, f);
+            None
+        }
     }
 }
 
@@ -644,7 +916,7 @@ fn run_test_in_process(
     }
 
     let start = report_time.then(Instant::now);
-    let result = fold_err(catch_unwind(AssertUnwindSafe(|| runnable_test.run())));
+    let result = fold_err(catch_unwind(AssertUnwindSafe(|| runnable_test.run(&desc.name))));
     let exec_time = start.map(|start| {
         let duration = start.elapsed();
         TestExecTime(duration)
@@ -678,10 +950,13 @@ fn spawn_test_subprocess(
     id: TestId,
     desc: TestDesc,
     nocapture: bool,
+    merge_output: bool,
     report_time: bool,
     monitor_ch: Sender<CompletedTest>,
     time_opts: Option<time::TestTimeOptions>,
     bench_benchmarks: bool,
+    panic_abort: bool,
+    hard_timeout: Option<Duration>,
 ) {
     let (result, test_output, exec_time) = (|| {
         let args = env::args().collect::<Vec<_>>();
@@ -695,10 +970,22 @@ fn spawn_test_subprocess(
         if nocapture {
             command.stdout(process::Stdio::inherit());
             command.stderr(process::Stdio::inherit());
+        } else {
+            command.stdout(process::Stdio::piped());
+            command.stderr(process::Stdio::piped());
+            if merge_output {
+                command.env(SECONDARY_TEST_MERGE_OUTPUT_VAR, "1");
+            }
         }
 
-        let start = report_time.then(Instant::now);
-        let output = match command.output() {
+        let start = (report_time || hard_timeout.is_some()).then(Instant::now);
+        let output = match hard_timeout {
+            Some(hard_timeout) => output_with_timeout(&mut command, hard_timeout),
+            None => {
+                command.output().map(|output| (Some(output.status), output.stdout, output.stderr))
+            }
+        };
+        let (status, stdout, stderr) = match output {
             Ok(out) => out,
             Err(e) => {
                 let err = format!("Failed to spawn {} as child for test: {:?}", args[0], e);
@@ -710,12 +997,20 @@ fn spawn_test_subprocess(
             TestExecTime(duration)
         });
 
-        let std::process::Output { stdout, stderr, status } = output;
         let mut test_output = stdout;
-        formatters::write_stderr_delimiter(&mut test_output, &desc.name);
-        test_output.extend_from_slice(&stderr);
+        // Merged output is all written to stdout, unless the platform doesn't
+        // support merging it.
+        if !merge_output || !stderr.is_empty() {
+            formatters::write_stderr_delimiter(&mut test_output, &desc.name);
+            test_output.extend_from_slice(&stderr);
+        }
 
-        let result = get_result_from_exit_code(&desc, status, &time_opts, &exec_time);
+        let result = match status {
+            Some(status) => {
+                get_result_from_exit_code(&desc, status, panic_abort, &time_opts, &exec_time)
+            }
+            None => TrTimedOut,
+        };
         (result, test_output, exec_time)
     })();
 
@@ -723,7 +1018,75 @@ fn spawn_test_subprocess(
     monitor_ch.send(message).unwrap();
 }
 
+/// Runs `command` like `Command::output`, but kills the child process if it is still
+/// running after `timeout`. In that case, no exit status is returned, and the output
+/// is whatever the child wrote before being killed.
+fn output_with_timeout(
+    command: &mut Command,
+    timeout: Duration,
+) -> io::Result<(Option<process::ExitStatus>, Vec<u8>, Vec<u8>)> {
+    type SharedReader = (thread::JoinHandle<()>, Arc<Mutex<Vec<u8>>>);
+
+    // Read the pipes from helper threads so that the child never blocks on a full
+    // pipe. The data is shared rather than returned from the threads, so that it
+    // can be retrieved even if a grandchild process keeps the pipes open.
+    fn read_to_end_shared(mut pipe: impl io::Read + Send + 'static) -> SharedReader {
+        let data = Arc::new(Mutex::new(Vec::new()));
+        let data2 = data.clone();
+        let handle = thread::spawn(move || {
+            let mut buf = [0; 4096];
+            while let Ok(n @ 1..) = pipe.read(&mut buf) {
+                data2.lock().unwrap().extend_from_slice(&buf[..n]);
+            }
+        });
+        (handle, data)
+    }
+
+    let mut child = command.spawn()?;
+    let stdout = child.stdout.take().map(read_to_end_shared);
+    let stderr = child.stderr.take().map(read_to_end_shared);
+
+    let status = child.wait_timeout(timeout)?;
+    if status.is_none() {
+        // The child may have exited in the meantime, in which case killing it fails.
+        let _ = child.kill();
+        child.wait()?;
+    }
+
+    let take = |reader: Option<SharedReader>| {
+        reader
+            .map(|(handle, data)| {
+                // Once the child has been killed, don't wait for pipes which may be
+                // held open by its own children.
+                if status.is_some() {
+                    let _ = handle.join();
+                }
+                std::mem::take(&mut *data.lock().unwrap())
+            })
+            .unwrap_or_default()
+    };
+    Ok((status, take(stdout), take(stderr)))
+}
+
+/// Redirects the standard error of this process to its standard output, so that
+/// everything the test writes, including from C code and child processes, is
+/// captured in the order it was written.
+#[cfg(unix)]
+fn merge_output() {
+    // SAFETY: this only changes which file the descriptor of standard error refers
+    // to, and both descriptors stay open.
+    if unsafe { libc::dup2(libc::STDOUT_FILENO, libc::STDERR_FILENO) } == -1 {
+        panic!("failed to redirect stderr to stdout: {}", io::Error::last_os_error());
+    }
+}
+
+#[cfg(not(unix))]
+fn merge_output() {
+    // The output is captured as separate streams instead.
+}
+
 fn run_test_in_spawned_subprocess(desc: TestDesc, runnable_test: RunnableTest) -> ! {
+    let name = desc.name.clone();
     let builtin_panic_hook = panic::take_hook();
     let record_result = Arc::new(move |panic_info: Option<&'_ PanicInfo<'_>>| {
         let test_result = match panic_info {
@@ -749,9 +1112,46 @@ fn run_test_in_spawned_subprocess(desc: TestDesc, runnable_test: RunnableTest) -
     });
     let record_result2 = record_result.clone();
     panic::set_hook(Box::new(move |info| record_result2(Some(info))));
-    if let Err(message) = runnable_test.run() {
+    if let Err(message) = runnable_test.run(&name) {
         panic!("{}", message);
     }
+    if !teardown_subprocess_fixtures() {
+        process::abort();
+    }
     record_result(None);
     unreachable!("panic=abort callback should have exited the process")
 }
+
+/// Runs a test in a subprocess spawned for `--isolate` with `panic=unwind`, where
+/// the panics of the test are caught as when it runs in-process.
+fn run_test_in_isolated_subprocess(desc: TestDesc, runnable_test: RunnableTest) -> ! {
+    let result = fold_err(catch_unwind(AssertUnwindSafe(|| runnable_test.run(&desc.name))));
+    let test_result = match result {
+        Ok(()) => calc_result(&desc, Ok(()), &None, &None),
+        Err(e) => calc_result(&desc, Err(e.as_ref()), &None, &None),
+    };
+
+    // We don't support serializing TrFailedMsg, so just
+    // print the message out to stderr.
+    if let TrFailedMsg(msg) = &test_result {
+        eprintln!("{msg}");
+    }
+
+    if teardown_subprocess_fixtures() && test_result == TrOk {
+        process::exit(test_result::TR_OK);
+    } else {
+        process::exit(test_result::TR_FAILED);
+    }
+}
+
+/// Tears down the fixtures of a subprocess which only runs one test, before it
+/// exits. Teardown failures fail the test, so this returns whether all fixtures
+/// were torn down successfully.
+fn teardown_subprocess_fixtures() -> bool {
+    fixture::teardown_all();
+    let teardown_failures = fixture::take_teardown_failures();
+    for failure in &teardown_failures {
+        eprintln!("teardown of {failure} failed: {}", failure.message);
+    }
+    teardown_failures.is_empty()
+}
@@ -36,6 +36,19 @@ pub enum OutputFormat {
     Json,
     /// JUnit output
     Junit,
+    /// TAP version 14 output
+    Tap,
+}
+
+/// How the output of tests is captured
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum OutputCapture {
+    /// Capture the output of the `print!` family of macros
+    #[default]
+    Print,
+    /// Run every test in its own process and capture everything written to its
+    /// standard output and standard error
+    Fd,
 }
 
 /// Whether ignored test should be run or not
@@ -47,6 +60,16 @@ pub enum RunIgnored {
     Only,
 }
 
+/// Slice of the filtered tests to run when a test run is split across
+/// several processes or machines
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct TestShard {
+    /// Zero-based index of this shard
+    pub index: usize,
+    /// Total number of shards
+    pub count: usize,
+}
+
 #[derive(Clone, Copy)]
 pub enum RunStrategy {
     /// Runs the test in the current process, and sends the result back over the
@@ -300,3 +300,110 @@ pub fn winsorize(samples: &mut [f64], pct: f64) {
         }
     }
 }
+
+/// Welch's t-test of the difference between the means of two sample sets of `n_a` and `n_b`
+/// samples, which unlike Student's t-test doesn't assume that both sets have the same
+/// variance. Returns the two-sided p-value: the probability of observing a difference of the
+/// means at least as large as this one if both sample sets came from distributions with the
+/// same mean.
+///
+/// See: <https://en.wikipedia.org/wiki/Welch%27s_t-test>
+pub fn welch_t_test(a: &Summary, n_a: usize, b: &Summary, n_b: usize) -> f64 {
+    if n_a < 2 || n_b < 2 {
+        return 1.0;
+    }
+    let (n_a, n_b) = (n_a as f64, n_b as f64);
+    let (var_a, var_b) = (a.var / n_a, b.var / n_b);
+    let var = var_a + var_b;
+    if var == 0.0 {
+        return if a.mean == b.mean { 1.0 } else { 0.0 };
+    }
+
+    let t = (a.mean - b.mean) / var.sqrt();
+    // Welch-Satterthwaite approximation of the degrees of freedom.
+    let df = var * var / (var_a * var_a / (n_a - 1.0) + var_b * var_b / (n_b - 1.0));
+    students_t_p_value(t, df)
+}
+
+// Two-sided p-value of `t` in Student's t-distribution with `df` degrees of freedom.
+fn students_t_p_value(t: f64, df: f64) -> f64 {
+    regularized_incomplete_beta(df / (df + t * t), df / 2.0, 0.5)
+}
+
+// Regularized incomplete beta function `I_x(a, b)`.
+//
+// See: Numerical Recipes in C, 2nd edition, section 6.4.
+fn regularized_incomplete_beta(x: f64, a: f64, b: f64) -> f64 {
+    if x <= 0.0 {
+        return 0.0;
+    }
+    if x >= 1.0 {
+        return 1.0;
+    }
+    let ln_front = ln_gamma(a + b) - ln_gamma(a) - ln_gamma(b) + a * x.ln() + b * (1.0 - x).ln();
+    // The continued fraction converges quickly for `x < (a + 1) / (a + b + 2)`, and the
+    // symmetry `I_x(a, b) = 1 - I_(1-x)(b, a)` is used otherwise.
+    if x < (a + 1.0) / (a + b + 2.0) {
+        ln_front.exp() * beta_continued_fraction(x, a, b) / a
+    } else {
+        1.0 - ln_front.exp() * beta_continued_fraction(1.0 - x, b, a) / b
+    }
+}
+
+// Continued fraction of the incomplete beta function, evaluated with the modified
+// Lentz's method.
+fn beta_continued_fraction(x: f64, a: f64, b: f64) -> f64 {
+    const MAX_ITERATIONS: usize = 300;
+    const EPSILON: f64 = 1.0e-15;
+    const TINY: f64 = 1.0e-300;
+
+    let not_tiny = |v: f64| if v.abs() < TINY { TINY } else { v };
+
+    let mut c = 1.0;
+    let mut d = 1.0 / not_tiny(1.0 - (a + b) * x / (a + 1.0));
+    let mut h = d;
+    for m in 1..=MAX_ITERATIONS {
+        let m = m as f64;
+        let m2 = 2.0 * m;
+
+        // Even step of the recurrence.
+        let aa = m * (b - m) * x / ((a - 1.0 + m2) * (a + m2));
+        d = 1.0 / not_tiny(1.0 + aa * d);
+        c = not_tiny(1.0 + aa / c);
+        h *= d * c;
+
+        // Odd step of the recurrence.
+        let aa = -(a + m) * (a + b + m) * x / ((a + m2) * (a + 1.0 + m2));
+        d = 1.0 / not_tiny(1.0 + aa * d);
+        c = not_tiny(1.0 + aa / c);
+        let delta = d * c;
+        h *= delta;
+
+        if (delta - 1.0).abs() < EPSILON {
+            break;
+        }
+    }
+    h
+}
+
+// Natural logarithm of the gamma function for `x > 0`, using the Lanczos approximation.
+//
+// See: Numerical Recipes in C, 2nd edition, section 6.1.
+fn ln_gamma(x: f64) -> f64 {
+    const COEFFICIENTS: [f64; 6] = [
+        76.18009172947146,
+        -86.50532032941677,
+        24.01409824083091,
+        -1.231739572450155,
+        0.1208650973866179e-2,
+        -0.5395239384953e-5,
+    ];
+
+    let tmp = x + 5.5;
+    let tmp = tmp - (x + 0.5) * tmp.ln();
+    let mut series = 1.000000000190015;
+    for (i, coefficient) in COEFFICIENTS.iter().enumerate() {
+        series += coefficient / (x + 1.0 + i as f64);
+    }
+    -tmp + (2.5066282746310005 * series / x).ln()
+}
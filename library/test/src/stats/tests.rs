@@ -562,6 +562,29 @@ fn test_unif25() {
     check(val, summ);
 }
 
+#[test]
+fn test_students_t_p_value() {
+    // The t-distribution has a closed form for one and two degrees of freedom.
+    for t in [0.0, 0.5, 1.0, 2.5, -3.0, 10.0] {
+        let one_df = 1.0 - 2.0 * f64::atan(f64::abs(t)) / std::f64::consts::PI;
+        assert_approx_eq!(students_t_p_value(t, 1.0), one_df);
+        let two_df = 1.0 - f64::abs(t) / f64::sqrt(2.0 + t * t);
+        assert_approx_eq!(students_t_p_value(t, 2.0), two_df);
+    }
+}
+
+#[test]
+fn test_welch_t_test() {
+    let a = Summary::new(&[10.0, 11.0, 9.0, 10.5, 9.5, 10.0, 10.2, 9.8]);
+    let b = Summary::new(&[10.1, 11.1, 9.1, 10.6, 9.6, 10.1, 10.3, 9.9]);
+    let c = Summary::new(&[20.0, 21.0, 19.0, 20.5, 19.5, 20.0, 20.2, 19.8]);
+    assert!(welch_t_test(&a, 8, &b, 8) > 0.05);
+    assert!(welch_t_test(&a, 8, &c, 8) < 1.0e-6);
+    assert_eq!(welch_t_test(&a, 8, &b, 8), welch_t_test(&b, 8, &a, 8));
+    assert_eq!(welch_t_test(&a, 8, &a, 8), 1.0);
+    assert_eq!(welch_t_test(&a, 8, &Summary::new(&[10.0]), 1), 1.0);
+}
+
 #[test]
 fn test_sum_f64s() {
     assert_eq!([0.5f64, 3.2321f64, 1.5678f64].sum(), 5.2999);
@@ -15,6 +15,10 @@
 // Start somewhere other than 0 so we know the return code means what we think
 // it means.
 pub const TR_OK: i32 = 50;
+// Return code for a secondary process spawned for `--isolate` whose test failed,
+// as opposed to one which crashed or exited on its own. With panic=abort, a
+// failing test aborts the process instead.
+pub const TR_FAILED: i32 = 51;
 
 // On Windows we use __fastfail to abort, which is documented to use this
 // exception code.
@@ -29,6 +33,10 @@ pub enum TestResult {
     TrIgnored,
     TrBench(BenchSamples),
     TrTimedFail,
+    /// The test exceeded its hard time limit and was killed or abandoned.
+    TrTimedOut,
+    /// The test passed after failing the given number of times.
+    TrFlaky(usize),
 }
 
 /// Creates a `TestResult` depending on the raw result of test execution
@@ -87,22 +95,28 @@ pub fn calc_result<'a>(
 }
 
 /// Creates a `TestResult` depending on the exit code of test subprocess.
+///
+/// With `panic_abort`, a failing test aborts the subprocess, so an abort is a
+/// plain failure. Otherwise it is a crash, like any other fatal signal.
 pub fn get_result_from_exit_code(
     desc: &TestDesc,
     status: ExitStatus,
+    #[cfg_attr(not(any(unix, windows)), allow(unused_variables))] panic_abort: bool,
     time_opts: &Option<time::TestTimeOptions>,
     exec_time: &Option<time::TestExecTime>,
 ) -> TestResult {
     let result = match status.code() {
         Some(TR_OK) => TestResult::TrOk,
+        Some(TR_FAILED) => TestResult::TrFailed,
         #[cfg(windows)]
-        Some(STATUS_ABORTED) => TestResult::TrFailed,
+        Some(STATUS_ABORTED) if panic_abort => TestResult::TrFailed,
         #[cfg(unix)]
         None => match status.signal() {
-            Some(libc::SIGABRT) => TestResult::TrFailed,
-            Some(signal) => {
-                TestResult::TrFailedMsg(format!("child process exited with signal {signal}"))
-            }
+            Some(libc::SIGABRT) if panic_abort => TestResult::TrFailed,
+            Some(signal) => TestResult::TrFailedMsg(match signal_name(signal) {
+                Some(name) => format!("child process terminated by signal {name}"),
+                None => format!("child process terminated by signal {signal}"),
+            }),
             None => unreachable!("status.code() returned None but status.signal() was None"),
         },
         #[cfg(not(unix))]
@@ -127,3 +141,27 @@ pub fn get_result_from_exit_code(
 
     result
 }
+
+/// Returns the name of the signals which usually terminate a test process.
+#[cfg(unix)]
+fn signal_name(signal: i32) -> Option<&'static str> {
+    Some(match signal {
+        libc::SIGHUP => "SIGHUP",
+        libc::SIGINT => "SIGINT",
+        libc::SIGQUIT => "SIGQUIT",
+        libc::SIGILL => "SIGILL",
+        libc::SIGTRAP => "SIGTRAP",
+        libc::SIGABRT => "SIGABRT",
+        #[cfg(not(target_os = "l4re"))]
+        libc::SIGBUS => "SIGBUS",
+        libc::SIGFPE => "SIGFPE",
+        libc::SIGKILL => "SIGKILL",
+        libc::SIGSEGV => "SIGSEGV",
+        libc::SIGPIPE => "SIGPIPE",
+        libc::SIGALRM => "SIGALRM",
+        libc::SIGTERM => "SIGTERM",
+        #[cfg(not(target_os = "l4re"))]
+        libc::SIGSYS => "SIGSYS",
+        _ => return None,
+    })
+}
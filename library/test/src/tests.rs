@@ -1,6 +1,9 @@
 use super::*;
 
+use std::sync::atomic::{AtomicU8, AtomicUsize, Ordering};
+
 use crate::{
+    baseline::{Baseline, BenchChange},
     console::OutputLocation,
     formatters::PrettyFormatter,
     test::{
@@ -20,19 +23,28 @@ fn new() -> TestOpts {
             filters: vec![],
             filter_exact: false,
             force_run_in_process: false,
+            isolate: false,
             exclude_should_panic: false,
             run_ignored: RunIgnored::No,
             run_tests: false,
             bench_benchmarks: false,
+            fuzz: false,
+            fuzz_time: None,
             logfile: None,
             nocapture: false,
+            capture: OutputCapture::Print,
             color: AutoColor,
             format: OutputFormat::Pretty,
             shuffle: false,
             shuffle_seed: None,
+            shard: None,
             test_threads: None,
             skip: vec![],
             time_options: None,
+            timeout: None,
+            retries: 0,
+            save_baseline: None,
+            baseline: None,
             options: Options::new(),
             fail_fast: false,
         }
@@ -52,6 +64,7 @@ fn one_ignored_one_unignored_test() -> Vec<TestDescAndFn> {
                 end_line: 0,
                 end_col: 0,
                 should_panic: ShouldPanic::No,
+                timeout_ms: None,
                 compile_fail: false,
                 no_run: false,
                 test_type: TestType::Unknown,
@@ -69,6 +82,7 @@ fn one_ignored_one_unignored_test() -> Vec<TestDescAndFn> {
                 end_line: 0,
                 end_col: 0,
                 should_panic: ShouldPanic::No,
+                timeout_ms: None,
                 compile_fail: false,
                 no_run: false,
                 test_type: TestType::Unknown,
@@ -94,6 +108,7 @@ fn f() -> Result<(), String> {
             end_line: 0,
             end_col: 0,
             should_panic: ShouldPanic::No,
+            timeout_ms: None,
             compile_fail: false,
             no_run: false,
             test_type: TestType::Unknown,
@@ -122,6 +137,7 @@ fn f() -> Result<(), String> {
             end_line: 0,
             end_col: 0,
             should_panic: ShouldPanic::No,
+            timeout_ms: None,
             compile_fail: false,
             no_run: false,
             test_type: TestType::Unknown,
@@ -153,6 +169,7 @@ fn f() -> Result<(), String> {
             end_line: 0,
             end_col: 0,
             should_panic: ShouldPanic::Yes,
+            timeout_ms: None,
             compile_fail: false,
             no_run: false,
             test_type: TestType::Unknown,
@@ -184,6 +201,7 @@ fn f() -> Result<(), String> {
             end_line: 0,
             end_col: 0,
             should_panic: ShouldPanic::YesWithMessage("error message"),
+            timeout_ms: None,
             compile_fail: false,
             no_run: false,
             test_type: TestType::Unknown,
@@ -220,6 +238,7 @@ fn f() -> Result<(), String> {
             end_line: 0,
             end_col: 0,
             should_panic: ShouldPanic::YesWithMessage(expected),
+            timeout_ms: None,
             compile_fail: false,
             no_run: false,
             test_type: TestType::Unknown,
@@ -260,6 +279,7 @@ fn f() -> Result<(), String> {
             end_line: 0,
             end_col: 0,
             should_panic: ShouldPanic::YesWithMessage(expected),
+            timeout_ms: None,
             compile_fail: false,
             no_run: false,
             test_type: TestType::Unknown,
@@ -294,6 +314,7 @@ fn f() -> Result<(), String> {
                 end_line: 0,
                 end_col: 0,
                 should_panic,
+                timeout_ms: None,
                 compile_fail: false,
                 no_run: false,
                 test_type: TestType::Unknown,
@@ -327,6 +348,7 @@ fn f() -> Result<(), String> {
             end_line: 0,
             end_col: 0,
             should_panic: ShouldPanic::No,
+            timeout_ms: None,
             compile_fail: false,
             no_run: false,
             test_type: TestType::Unknown,
@@ -369,6 +391,7 @@ fn f() -> Result<(), String> {
             end_line: 0,
             end_col: 0,
             should_panic: ShouldPanic::No,
+            timeout_ms: None,
             compile_fail: false,
             no_run: false,
             test_type,
@@ -413,6 +436,7 @@ fn typed_test_desc(test_type: TestType) -> TestDesc {
         end_line: 0,
         end_col: 0,
         should_panic: ShouldPanic::No,
+        timeout_ms: None,
         compile_fail: false,
         no_run: false,
         test_type,
@@ -457,6 +481,162 @@ fn test_time_options_threshold() {
     }
 }
 
+#[test]
+fn test_parse_timeout() {
+    assert_eq!(time::parse_timeout("250"), Ok(Duration::from_millis(250)));
+    assert_eq!(time::parse_timeout("120000"), Ok(Duration::from_secs(120)));
+    assert!(time::parse_timeout("0").is_err());
+    assert!(time::parse_timeout("10s").is_err());
+    assert!(time::parse_timeout("-1").is_err());
+    assert!(time::parse_timeout("").is_err());
+}
+
+fn hard_timeout_template(test_threads: usize) -> Vec<(String, TestResult)> {
+    let test = |name: &'static str, timeout_ms: Option<u64>, sleep_ms: u64| TestDescAndFn {
+        desc: TestDesc {
+            name: StaticTestName(name),
+            timeout_ms,
+            ..typed_test_desc(TestType::Unknown)
+        },
+        testfn: DynTestFn(Box::new(move || {
+            thread::sleep(Duration::from_millis(sleep_ms));
+            Ok(())
+        })),
+    };
+    let tests = vec![
+        test("hangs", Some(10), 60_000),
+        test("quick", Some(60_000), 0),
+        test("default_timeout", None, 60_000),
+    ];
+
+    let opts = TestOpts {
+        run_tests: true,
+        test_threads: Some(test_threads),
+        timeout: Some(Duration::from_millis(10)),
+        ..TestOpts::new()
+    };
+    let mut results = Vec::new();
+    let notify = |event: TestEvent| {
+        if let TestEvent::TeResult(completed) = event {
+            results.push((completed.desc.name.to_string(), completed.result));
+        }
+        Ok(())
+    };
+    run_tests(&opts, tests, notify).unwrap();
+    results.sort_by(|a, b| a.0.cmp(&b.0));
+    results
+}
+
+#[test]
+#[cfg(not(target_os = "emscripten"))]
+fn test_hard_timeout() {
+    let expected = vec![
+        ("default_timeout".to_string(), TrTimedOut),
+        ("hangs".to_string(), TrTimedOut),
+        ("quick".to_string(), TrOk),
+    ];
+    assert_eq!(hard_timeout_template(1), expected);
+    assert_eq!(hard_timeout_template(3), expected);
+}
+
+static FLAKY_RUNS: AtomicUsize = AtomicUsize::new(0);
+static FAILING_RUNS: AtomicUsize = AtomicUsize::new(0);
+static DYNAMIC_RUNS: AtomicUsize = AtomicUsize::new(0);
+
+fn retries_template(test_threads: usize) -> Vec<(String, TestResult, String)> {
+    FLAKY_RUNS.store(0, Ordering::SeqCst);
+    FAILING_RUNS.store(0, Ordering::SeqCst);
+    DYNAMIC_RUNS.store(0, Ordering::SeqCst);
+
+    fn fails_twice() -> Result<(), String> {
+        if FLAKY_RUNS.fetch_add(1, Ordering::SeqCst) < 2 { Err("flake".into()) } else { Ok(()) }
+    }
+    fn always_fails() -> Result<(), String> {
+        FAILING_RUNS.fetch_add(1, Ordering::SeqCst);
+        Err("failure".into())
+    }
+    fn passes() -> Result<(), String> {
+        Ok(())
+    }
+    let test = |name: &'static str, testfn: TestFn| TestDescAndFn {
+        desc: TestDesc { name: StaticTestName(name), ..typed_test_desc(TestType::Unknown) },
+        testfn,
+    };
+    let tests = vec![
+        test("fails_twice", StaticTestFn(fails_twice)),
+        test("always_fails", StaticTestFn(always_fails)),
+        test("passes", StaticTestFn(passes)),
+        test(
+            "dynamic",
+            DynTestFn(Box::new(|| {
+                DYNAMIC_RUNS.fetch_add(1, Ordering::SeqCst);
+                Err("failure".into())
+            })),
+        ),
+    ];
+
+    let opts = TestOpts {
+        run_tests: true,
+        test_threads: Some(test_threads),
+        retries: 3,
+        ..TestOpts::new()
+    };
+    let mut results = Vec::new();
+    let notify = |event: TestEvent| {
+        if let TestEvent::TeResult(completed) = event {
+            let stdout = String::from_utf8(completed.stdout).unwrap();
+            results.push((completed.desc.name.to_string(), completed.result, stdout));
+        }
+        Ok(())
+    };
+    run_tests(&opts, tests, notify).unwrap();
+    results.sort_by(|a, b| a.0.cmp(&b.0));
+    results
+}
+
+#[test]
+fn test_retries() {
+    for test_threads in [1, 3] {
+        let results = retries_template(test_threads);
+        let outcomes: Vec<_> =
+            results.iter().map(|(name, result, _)| (name.as_str(), result.clone())).collect();
+        assert_eq!(
+            outcomes,
+            vec![
+                ("always_fails", TrFailed),
+                ("dynamic", TrFailed),
+                ("fails_twice", TrFlaky(2)),
+                ("passes", TrOk),
+            ]
+        );
+        assert_eq!(FLAKY_RUNS.load(Ordering::SeqCst), 3);
+        assert_eq!(FAILING_RUNS.load(Ordering::SeqCst), 4);
+        assert_eq!(DYNAMIC_RUNS.load(Ordering::SeqCst), 1);
+
+        // The output of the failed attempts is kept.
+        assert_eq!(
+            results[0].2,
+            "note: attempt 1 failed\nnote: attempt 2 failed\nnote: attempt 3 failed\n"
+        );
+    }
+}
+
+#[test]
+fn parse_retries_option() {
+    let args = vec![
+        "progname".to_string(),
+        "-Zunstable-options".to_string(),
+        "--retries".to_string(),
+        "2".to_string(),
+    ];
+    let opts = parse_opts(&args).unwrap().unwrap();
+    assert_eq!(opts.retries, 2);
+
+    let args = vec!["progname".to_string(), "-Zunstable-options".to_string()];
+    let opts = parse_opts(&args).unwrap().unwrap();
+    assert_eq!(opts.retries, 0);
+}
+
 #[test]
 fn parse_ignored_flag() {
     let args = vec!["progname".to_string(), "filter".to_string(), "--ignored".to_string()];
@@ -478,6 +658,162 @@ fn parse_include_ignored_flag() {
     assert_eq!(opts.run_ignored, RunIgnored::Yes);
 }
 
+#[test]
+fn parse_shard_option() {
+    let args = vec![
+        "progname".to_string(),
+        "-Zunstable-options".to_string(),
+        "--shard".to_string(),
+        "1/3".to_string(),
+    ];
+    let opts = parse_opts(&args).unwrap().unwrap();
+    assert_eq!(opts.shard, Some(TestShard { index: 1, count: 3 }));
+
+    for bad in ["3/3", "0/0", "1", "a/3"] {
+        let args = vec![
+            "progname".to_string(),
+            "-Zunstable-options".to_string(),
+            "--shard".to_string(),
+            bad.to_string(),
+        ];
+        assert!(parse_opts(&args).unwrap().is_err(), "--shard {bad} should be rejected");
+    }
+}
+
+#[test]
+fn parse_isolate_flag() {
+    let args =
+        vec!["progname".to_string(), "-Zunstable-options".to_string(), "--isolate".to_string()];
+    let opts = parse_opts(&args).unwrap().unwrap();
+    assert!(opts.isolate);
+
+    let args = vec!["progname".to_string(), "--isolate".to_string()];
+    assert!(parse_opts(&args).unwrap().is_err());
+
+    let args = vec![
+        "progname".to_string(),
+        "-Zunstable-options".to_string(),
+        "--isolate".to_string(),
+        "--force-run-in-process".to_string(),
+    ];
+    assert!(parse_opts(&args).unwrap().is_err());
+}
+
+#[test]
+fn parse_capture_option() {
+    let args = vec!["progname".to_string()];
+    let opts = parse_opts(&args).unwrap().unwrap();
+    assert_eq!(opts.capture, OutputCapture::Print);
+
+    let args =
+        vec!["progname".to_string(), "-Zunstable-options".to_string(), "--capture=fd".to_string()];
+    let opts = parse_opts(&args).unwrap().unwrap();
+    assert_eq!(opts.capture, OutputCapture::Fd);
+
+    for bad in [
+        &["--capture=fd"][..],
+        &["-Zunstable-options", "--capture=all"],
+        &["-Zunstable-options", "--capture=fd", "--nocapture"],
+    ] {
+        let args: Vec<_> = ["progname"].iter().chain(bad).map(|arg| arg.to_string()).collect();
+        assert!(parse_opts(&args).unwrap().is_err(), "{bad:?} should be rejected");
+    }
+}
+
+#[test]
+fn parse_fuzz_options() {
+    let args = vec!["progname".to_string()];
+    let opts = parse_opts(&args).unwrap().unwrap();
+    assert!(!opts.fuzz);
+    assert!(opts.run_tests);
+
+    let args = vec![
+        "progname".to_string(),
+        "-Zunstable-options".to_string(),
+        "--fuzz".to_string(),
+        "--fuzz-time=120000".to_string(),
+    ];
+    let opts = parse_opts(&args).unwrap().unwrap();
+    assert!(opts.fuzz);
+    assert!(!opts.run_tests);
+    assert_eq!(opts.fuzz_time, Some(Duration::from_secs(120)));
+
+    for bad in [&["--fuzz"][..], &["-Zunstable-options", "--fuzz", "--fuzz-time=0"]] {
+        let args: Vec<_> = ["progname"].iter().chain(bad).map(|arg| arg.to_string()).collect();
+        assert!(parse_opts(&args).unwrap().is_err(), "{bad:?} should be rejected");
+    }
+}
+
+#[test]
+fn fuzz_target_runs_its_corpus() {
+    let dir = env::temp_dir().join(format!("libtest-fuzz-corpus-{}", process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(dir.join("a"), b"fine").unwrap();
+    std::fs::write(dir.join("b"), b"bad").unwrap();
+
+    let result = fuzz::run_corpus(&dir, |data| match data {
+        b"bad" => Err("bad input".to_string()),
+        _ => Ok(()),
+    });
+    std::fs::remove_dir_all(&dir).unwrap();
+    assert_eq!(result, Err(format!("bad input\ninput: {}", dir.join("b").display())));
+
+    // Without a corpus, the target runs with an empty input.
+    let result = fuzz::run_corpus(&dir, |data| {
+        if data.is_empty() { Err("empty input".to_string()) } else { Ok(()) }
+    });
+    assert_eq!(result, Err("empty input".to_string()));
+}
+
+#[test]
+fn fuzzing_saves_crashing_input() {
+    let dir = env::temp_dir().join(format!("libtest-fuzz-crash-{}", process::id()));
+    let options = fuzz::FuzzOptions { time: Duration::from_secs(60), panic_abort: false };
+    let result = fuzz::fuzz_loop(
+        &dir,
+        options,
+        |data| match data.first() {
+            Some(0xff) => Err("found it".to_string()),
+            _ => Ok(()),
+        },
+        || {},
+    );
+
+    let crashes: Vec<_> = std::fs::read_dir(&dir).unwrap().map(|e| e.unwrap().path()).collect();
+    std::fs::remove_dir_all(&dir).unwrap();
+    let [crash] = &crashes[..] else { panic!("expected one crashing input, got {crashes:?}") };
+    assert!(crash.file_name().unwrap().to_str().unwrap().starts_with("crash-"));
+    assert_eq!(
+        result.err(),
+        Some(format!("found it\ncrashing input written to {}", crash.display()))
+    );
+}
+
+#[test]
+fn fuzzing_measures_registered_counters() {
+    static COUNTERS: [AtomicU8; 4] =
+        [AtomicU8::new(0), AtomicU8::new(0), AtomicU8::new(0), AtomicU8::new(0)];
+    let start = COUNTERS.as_ptr() as *mut u8;
+    // SAFETY: the counters are statics, and are only written as atomics.
+    unsafe { fuzz::register_8bit_counters(start, start.add(COUNTERS.len())) };
+
+    let dir = env::temp_dir().join(format!("libtest-fuzz-coverage-{}", process::id()));
+    let options = fuzz::FuzzOptions { time: Duration::from_millis(100), panic_abort: false };
+    let result = fuzz::fuzz_loop(
+        &dir,
+        options,
+        |data| {
+            COUNTERS[data.len() % 4].fetch_add(1, Ordering::Relaxed);
+            Ok(())
+        },
+        || {},
+    );
+    let _ = std::fs::remove_dir_all(&dir);
+
+    let stats = result.unwrap().to_string();
+    assert!(stats.ends_with(" coverage features\n"), "{stats}");
+}
+
 #[test]
 pub fn filter_for_ignored_option() {
     // When we run ignored tests the test filter should filter out all the
@@ -530,6 +866,7 @@ pub fn exclude_should_panic_option() {
             end_line: 0,
             end_col: 0,
             should_panic: ShouldPanic::Yes,
+            timeout_ms: None,
             compile_fail: false,
             no_run: false,
             test_type: TestType::Unknown,
@@ -559,6 +896,7 @@ fn tests() -> Vec<TestDescAndFn> {
                     end_line: 0,
                     end_col: 0,
                     should_panic: ShouldPanic::No,
+                    timeout_ms: None,
                     compile_fail: false,
                     no_run: false,
                     test_type: TestType::Unknown,
@@ -655,6 +993,7 @@ fn testfn() -> Result<(), String> {
                 end_line: 0,
                 end_col: 0,
                 should_panic: ShouldPanic::No,
+                timeout_ms: None,
                 compile_fail: false,
                 no_run: false,
                 test_type: TestType::Unknown,
@@ -666,6 +1005,26 @@ fn testfn() -> Result<(), String> {
     tests
 }
 
+#[test]
+pub fn shards_partition_tests() {
+    let all_names: Vec<_> = sample_tests().into_iter().map(|t| t.desc.name).collect();
+
+    let count = 3;
+    let mut seen = Vec::new();
+    for index in 0..count {
+        let opts = TestOpts { shard: Some(TestShard { index, count }), ..TestOpts::new() };
+        let shard = filter_tests(&opts, sample_tests());
+        // Sharding is deterministic.
+        let again = filter_tests(&opts, sample_tests());
+        assert!(shard.iter().zip(&again).all(|(a, b)| a.desc.name == b.desc.name));
+        seen.extend(shard.into_iter().map(|t| t.desc.name));
+    }
+
+    // Every test runs in exactly one shard.
+    assert_eq!(seen.len(), all_names.len());
+    assert!(all_names.iter().all(|name| seen.contains(name)));
+}
+
 #[test]
 pub fn shuffle_tests() {
     let mut opts = TestOpts::new();
@@ -789,6 +1148,7 @@ fn f(_: &mut Bencher) -> Result<(), String> {
         end_line: 0,
         end_col: 0,
         should_panic: ShouldPanic::No,
+        timeout_ms: None,
         compile_fail: false,
         no_run: false,
         test_type: TestType::Unknown,
@@ -817,6 +1177,7 @@ fn f(b: &mut Bencher) -> Result<(), String> {
         end_line: 0,
         end_col: 0,
         should_panic: ShouldPanic::No,
+        timeout_ms: None,
         compile_fail: false,
         no_run: false,
         test_type: TestType::Unknown,
@@ -826,6 +1187,85 @@ fn f(b: &mut Bencher) -> Result<(), String> {
     rx.recv().unwrap();
 }
 
+#[test]
+fn parse_baseline_options() {
+    let args = vec![
+        "progname".to_string(),
+        "-Zunstable-options".to_string(),
+        "--bench".to_string(),
+        "--save-baseline".to_string(),
+        "new".to_string(),
+        "--baseline".to_string(),
+        "main".to_string(),
+    ];
+    let opts = parse_opts(&args).unwrap().unwrap();
+    assert_eq!(opts.save_baseline.as_deref(), Some("new"));
+    assert_eq!(opts.baseline.as_deref(), Some("main"));
+
+    for name in ["", "..", "a/b"] {
+        let args = vec![
+            "progname".to_string(),
+            "-Zunstable-options".to_string(),
+            "--baseline".to_string(),
+            name.to_string(),
+        ];
+        assert!(parse_opts(&args).unwrap().is_err());
+    }
+}
+
+#[test]
+pub fn baseline_round_trip_and_compare() {
+    let noisy = |median: f64| {
+        let samples: Vec<f64> = (0..50).map(|i| median + (i % 5) as f64).collect();
+        stats::Summary::new(&samples)
+    };
+    let mut old = Baseline::new();
+    old.insert("same", noisy(100.0), 50);
+    old.insert("slower", noisy(100.0), 50);
+    old.insert("faster", noisy(100.0), 50);
+    old.insert("removed", noisy(100.0), 50);
+
+    let path = env::temp_dir().join(format!("libtest-baseline-{}", process::id()));
+    old.save(&path).unwrap();
+    let loaded = Baseline::load(&path);
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(loaded.unwrap(), old);
+
+    let mut new = Baseline::new();
+    new.insert("same", noisy(100.0), 50);
+    new.insert("slower", noisy(150.0), 50);
+    new.insert("faster", noisy(50.0), 50);
+    new.insert("added", noisy(100.0), 50);
+
+    let changes: Vec<_> = old.compare(&new).iter().map(|c| (c.name.clone(), c.change())).collect();
+    assert_eq!(
+        changes,
+        vec![
+            ("faster".to_string(), BenchChange::Improved),
+            ("same".to_string(), BenchChange::Unchanged),
+            ("slower".to_string(), BenchChange::Regressed),
+        ]
+    );
+}
+
+#[test]
+pub fn baseline_change_agrees_with_reported_mean() {
+    // The median gets lower, but a long tail makes the mean higher.
+    let old: Vec<f64> = (0..50).map(|i| 100.0 + (i % 5) as f64).collect();
+    let new: Vec<f64> =
+        (0..50).map(|i| if i < 26 { 90.0 } else { 200.0 } + (i % 5) as f64).collect();
+    let (old, new) = (stats::Summary::new(&old), stats::Summary::new(&new));
+    assert!(new.median < old.median);
+
+    let mut baseline = Baseline::new();
+    baseline.insert("bench", old, 50);
+    let mut current = Baseline::new();
+    current.insert("bench", new, 50);
+    let comparisons = baseline.compare(&current);
+    assert_eq!(comparisons[0].change(), BenchChange::Regressed);
+    assert!(comparisons[0].mean_change_pct() > 0.0);
+}
+
 #[test]
 fn should_sort_failures_before_printing_them() {
     let test_a = TestDesc {
@@ -838,6 +1278,7 @@ fn should_sort_failures_before_printing_them() {
         end_line: 0,
         end_col: 0,
         should_panic: ShouldPanic::No,
+        timeout_ms: None,
         compile_fail: false,
         no_run: false,
         test_type: TestType::Unknown,
@@ -853,6 +1294,7 @@ fn should_sort_failures_before_printing_them() {
         end_line: 0,
         end_col: 0,
         should_panic: ShouldPanic::No,
+        timeout_ms: None,
         compile_fail: false,
         no_run: false,
         test_type: TestType::Unknown,
@@ -864,17 +1306,21 @@ fn should_sort_failures_before_printing_them() {
         log_out: None,
         total: 0,
         passed: 0,
+        flaky: 0,
         failed: 0,
         ignored: 0,
         filtered_out: 0,
         measured: 0,
         exec_time: None,
         metrics: MetricMap::new(),
+        benchmarks: Baseline::new(),
         failures: vec![(test_b, Vec::new()), (test_a, Vec::new())],
         options: Options::new(),
         not_failures: Vec::new(),
+        flakes: Vec::new(),
         ignores: Vec::new(),
         time_failures: Vec::new(),
+        teardown_failures: Vec::new(),
     };
 
     out.write_failures(&st).unwrap();
@@ -905,6 +1351,7 @@ fn f(_: &mut Bencher) -> Result<(), String> {
             end_line: 0,
             end_col: 0,
             should_panic: ShouldPanic::No,
+            timeout_ms: None,
             compile_fail: false,
             no_run: false,
             test_type: TestType::Unknown,
@@ -922,3 +1369,11 @@ fn f(_: &mut Bencher) -> Result<(), String> {
     let result = rx.recv().unwrap().result;
     assert_eq!(result, TrFailed);
 }
+
+#[test]
+fn test_module_of_parameterized_test_case() {
+    assert_eq!(fixture::test_module("db::insert"), "db");
+    assert_eq!(fixture::test_module("db::insert::case_3"), "db");
+    assert_eq!(fixture::test_module("insert::case_12"), "");
+    assert_eq!(fixture::test_module("db::case_study"), "db");
+}
@@ -61,6 +61,25 @@ pub fn get_default_test_timeout() -> Instant {
     Instant::now() + Duration::from_secs(TEST_WARN_TIMEOUT_S)
 }
 
+/// Parses a time limit given as a number of milliseconds.
+///
+/// Time limits with units are only accepted by `#[test_timeout]`, which the
+/// compiler turns into milliseconds.
+pub fn parse_timeout(timeout: &str) -> Result<Duration, String> {
+    match timeout.parse::<u64>() {
+        Ok(0) | Err(_) => {
+            Err(format!("expected a nonzero number of milliseconds, but got `{timeout}`"))
+        }
+        Ok(millis) => Ok(Duration::from_millis(millis)),
+    }
+}
+
+/// Returns the hard time limit of a test: the one set with `#[test_timeout]` takes
+/// precedence over the default one given on the command line.
+pub fn get_test_hard_timeout(desc: &TestDesc, default: Option<Duration>) -> Option<Duration> {
+    desc.timeout_ms.map(Duration::from_millis).or(default)
+}
+
 /// The measured execution time of a unit test.
 #[derive(Debug, Clone, PartialEq)]
 pub struct TestExecTime(pub Duration);
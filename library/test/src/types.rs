@@ -89,6 +89,8 @@ pub enum TestFn {
     DynTestFn(Box<dyn FnOnce() -> Result<(), String> + Send>),
     DynBenchFn(Box<dyn Fn(&mut Bencher) -> Result<(), String> + Send>),
     DynBenchAsTestFn(Box<dyn Fn(&mut Bencher) -> Result<(), String> + Send>),
+    StaticFuzzFn(fn(&[u8]) -> Result<(), String>),
+    StaticFuzzAsTestFn(fn(&[u8]) -> Result<(), String>),
 }
 
 impl TestFn {
@@ -100,6 +102,21 @@ pub fn padding(&self) -> NamePadding {
             DynTestFn(..) => PadNone,
             DynBenchFn(..) => PadOnRight,
             DynBenchAsTestFn(..) => PadNone,
+            StaticFuzzFn(..) => PadNone,
+            StaticFuzzAsTestFn(..) => PadNone,
+        }
+    }
+
+    /// Copies the test function, if it can be run more than once. Dynamic
+    /// test functions are consumed by running them.
+    pub(crate) fn try_clone(&self) -> Option<TestFn> {
+        match *self {
+            StaticTestFn(f) => Some(StaticTestFn(f)),
+            StaticBenchFn(f) => Some(StaticBenchFn(f)),
+            StaticBenchAsTestFn(f) => Some(StaticBenchAsTestFn(f)),
+            StaticFuzzFn(f) => Some(StaticFuzzFn(f)),
+            StaticFuzzAsTestFn(f) => Some(StaticFuzzAsTestFn(f)),
+            DynTestFn(..) | DynBenchFn(..) | DynBenchAsTestFn(..) => None,
         }
     }
 
@@ -111,6 +128,8 @@ pub(crate) fn into_runnable(self) -> Runnable {
             DynTestFn(f) => Runnable::Test(RunnableTest::Dynamic(f)),
             DynBenchFn(f) => Runnable::Bench(RunnableBench::Dynamic(f)),
             DynBenchAsTestFn(f) => Runnable::Test(RunnableTest::DynamicBenchAsTest(f)),
+            StaticFuzzFn(f) => Runnable::Fuzz(f),
+            StaticFuzzAsTestFn(f) => Runnable::Test(RunnableTest::StaticFuzzAsTest(f)),
         }
     }
 }
@@ -124,6 +143,8 @@ fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
             DynTestFn(..) => "DynTestFn(..)",
             DynBenchFn(..) => "DynBenchFn(..)",
             DynBenchAsTestFn(..) => "DynBenchAsTestFn(..)",
+            StaticFuzzFn(..) => "StaticFuzzFn(..)",
+            StaticFuzzAsTestFn(..) => "StaticFuzzAsTestFn(..)",
         })
     }
 }
@@ -131,6 +152,7 @@ fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
 pub(crate) enum Runnable {
     Test(RunnableTest),
     Bench(RunnableBench),
+    Fuzz(fn(&[u8]) -> Result<(), String>),
 }
 
 pub(crate) enum RunnableTest {
@@ -138,10 +160,12 @@ pub(crate) enum RunnableTest {
     Dynamic(Box<dyn FnOnce() -> Result<(), String> + Send>),
     StaticBenchAsTest(fn(&mut Bencher) -> Result<(), String>),
     DynamicBenchAsTest(Box<dyn Fn(&mut Bencher) -> Result<(), String> + Send>),
+    StaticFuzzAsTest(fn(&[u8]) -> Result<(), String>),
 }
 
 impl RunnableTest {
-    pub(crate) fn run(self) -> Result<(), String> {
+    /// Runs the test named `name`.
+    pub(crate) fn run(self, name: &TestName) -> Result<(), String> {
         match self {
             RunnableTest::Static(f) => __rust_begin_short_backtrace(f),
             RunnableTest::Dynamic(f) => __rust_begin_short_backtrace(f),
@@ -151,6 +175,9 @@ pub(crate) fn run(self) -> Result<(), String> {
             RunnableTest::DynamicBenchAsTest(f) => {
                 crate::bench::run_once(|b| __rust_begin_short_backtrace(|| f(b)))
             }
+            RunnableTest::StaticFuzzAsTest(f) => {
+                crate::fuzz::run_corpus(&crate::fuzz::corpus_dir(name), f)
+            }
         }
     }
 
@@ -160,6 +187,7 @@ pub(crate) fn is_dynamic(&self) -> bool {
             RunnableTest::StaticBenchAsTest(_) => false,
             RunnableTest::Dynamic(_) => true,
             RunnableTest::DynamicBenchAsTest(_) => true,
+            RunnableTest::StaticFuzzAsTest(_) => false,
         }
     }
 }
@@ -205,6 +233,9 @@ pub struct TestDesc {
     pub end_line: usize,
     pub end_col: usize,
     pub should_panic: options::ShouldPanic,
+    /// Hard time limit in milliseconds, after which the test is considered failed
+    /// and abandoned.
+    pub timeout_ms: Option<u64>,
     pub compile_fail: bool,
     pub no_run: bool,
     pub test_type: TestType,
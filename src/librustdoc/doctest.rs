@@ -1161,6 +1161,7 @@ fn add_test(&mut self, test: String, config: LangString, line: usize) {
                 end_col: 0,
                 // compiler failures are test failures
                 should_panic: test::ShouldPanic::No,
+                timeout_ms: None,
                 compile_fail: config.compile_fail,
                 no_run,
                 test_type: test::TestType::DocTest,
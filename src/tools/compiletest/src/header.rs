@@ -1441,6 +1441,7 @@ macro_rules! decision {
         end_line: 0,
         end_col: 0,
         should_panic,
+        timeout_ms: None,
         compile_fail: false,
         no_run: false,
         test_type: test::TestType::Unknown,
@@ -533,16 +533,25 @@ pub fn test_opts(config: &Config) -> test::TestOpts {
         logfile: config.logfile.clone(),
         run_tests: true,
         bench_benchmarks: true,
+        fuzz: false,
+        fuzz_time: None,
         nocapture: config.nocapture,
+        capture: test::OutputCapture::Print,
         color: config.color,
         shuffle: false,
         shuffle_seed: None,
+        shard: None,
         test_threads: None,
         skip: config.skip.clone(),
         list: false,
         options: test::Options::new(),
         time_options: None,
+        timeout: None,
+        retries: 0,
+        save_baseline: None,
+        baseline: None,
         force_run_in_process: false,
+        isolate: false,
         fail_fast: std::env::var_os("RUSTC_TEST_FAIL_FAST").is_some(),
     }
 }
@@ -0,0 +1,16 @@
+#![feature(fuzz, test)]
+
+extern crate test;
+
+#[no_mangle]
+pub extern "C" fn __sanitizer_cov_8bit_counters_init(start: *mut u8, stop: *mut u8) {
+    // SAFETY: the instrumentation passes the bounds of its counters.
+    unsafe { test::fuzz::register_8bit_counters(start, stop) }
+}
+
+#[fuzz]
+fn parses(data: &[u8]) {
+    if let Ok(s) = std::str::from_utf8(data) {
+        let _ = s.parse::<i64>();
+    }
+}
@@ -0,0 +1,30 @@
+#![feature(fuzz, test)]
+
+extern crate test;
+
+extern "C" {
+    fn __llvm_profile_begin_counters() -> *mut u64;
+    fn __llvm_profile_end_counters() -> *mut u64;
+}
+
+// Registers the counters of the profiler runtime before `main` runs.
+#[used]
+#[link_section = ".init_array"]
+static REGISTER_PROFILE_COUNTERS: extern "C" fn() = register_profile_counters;
+
+extern "C" fn register_profile_counters() {
+    // SAFETY: the profiler runtime returns the bounds of its counters.
+    unsafe {
+        test::fuzz::register_profile_counters(
+            __llvm_profile_begin_counters(),
+            __llvm_profile_end_counters(),
+        )
+    }
+}
+
+#[fuzz]
+fn parses(data: &[u8]) {
+    if let Ok(s) = std::str::from_utf8(data) {
+        let _ = s.parse::<i64>();
+    }
+}
@@ -0,0 +1,46 @@
+//@ ignore-cross-compile
+//@ needs-unwind
+//@ needs-profiler-support
+//@ only-linux
+
+// Tests that `--fuzz` measures coverage with the counters of SanitizerCoverage and of
+// `-Cinstrument-coverage` which the fuzz crate registers, and says when it has none.
+
+extern crate run_make_support;
+
+use run_make_support::{rustc, tmp_dir};
+use std::process::Command;
+
+fn fuzz(bin: &str) -> String {
+    let output = Command::new(tmp_dir().join(bin))
+        .env("RUST_BACKTRACE", "0")
+        .env("RUST_TEST_FUZZ_CORPUS_DIR", tmp_dir().join("corpus"))
+        .env("LLVM_PROFILE_FILE", tmp_dir().join("default.profraw"))
+        .args(["-Zunstable-options", "--fuzz", "--fuzz-time=1000", "--show-output"])
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(output.status.success(), "fuzzing failed:\n{stdout}");
+    stdout
+}
+
+fn main() {
+    // Without instrumentation, fuzzing says that it is blind.
+    rustc().arg("--test").input("f.rs").run();
+    let stdout = fuzz("f");
+    assert!(stdout.contains("mutated blindly without coverage instrumentation"), "{stdout}");
+
+    rustc()
+        .arg("--test")
+        .input("f.rs")
+        .arg("-Cpasses=sancov-module")
+        .arg("-Cllvm-args=-sanitizer-coverage-level=3")
+        .arg("-Cllvm-args=-sanitizer-coverage-inline-8bit-counters")
+        .run();
+    let stdout = fuzz("f");
+    assert!(stdout.contains(" coverage features"), "{stdout}");
+
+    rustc().arg("--test").input("profile.rs").arg("-Cinstrument-coverage").run();
+    let stdout = fuzz("profile");
+    assert!(stdout.contains(" coverage features"), "{stdout}");
+}
@@ -0,0 +1,17 @@
+#![feature(fuzz)]
+
+#[fuzz]
+fn finds_bang(data: &[u8]) {
+    if data.contains(&b'!') {
+        panic!("found a bang");
+    }
+}
+
+#[fuzz]
+fn never_crashes(data: &[u8]) -> Result<(), String> {
+    match std::str::from_utf8(data) {
+        Ok(s) => assert_eq!(s.len(), data.len()),
+        Err(e) => assert!(e.valid_up_to() < data.len()),
+    }
+    Ok(())
+}
@@ -0,0 +1,63 @@
+//@ ignore-cross-compile
+//@ needs-unwind
+
+// Tests that fuzz targets run their corpus as tests, and that `--fuzz` writes the
+// inputs which make them crash to their corpus, so that later test runs fail.
+
+extern crate run_make_support;
+
+use run_make_support::{rustc, tmp_dir};
+use std::process::{Command, Output};
+
+fn run_f(args: &[&str]) -> Output {
+    Command::new(tmp_dir().join("f"))
+        .env("RUST_BACKTRACE", "0")
+        .env("RUST_TEST_FUZZ_CORPUS_DIR", tmp_dir().join("corpus"))
+        .arg("--test-threads=1")
+        .args(args)
+        .output()
+        .unwrap()
+}
+
+#[track_caller]
+fn assert_contains(output: &Output, needles: &[&str]) {
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    for needle in needles {
+        assert!(stdout.contains(needle), "expected `{needle}` in:\n{stdout}");
+    }
+}
+
+fn main() {
+    rustc().arg("--test").input("f.rs").run();
+    let corpus = tmp_dir().join("corpus").join("finds_bang");
+
+    // Without a corpus, fuzz targets run with an empty input.
+    let output = run_f(&[]);
+    assert!(output.status.success());
+    assert_contains(&output, &["test finds_bang ... ok", "test never_crashes ... ok"]);
+
+    // Fuzzing a target which doesn't crash succeeds once its time is up.
+    let output = run_f(&[
+        "-Zunstable-options",
+        "--fuzz",
+        "--fuzz-time=1000",
+        "--show-output",
+        "never_crashes",
+    ]);
+    assert!(output.status.success());
+    assert_contains(&output, &["test never_crashes ... ok", "fuzzed for"]);
+
+    // Fuzzing a target which crashes writes the crashing input to its corpus...
+    let output = run_f(&["-Zunstable-options", "--fuzz", "finds_bang"]);
+    assert!(!output.status.success());
+    let crash = format!("{}/crash-", corpus.display());
+    let written = format!("crashing input written to {crash}");
+    assert_contains(&output, &["test finds_bang ... FAILED", "found a bang", &written]);
+    let crashes = std::fs::read_dir(&corpus).unwrap().count();
+    assert_eq!(crashes, 1);
+
+    // ...which then makes the target fail as a test.
+    let output = run_f(&[]);
+    assert!(!output.status.success());
+    assert_contains(&output, &["test finds_bang ... FAILED", &format!("input: {crash}")]);
+}
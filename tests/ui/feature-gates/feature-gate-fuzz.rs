@@ -0,0 +1,4 @@
+//@ compile-flags: --test
+
+#[fuzz] //~ ERROR use of unstable library feature 'fuzz'
+fn target(_data: &[u8]) {}
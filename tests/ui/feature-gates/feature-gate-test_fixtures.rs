@@ -0,0 +1,4 @@
+//@ compile-flags: --test
+
+#[test]
+fn uses_fixture(_dir: &String) {} //~ ERROR fixtures passed to tests are experimental
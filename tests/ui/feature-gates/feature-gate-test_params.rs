@@ -0,0 +1,5 @@
+//@ compile-flags: --test
+
+#[test]
+#[test_params(1)] //~ ERROR the `#[test_params]` attribute is an experimental feature
+fn case(n: i32) {}
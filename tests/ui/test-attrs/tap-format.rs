@@ -0,0 +1,29 @@
+//@ compile-flags: --test
+//@ run-fail
+//@ run-flags: --test-threads=1 --format=tap -Zunstable-options
+//@ check-run-results
+//@ exec-env:RUST_BACKTRACE=0
+//@ normalize-stdout-test "finished in \d+\.\d+s" -> "finished in $$TIME"
+//@ ignore-emscripten no threads support
+//@ needs-unwind
+
+#[test]
+fn a() {
+    println!("print from successful test");
+}
+
+#[test]
+fn b() {
+    println!("print from failing test");
+    assert!(false);
+}
+
+#[test]
+#[should_panic(expected = "foo")]
+fn c() {
+    panic!("bar");
+}
+
+#[test]
+#[ignore = "not yet"]
+fn d() {}
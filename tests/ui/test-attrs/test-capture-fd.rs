@@ -0,0 +1,33 @@
+//@ compile-flags: --test
+//@ run-flags: -Zunstable-options --capture=fd --test-threads=1
+//@ run-fail
+//@ check-run-results
+//@ exec-env:RUST_BACKTRACE=0
+//@ normalize-stdout-test "finished in \d+\.\d+s" -> "finished in $$TIME"
+
+//@ only-unix standard error is merged into standard output with dup2
+//@ ignore-android #120567
+//@ ignore-sgx no subprocess support
+
+use std::fs::File;
+use std::io::Write;
+use std::mem::ManuallyDrop;
+use std::os::unix::io::FromRawFd;
+
+#[test]
+fn it_fails() {
+    println!("printed to stdout");
+    eprintln!("printed to stderr");
+    // Writes which bypass `print!`, like those of C libraries, are captured too.
+    let mut stdout = ManuallyDrop::new(unsafe { File::from_raw_fd(1) });
+    stdout.write_all(b"written to fd 1\n").unwrap();
+    let mut stderr = ManuallyDrop::new(unsafe { File::from_raw_fd(2) });
+    stderr.write_all(b"written to fd 2\n").unwrap();
+    assert_eq!(1 + 1, 3);
+}
+
+#[test]
+fn it_works() {
+    let mut stdout = ManuallyDrop::new(unsafe { File::from_raw_fd(1) });
+    stdout.write_all(b"not shown\n").unwrap();
+}
@@ -0,0 +1,71 @@
+//@ compile-flags: --test
+//@ run-flags: --test-threads=1
+//@ run-fail
+//@ check-run-results
+//@ exec-env:RUST_BACKTRACE=0
+//@ normalize-stdout-test "finished in \d+\.\d+s" -> "finished in $$TIME"
+//@ needs-unwind
+
+#![feature(test, test_fixtures)]
+
+extern crate test;
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use test::fixture::{Fixture, FixtureScope};
+
+static CONNECTIONS: AtomicUsize = AtomicUsize::new(0);
+
+struct TempDir {
+    files: AtomicUsize,
+}
+
+impl Fixture for TempDir {
+    fn setup() -> Self {
+        TempDir { files: AtomicUsize::new(0) }
+    }
+
+    fn teardown(self) -> Result<(), String> {
+        if self.files.into_inner() > 0 { Err("directory not empty".to_string()) } else { Ok(()) }
+    }
+}
+
+#[test]
+fn empty_dir(dir: &TempDir) {
+    assert_eq!(dir.files.load(Ordering::SeqCst), 0);
+}
+
+#[test]
+fn leaves_files(dir: &TempDir) {
+    dir.files.fetch_add(1, Ordering::SeqCst);
+    panic!("failed to write file");
+}
+
+mod db {
+    use super::*;
+
+    struct Database;
+
+    impl Fixture for Database {
+        const SCOPE: FixtureScope = FixtureScope::Module;
+
+        fn setup() -> Self {
+            CONNECTIONS.fetch_add(1, Ordering::SeqCst);
+            Database
+        }
+
+        fn teardown(self) -> Result<(), String> {
+            Err("connection reset".to_string())
+        }
+    }
+
+    #[test]
+    fn insert(_db: &Database, dir: &TempDir) {
+        assert_eq!(CONNECTIONS.load(Ordering::SeqCst), 1);
+        assert_eq!(dir.files.load(Ordering::SeqCst), 0);
+    }
+
+    #[test]
+    fn select(_db: &Database) {
+        assert_eq!(CONNECTIONS.load(Ordering::SeqCst), 1);
+    }
+}
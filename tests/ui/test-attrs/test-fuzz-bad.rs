@@ -0,0 +1,17 @@
+//@ compile-flags: --test
+
+#![feature(fuzz, test_params)]
+
+#[fuzz]
+fn no_input() {} //~ ERROR functions used as fuzz targets must have signature
+
+#[fuzz]
+fn two_inputs(_data: &[u8], _more: &[u8]) {} //~ ERROR functions used as fuzz targets must have signature
+
+#[fuzz]
+#[test_params(b"")] //~ ERROR `#[test_params]` cannot be used on fuzz targets
+fn params(_data: &[u8]) {}
+
+#[fuzz]
+#[should_panic] //~ ERROR `#[should_panic]` cannot be used on fuzz targets
+fn should_panic(_data: &[u8]) {}
@@ -0,0 +1,50 @@
+//@ compile-flags: --test
+//@ run-flags: -Zunstable-options --isolate --test-threads=1
+//@ run-fail
+//@ check-run-results
+//@ exec-env:RUST_BACKTRACE=0
+//@ normalize-stdout-test "finished in \d+\.\d+s" -> "finished in $$TIME"
+//@ needs-unwind
+//@ ignore-android #120567
+//@ ignore-sgx no subprocess support
+//@ ignore-windows aborts are reported by exit code rather than by signal
+
+use std::env;
+
+#[test]
+fn aborts() {
+    println!("about to abort");
+    std::process::abort();
+}
+
+#[test]
+fn changes_env() {
+    env::set_var("TEST_ISOLATE_VAR", "1");
+}
+
+#[test]
+fn exits() {
+    std::process::exit(0);
+}
+
+#[test]
+fn panics() {
+    assert_eq!(1 + 1, 3);
+}
+
+#[test]
+#[should_panic]
+fn should_panic() {
+    panic!("expected");
+}
+
+#[test]
+fn sees_clean_env() {
+    assert!(env::var_os("TEST_ISOLATE_VAR").is_none());
+    for (key, _) in env::vars() {
+        // Look for keys like __RUST_TEST_INVOKE.
+        if key.contains("TEST_INVOKE") {
+            panic!("shouldn't have '{}' in environment", key);
+        }
+    }
+}
@@ -0,0 +1,26 @@
+//@ compile-flags: --test
+
+#![feature(test, test_params)]
+
+extern crate test;
+
+#[test]
+#[test_params(1, 2)]
+#[test_params(3)] //~ ERROR this test case has 1 argument but the test function takes 2
+fn wrong_count(a: i32, b: i32) {}
+
+#[test]
+#[test_params = "1"] //~ ERROR malformed `test_params` attribute input
+fn name_value(a: i32) {}
+
+#[test]
+#[test_params(1 2)] //~ ERROR expected one of `,`, `.`, `?`, or an operator, found `2`
+fn missing_comma(a: i32) {}
+
+#[test]
+#[test_params(1; skip)] //~ ERROR expected `ignore` or `ignore = "reason"`
+fn bad_ignore(a: i32) {}
+
+#[bench]
+#[test_params(1)] //~ ERROR `#[test_params]` cannot be used on benchmarks
+fn bench(b: &mut test::Bencher) {}
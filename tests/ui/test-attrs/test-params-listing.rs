@@ -0,0 +1,24 @@
+//@ no-prefer-dynamic
+//@ compile-flags: --test
+//@ run-flags: --list --format json -Zunstable-options
+//@ run-pass
+//@ check-run-results
+//@ only-nightly
+//@ normalize-stdout-test: "fake-test-src-base/test-attrs/" -> "$$DIR/"
+//@ normalize-stdout-test: "fake-test-src-base\\test-attrs\\" -> "$$DIR/"
+
+// Checks that each case of a parameterized test is listed as its own test,
+// located at its `#[test_params]` attribute.
+
+#![cfg(test)]
+#![feature(test_params)]
+
+#[test]
+#[test_params(1)]
+#[test_params(2)]
+fn positive(n: i32) {
+    assert!(n > 0);
+}
+
+#[test]
+fn plain() {}
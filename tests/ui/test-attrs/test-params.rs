@@ -0,0 +1,37 @@
+//@ compile-flags: --test
+//@ run-flags: --test-threads=1 --skip parse::case_3
+//@ run-fail
+//@ check-run-results
+//@ exec-env:RUST_BACKTRACE=0
+//@ normalize-stdout-test "finished in \d+\.\d+s" -> "finished in $$TIME"
+//@ needs-unwind
+
+#![feature(test_params)]
+
+use std::num::ParseIntError;
+
+#[test]
+#[test_params(1, 2, 3)]
+#[test_params(-1, 1, 0)]
+#[test_params(2, 2, 5)]
+#[test_params(0, 0, 1; ignore = "wrong sum")]
+fn add(a: i32, b: i32, sum: i32) {
+    assert_eq!(a + b, sum);
+}
+
+#[test]
+#[test_params("1", 1)]
+#[test_params("-7", -7)]
+#[test_params("x", 0)]
+fn parse(s: &str, n: i32) -> Result<(), ParseIntError> {
+    assert_eq!(s.parse::<i32>()?, n);
+    Ok(())
+}
+
+#[test]
+#[ignore = "slow"]
+#[test_params(vec![1; 1000])]
+#[test_params(Vec::new())]
+fn sorted(mut v: Vec<u8>) {
+    v.sort();
+}
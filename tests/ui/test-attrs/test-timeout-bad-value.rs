@@ -0,0 +1,22 @@
+//@ compile-flags: --test
+
+#![feature(test_params, test_timeout)]
+
+#[test]
+#[test_timeout = "10h"] //~ ERROR invalid time limit `10h` in `#[test_timeout]`
+fn bad_unit() {}
+
+#[test]
+#[test_timeout = "0ms"] //~ ERROR invalid time limit `0ms` in `#[test_timeout]`
+fn zero() {}
+
+#[test]
+#[test_timeout = "250ms"]
+fn fine() {}
+
+// Reported once, not once per case.
+#[test]
+#[test_params(1)]
+#[test_params(2)]
+#[test_timeout = "5d"] //~ ERROR invalid time limit `5d` in `#[test_timeout]`
+fn params(_: u32) {}
@@ -0,0 +1,29 @@
+//@ no-prefer-dynamic
+//@ compile-flags: --test -Cpanic=abort -Zpanic_abort_tests
+//@ run-flags: --test-threads=1
+//@ run-fail
+//@ check-run-results
+//@ normalize-stdout-test "finished in \d+\.\d+s" -> "finished in $$TIME"
+//@ normalize-stdout-test "timed out after \d+\.\d+s" -> "timed out after $$TIME"
+
+//@ ignore-wasm no subprocess support
+//@ ignore-emscripten no subprocess support
+//@ ignore-sgx no subprocess support
+
+#![feature(test_timeout)]
+
+use std::thread;
+use std::time::Duration;
+
+#[test]
+#[test_timeout = "100ms"]
+fn hangs() {
+    println!("about to hang");
+    loop {
+        thread::sleep(Duration::from_secs(1));
+    }
+}
+
+#[test]
+#[test_timeout = "1m"]
+fn quick() {}
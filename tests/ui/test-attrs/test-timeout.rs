@@ -0,0 +1,24 @@
+//@ compile-flags: --test
+//@ run-flags: --test-threads=1
+//@ run-fail
+//@ check-run-results
+//@ normalize-stdout-test "finished in \d+\.\d+s" -> "finished in $$TIME"
+//@ normalize-stdout-test "timed out after \d+\.\d+s" -> "timed out after $$TIME"
+//@ needs-threads
+
+#![feature(test_timeout)]
+
+use std::thread;
+use std::time::Duration;
+
+#[test]
+#[test_timeout = "100ms"]
+fn hangs() {
+    loop {
+        thread::sleep(Duration::from_secs(1));
+    }
+}
+
+#[test]
+#[test_timeout = "1m"]
+fn quick() {}